@@ -14,13 +14,16 @@
 
 const WINDOWS_ICON_PATH: &str = "./resources/packaging/windows/graphics/sniffnet.ico";
 const SERVICES_LIST_PATH: &str = "./services.txt";
+const OUI_LIST_PATH: &str = "./oui.txt";
 
 fn main() {
     println!("cargo:rerun-if-changed={WINDOWS_ICON_PATH}");
     println!("cargo:rerun-if-changed={SERVICES_LIST_PATH}");
+    println!("cargo:rerun-if-changed={OUI_LIST_PATH}");
 
     set_icon();
     build_services_phf();
+    build_oui_phf();
 }
 
 fn set_icon() {
@@ -67,6 +70,53 @@ fn build_services_phf() {
     .unwrap();
 }
 
+fn build_oui_phf() {
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("oui.rs");
+    let mut output = BufWriter::new(File::create(out_path).unwrap());
+
+    let mut oui_map = phf_codegen::Map::new();
+
+    let input = BufReader::new(File::open(OUI_LIST_PATH).unwrap());
+    let mut num_entries = 0;
+    for line_res in input.lines() {
+        let line = line_res.unwrap();
+        // skip comment or blank lines
+        if line.trim().is_empty() || line.trim().starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split('\t');
+        // we want to panic if one of the OUIs isn't made of three hexadecimal octets
+        let key = get_valid_oui(parts.next().unwrap());
+        let vendor = parts.next().unwrap().trim();
+        assert!(!vendor.is_empty());
+        assert!(parts.next().is_none());
+        // vendor names of the registry can contain quotes and non-ASCII characters
+        oui_map.entry(key, format!("{vendor:?}"));
+        num_entries += 1;
+    }
+    assert_eq!(num_entries, 158);
+
+    writeln!(
+        &mut output,
+        "#[allow(clippy::unreadable_literal)]\n\
+        static OUI_VENDORS: phf::Map<u32, &'static str> = {};",
+        oui_map.build()
+    )
+    .unwrap();
+}
+
+fn get_valid_oui(s: &str) -> u32 {
+    let octets: Vec<u8> = s
+        .split(':')
+        .map(|octet| {
+            assert_eq!(octet.len(), 2);
+            u8::from_str_radix(octet, 16).unwrap()
+        })
+        .collect();
+    assert_eq!(octets.len(), 3);
+    u32::from_be_bytes([0, octets[0], octets[1], octets[2]])
+}
+
 fn get_valid_service_fmt_const(s: &str) -> String {
     match s.trim() {
         invalid
@@ -0,0 +1,17 @@
+#!/bin/bash
+
+# Script used to generate the file ./oui.txt
+# The source for the vendors assigned to each organizationally unique identifier (OUI) is the IEEE MA-L registry
+# Available at: https://standards-oui.ieee.org/oui/oui.txt
+# Remember to update the number of entries asserted in build.rs
+
+OUT=./oui.txt
+
+echo -e "# This file is automatically generated from the IEEE MA-L registry\n# Available at: https://standards-oui.ieee.org/oui/oui.txt\n# Don't edit this file manually\n" > $OUT
+
+curl https://standards-oui.ieee.org/oui/oui.txt \
+  | tr -d '\r' \
+  | grep -E '^[0-9A-F]{2}-[0-9A-F]{2}-[0-9A-F]{2} +\(hex\)' \
+  | sed -E 's/^([0-9A-F]{2})-([0-9A-F]{2})-([0-9A-F]{2}) +\(hex\)[[:space:]]+/\1:\2:\3\t/' \
+  | awk -F'\t' '$2 != "" && !seen[$1]++' \
+  | sort >> $OUT
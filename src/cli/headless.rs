@@ -0,0 +1,350 @@
+//! Module to capture traffic without the graphical interface, writing reports to file or stdout.
+
+use crate::cli::Args;
+use crate::gui::types::conf::CONF;
+use crate::gui::types::filters::Filters;
+use crate::location;
+use crate::mmdb::asn::ASN_MMDB;
+use crate::mmdb::country::COUNTRY_MMDB;
+use crate::mmdb::types::mmdb_reader::{MmdbReader, MmdbReaders};
+use crate::networking::flow_export::FlowExporter;
+use crate::networking::ip_feeds::{ListUpdate, load_ip_list};
+use crate::networking::parse_packets::{BackendTrafficMessage, PacketCommentRules, parse_packets};
+use crate::networking::types::capture_context::{CaptureContext, CaptureSource, MyPcapImport};
+use crate::networking::types::host::{Host, HostMessage};
+use crate::networking::types::info_traffic::InfoTraffic;
+use crate::networking::types::ip_blacklist::{IpBlacklist, IpListConf, IpLists};
+use crate::networking::types::my_device::MyDevice;
+use crate::networking::types::pcap_rotation::PcapRotation;
+use crate::networking::types::program_lookup::{ProgramLookup, lookup_program};
+use crate::report::export_report::TrafficReport;
+use crate::report::metrics::{MetricsServer, render_metrics};
+use crate::report::types::report_format::ReportFormat;
+use crate::translations::translations_3::unsupported_link_type_translation;
+use crate::translations::types::language::Language;
+use crate::utils::error_logger::{ErrorLogger, Location};
+use crate::utils::types::timestamp::Timestamp;
+use async_channel::TryRecvError;
+use pcap::Device;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Captures traffic from the adapter or the PCAP file specified in the arguments,
+/// until the capture duration has elapsed, the file is over, or the process is interrupted
+#[allow(clippy::print_stderr, clippy::too_many_lines)]
+pub(super) fn run(args: &Args) -> Result<(), String> {
+    let conf = &*CONF;
+
+    let output = args.output.as_deref().map(Path::new);
+    let format = args
+        .format
+        .or_else(|| output.and_then(ReportFormat::from_path))
+        .unwrap_or_default();
+    if format == ReportFormat::Csv && output.is_none() {
+        return Err("CSV reports can only be written to file (use --output)".to_string());
+    }
+
+    let mut capture_source = get_capture_source(args)?;
+
+    let filters = Filters::default();
+    let mut capture_contexts = CaptureContext::new_all(
+        &capture_source,
+        None,
+        PcapRotation::default(),
+        &filters,
+        conf.settings.extract_server_names,
+    );
+    for (name, error) in capture_source.remove_failed_devices(&mut capture_contexts) {
+        eprintln!(
+            "Skipping network adapter {name}: {}",
+            error.get_message(Language::EN)
+        );
+    }
+    if let Some(error) = capture_contexts.iter().find_map(CaptureContext::error) {
+        return Err(error.get_message(Language::EN));
+    }
+    capture_source.set_link_types(&capture_contexts);
+    capture_source.set_addresses();
+    if !capture_source.get_link_type().is_supported() {
+        return Err(unsupported_link_type_translation(Language::EN).to_string());
+    }
+
+    let is_live = !matches!(capture_source, CaptureSource::File(_));
+    let mmdb_readers = MmdbReaders {
+        country: Arc::new(MmdbReader::from(&conf.settings.mmdb_country, COUNTRY_MMDB)),
+        asn: Arc::new(MmdbReader::from(&conf.settings.mmdb_asn, ASN_MMDB)),
+    };
+    let ip_lists = Arc::new(RwLock::new(load_ip_lists(
+        &conf.settings.ip_blacklist,
+        &conf.settings.ip_lists,
+    )));
+    let decapsulate_tunnels = conf.settings.decapsulate_tunnels;
+    let extract_server_names = conf.settings.extract_server_names;
+    let metrics_server = start_metrics_server(args)?;
+    let flow_exporter = start_flow_exporter()?;
+
+    let (tx, rx) = async_channel::unbounded();
+    // headless captures are never frozen, but the sender must be kept alive
+    let (freeze_tx, freeze_rx) = tokio::sync::broadcast::channel(1);
+    let freeze_rx2 = freeze_tx.subscribe();
+    let cs = capture_source.clone();
+    let _ = thread::Builder::new()
+        .name("thread_parse_packets".to_string())
+        .spawn(move || {
+            parse_packets(
+                0,
+                cs,
+                &mmdb_readers,
+                &ip_lists,
+                capture_contexts,
+                &filters,
+                &PacketCommentRules::default(),
+                decapsulate_tunnels,
+                extract_server_names,
+                &tx,
+                (freeze_rx, freeze_rx2),
+            );
+        })
+        .log_err(location!());
+
+    let mut program_lookup = if is_live {
+        Some(start_program_lookup())
+    } else {
+        None
+    };
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let interrupted_2 = interrupted.clone();
+    let _ = ctrlc::set_handler(move || interrupted_2.store(true, Ordering::Relaxed))
+        .log_err(location!());
+
+    if is_live {
+        eprintln!(
+            "Capturing from {}... press Ctrl+C to stop",
+            capture_source.get_name()
+        );
+    }
+
+    let mut state = HeadlessState {
+        is_live,
+        metrics_server,
+        flow_exporter,
+        ..HeadlessState::default()
+    };
+    let start = Instant::now();
+    let mut last_report = Instant::now();
+    loop {
+        match rx.try_recv() {
+            Ok(BackendTrafficMessage::TickRun(_, mut msg, host_msgs, _)) => {
+                state.handle_new_hosts(host_msgs);
+                if let Some(program_lookup) = &mut program_lookup {
+                    for program_res in program_lookup.pending_results() {
+                        program_lookup.update(program_res, &mut state.info_traffic);
+                    }
+                }
+                state.refresh(&mut msg, &mut program_lookup);
+            }
+            Ok(BackendTrafficMessage::PendingHosts(_, host_msgs)) => {
+                state.handle_new_hosts(host_msgs);
+            }
+            Ok(BackendTrafficMessage::OfflineGap(..)) => {}
+            Err(TryRecvError::Empty) => thread::sleep(Duration::from_millis(100)),
+            // the parsing thread has finished (e.g., the PCAP file is over)
+            Err(TryRecvError::Closed) => break,
+        }
+
+        if interrupted.load(Ordering::Relaxed)
+            || args.duration.is_some_and(|d| start.elapsed() >= d)
+        {
+            break;
+        }
+
+        if args.interval.is_some_and(|i| last_report.elapsed() >= i) {
+            state.write_report(program_lookup.as_ref(), output, format)?;
+            last_report = Instant::now();
+        }
+    }
+    // stop the parsing thread
+    rx.close();
+    state.flush_flows();
+
+    state.write_report(program_lookup.as_ref(), output, format)
+}
+
+fn get_capture_source(args: &Args) -> Result<CaptureSource, String> {
+    if let Some(path) = &args.import {
+        return Ok(CaptureSource::File(MyPcapImport::new(path.clone())));
+    }
+    let name = args
+        .adapter
+        .clone()
+        .unwrap_or_else(|| CONF.device.device_name.clone());
+    let device = Device::list()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|d| d.name.eq(&name))
+        .ok_or_else(|| format!("Network adapter not found: {name}"))?;
+    Ok(CaptureSource::Device(MyDevice::from_pcap_device(device)))
+}
+
+#[derive(Default)]
+struct HeadlessState {
+    info_traffic: InfoTraffic,
+    addresses_resolved: HashMap<IpAddr, (String, Host)>,
+    is_live: bool,
+    metrics_server: Option<MetricsServer>,
+    flow_exporter: Option<FlowExporter>,
+}
+
+impl HeadlessState {
+    fn refresh(&mut self, msg: &mut InfoTraffic, program_lookup: &mut Option<ProgramLookup>) {
+        self.info_traffic.refresh(msg, program_lookup);
+        if let Some(server) = &self.metrics_server {
+            server.set_metrics(render_metrics(
+                &self.info_traffic,
+                program_lookup.as_ref().map(ProgramLookup::programs),
+                &CONF.settings.metrics,
+            ));
+        }
+        let now = self.flow_export_now();
+        if let Some(exporter) = &mut self.flow_exporter {
+            exporter.update(&self.info_traffic.map, msg.map.keys(), now);
+        }
+    }
+
+    /// Sends the records of all the flows not exported yet
+    fn flush_flows(&mut self) {
+        let now = self.flow_export_now();
+        if let Some(exporter) = &mut self.flow_exporter {
+            exporter.flush(&self.info_traffic.map, now);
+        }
+    }
+
+    /// Current time for the flow exporter: the time of the last packet when reading a file
+    fn flow_export_now(&self) -> Timestamp {
+        if self.is_live {
+            Timestamp::now()
+        } else {
+            self.info_traffic.last_packet_timestamp
+        }
+    }
+
+    fn handle_new_hosts(&mut self, host_msgs: Vec<HostMessage>) {
+        for host_msg in host_msgs {
+            let HostMessage {
+                host,
+                data_info_host,
+                address_to_lookup,
+                rdns,
+            } = host_msg;
+
+            self.info_traffic
+                .hosts
+                .entry(host.clone())
+                .and_modify(|d| d.refresh(&data_info_host))
+                .or_insert(data_info_host);
+
+            self.addresses_resolved
+                .insert(address_to_lookup, (rdns, host));
+        }
+    }
+
+    fn write_report(
+        &self,
+        program_lookup: Option<&ProgramLookup>,
+        output: Option<&Path>,
+        format: ReportFormat,
+    ) -> Result<(), String> {
+        let report = TrafficReport::new(
+            &self.info_traffic,
+            &self.addresses_resolved,
+            program_lookup.map(ProgramLookup::programs),
+        );
+        let res = match output {
+            Some(path) => report.write_to_file(path, format),
+            None => report.write_json(&mut std::io::stdout().lock()),
+        };
+        res.map_err(|e| format!("Could not write the report: {e}"))
+    }
+}
+
+/// Starts the metrics endpoint, if requested from the command line or enabled in the settings
+#[allow(clippy::print_stderr)]
+fn start_metrics_server(args: &Args) -> Result<Option<MetricsServer>, String> {
+    let metrics = &CONF.settings.metrics;
+    if args.metrics.is_none() && !metrics.is_active {
+        return Ok(None);
+    }
+    let address = args
+        .metrics_address()
+        .or_else(|| metrics.socket_address())
+        .ok_or_else(|| format!("Invalid metrics address: {}", metrics.address))?;
+    let server =
+        MetricsServer::start(address).map_err(|e| format!("Could not expose the metrics: {e}"))?;
+    eprintln!("Exposing metrics at http://{}/metrics", server.address());
+    Ok(Some(server))
+}
+
+/// Creates the flow exporter, if enabled in the settings
+#[allow(clippy::print_stderr)]
+fn start_flow_exporter() -> Result<Option<FlowExporter>, String> {
+    let flow_export = &CONF.settings.flow_export;
+    if !flow_export.is_active {
+        return Ok(None);
+    }
+    let exporter = FlowExporter::new(flow_export).map_err(|e| {
+        format!(
+            "Could not export the flows: {}",
+            e.get_message(Language::EN)
+        )
+    })?;
+    eprintln!(
+        "Exporting flows as {} to {}",
+        flow_export.protocol,
+        exporter.collector()
+    );
+    Ok(Some(exporter))
+}
+
+fn start_program_lookup() -> ProgramLookup {
+    let (port_tx, port_rx) = std::sync::mpsc::channel();
+    let (program_tx, program_rx) = std::sync::mpsc::channel();
+    let _ = thread::Builder::new()
+        .name("thread_lookup_program".to_string())
+        .spawn(move || {
+            lookup_program(&port_rx, &program_tx);
+        })
+        .log_err(location!());
+    // program icons aren't needed without the graphical interface
+    let (icon_key_tx, _) = std::sync::mpsc::channel();
+    let (_, picon_rx) = std::sync::mpsc::channel();
+    ProgramLookup::new(port_tx, program_rx, icon_key_tx, picon_rx)
+}
+
+fn load_ip_lists(default_path: &str, named: &[IpListConf]) -> IpLists {
+    let Ok(rt) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .log_err(location!())
+    else {
+        return IpLists::default();
+    };
+    let default = if default_path.is_empty() {
+        IpBlacklist::default()
+    } else {
+        rt.block_on(IpBlacklist::from_file(default_path.to_string()))
+    };
+    let loaded = named
+        .iter()
+        .filter_map(|list| match rt.block_on(load_ip_list(list.clone(), None)) {
+            ListUpdate::Loaded(content) => Some((list.path.clone(), content)),
+            ListUpdate::Unchanged => None,
+        })
+        .collect();
+    IpLists::new(default_path, &default, named, &loaded)
+}
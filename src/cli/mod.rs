@@ -2,9 +2,14 @@
 use crate::gui::types::conf::{CONF, Conf};
 use crate::gui::types::message::Message;
 use crate::networking::types::capture_context::CaptureSourcePicklist;
+use crate::report::types::report_format::ReportFormat;
 use crate::utils::formatted_strings::APP_VERSION;
 use clap::Parser;
 use iced::{Task, window};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+mod headless;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -27,6 +32,34 @@ pub(crate) struct Args {
     /// Restore default settings
     #[arg(short, long, exclusive = true)]
     restore_default: bool,
+    /// Capture without graphical interface, and write a report of the analyzed traffic
+    #[arg(long)]
+    headless: bool,
+    /// Read packets from the supplied PCAP file instead of a network adapter (headless mode only)
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "headless",
+        conflicts_with = "adapter"
+    )]
+    import: Option<String>,
+    /// Stop capturing after the supplied duration, e.g. 90s, 5m, 1h (headless mode only)
+    #[arg(short, long, value_name = "DURATION", value_parser = parse_duration, requires = "headless")]
+    duration: Option<Duration>,
+    /// Also write the report periodically, e.g. every 30s (headless mode only)
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, requires = "headless")]
+    interval: Option<Duration>,
+    /// Write the report to the supplied file instead of stdout (headless mode only)
+    #[arg(short, long, value_name = "PATH", requires = "headless")]
+    output: Option<String>,
+    /// Format of the report; by default it's inferred from the output file extension (headless mode only)
+    #[arg(short, long, value_enum, requires = "headless")]
+    format: Option<ReportFormat>,
+    /// Expose the traffic statistics as Prometheus metrics at http://ADDRESS:PORT/metrics;
+    /// by default, the address configured in the settings is used
+    #[arg(long, value_name = "ADDRESS:PORT", num_args = 0..=1)]
+    #[allow(clippy::option_option)] // the flag can be passed without a value
+    metrics: Option<Option<SocketAddr>>,
 }
 
 impl Args {
@@ -80,11 +113,22 @@ pub fn handle() -> Self {
             }
         }
 
+        if args.headless {
+            if let Err(e) = headless::run(&args) {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+            std::process::exit(0);
+        }
+
         args
     }
 
     pub fn get_boot_task_chain(&self) -> Task<Message> {
         let mut boot_task_chain = window::latest().map(Message::StartApp);
+        if let Some(address) = self.metrics_address() {
+            boot_task_chain = boot_task_chain.chain(Task::done(Message::EnableMetrics(address)));
+        }
         if let Some(adapter) = self.adapter.clone() {
             boot_task_chain = boot_task_chain
                 .chain(Task::done(Message::SetCaptureSource(
@@ -96,12 +140,37 @@ pub fn get_boot_task_chain(&self) -> Task<Message> {
 
         boot_task_chain
     }
+
+    /// Address of the metrics endpoint requested from the command line, if any
+    fn metrics_address(&self) -> Option<SocketAddr> {
+        self.metrics?
+            .or_else(|| CONF.settings.metrics.socket_address())
+    }
+}
+
+/// Parses durations such as `90`, `90s`, `5m`, or `1h` (seconds are the default unit)
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (value, multiplier) = match s.char_indices().last() {
+        Some((i, 's')) => (&s[..i], 1),
+        Some((i, 'm')) => (&s[..i], 60),
+        Some((i, 'h')) => (&s[..i], 3600),
+        _ => (s, 1),
+    };
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|v| v.checked_mul(multiplier))
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("invalid duration: '{s}'"))
 }
 
 #[cfg(test)]
 mod tests {
     use serial_test::serial;
 
+    use crate::cli::Args;
     use crate::gui::pages::types::running_page::RunningPage;
     use crate::gui::pages::types::settings_page::SettingsPage;
     use crate::gui::styles::types::gradient_type::GradientType;
@@ -114,10 +183,18 @@ mod tests {
     use crate::networking::types::capture_context::CaptureSourcePicklist;
     use crate::networking::types::config_device::ConfigDevice;
     use crate::networking::types::data_representation::DataRepr;
+    use crate::networking::types::flow_export_conf::{FlowExportConf, FlowProtocol};
+    use crate::networking::types::ip_blacklist::{
+        FeedFormat, IpListConf, ListCategory, ListSeverity,
+    };
     use crate::networking::types::service::Service;
     use crate::notifications::types::notifications::Notifications;
+    use crate::report::types::metrics_conf::MetricsConf;
+    use crate::report::types::report_format::ReportFormat;
     use crate::report::types::sort_type::SortType;
     use crate::{Language, Sniffer, StyleType};
+    use clap::Parser;
+    use std::time::Duration;
 
     #[test]
     #[serial]
@@ -141,17 +218,52 @@ fn test_restore_default_configs() {
                     favorite_notification: Default::default(),
                     remote_notifications: Default::default(),
                     ip_blacklist_notification: Default::default(),
+                    arp_anomaly_notification: Default::default(),
+                    scan_notification: Default::default(),
+                    rate_notification: Default::default(),
+                    quota_notification: Default::default(),
+                    threshold_rule_notification: Default::default(),
+                    threshold_rules: Default::default(),
                 },
                 style: StyleType::DraculaDark,
                 ip_blacklist: "some-path".to_string(),
+                ip_lists: vec![IpListConf {
+                    name: "Tor exits".to_string(),
+                    path: "https://example.com/tor.csv".to_string(),
+                    format: FeedFormat::Csv,
+                    csv_column: 3,
+                    refresh_minutes: 30,
+                    category: ListCategory::Tor,
+                    severity: ListSeverity::Medium,
+                    is_allowlist: false,
+                }],
+                decapsulate_tunnels: true,
+                extract_server_names: true,
+                metrics: MetricsConf {
+                    is_active: false,
+                    address: "0.0.0.0".to_string(),
+                    port: 9100,
+                    top_hosts: 5,
+                    max_label_values: 50,
+                },
+                flow_export: FlowExportConf {
+                    is_active: true,
+                    address: "10.0.0.5".to_string(),
+                    port: 9995,
+                    protocol: FlowProtocol::NetflowV9,
+                    active_timeout: 120,
+                    inactive_timeout: 30,
+                },
             },
             favorites: Favorites::from([FavoriteKey::Service(Service::Name("https"))]),
             device: ConfigDevice {
                 device_name: "hey-hey".to_string(),
+                device_names: vec!["hey-hey".to_string(), "tun0".to_string()],
             },
             window: ConfigWindow::new((452.0, 870.0), (440.0, 99.0), (20.0, 20.0)),
             capture_source_picklist: CaptureSourcePicklist::File,
             report_sort_type: SortType::Ascending,
+            report_flows: true,
             host_favorites_filter: false,
             service_favorites_filter: true,
             program_favorites_filter: false,
@@ -167,6 +279,9 @@ fn test_restore_default_configs() {
                 enabled: true,
                 file_name: "sniffnet.pcap".to_string(),
                 directory: "home".to_string(),
+                rotation_megabytes: 100,
+                rotation_minutes: 60,
+                ring_buffer_files: 24,
             },
             last_opened_setting: SettingsPage::General,
             last_opened_page: RunningPage::Inspect,
@@ -186,4 +301,84 @@ fn test_restore_default_configs() {
         // only needed because it will delete config files via its Drop implementation
         Sniffer::new(Conf::default());
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(super::parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(super::parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(super::parse_duration(" 5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(super::parse_duration("2h"), Ok(Duration::from_secs(7200)));
+        assert!(super::parse_duration("").is_err());
+        assert!(super::parse_duration("m").is_err());
+        assert!(super::parse_duration("-3s").is_err());
+        assert!(super::parse_duration("1d").is_err());
+    }
+
+    #[test]
+    fn test_headless_args() {
+        let args = Args::try_parse_from([
+            "sniffnet",
+            "--headless",
+            "--adapter",
+            "eth0",
+            "--duration",
+            "60s",
+            "--output",
+            "report.csv",
+        ])
+        .unwrap();
+        assert!(args.headless);
+        assert_eq!(args.adapter, Some("eth0".to_string()));
+        assert_eq!(args.duration, Some(Duration::from_secs(60)));
+        assert_eq!(args.output, Some("report.csv".to_string()));
+        assert_eq!(args.format, None);
+
+        let args = Args::try_parse_from([
+            "sniffnet",
+            "--headless",
+            "--import",
+            "capture.pcap",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(args.import, Some("capture.pcap".to_string()));
+        assert_eq!(args.format, Some(ReportFormat::Csv));
+
+        // headless options require the headless flag
+        assert!(Args::try_parse_from(["sniffnet", "--output", "report.json"]).is_err());
+        // a PCAP file and an adapter can't be used together
+        assert!(
+            Args::try_parse_from([
+                "sniffnet",
+                "--headless",
+                "--import",
+                "capture.pcap",
+                "--adapter",
+                "eth0"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_metrics_args() {
+        let args = Args::try_parse_from(["sniffnet"]).unwrap();
+        assert_eq!(args.metrics, None);
+        assert_eq!(args.metrics_address(), None);
+
+        // the address from the settings is used
+        let args = Args::try_parse_from(["sniffnet", "--metrics", "--adapter", "eth0"]).unwrap();
+        assert_eq!(args.metrics, Some(None));
+        assert_eq!(args.adapter, Some("eth0".to_string()));
+
+        let args =
+            Args::try_parse_from(["sniffnet", "--headless", "--metrics", "0.0.0.0:9100"]).unwrap();
+        assert_eq!(args.metrics_address(), "0.0.0.0:9100".parse().ok());
+        let args = Args::try_parse_from(["sniffnet", "--metrics=[::1]:9100"]).unwrap();
+        assert_eq!(args.metrics_address(), "[::1]:9100".parse().ok());
+
+        assert!(Args::try_parse_from(["sniffnet", "--metrics", "localhost:9100"]).is_err());
+        assert!(Args::try_parse_from(["sniffnet", "--metrics", "127.0.0.1"]).is_err());
+    }
 }
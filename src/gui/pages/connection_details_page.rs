@@ -19,10 +19,14 @@
 use crate::networking::types::bogon::is_bogon;
 use crate::networking::types::capture_context::CaptureSource;
 use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::flow::InfoFlow;
 use crate::networking::types::host::Host;
 use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::igmp_type::IgmpType;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::latency::LatencyStatus;
+use crate::networking::types::tcp_metrics::{TcpMetrics, format_rtt};
+use crate::networking::types::tcp_state::TcpFlags;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::networking::types::traffic_type::TrafficType;
 use crate::translations::translations::{
@@ -37,9 +41,17 @@
 use crate::translations::translations_3::{
     copy_translation, messages_translation, service_translation,
 };
-use crate::translations::translations_5::program_translation;
-use crate::translations::translations_6::latency_translation;
-use crate::utils::formatted_strings::{get_formatted_timestamp, get_socket_address};
+use crate::translations::translations_5::{ip_blacklist_translation, program_translation};
+use crate::translations::translations_6::{
+    adapter_translation, duplicate_acks_translation, flow_duration_translation,
+    fragments_translation, host_rtt_translation, latency_translation, mpls_labels_translation,
+    queried_domain_translation, retransmissions_translation, rtt_translation,
+    server_name_translation, tcp_flags_translation, tcp_state_translation, tunnel_translation,
+    zero_window_translation,
+};
+use crate::utils::formatted_strings::{
+    get_formatted_num_seconds, get_formatted_timestamp, get_socket_address,
+};
 use crate::utils::types::icon::Icon;
 use crate::{Language, Protocol, Sniffer, StyleType};
 use iced::alignment::Vertical;
@@ -65,7 +77,16 @@ fn page_content<'a>(sniffer: &Sniffer, key: &AddressPortPair) -> Container<'a, M
 
     let info_traffic = &sniffer.info_traffic;
     let default_val = InfoAddressPortPair::default();
-    let val = info_traffic.map.get(key).unwrap_or(&default_val);
+    let flow = if sniffer.conf.report_flows {
+        InfoFlow::from_map(&info_traffic.map, key).map(|(_, flow)| flow)
+    } else {
+        None
+    };
+    let val = flow
+        .as_ref()
+        .map(|flow| &flow.info)
+        .or_else(|| info_traffic.map.get(key))
+        .unwrap_or(&default_val);
     let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
     let host_option = sniffer.addresses_resolved.get(&address_to_lookup);
     let default_host = Host::default();
@@ -126,7 +147,7 @@ fn page_content<'a>(sniffer: &Sniffer, key: &AddressPortPair) -> Container<'a, M
         dest_col = dest_col.push(host_info_col);
     }
 
-    let col_info = col_info(sniffer, key, val, address_to_lookup);
+    let col_info = col_info(sniffer, key, val, flow.as_ref(), address_to_lookup);
 
     let content = assemble_widgets(col_info, source_col, dest_col);
 
@@ -162,10 +183,12 @@ fn page_header<'a>(
     .class(ContainerType::Gradient(color_gradient))
 }
 
+#[allow(clippy::too_many_lines, clippy::similar_names)]
 fn col_info<'a>(
     sniffer: &Sniffer,
     key: &AddressPortPair,
     val: &InfoAddressPortPair,
+    flow: Option<&InfoFlow>,
     latency_target: IpAddr,
 ) -> Column<'a, Message, StyleType> {
     let Settings { language, .. } = sniffer.conf.settings;
@@ -175,9 +198,11 @@ fn col_info<'a>(
         sniffer.capture_source.get_addresses(),
         val.traffic_direction,
     ) == TrafficType::Unicast;
-    let measure_latency = matches!(sniffer.capture_source, CaptureSource::Device(_)) && is_unicast;
+    let measure_latency = !matches!(sniffer.capture_source, CaptureSource::File(_)) && is_unicast;
     let is_icmp = key.protocol.eq(&Protocol::ICMP);
     let is_arp = key.protocol.eq(&Protocol::ARP);
+    let is_igmp = key.protocol.eq(&Protocol::IGMP);
+    let is_tcp = key.protocol.eq(&Protocol::TCP);
 
     let mut ret_val = Column::new()
         .spacing(10)
@@ -200,7 +225,35 @@ fn col_info<'a>(
             &key.protocol.to_string(),
         ));
 
-    if !is_icmp && !is_arp {
+    if let Some(tunnel) = val.tunnel {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            tunnel_translation(language),
+            &tunnel.to_string(),
+        ));
+    }
+
+    if key.vlan.is_tagged() {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            "VLAN",
+            &key.vlan.to_string(),
+        ));
+    }
+
+    if !val.mpls_labels.is_empty() {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            mpls_labels_translation(language),
+            &val.mpls_labels.to_string(),
+        ));
+    }
+
+    if val.fragments > 0 {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            fragments_translation(language),
+            &val.fragments.to_string(),
+        ));
+    }
+
+    if !is_icmp && !is_arp && !is_igmp {
         ret_val = ret_val
             .push(TextType::highlighted_subtitle_with_desc(
                 service_translation(language),
@@ -212,24 +265,76 @@ fn col_info<'a>(
             ));
     }
 
-    ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
-        &format!(
-            "{} ({})",
-            transmitted_data_translation(language),
-            if val.traffic_direction.eq(&TrafficDirection::Outgoing) {
-                outgoing_translation(language).to_lowercase()
-            } else {
-                incoming_translation(language).to_lowercase()
-            }
-        ),
-        &(data_repr.formatted_string(val.transmitted_data(data_repr))
-            + if data_repr == DataRepr::Packets {
-                format!(" {}", packets_translation(language))
-            } else {
-                String::new()
-            }
-            .as_ref()),
-    ));
+    if let Some(tcp_state) = val.tcp_state {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            tcp_state_translation(language),
+            &tcp_state.to_string(),
+        ));
+    }
+
+    if let Some(server_name) = &val.server_name {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            server_name_translation(language),
+            server_name,
+        ));
+    }
+
+    if let Some(blacklist_match) = &val.blacklist_match {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            ip_blacklist_translation(language),
+            &blacklist_match.to_string(),
+        ));
+    }
+
+    if let Some(flow) = flow {
+        ret_val = ret_val
+            .push(transmitted_data_row(
+                language,
+                data_repr,
+                val.traffic_direction,
+                flow.upstream.data(data_repr),
+            ))
+            .push(transmitted_data_row(
+                language,
+                data_repr,
+                val.traffic_direction.reversed(),
+                flow.downstream.data(data_repr),
+            ))
+            .push(TextType::highlighted_subtitle_with_desc(
+                flow_duration_translation(language),
+                &get_formatted_num_seconds(
+                    u128::try_from(flow.duration_usecs() / 1_000_000).unwrap_or_default(),
+                ),
+            ));
+    } else {
+        ret_val = ret_val.push(transmitted_data_row(
+            language,
+            data_repr,
+            val.traffic_direction,
+            val.transmitted_data(data_repr),
+        ));
+    }
+
+    if !val.adapters.is_empty() {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            adapter_translation(language),
+            &val.adapters.join(", "),
+        ));
+    }
+
+    // like the TCP state, flags and metrics always refer to both directions of the connection
+    let mut tcp_flags = val.tcp_flags;
+    let mut tcp_metrics = val.tcp_metrics;
+    if flow.is_none()
+        && let Some(reversed) = sniffer.info_traffic.map.get(&key.reversed())
+    {
+        tcp_flags.add(reversed.tcp_flags);
+        tcp_metrics.add(reversed.tcp_metrics);
+    }
+    if is_tcp {
+        let host_tcp_metrics = sniffer.info_traffic.hosts_tcp_metrics.get(&latency_target);
+        ret_val = ret_val.push(rtt_rows(language, &tcp_metrics, host_tcp_metrics));
+    }
 
     if measure_latency {
         let latency_status = sniffer.latency_statuses.get(&latency_target);
@@ -242,20 +347,31 @@ fn col_info<'a>(
         ));
     }
 
-    if is_icmp || is_arp {
+    if is_icmp || is_arp || is_igmp || is_tcp {
         ret_val = ret_val.push(
             Column::new()
                 .push(
-                    Text::new(format!("{}:", messages_translation(language)))
-                        .class(TextType::Subtitle),
+                    Text::new(format!(
+                        "{}:",
+                        if is_tcp {
+                            tcp_flags_translation(language)
+                        } else {
+                            messages_translation(language)
+                        }
+                    ))
+                    .class(TextType::Subtitle),
                 )
                 .push(Scrollable::with_direction(
                     Column::new()
                         .padding(Padding::ZERO.right(10).bottom(10))
                         .push(Text::new(if is_icmp {
                             IcmpType::pretty_print_types(&val.icmp_types)
-                        } else {
+                        } else if is_arp {
                             ArpType::pretty_print_types(&val.arp_types)
+                        } else if is_igmp {
+                            IgmpType::pretty_print_types(&val.igmp_types)
+                        } else {
+                            tcp_flags_and_events_string(language, &tcp_flags, &tcp_metrics)
                         })),
                     Direction::Both {
                         vertical: ScrollbarType::properties(),
@@ -270,6 +386,80 @@ fn col_info<'a>(
     ret_val
 }
 
+fn transmitted_data_row<'a>(
+    language: Language,
+    data_repr: DataRepr,
+    traffic_direction: TrafficDirection,
+    data: u128,
+) -> Column<'a, Message, StyleType> {
+    TextType::highlighted_subtitle_with_desc(
+        &format!(
+            "{} ({})",
+            transmitted_data_translation(language),
+            if traffic_direction.eq(&TrafficDirection::Outgoing) {
+                outgoing_translation(language).to_lowercase()
+            } else {
+                incoming_translation(language).to_lowercase()
+            }
+        ),
+        &(data_repr.formatted_string(data)
+            + if data_repr == DataRepr::Packets {
+                format!(" {}", packets_translation(language))
+            } else {
+                String::new()
+            }
+            .as_ref()),
+    )
+}
+
+fn rtt_rows<'a>(
+    language: Language,
+    tcp_metrics: &TcpMetrics,
+    host_tcp_metrics: Option<&TcpMetrics>,
+) -> Column<'a, Message, StyleType> {
+    let rtt_string = |metrics: &TcpMetrics| match (metrics.avg_rtt_usecs(), metrics.rtt_min_usecs) {
+        (Some(avg), Some(min)) => format!("{} (min {})", format_rtt(avg), format_rtt(min)),
+        _ => "-".to_string(),
+    };
+
+    let mut ret_val = Column::new()
+        .spacing(10)
+        .push(TextType::highlighted_subtitle_with_desc(
+            rtt_translation(language),
+            &rtt_string(tcp_metrics),
+        ));
+
+    if let Some(host_tcp_metrics) = host_tcp_metrics.filter(|m| m.rtt_samples > 0) {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            host_rtt_translation(language),
+            &rtt_string(host_tcp_metrics),
+        ));
+    }
+
+    ret_val
+}
+
+/// Flags of the TCP segments, followed by the counters of the anomalous events
+fn tcp_flags_and_events_string(
+    language: Language,
+    tcp_flags: &TcpFlags,
+    tcp_metrics: &TcpMetrics,
+) -> String {
+    let retransmissions = match tcp_metrics.retransmission_rate() {
+        Some(rate) => format!("{} ({rate:.1}%)", tcp_metrics.retransmissions),
+        None => tcp_metrics.retransmissions.to_string(),
+    };
+    format!(
+        "{}\n\n   {}: {retransmissions}\n   {}: {}\n   {}: {}",
+        tcp_flags.pretty_print(),
+        retransmissions_translation(language),
+        duplicate_acks_translation(language),
+        tcp_metrics.duplicate_acks,
+        zero_window_translation(language),
+        tcp_metrics.zero_windows,
+    )
+}
+
 fn latency_row<'a>(
     language: Language,
     latency_target: IpAddr,
@@ -317,10 +507,18 @@ fn get_host_info_col<'a>(
     language: Language,
 ) -> Column<'a, Message, StyleType> {
     let mut host_info_col = Column::new().spacing(4);
-    if r_dns.parse::<IpAddr>().is_err() || (!host.asn.name.is_empty() && !host.asn.code.is_empty())
+    if r_dns.parse::<IpAddr>().is_err()
+        || !host.dns_name.is_empty()
+        || (!host.asn.name.is_empty() && !host.asn.code.is_empty())
     {
         host_info_col = host_info_col.push(RuleType::Standard.horizontal(10));
     }
+    if !host.dns_name.is_empty() {
+        host_info_col = host_info_col.push(TextType::highlighted_subtitle_with_desc(
+            queried_domain_translation(language),
+            &host.dns_name,
+        ));
+    }
     if r_dns.parse::<IpAddr>().is_err() {
         host_info_col = host_info_col.push(TextType::highlighted_subtitle_with_desc(
             fqdn_translation(language),
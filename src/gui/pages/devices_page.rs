@@ -0,0 +1,191 @@
+use iced::widget::scrollable::Direction;
+use iced::widget::tooltip::Position;
+use iced::widget::{Column, Container, Row, Scrollable, Space, Text, Tooltip};
+use iced::{Alignment, Length, Padding};
+
+use crate::gui::components::tab::get_pages_tabs;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::rule::RuleType;
+use crate::gui::styles::scrollbar::ScrollbarType;
+use crate::gui::styles::style_constants::TOOLTIP_DELAY;
+use crate::gui::styles::text::TextType;
+use crate::gui::types::message::Message;
+use crate::gui::types::settings::Settings;
+use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::lan_device::LanDevice;
+use crate::translations::translations_2::mac_address_translation;
+use crate::translations::translations_6::{
+    first_seen_translation, hostname_translation, ip_addresses_translation,
+    known_vendors_translation, last_seen_translation, no_devices_translation, vendor_translation,
+};
+use crate::utils::formatted_strings::{clip_text, get_formatted_timestamp};
+use crate::{Language, RunningPage, Sniffer, StyleType};
+
+const MAC_COL_WIDTH: f32 = 145.0;
+const VENDOR_COL_WIDTH: f32 = 130.0;
+const IP_COL_WIDTH: f32 = 221.0;
+const HOSTNAME_COL_WIDTH: f32 = 140.0;
+const TIMESTAMP_COL_WIDTH: f32 = 160.0;
+const DATA_COL_WIDTH: f32 = 95.0;
+const TABLE_WIDTH: f32 = MAC_COL_WIDTH
+    + VENDOR_COL_WIDTH
+    + IP_COL_WIDTH
+    + HOSTNAME_COL_WIDTH
+    + 2.0 * TIMESTAMP_COL_WIDTH
+    + DATA_COL_WIDTH
+    + 14.0;
+
+/// Computes the body of gui devices page
+pub fn devices_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
+    let Settings { language, .. } = sniffer.conf.settings;
+    let data_repr = sniffer.conf.data_repr;
+
+    let mut tab_and_body = Column::new()
+        .align_x(Alignment::Center)
+        .height(Length::Fill);
+
+    let tabs = get_pages_tabs(RunningPage::Devices, language, sniffer.unread_notifications);
+
+    tab_and_body = tab_and_body.push(tabs);
+
+    let devices = &sniffer.info_traffic.devices;
+    if devices.is_empty() {
+        let body = body_no_devices(language, &sniffer.dots_pulse.0);
+        tab_and_body = tab_and_body.push(body);
+    } else {
+        let mut sorted_devices: Vec<(&String, &LanDevice)> = devices.iter().collect();
+        sorted_devices.sort_by(|(mac_a, a), (mac_b, b)| {
+            b.transmitted_data(data_repr)
+                .cmp(&a.transmitted_data(data_repr))
+                .then_with(|| mac_a.cmp(mac_b))
+        });
+
+        let mut scroll_devices = Column::new().align_x(Alignment::Start);
+        for (mac_address, device) in sorted_devices {
+            scroll_devices = scroll_devices.push(row_device(mac_address, device, data_repr));
+        }
+
+        let col_devices = Column::new()
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_x(Alignment::Start)
+            .push(devices_header_row(language, data_repr))
+            .push(Space::new().height(4))
+            .push(RuleType::Standard.horizontal(5))
+            .push(
+                Scrollable::with_direction(
+                    scroll_devices,
+                    Direction::Vertical(ScrollbarType::properties()),
+                )
+                .height(Length::Fill)
+                .width(Length::Fill),
+            );
+
+        let body = Column::new()
+            .width(Length::Fill)
+            .padding(10)
+            .align_x(Alignment::Center)
+            .push(
+                Container::new(col_devices)
+                    .padding(Padding::new(7.0).top(10).bottom(3))
+                    .width(TABLE_WIDTH)
+                    .class(ContainerType::BorderedRound),
+            );
+        tab_and_body = tab_and_body.push(body);
+    }
+
+    Container::new(Column::new().push(tab_and_body)).height(Length::Fill)
+}
+
+fn body_no_devices(language: Language, dots: &str) -> Column<'_, Message, StyleType> {
+    Column::new()
+        .padding(5)
+        .spacing(5)
+        .align_x(Alignment::Center)
+        .width(Length::Fill)
+        .push(Space::new().height(Length::Fill))
+        .push(Text::new(no_devices_translation(language)).align_x(Alignment::Center))
+        .push(Text::new(dots).size(50))
+        .push(Space::new().height(Length::FillPortion(2)))
+}
+
+fn devices_header_row<'a>(language: Language, data_repr: DataRepr) -> Row<'a, Message, StyleType> {
+    let titles = [
+        (mac_address_translation(language), MAC_COL_WIDTH, None),
+        (
+            vendor_translation(language),
+            VENDOR_COL_WIDTH,
+            // vendors are looked up in an embedded subset of the IEEE registry
+            Some(known_vendors_translation(language)),
+        ),
+        (ip_addresses_translation(language), IP_COL_WIDTH, None),
+        (hostname_translation(language), HOSTNAME_COL_WIDTH, None),
+        (first_seen_translation(language), TIMESTAMP_COL_WIDTH, None),
+        (last_seen_translation(language), TIMESTAMP_COL_WIDTH, None),
+        (data_repr.get_label(language), DATA_COL_WIDTH, None),
+    ];
+    let mut ret_val = Row::new().padding([0, 2]).align_y(Alignment::Center);
+    for (title, width, tooltip) in titles {
+        let title = Text::new(title.to_string()).class(TextType::Title);
+        let content = match tooltip {
+            Some(tooltip) => Container::new(
+                Tooltip::new(title, Text::new(tooltip), Position::FollowCursor)
+                    .class(ContainerType::Tooltip)
+                    .delay(TOOLTIP_DELAY),
+            ),
+            None => Container::new(title),
+        };
+        ret_val = ret_val.push(content.align_x(Alignment::Center).width(width));
+    }
+    ret_val
+}
+
+fn row_device<'a>(
+    mac_address: &str,
+    device: &LanDevice,
+    data_repr: DataRepr,
+) -> Row<'a, Message, StyleType> {
+    let ip_addresses = if device.ip_addresses.is_empty() {
+        "-".to_string()
+    } else {
+        device
+            .ip_addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let values = [
+        (mac_address.to_string(), MAC_COL_WIDTH),
+        (
+            clip_text(device.vendor.unwrap_or("-"), 16),
+            VENDOR_COL_WIDTH,
+        ),
+        (ip_addresses, IP_COL_WIDTH),
+        (
+            clip_text(device.hostname.as_deref().unwrap_or("-"), 16),
+            HOSTNAME_COL_WIDTH,
+        ),
+        (
+            get_formatted_timestamp(device.first_seen),
+            TIMESTAMP_COL_WIDTH,
+        ),
+        (
+            get_formatted_timestamp(device.last_seen),
+            TIMESTAMP_COL_WIDTH,
+        ),
+        (
+            data_repr.formatted_string(device.transmitted_data(data_repr)),
+            DATA_COL_WIDTH,
+        ),
+    ];
+    let mut ret_val = Row::new().padding([4, 2]).align_y(Alignment::Center);
+    for (value, width) in values {
+        ret_val = ret_val.push(
+            Container::new(Text::new(value))
+                .align_x(Alignment::Center)
+                .width(width),
+        );
+    }
+    ret_val
+}
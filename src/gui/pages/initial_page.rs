@@ -23,6 +23,10 @@
 };
 use crate::translations::translations_4::capture_file_translation;
 use crate::translations::translations_5::{filter_traffic_translation, traffic_source_translation};
+use crate::translations::translations_6::{
+    files_translation, keep_last_translation, network_adapters_translation,
+    new_file_every_translation,
+};
 use crate::utils::formatted_strings::get_path_termination_string;
 use crate::utils::types::file_info::FileInfo;
 use crate::utils::types::icon::Icon;
@@ -102,20 +106,23 @@ fn button_start<'a>(
 }
 
 fn get_col_data_source(sniffer: &Sniffer, language: Language) -> Column<'_, Message, StyleType> {
-    let current_option = if sniffer.conf.capture_source_picklist == CaptureSourcePicklist::Device {
-        network_adapter_translation(language)
-    } else {
-        capture_file_translation(language)
+    let current_option = match sniffer.conf.capture_source_picklist {
+        CaptureSourcePicklist::Device => network_adapter_translation(language),
+        CaptureSourcePicklist::Devices => network_adapters_translation(language),
+        CaptureSourcePicklist::File => capture_file_translation(language),
     };
     let picklist = PickList::new(
         [
             network_adapter_translation(language),
+            network_adapters_translation(language),
             capture_file_translation(language),
         ],
         Some(current_option),
         move |option| {
             if option == network_adapter_translation(language) {
                 Message::SetCaptureSource(CaptureSourcePicklist::Device)
+            } else if option == network_adapters_translation(language) {
+                Message::SetCaptureSource(CaptureSourcePicklist::Devices)
             } else {
                 Message::SetCaptureSource(CaptureSourcePicklist::File)
             }
@@ -141,7 +148,7 @@ fn get_col_data_source(sniffer: &Sniffer, language: Language) -> Column<'_, Mess
         );
 
     match &sniffer.conf.capture_source_picklist {
-        CaptureSourcePicklist::Device => {
+        CaptureSourcePicklist::Device | CaptureSourcePicklist::Devices => {
             col = col.push(get_col_adapter(sniffer));
         }
         CaptureSourcePicklist::File => {
@@ -195,17 +202,21 @@ fn get_col_adapter(sniffer: &Sniffer) -> Column<'_, Message, StyleType> {
                             .padding(15)
                             .width(Length::Fill)
                             .class(
-                                if let CaptureSource::Device(device) = &sniffer.capture_source {
-                                    if name == device.get_name() {
-                                        ButtonType::BorderedRoundSelected
-                                    } else {
-                                        ButtonType::BorderedRound
-                                    }
+                                if sniffer.capture_source.get_device_names().contains(&name) {
+                                    ButtonType::BorderedRoundSelected
                                 } else {
                                     ButtonType::BorderedRound
                                 },
                             )
-                            .on_press(Message::DeviceSelection(name.clone())),
+                            .on_press(
+                                if sniffer.conf.capture_source_picklist
+                                    == CaptureSourcePicklist::Devices
+                                {
+                                    Message::ToggleDeviceSelection(name.clone())
+                                } else {
+                                    Message::DeviceSelection(name.clone())
+                                },
+                            ),
                         )
                     },
                 ),
@@ -340,6 +351,63 @@ fn get_filters_group<'a>(
         .class(ContainerType::BorderedRound)
 }
 
+fn get_pcap_rotation_rows<'a>(
+    export_pcap: &ExportPcap,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let rotation_row = Row::new()
+        .align_y(Alignment::Center)
+        .spacing(5)
+        .push(Text::new(format!(
+            "{}:",
+            new_file_every_translation(language)
+        )))
+        .push(count_input(
+            export_pcap.rotation_megabytes(),
+            Message::OutputPcapRotationSize,
+        ))
+        .push(Text::new("MB"))
+        .push(Space::new().width(10))
+        .push(count_input(
+            export_pcap.rotation_minutes(),
+            Message::OutputPcapRotationInterval,
+        ))
+        .push(Text::new("min"));
+
+    let mut ret_val = Column::new().spacing(10).push(rotation_row);
+
+    if export_pcap.rotation().is_active() {
+        let ring_buffer_row = Row::new()
+            .align_y(Alignment::Center)
+            .spacing(5)
+            .push(Text::new(format!("{}:", keep_last_translation(language))))
+            .push(count_input(
+                export_pcap.ring_buffer_files(),
+                Message::OutputPcapRingBuffer,
+            ))
+            .push(Text::new(files_translation(language)));
+        ret_val = ret_val.push(ring_buffer_row);
+    }
+
+    ret_val
+}
+
+/// Input for a number of units, left empty when zero (i.e., when the option is disabled)
+fn count_input<'a>(
+    value: u32,
+    on_input: fn(String) -> Message,
+) -> TextInput<'a, Message, StyleType> {
+    let value = if value == 0 {
+        String::new()
+    } else {
+        value.to_string()
+    };
+    TextInput::new("-", &value)
+        .on_input(on_input)
+        .padding([2, 5])
+        .width(60)
+}
+
 fn get_export_pcap_group_maybe<'a>(
     cs_pick: CaptureSourcePicklist,
     export_pcap: &ExportPcap,
@@ -389,7 +457,8 @@ fn get_export_pcap_group_maybe<'a>(
                         true,
                         Message::OutputPcapDir,
                     )),
-            );
+            )
+            .push(get_pcap_rotation_rows(export_pcap, language));
         ret_val = ret_val.push(inner_col);
     }
 
@@ -21,6 +21,7 @@
 use crate::gui::types::message::Message;
 use crate::gui::types::settings::Settings;
 use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::capture_context::CaptureSource;
 use crate::networking::types::combobox_data_states::ComboboxStates;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_representation::DataRepr;
@@ -28,6 +29,7 @@
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::report::get_report_entries::get_searched_entries;
 use crate::report::types::report_col::ReportCol;
+use crate::report::types::report_format::ReportFormat;
 use crate::report::types::search_parameters::{FilterInputType, SearchParameters};
 use crate::report::types::sort_type::SortType;
 use crate::translations::translations_2::{
@@ -35,6 +37,10 @@
     only_show_favorites_translation, showing_results_translation,
 };
 use crate::translations::translations_5::{only_show_blacklisted_translation, program_translation};
+use crate::translations::translations_6::{
+    bidirectional_flows_translation, export_results_translation, server_name_translation,
+    tcp_state_translation,
+};
 use crate::utils::formatted_strings::clip_text;
 use crate::utils::types::icon::Icon;
 use crate::{Language, RunningPage, Sniffer, StyleType};
@@ -55,7 +61,9 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
 
     tab_and_body = tab_and_body.push(tabs);
 
-    let report = report(sniffer);
+    let show_adapter = matches!(sniffer.capture_source, CaptureSource::Devices(_));
+
+    let report = report(sniffer, show_adapter);
 
     let col_report = Column::new()
         .height(Length::Fill)
@@ -66,6 +74,7 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
             &sniffer.search,
             sniffer.conf.report_sort_type,
             sniffer.conf.data_repr,
+            show_adapter,
         ))
         .push(Space::new().height(4))
         .push(RuleType::Standard.horizontal(5))
@@ -75,6 +84,7 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
         .push(additional_filters_row(
             &sniffer.search,
             &sniffer.combobox_data_states.states,
+            sniffer.conf.report_flows,
             language,
         ))
         .push(
@@ -82,14 +92,18 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
                 .align_y(Alignment::Center)
                 .align_x(Alignment::Center)
                 .padding(Padding::new(7.0).top(10).bottom(3))
-                .width(947)
+                .width(if show_adapter {
+                    947.0 + ReportCol::Adapter.get_width()
+                } else {
+                    947.0
+                })
                 .class(ContainerType::BorderedRound),
         );
 
     Container::new(Column::new().push(tab_and_body.push(body))).height(Length::Fill)
 }
 
-fn report<'a>(sniffer: &Sniffer) -> Column<'a, Message, StyleType> {
+fn report<'a>(sniffer: &Sniffer, show_adapter: bool) -> Column<'a, Message, StyleType> {
     let Settings { language, .. } = sniffer.conf.settings;
     let data_repr = sniffer.conf.data_repr;
 
@@ -105,9 +119,9 @@ fn report<'a>(sniffer: &Sniffer) -> Column<'a, Message, StyleType> {
     let end_entry_num = start_entry_num + search_results.len() - 1;
     for (key, val) in search_results {
         scroll_report = scroll_report.push(
-            button(row_report_entry(key, val, data_repr))
+            button(row_report_entry(&key, &val, data_repr, show_adapter))
                 .padding(2)
-                .on_press(Message::ShowModal(MyModal::ConnectionDetails(*key)))
+                .on_press(Message::ShowModal(MyModal::ConnectionDetails(key)))
                 .class(ButtonType::Neutral),
         );
     }
@@ -122,7 +136,11 @@ fn report<'a>(sniffer: &Sniffer) -> Column<'a, Message, StyleType> {
                 .width(Length::Fill),
             )
             .push(RuleType::Standard.horizontal(5))
-            .push(get_agglomerates_row(agglomerate, sniffer.conf.data_repr))
+            .push(get_agglomerates_row(
+                agglomerate,
+                sniffer.conf.data_repr,
+                show_adapter,
+            ))
             .push(RuleType::Standard.horizontal(5))
             .push(get_change_page_row(
                 language,
@@ -130,6 +148,7 @@ fn report<'a>(sniffer: &Sniffer) -> Column<'a, Message, StyleType> {
                 start_entry_num,
                 end_entry_num,
                 results_number,
+                sniffer.export_results_error.as_deref(),
             ));
     } else {
         ret_val = ret_val.push(
@@ -154,9 +173,10 @@ fn report_header_row(
     search_params: &SearchParameters,
     sort_type: SortType,
     data_repr: DataRepr,
+    show_adapter: bool,
 ) -> Row<'_, Message, StyleType> {
     let mut ret_val = Row::new().padding([0, 2]).align_y(Alignment::Center);
-    for report_col in ReportCol::ALL {
+    for report_col in ReportCol::displayed(show_adapter) {
         let (title_display, title_small_display, tooltip_val) =
             title_report_col_display(&report_col, data_repr, language);
         let title_row = Row::new()
@@ -249,6 +269,7 @@ fn row_report_entry<'a>(
     key: &AddressPortPair,
     val: &InfoAddressPortPair,
     data_repr: DataRepr,
+    show_adapter: bool,
 ) -> Row<'a, Message, StyleType> {
     let text_type = if val.traffic_direction == TrafficDirection::Outgoing {
         TextType::Outgoing
@@ -258,7 +279,7 @@ fn row_report_entry<'a>(
 
     let mut ret_val = Row::new().align_y(Alignment::Center);
 
-    for report_col in ReportCol::ALL {
+    for report_col in ReportCol::displayed(show_adapter) {
         let max_chars = report_col.get_max_chars(None);
         let col_value = report_col.get_value(key, val, data_repr);
         ret_val = ret_val.push(
@@ -274,6 +295,7 @@ fn row_report_entry<'a>(
 fn additional_filters_row<'a>(
     search_params: &'a SearchParameters,
     combobox_states: &'a ComboboxStates,
+    report_flows: bool,
     language: Language,
 ) -> Row<'a, Message, StyleType> {
     let clear_all_filters: Element<'a, Message, StyleType> =
@@ -324,6 +346,27 @@ fn additional_filters_row<'a>(
     )
     .width(160);
 
+    let combobox_server_name = filter_combobox(
+        FilterInputType::ServerName,
+        &combobox_states.server_names,
+        search_params.clone(),
+    )
+    .width(160);
+
+    let combobox_tcp_state = filter_combobox(
+        FilterInputType::TcpState,
+        &combobox_states.tcp_states,
+        search_params.clone(),
+    )
+    .width(160);
+
+    let combobox_vlan = filter_combobox(
+        FilterInputType::Vlan,
+        &combobox_states.vlans,
+        search_params.clone(),
+    )
+    .width(100);
+
     let container_country = Row::new()
         .spacing(5)
         .align_y(Alignment::Center)
@@ -348,6 +391,24 @@ fn additional_filters_row<'a>(
         .push(Text::new(format!("{}:", program_translation(language))))
         .push(combobox_program);
 
+    let container_server_name = Row::new()
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .push(Text::new(format!("{}:", server_name_translation(language))))
+        .push(combobox_server_name);
+
+    let container_tcp_state = Row::new()
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .push(Text::new(format!("{}:", tcp_state_translation(language))))
+        .push(combobox_tcp_state);
+
+    let container_vlan = Row::new()
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .push(Text::new("VLAN:"))
+        .push(combobox_vlan);
+
     let favorites_only = toggler_filter(
         search_params.only_favorites,
         |new_value| {
@@ -372,16 +433,27 @@ fn additional_filters_row<'a>(
         only_show_blacklisted_translation(language),
     );
 
+    let flows = toggler_filter(
+        report_flows,
+        |_| Message::ReportFlowsToggle,
+        Icon::Waves.codepoint(),
+        bidirectional_flows_translation(language),
+    );
+
     let container = Container::new(
         Row::new()
             .align_y(Alignment::Center)
             .spacing(25)
+            .push(flows)
             .push(blacklisted_only)
             .push(favorites_only)
             .push(container_country)
             .push(container_domain)
             .push(container_as_name)
             .push(container_program)
+            .push(container_server_name)
+            .push(container_tcp_state)
+            .push(container_vlan)
             .wrap()
             .vertical_spacing(5),
     )
@@ -549,8 +621,12 @@ fn get_button_change_page<'a>(increment: bool) -> Button<'a, Message, StyleType>
     .on_press(Message::UpdatePageNumber(increment))
 }
 
-fn get_agglomerates_row<'a>(tot: DataInfo, data_repr: DataRepr) -> Row<'a, Message, StyleType> {
-    let bars = get_bars(data_repr, &tot, &tot).width(ReportCol::FILTER_COLUMNS_WIDTH);
+fn get_agglomerates_row<'a>(
+    tot: DataInfo,
+    data_repr: DataRepr,
+    show_adapter: bool,
+) -> Row<'a, Message, StyleType> {
+    let bars = get_bars(data_repr, &tot, &tot).width(ReportCol::filter_columns_width(show_adapter));
 
     let data_col = Column::new()
         .align_x(Alignment::Center)
@@ -573,6 +649,7 @@ fn get_change_page_row<'a>(
     start_entry_num: usize,
     end_entry_num: usize,
     results_number: usize,
+    export_error: Option<&str>,
 ) -> Row<'a, Message, StyleType> {
     Row::new()
         .height(40)
@@ -595,7 +672,39 @@ fn get_change_page_row<'a>(
         } else {
             Container::new(Space::new().width(25))
         })
-        .push(Space::new().width(Length::Fill))
+        .push(
+            Container::new(export_results_row(language, export_error))
+                .width(Length::Fill)
+                .align_x(Alignment::End),
+        )
+}
+
+fn export_results_row<'a>(
+    language: Language,
+    export_error: Option<&str>,
+) -> Row<'a, Message, StyleType> {
+    ReportFormat::ALL.into_iter().fold(
+        Row::new()
+            .spacing(5)
+            .padding(Padding::ZERO.right(5))
+            .align_y(Alignment::Center)
+            .push(export_error.map(|error| {
+                Text::new(error.to_string())
+                    .class(TextType::Danger)
+                    .size(FONT_SIZE_FOOTER)
+            }))
+            .push(
+                Text::new(format!("{}:", export_results_translation(language)))
+                    .size(FONT_SIZE_FOOTER),
+            ),
+        |row, format| {
+            row.push(
+                button(Text::new(format.to_string()).size(FONT_SIZE_FOOTER))
+                    .padding([2, 7])
+                    .on_press(Message::ExportSearchResults(format)),
+            )
+        },
+    )
 }
 
 fn button_clear_filter<'a>(
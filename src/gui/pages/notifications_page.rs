@@ -19,7 +19,9 @@
 use crate::networking::types::program_lookup::ProgramLookup;
 use crate::networking::types::service::Service;
 use crate::notifications::types::logged_notification::{
-    BlacklistedTransmitted, DataThresholdExceeded, FavoriteTransmitted, LoggedNotification,
+    ArpAnomalyDetected, BandwidthAlert, BandwidthAlertKind, BlacklistedTransmitted,
+    DataThresholdExceeded, FavoriteTransmitted, LoggedNotification, RuleThresholdExceeded,
+    ScanDetected,
 };
 use crate::report::types::sort_type::SortType;
 use crate::translations::translations::{
@@ -28,6 +30,10 @@
     threshold_translation,
 };
 use crate::translations::translations_5::blacklisted_transmitted_translation;
+use crate::translations::translations_6::{
+    arp_anomaly_translation, custom_threshold_exceeded_translation, direction_translation,
+    per_day_translation, scan_translation,
+};
 use crate::utils::types::icon::Icon;
 use crate::{Language, RunningPage, Sniffer, StyleType};
 use iced::Length::FillPortion;
@@ -62,6 +68,11 @@ pub fn notifications_page(sniffer: &Sniffer) -> Container<'_, Message, StyleType
     if notifications.data_notification.threshold.is_none()
         && !notifications.favorite_notification.is_active
         && !notifications.ip_blacklist_notification.is_active
+        && !notifications.arp_anomaly_notification.is_active
+        && !notifications.scan_notification.is_active
+        && !notifications.rate_notification.is_active
+        && !notifications.quota_notification.is_active
+        && !notifications.threshold_rule_notification.is_active
         && sniffer.logged_notifications.is_empty()
     {
         let body = body_no_notifications_set(language);
@@ -281,6 +292,11 @@ fn blacklisted_notification_log<'a>(
                 )
                 .push(
                     Text::new(blacklisted_transmitted_translation(language)).class(TextType::Title),
+                )
+                .push(
+                    Text::new(logged_notification.list.to_string())
+                        .class(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER),
                 ),
         )
         .push(blacklisted_bar);
@@ -291,6 +307,219 @@ fn blacklisted_notification_log<'a>(
         .class(ContainerType::BorderedRound)
 }
 
+fn rule_notification_log<'a>(
+    logged_notification: &RuleThresholdExceeded,
+    first_entry_data_info: DataInfo,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let data_repr = logged_notification.data_repr;
+    let rule_bar = item_bar(
+        Space::new().width(ICONS_SIZE_BIG),
+        logged_notification.entity.clone(),
+        &logged_notification.data_info,
+        data_repr,
+        first_entry_data_info,
+    );
+    let icon = if data_repr == DataRepr::Packets {
+        Icon::PacketsThreshold
+    } else {
+        Icon::BytesThreshold
+    }
+    .to_text()
+    .size(80)
+    .line_height(LineHeight::Relative(1.0));
+    let threshold_str = format!(
+        "{}: {} {}",
+        threshold_translation(language),
+        data_repr.formatted_string(logged_notification.threshold.into()),
+        per_second_translation(language)
+    );
+    let content = Row::new()
+        .spacing(30)
+        .align_y(Alignment::Center)
+        .push(icon)
+        .push(
+            Column::new()
+                .width(250)
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(8)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(logged_notification.timestamp.clone())),
+                )
+                .push(
+                    Text::new(custom_threshold_exceeded_translation(language))
+                        .class(TextType::Title),
+                )
+                .push(
+                    Text::new(threshold_str)
+                        .class(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER),
+                ),
+        )
+        .push(rule_bar);
+
+    Container::new(content)
+        .width(Length::Fill)
+        .padding(15)
+        .class(ContainerType::BorderedRound)
+}
+
+fn arp_anomaly_notification_log<'a>(
+    logged_notification: &ArpAnomalyDetected,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let anomaly = &logged_notification.anomaly;
+    let content = Row::new()
+        .spacing(30)
+        .align_y(Alignment::Center)
+        .push(
+            Icon::Warning
+                .to_text()
+                .size(80)
+                .line_height(LineHeight::Relative(1.0)),
+        )
+        .push(
+            Column::new()
+                .width(250)
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(8)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(logged_notification.timestamp.clone())),
+                )
+                .push(Text::new(arp_anomaly_translation(language)).class(TextType::Title)),
+        )
+        .push(
+            Column::new()
+                .spacing(7)
+                .push(Text::new(anomaly.title(language)))
+                .push(Text::new(anomaly.to_string()).class(TextType::Subtitle)),
+        );
+
+    Container::new(content)
+        .width(Length::Fill)
+        .padding(15)
+        .class(ContainerType::BorderedRound)
+}
+
+fn scan_notification_log<'a>(
+    logged_notification: &ScanDetected,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let scan = &logged_notification.scan;
+    let content = Row::new()
+        .spacing(30)
+        .align_y(Alignment::Center)
+        .push(
+            Icon::Inspect
+                .to_text()
+                .size(80)
+                .line_height(LineHeight::Relative(1.0)),
+        )
+        .push(
+            Column::new()
+                .width(250)
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(8)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(logged_notification.timestamp.clone())),
+                )
+                .push(Text::new(scan_translation(language)).class(TextType::Title)),
+        )
+        .push(
+            Column::new()
+                .spacing(7)
+                .push(Text::new(format!(
+                    "{}: {}",
+                    scan.title(language),
+                    scan.source
+                )))
+                .push(Text::new(scan.targets_string()).class(TextType::Subtitle))
+                .push(
+                    Text::new(format!("SYN: {}   RST: {}", scan.syn_only, scan.resets))
+                        .class(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER),
+                ),
+        );
+
+    Container::new(content)
+        .width(Length::Fill)
+        .padding(15)
+        .class(ContainerType::BorderedRound)
+}
+
+fn bandwidth_notification_log<'a>(
+    logged_notification: &BandwidthAlert,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let data_repr = logged_notification.data_repr;
+    let icon = if data_repr == DataRepr::Packets {
+        Icon::PacketsThreshold
+    } else {
+        Icon::BytesThreshold
+    }
+    .to_text()
+    .size(80)
+    .line_height(LineHeight::Relative(1.0));
+    let (period, duration) = match logged_notification.kind {
+        BandwidthAlertKind::Rate { duration } => {
+            (per_second_translation(language), format!(" ({duration}s)"))
+        }
+        BandwidthAlertKind::Quota => (per_day_translation(language), String::new()),
+    };
+    let threshold_str = format!(
+        "{}: {} {period}{duration}",
+        threshold_translation(language),
+        data_repr.formatted_string(logged_notification.threshold.into()),
+    );
+    let content = Row::new()
+        .spacing(30)
+        .align_y(Alignment::Center)
+        .push(icon)
+        .push(
+            Column::new()
+                .width(250)
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(8)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(logged_notification.timestamp.clone())),
+                )
+                .push(Text::new(logged_notification.title(language)).class(TextType::Title))
+                .push(
+                    Text::new(threshold_str)
+                        .class(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER),
+                ),
+        )
+        .push(
+            Column::new()
+                .spacing(7)
+                .push(Text::new(
+                    data_repr.formatted_string(logged_notification.amount),
+                ))
+                .push(
+                    Text::new(format!(
+                        "{}: {}",
+                        direction_translation(language),
+                        logged_notification.direction.get_label(language)
+                    ))
+                    .class(TextType::Subtitle),
+                ),
+        );
+
+    Container::new(content)
+        .width(Length::Fill)
+        .padding(15)
+        .class(ContainerType::BorderedRound)
+}
+
 fn get_button_clear_all<'a>(language: Language) -> Tooltip<'a, Message, StyleType> {
     let content = button(
         Icon::Bin
@@ -352,6 +581,18 @@ fn logged_notifications(sniffer: &Sniffer) -> Column<'_, Message, StyleType> {
                     language,
                 )
             }
+            LoggedNotification::ArpAnomalyDetected(arp_anomaly_detected) => {
+                arp_anomaly_notification_log(arp_anomaly_detected, language)
+            }
+            LoggedNotification::ScanDetected(scan_detected) => {
+                scan_notification_log(scan_detected, language)
+            }
+            LoggedNotification::BandwidthAlert(bandwidth_alert) => {
+                bandwidth_notification_log(bandwidth_alert, language)
+            }
+            LoggedNotification::RuleThresholdExceeded(rule_threshold_exceeded) => {
+                rule_notification_log(rule_threshold_exceeded, first_entry_data_info, language)
+            }
         });
     }
     ret_val
@@ -22,6 +22,8 @@
 use crate::networking::types::capture_context::CaptureSource;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::vlan_ids::VlanIds;
+use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
 use crate::translations::translations::{
     active_filters_translation, incoming_translation, none_translation, outgoing_translation,
@@ -31,6 +33,7 @@
     data_representation_translation, dropped_translation, only_top_30_items_translation,
 };
 use crate::translations::translations_5::no_favorites_saved_translation;
+use crate::translations::translations_6::{skipped_adapters_translation, untagged_translation};
 use crate::utils::types::icon::Icon;
 use crate::{Language, RunningPage, StyleType};
 use iced::Length::Fill;
@@ -83,12 +86,14 @@ fn row_report(sniffer: &Sniffer) -> Row<'_, Message, StyleType> {
     let col_host = col_favorite_item(sniffer, Favorite::Host);
     let col_service = col_favorite_item(sniffer, Favorite::Service);
     let col_program = col_favorite_item(sniffer, Favorite::Program);
+    let col_vlan = col_vlan(sniffer);
 
     Row::new()
         .spacing(10)
         .push(col_host)
         .push(col_service)
         .push(col_program)
+        .push(col_vlan)
 }
 
 #[allow(clippy::too_many_lines)]
@@ -217,6 +222,79 @@ fn col_favorite_item(
     )
 }
 
+/// Traffic breakdown per VLAN (only displayed if some tagged traffic has been seen)
+fn col_vlan(sniffer: &Sniffer) -> Option<Container<'_, Message, StyleType>> {
+    let Settings { language, .. } = sniffer.conf.settings;
+    let data_repr = sniffer.conf.data_repr;
+    let vlans = &sniffer.info_traffic.vlans;
+
+    if !vlans.keys().any(|vlan| vlan.is_tagged()) {
+        return None;
+    }
+
+    let mut entries: Vec<(&VlanIds, &DataInfo)> = vlans.iter().collect();
+    entries.sort_by(|&(_, a), &(_, b)| a.compare(b, SortType::Descending, data_repr));
+    let first_entry_data_info = entries.first().map(|(_, d)| **d).unwrap_or_default();
+
+    let mut scroll_item = Column::new()
+        .padding(Padding::ZERO.right(11.0))
+        .align_x(Alignment::Center);
+    for (vlan, data_info) in entries.iter().take(30) {
+        let item = if vlan.is_tagged() {
+            vlan.to_string()
+        } else {
+            untagged_translation(language).to_string()
+        };
+        scroll_item = scroll_item.push(
+            button(item_bar(
+                None::<Element<Message, StyleType>>,
+                item,
+                data_info,
+                data_repr,
+                first_entry_data_info,
+            ))
+            .padding(Padding::new(5.0).right(10))
+            .on_press(Message::Search(SearchParameters::new_vlan_search(**vlan)))
+            .class(ButtonType::Neutral),
+        );
+    }
+
+    if entries.len() > 30 {
+        scroll_item = scroll_item.push(
+            Text::new(only_top_30_items_translation(language))
+                .height(50)
+                .align_y(Alignment::Center)
+                .align_x(Alignment::Center),
+        );
+    }
+
+    let col = Column::new()
+        .push(
+            Row::new().height(45).align_y(Alignment::Center).push(
+                Text::new("VLAN")
+                    .class(TextType::Title)
+                    .size(FONT_SIZE_TITLE)
+                    .width(Length::Fill)
+                    .align_x(Alignment::Center),
+            ),
+        )
+        .push(
+            Scrollable::with_direction(
+                scroll_item,
+                Direction::Vertical(ScrollbarType::properties()),
+            )
+            .width(Length::Fill),
+        );
+
+    Some(
+        Container::new(col)
+            .width(Length::FillPortion(1))
+            .height(Length::Fill)
+            .padding(Padding::new(7.0).top(0))
+            .class(ContainerType::BorderedRound),
+    )
+}
+
 pub fn item_bar<'a>(
     icon: impl Into<Element<'a, Message, StyleType>>,
     item: String,
@@ -261,7 +339,12 @@ pub fn item_bar<'a>(
 fn col_info(sniffer: &Sniffer) -> Container<'_, Message, StyleType> {
     let Settings { language, .. } = sniffer.conf.settings;
 
-    let col_device = col_device(language, &sniffer.capture_source, &sniffer.conf.filters);
+    let col_device = col_device(
+        language,
+        &sniffer.capture_source,
+        &sniffer.conf.filters,
+        &sniffer.skipped_adapters,
+    );
 
     let col_data_representation = col_data_representation(language, sniffer.conf.data_repr);
 
@@ -319,6 +402,7 @@ pub(crate) fn col_device<'a>(
     language: Language,
     cs: &'a CaptureSource,
     filters: &'a Filters,
+    skipped_adapters: &[String],
 ) -> Column<'a, Message, StyleType> {
     let link_type = cs.get_link_type();
     #[cfg(not(target_os = "windows"))]
@@ -336,25 +420,38 @@ pub(crate) fn col_device<'a>(
         Text::new(none_translation(language)).into()
     };
 
+    let mut col_cs = Column::new()
+        .push(Text::new(format!("{}:", cs.title(language))).class(TextType::Subtitle))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new(format!("   {cs_info}")))
+                .push(get_info_tooltip(
+                    Column::new()
+                        .spacing(10)
+                        .push(Text::new(link_type.full_print_on_one_line(language)))
+                        .push(get_addresses_row(link_type, cs.get_addresses()))
+                        .into(),
+                )),
+        );
+    if !skipped_adapters.is_empty() {
+        col_cs = col_cs.push(
+            Row::new()
+                .spacing(10)
+                .push(Text::new(format!(
+                    "   {}",
+                    skipped_adapters_translation(language)
+                )))
+                .push(get_info_tooltip(
+                    Text::new(skipped_adapters.join("\n")).into(),
+                )),
+        );
+    }
+
     Column::new()
         .height(Length::Fill)
         .spacing(10)
-        .push(
-            Column::new()
-                .push(Text::new(format!("{}:", cs.title(language))).class(TextType::Subtitle))
-                .push(
-                    Row::new()
-                        .spacing(10)
-                        .push(Text::new(format!("   {cs_info}")))
-                        .push(get_info_tooltip(
-                            Column::new()
-                                .spacing(10)
-                                .push(Text::new(link_type.full_print_on_one_line(language)))
-                                .push(get_addresses_row(link_type, cs.get_addresses()))
-                                .into(),
-                        )),
-                ),
-        )
+        .push(col_cs)
         .push(
             Column::new()
                 .push(
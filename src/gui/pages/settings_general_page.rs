@@ -1,7 +1,12 @@
+use iced::widget::scrollable::Direction;
 use iced::widget::text::LineHeight;
 use iced::widget::tooltip::Position;
-use iced::widget::{Column, Container, PickList, Row, Slider, Space, Text, Tooltip, button};
+use iced::widget::{
+    Checkbox, Column, Container, PickList, Row, Scrollable, Slider, Space, Text, TextInput,
+    Toggler, Tooltip, button,
+};
 use iced::{Alignment, Length, Padding};
+use std::collections::HashMap;
 
 use crate::gui::components::button::{button_open_file, row_open_link_tooltip};
 use crate::gui::components::tab::get_settings_tabs;
@@ -10,19 +15,35 @@
 use crate::gui::styles::button::ButtonType;
 use crate::gui::styles::container::ContainerType;
 use crate::gui::styles::rule::RuleType;
+use crate::gui::styles::scrollbar::ScrollbarType;
 use crate::gui::styles::style_constants::{FONT_SIZE_FOOTER, FONT_SIZE_SUBTITLE, TOOLTIP_DELAY};
 use crate::gui::styles::text::TextType;
 use crate::gui::types::message::Message;
 use crate::gui::types::settings::Settings;
 use crate::mmdb::types::mmdb_reader::{MmdbReader, MmdbReaders};
-use crate::networking::types::ip_blacklist::IpBlacklist;
-use crate::translations::translations::language_translation;
+use crate::networking::flow_export::{FlowExportError, FlowExporter};
+use crate::networking::types::flow_export_conf::{FlowExportConf, FlowProtocol};
+use crate::networking::types::ip_blacklist::{
+    FeedFormat, IpBlacklist, IpListConf, ListCategory, ListSeverity,
+};
+use crate::report::metrics::MetricsServer;
+use crate::report::types::metrics_conf::MetricsConf;
+use crate::translations::translations::{address_translation, language_translation};
 use crate::translations::translations_2::country_translation;
 use crate::translations::translations_3::{
-    mmdb_files_translation, params_not_editable_translation, zoom_translation,
+    mmdb_files_translation, params_not_editable_translation, port_translation, zoom_translation,
 };
 use crate::translations::translations_4::share_feedback_translation;
 use crate::translations::translations_5::ip_blacklist_translation;
+use crate::translations::translations_6::{
+    active_timeout_translation, add_ip_list_translation, allowlist_translation,
+    csv_column_translation, decapsulate_tunnels_translation, export_flows_translation,
+    expose_metrics_translation, extract_server_names_translation, feed_format_translation,
+    flow_export_translation, inactive_timeout_translation, ip_lists_translation,
+    max_label_values_translation, metrics_translation, press_enter_translation,
+    refresh_minutes_translation, server_names_translation, top_hosts_translation,
+    tunnels_translation,
+};
 use crate::utils::formatted_strings::get_path_termination_string;
 use crate::utils::types::file_info::FileInfo;
 use crate::utils::types::icon::Icon;
@@ -42,7 +63,10 @@ pub fn settings_general_page(sniffer: &Sniffer) -> Container<'_, Message, StyleT
         .push(settings_header(color_gradient, language))
         .push(get_settings_tabs(SettingsPage::General, language))
         .push(Space::new().height(10))
-        .push(column_all_general_setting(sniffer));
+        .push(Scrollable::with_direction(
+            column_all_general_setting(sniffer),
+            Direction::Vertical(ScrollbarType::properties().margin(15)),
+        ));
 
     Container::new(content)
         .height(400)
@@ -57,6 +81,11 @@ fn column_all_general_setting(sniffer: &Sniffer) -> Column<'_, Message, StyleTyp
         ref mmdb_country,
         ref mmdb_asn,
         ip_blacklist: ref ip_blacklist_str,
+        ref ip_lists,
+        decapsulate_tunnels,
+        extract_server_names,
+        ref metrics,
+        ref flow_export,
         ..
     } = sniffer.conf.settings;
     let ip_blacklist = &sniffer.ip_blacklist;
@@ -98,7 +127,39 @@ fn column_all_general_setting(sniffer: &Sniffer) -> Column<'_, Message, StyleTyp
             language,
         ));
 
-    column = column.push(import_files_row);
+    column = column
+        .push(import_files_row)
+        .push(RuleType::Standard.horizontal(25))
+        .push(tunnels_setting(is_editable, decapsulate_tunnels, language))
+        .push(RuleType::Standard.horizontal(25))
+        .push(server_names_setting(
+            is_editable,
+            extract_server_names,
+            language,
+        ))
+        .push(RuleType::Standard.horizontal(25))
+        .push(metrics_setting(
+            metrics,
+            sniffer.metrics_server.as_ref(),
+            sniffer.metrics_error.as_deref(),
+            language,
+        ))
+        .push(RuleType::Standard.horizontal(25))
+        .push(flow_export_setting(
+            is_editable,
+            flow_export,
+            sniffer.flow_exporter.as_ref(),
+            sniffer.flow_export_error.as_ref(),
+            language,
+        ))
+        .push(RuleType::Standard.horizontal(25))
+        .push(ip_lists_setting(
+            is_editable,
+            ip_lists,
+            &sniffer.ip_lists,
+            &sniffer.ip_list_url,
+            language,
+        ));
 
     column
 }
@@ -369,6 +430,561 @@ fn blacklist_selection<'a>(
         )
 }
 
+fn tunnels_setting<'a>(
+    is_editable: bool,
+    decapsulate_tunnels: bool,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let mut toggler = Toggler::new(decapsulate_tunnels)
+        .label(decapsulate_tunnels_translation(language))
+        .width(Length::Shrink)
+        .spacing(10)
+        .size(23);
+    if is_editable {
+        toggler = toggler.on_toggle(|_| Message::TunnelDecapsulationToggle);
+    }
+
+    Column::new()
+        .width(Length::Fill)
+        .spacing(5)
+        .padding(Padding::ZERO.bottom(10))
+        .align_x(Alignment::Center)
+        .push(
+            Text::new(tunnels_translation(language))
+                .class(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(toggler)
+}
+
+fn server_names_setting<'a>(
+    is_editable: bool,
+    extract_server_names: bool,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let mut toggler = Toggler::new(extract_server_names)
+        .label(extract_server_names_translation(language))
+        .width(Length::Shrink)
+        .spacing(10)
+        .size(23);
+    if is_editable {
+        toggler = toggler.on_toggle(|_| Message::ServerNamesToggle);
+    }
+
+    Column::new()
+        .width(Length::Fill)
+        .spacing(5)
+        .padding(Padding::ZERO.bottom(10))
+        .align_x(Alignment::Center)
+        .push(
+            Text::new(server_names_translation(language))
+                .class(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(toggler)
+}
+
+fn metrics_setting<'a>(
+    metrics: &MetricsConf,
+    server: Option<&MetricsServer>,
+    error: Option<&str>,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let update = |metrics: MetricsConf| Message::UpdateMetricsConf(metrics);
+    let toggler = Toggler::new(metrics.is_active)
+        .label(expose_metrics_translation(language))
+        .on_toggle(|_| Message::ToggleMetrics)
+        .width(Length::Shrink)
+        .spacing(10)
+        .size(23);
+
+    // the endpoint must be stopped to change its address
+    let mut address_input = TextInput::new("127.0.0.1", &metrics.address)
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(150);
+    let mut port_input = TextInput::new("9797", &metrics.port.to_string())
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(60);
+    if !metrics.is_active {
+        let address_metrics = metrics.clone();
+        address_input = address_input.on_input(move |address| {
+            update(MetricsConf {
+                address,
+                ..address_metrics.clone()
+            })
+        });
+        let port_metrics = metrics.clone();
+        port_input = port_input.on_input(move |value| {
+            update(MetricsConf {
+                port: value.parse().unwrap_or(port_metrics.port),
+                ..port_metrics.clone()
+            })
+        });
+    }
+    let top_hosts_metrics = metrics.clone();
+    let top_hosts_input = TextInput::new("10", &metrics.top_hosts.to_string())
+        .on_input(move |value| {
+            update(MetricsConf {
+                top_hosts: value.parse().unwrap_or(top_hosts_metrics.top_hosts),
+                ..top_hosts_metrics.clone()
+            })
+        })
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(50);
+    let label_values_metrics = metrics.clone();
+    let label_values_input = TextInput::new("20", &metrics.max_label_values.to_string())
+        .on_input(move |value| {
+            update(MetricsConf {
+                max_label_values: value
+                    .parse()
+                    .unwrap_or(label_values_metrics.max_label_values),
+                ..label_values_metrics.clone()
+            })
+        })
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(50);
+
+    let inputs_row = Row::new()
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .push(Text::new(format!("{}:", address_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(address_input)
+        .push(Space::new().width(5))
+        .push(Text::new(format!("{}:", port_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(port_input)
+        .push(Space::new().width(5))
+        .push(Text::new(format!("{}:", top_hosts_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(top_hosts_input)
+        .push(Space::new().width(5))
+        .push(
+            Text::new(format!("{}:", max_label_values_translation(language)))
+                .size(FONT_SIZE_FOOTER),
+        )
+        .push(label_values_input);
+
+    let status = if let Some(server) = server {
+        Some(Text::new(format!("http://{}/metrics", server.address())).size(FONT_SIZE_FOOTER))
+    } else {
+        error.map(|e| {
+            Text::new(e.to_string())
+                .class(TextType::Danger)
+                .size(FONT_SIZE_FOOTER)
+        })
+    };
+
+    Column::new()
+        .width(Length::Fill)
+        .spacing(10)
+        .padding(Padding::ZERO.bottom(10))
+        .align_x(Alignment::Center)
+        .push(
+            Text::new(metrics_translation(language))
+                .class(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(toggler)
+        .push(inputs_row)
+        .push(status)
+}
+
+fn flow_export_setting<'a>(
+    is_editable: bool,
+    flow_export: &FlowExportConf,
+    exporter: Option<&FlowExporter>,
+    error: Option<&FlowExportError>,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let update = |flow_export: FlowExportConf| Message::UpdateFlowExportConf(flow_export);
+    let mut toggler = Toggler::new(flow_export.is_active)
+        .label(export_flows_translation(language))
+        .width(Length::Shrink)
+        .spacing(10)
+        .size(23);
+    let mut address_input = TextInput::new("127.0.0.1", &flow_export.address)
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(150);
+    let mut port_input = TextInput::new("2055", &flow_export.port.to_string())
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(60);
+    // the settings apply from the next capture
+    if is_editable {
+        toggler = toggler.on_toggle(|_| Message::ToggleFlowExport);
+        let address_conf = flow_export.clone();
+        address_input = address_input.on_input(move |address| {
+            update(FlowExportConf {
+                address,
+                ..address_conf.clone()
+            })
+        });
+        let port_conf = flow_export.clone();
+        port_input = port_input.on_input(move |value| {
+            update(FlowExportConf {
+                port: value.parse().unwrap_or(port_conf.port),
+                ..port_conf.clone()
+            })
+        });
+    }
+
+    let mut collector_row = Row::new().spacing(5).align_y(Alignment::Center);
+    if is_editable {
+        let protocol_conf = flow_export.clone();
+        collector_row = collector_row.push(
+            PickList::new(
+                &FlowProtocol::ALL[..],
+                Some(flow_export.protocol),
+                move |protocol| {
+                    update(FlowExportConf {
+                        protocol,
+                        ..protocol_conf.clone()
+                    })
+                },
+            )
+            .text_size(FONT_SIZE_FOOTER)
+            .padding([2, 7]),
+        );
+    } else {
+        collector_row =
+            collector_row.push(Text::new(flow_export.protocol.to_string()).size(FONT_SIZE_FOOTER));
+    }
+    collector_row = collector_row
+        .push(Space::new().width(5))
+        .push(Text::new(format!("{}:", address_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(address_input)
+        .push(Space::new().width(5))
+        .push(Text::new(format!("{}:", port_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(port_input);
+    Column::new()
+        .width(Length::Fill)
+        .spacing(10)
+        .padding(Padding::ZERO.bottom(10))
+        .align_x(Alignment::Center)
+        .push(
+            Text::new(flow_export_translation(language))
+                .class(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(toggler)
+        .push(collector_row)
+        .push(flow_export_timeouts_row(is_editable, flow_export, language))
+        .push(flow_export_status(flow_export, exporter, error, language))
+}
+
+fn flow_export_timeouts_row<'a>(
+    is_editable: bool,
+    flow_export: &FlowExportConf,
+    language: Language,
+) -> Row<'a, Message, StyleType> {
+    let update = |flow_export: FlowExportConf| Message::UpdateFlowExportConf(flow_export);
+    let mut active_input = TextInput::new("60", &flow_export.active_timeout.to_string())
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(50);
+    let mut inactive_input = TextInput::new("15", &flow_export.inactive_timeout.to_string())
+        .size(FONT_SIZE_FOOTER)
+        .padding([2, 5])
+        .width(50);
+    if is_editable {
+        let active_conf = flow_export.clone();
+        active_input = active_input.on_input(move |value| {
+            update(FlowExportConf {
+                active_timeout: value.parse().unwrap_or(active_conf.active_timeout),
+                ..active_conf.clone()
+            })
+        });
+        let inactive_conf = flow_export.clone();
+        inactive_input = inactive_input.on_input(move |value| {
+            update(FlowExportConf {
+                inactive_timeout: value.parse().unwrap_or(inactive_conf.inactive_timeout),
+                ..inactive_conf.clone()
+            })
+        });
+    }
+
+    Row::new()
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .push(
+            Text::new(format!("{}:", active_timeout_translation(language))).size(FONT_SIZE_FOOTER),
+        )
+        .push(active_input)
+        .push(Space::new().width(5))
+        .push(
+            Text::new(format!("{}:", inactive_timeout_translation(language)))
+                .size(FONT_SIZE_FOOTER),
+        )
+        .push(inactive_input)
+}
+
+/// Collector the records are being exported to, or the reason why they can't be exported
+fn flow_export_status<'a>(
+    flow_export: &FlowExportConf,
+    exporter: Option<&FlowExporter>,
+    error: Option<&FlowExportError>,
+    language: Language,
+) -> Option<Text<'a, StyleType>> {
+    if let Some(exporter) = exporter {
+        return Some(
+            Text::new(format!(
+                "{} → {}",
+                flow_export.protocol,
+                exporter.collector()
+            ))
+            .size(FONT_SIZE_FOOTER),
+        );
+    }
+    let error = match error {
+        Some(error) => error.get_message(language),
+        None if flow_export.is_active && flow_export.collector().is_none() => {
+            FlowExportError::InvalidAddress(flow_export.address.clone()).get_message(language)
+        }
+        None => return None,
+    };
+    Some(
+        Text::new(error)
+            .class(TextType::Danger)
+            .size(FONT_SIZE_FOOTER),
+    )
+}
+
+fn ip_lists_setting<'a>(
+    is_editable: bool,
+    ip_lists: &[IpListConf],
+    loaded: &HashMap<String, IpBlacklist>,
+    ip_list_url: &str,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let mut column = Column::new()
+        .width(Length::Fill)
+        .spacing(10)
+        .padding(Padding::ZERO.bottom(10))
+        .align_x(Alignment::Center)
+        .push(
+            Text::new(ip_lists_translation(language))
+                .class(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        );
+
+    for (index, list) in ip_lists.iter().enumerate() {
+        column = column.push(ip_list_row(
+            is_editable,
+            index,
+            list,
+            loaded.get(&list.path),
+            language,
+        ));
+        if is_editable {
+            column = column.push(ip_list_source_row(index, list, language));
+        }
+    }
+
+    let mut url_input = TextInput::new(
+        &format!("https://... ({})", press_enter_translation(language)),
+        ip_list_url,
+    )
+    .padding([2, 5])
+    .width(300);
+    if is_editable {
+        url_input = url_input
+            .on_input(Message::IpListUrl)
+            .on_submit(Message::AddIpList(ip_list_url.to_string()));
+    }
+
+    column.push(
+        Row::new()
+            .spacing(5)
+            .align_y(Alignment::Center)
+            .push(Text::new(add_ip_list_translation(language)).size(FONT_SIZE_FOOTER))
+            .push(button_open_file(
+                String::new(),
+                FileInfo::Blacklist,
+                language,
+                is_editable,
+                Message::AddIpList,
+            ))
+            .push(Space::new().width(10))
+            .push(url_input),
+    )
+}
+
+/// Settings about the source of a list: its format and, for lists served over HTTP, the refresh interval
+fn ip_list_source_row<'a>(
+    index: usize,
+    list: &IpListConf,
+    language: Language,
+) -> Row<'a, Message, StyleType> {
+    let update = move |list: IpListConf| Message::UpdateIpList(index, list);
+    let format_list = list.clone();
+    let mut row = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .padding(Padding::ZERO.left(30))
+        .push(Text::new(format!("{}:", feed_format_translation(language))).size(FONT_SIZE_FOOTER))
+        .push(
+            PickList::new(&FeedFormat::ALL[..], Some(list.format), move |format| {
+                update(IpListConf {
+                    format,
+                    ..format_list.clone()
+                })
+            })
+            .text_size(FONT_SIZE_FOOTER)
+            .padding([2, 7]),
+        );
+
+    if list.format == FeedFormat::Csv {
+        let column_list = list.clone();
+        row = row
+            .push(
+                Text::new(format!("{}:", csv_column_translation(language))).size(FONT_SIZE_FOOTER),
+            )
+            .push(
+                TextInput::new("1", &list.csv_column.to_string())
+                    .on_input(move |value| {
+                        update(IpListConf {
+                            csv_column: value.parse().unwrap_or(column_list.csv_column),
+                            ..column_list.clone()
+                        })
+                    })
+                    .size(FONT_SIZE_FOOTER)
+                    .padding([2, 5])
+                    .width(40),
+            );
+    }
+
+    if list.is_url() {
+        let refresh_list = list.clone();
+        row = row
+            .push(
+                Text::new(format!("{}:", refresh_minutes_translation(language)))
+                    .size(FONT_SIZE_FOOTER),
+            )
+            .push(
+                TextInput::new("0", &refresh_list.refresh_minutes.to_string())
+                    .on_input(move |value| {
+                        update(IpListConf {
+                            refresh_minutes: value.parse().unwrap_or(refresh_list.refresh_minutes),
+                            ..refresh_list.clone()
+                        })
+                    })
+                    .size(FONT_SIZE_FOOTER)
+                    .padding([2, 5])
+                    .width(60),
+            );
+    }
+
+    row
+}
+
+fn ip_list_row<'a>(
+    is_editable: bool,
+    index: usize,
+    list: &IpListConf,
+    loaded: Option<&IpBlacklist>,
+    language: Language,
+) -> Row<'a, Message, StyleType> {
+    let is_error = loaded.is_none_or(IpBlacklist::is_invalid);
+    let items_info = loaded
+        .and_then(IpBlacklist::imported_items_info)
+        .unwrap_or_default();
+    let file_text = Text::new(format!(
+        "{} {items_info}",
+        get_path_termination_string(&list.path, 25)
+    ))
+    .size(FONT_SIZE_FOOTER)
+    .class(if is_error {
+        TextType::Danger
+    } else {
+        TextType::Standard
+    });
+
+    let row = Row::new().spacing(10).align_y(Alignment::Center);
+
+    if !is_editable {
+        let kind = if list.is_allowlist {
+            allowlist_translation(language).to_string()
+        } else {
+            format!("{}, {}", list.category, list.severity)
+        };
+        return row
+            .push(Text::new(format!("{} ({kind})", list.name)))
+            .push(file_text);
+    }
+
+    let update = move |list: IpListConf| Message::UpdateIpList(index, list);
+    let name_list = list.clone();
+    let category_list = list.clone();
+    let severity_list = list.clone();
+    let allowlist_list = list.clone();
+
+    row.push(
+        button(
+            Icon::Bin
+                .to_text()
+                .size(FONT_SIZE_FOOTER)
+                .align_x(Alignment::Center)
+                .align_y(Alignment::Center),
+        )
+        .padding(0)
+        .height(20)
+        .width(20)
+        .on_press(Message::RemoveIpList(index)),
+    )
+    .push(
+        TextInput::new("", &list.name)
+            .on_input(move |name| {
+                update(IpListConf {
+                    name,
+                    ..name_list.clone()
+                })
+            })
+            .padding([2, 5])
+            .width(150),
+    )
+    .push(
+        PickList::new(
+            &ListCategory::ALL[..],
+            Some(list.category),
+            move |category| {
+                update(IpListConf {
+                    category,
+                    ..category_list.clone()
+                })
+            },
+        )
+        .padding([2, 7]),
+    )
+    .push(
+        PickList::new(
+            &ListSeverity::ALL[..],
+            Some(list.severity),
+            move |severity| {
+                update(IpListConf {
+                    severity,
+                    ..severity_list.clone()
+                })
+            },
+        )
+        .padding([2, 7]),
+    )
+    .push(
+        Checkbox::new(list.is_allowlist)
+            .label(allowlist_translation(language))
+            .on_toggle(move |is_allowlist| {
+                update(IpListConf {
+                    is_allowlist,
+                    ..allowlist_list.clone()
+                })
+            })
+            .size(16),
+    )
+    .push(file_text)
+}
+
 fn button_clear_mmdb<'a>(
     message: fn(String) -> Message,
     is_editable: bool,
@@ -2,6 +2,7 @@
 use iced::widget::{Button, Slider, row};
 use iced::widget::{Checkbox, Column, Container, Row, Scrollable, Space, Text, TextInput};
 use iced::{Alignment, Length, Padding};
+use std::ops::RangeInclusive;
 
 use crate::gui::components::button::button_hide;
 use crate::gui::components::tab::get_settings_tabs;
@@ -13,12 +14,16 @@
 use crate::gui::styles::style_constants::{FONT_SIZE_FOOTER, FONT_SIZE_SUBTITLE, FONT_SIZE_TITLE};
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::types::favorite::{FavoriteKey, Favorites};
 use crate::gui::types::message::Message;
-use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::data_representation::{ByteMultiple, DataRepr};
 use crate::notifications::types::notifications::{
-    DataNotification, Notification, RemoteNotifications, SimpleNotification,
+    DataDirection, DataNotification, Notification, QuotaNotification, RateNotification,
+    RemoteNotifications, ScanNotification, SimpleNotification,
 };
 use crate::notifications::types::sound::Sound;
+use crate::notifications::types::threshold_rule::{RuleTarget, ThresholdRule};
+use crate::report::types::search_parameters::SearchParameters;
 use crate::translations::translations::{
     favorite_transmitted_translation, notifications_title_translation, per_second_translation,
     settings_translation, sound_translation, threshold_translation, volume_translation,
@@ -28,6 +33,13 @@
 use crate::translations::translations_5::{
     blacklisted_transmitted_translation, remote_notifications_translation,
 };
+use crate::translations::translations_6::{
+    add_threshold_translation, arp_anomaly_translation, current_filters_translation,
+    custom_thresholds_hint_translation, custom_thresholds_translation, daily_quota_translation,
+    direction_translation, duration_seconds_translation, hosts_threshold_translation,
+    hysteresis_translation, per_day_translation, ports_threshold_translation, scan_translation,
+    sustained_rate_translation,
+};
 use crate::utils::types::icon::Icon;
 use crate::{Language, Sniffer, StyleType};
 
@@ -81,6 +93,20 @@ pub fn settings_notifications_page<'a>(sniffer: &Sniffer) -> Container<'a, Messa
                     notifications.ip_blacklist_notification,
                     language,
                 ))
+                .push(get_arp_anomaly_notify(
+                    notifications.arp_anomaly_notification,
+                    language,
+                ))
+                .push(get_scan_notify(notifications.scan_notification, language))
+                .push(get_rate_notify(notifications.rate_notification, language))
+                .push(get_quota_notify(notifications.quota_notification, language))
+                .push(get_threshold_rules_notify(
+                    notifications.threshold_rule_notification,
+                    &notifications.threshold_rules,
+                    &sniffer.conf.favorites,
+                    &sniffer.search,
+                    language,
+                ))
                 .push(
                     Container::new(RuleType::Standard.horizontal(10))
                         .padding(Padding::ZERO.left(40).right(40)),
@@ -228,6 +254,443 @@ fn get_ip_blacklist_notify<'a>(
     }
 }
 
+fn get_arp_anomaly_notify<'a>(
+    arp_anomaly_notification: SimpleNotification,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let checkbox = Checkbox::new(arp_anomaly_notification.is_active)
+        .label(arp_anomaly_translation(language))
+        .on_toggle(move |toggled| {
+            Message::UpdateNotificationSettings(
+                if toggled {
+                    Notification::ArpAnomaly(SimpleNotification::on(arp_anomaly_notification.sound))
+                } else {
+                    Notification::ArpAnomaly(SimpleNotification::off(
+                        arp_anomaly_notification.sound,
+                    ))
+                },
+                false,
+            )
+        })
+        .size(18);
+
+    let mut ret_val = Column::new().spacing(15).push(checkbox);
+
+    if arp_anomaly_notification.is_active {
+        let sound_row = sound_buttons(Notification::ArpAnomaly(arp_anomaly_notification), language);
+        ret_val = ret_val.push(sound_row);
+    }
+    Container::new(ret_val)
+        .padding(15)
+        .width(CONTAINERS_WIDTH)
+        .class(ContainerType::BorderedRound)
+}
+
+fn get_scan_notify<'a>(
+    scan_notification: ScanNotification,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let checkbox = Checkbox::new(scan_notification.is_active)
+        .label(scan_translation(language))
+        .on_toggle(move |is_active| {
+            Message::UpdateNotificationSettings(
+                Notification::Scan(ScanNotification {
+                    is_active,
+                    ..scan_notification
+                }),
+                false,
+            )
+        })
+        .size(18);
+
+    let mut ret_val = Column::new().spacing(15).push(checkbox);
+
+    if scan_notification.is_active {
+        let sound_row = sound_buttons(Notification::Scan(scan_notification), language);
+        let ports_slider = threshold_slider(
+            ports_threshold_translation(language),
+            5..=200,
+            scan_notification.ports_threshold,
+            move |ports_threshold| {
+                Message::UpdateNotificationSettings(
+                    Notification::Scan(ScanNotification {
+                        ports_threshold,
+                        ..scan_notification
+                    }),
+                    false,
+                )
+            },
+        );
+        let hosts_slider = threshold_slider(
+            hosts_threshold_translation(language),
+            2..=100,
+            scan_notification.hosts_threshold,
+            move |hosts_threshold| {
+                Message::UpdateNotificationSettings(
+                    Notification::Scan(ScanNotification {
+                        hosts_threshold,
+                        ..scan_notification
+                    }),
+                    false,
+                )
+            },
+        );
+        ret_val = ret_val
+            .push(sound_row)
+            .push(ports_slider)
+            .push(hosts_slider);
+    }
+    Container::new(ret_val)
+        .padding(15)
+        .width(CONTAINERS_WIDTH)
+        .class(ContainerType::BorderedRound)
+}
+
+fn get_rate_notify<'a>(
+    rate_notification: RateNotification,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let update = move |rate_notification| {
+        Message::UpdateNotificationSettings(Notification::Rate(rate_notification), false)
+    };
+    let checkbox = Checkbox::new(rate_notification.is_active)
+        .label(sustained_rate_translation(language))
+        .on_toggle(move |is_active| {
+            update(RateNotification {
+                is_active,
+                ..rate_notification
+            })
+        })
+        .size(18);
+
+    let mut ret_val = Column::new().spacing(15).push(checkbox);
+
+    if rate_notification.is_active {
+        let sound_row = sound_buttons(Notification::Rate(rate_notification), language);
+        let direction_row =
+            row_direction(language, rate_notification.direction, move |direction| {
+                update(RateNotification {
+                    direction,
+                    ..rate_notification
+                })
+            });
+        let data_repr_row = row_options(
+            data_representation_translation(language),
+            &DataRepr::ALL,
+            rate_notification.data_repr,
+            |data_repr| data_repr.get_label(language).to_owned(),
+            move |data_repr| {
+                update(RateNotification {
+                    data_repr,
+                    ..rate_notification
+                })
+            },
+        );
+        let input = threshold_input(
+            language,
+            rate_notification.threshold,
+            rate_notification.byte_multiple,
+            per_second_translation(language),
+            move |value| update(rate_notification.with_threshold(&value)),
+        );
+        let duration_slider = threshold_slider(
+            duration_seconds_translation(language),
+            1..=300,
+            rate_notification.duration,
+            move |duration| {
+                update(RateNotification {
+                    duration,
+                    ..rate_notification
+                })
+            },
+        );
+        let hysteresis_slider = threshold_slider(
+            hysteresis_translation(language),
+            0..=90,
+            u16::from(rate_notification.hysteresis),
+            move |hysteresis| {
+                update(RateNotification {
+                    hysteresis: u8::try_from(hysteresis).unwrap_or_default(),
+                    ..rate_notification
+                })
+            },
+        );
+        ret_val = ret_val
+            .push(sound_row)
+            .push(direction_row)
+            .push(data_repr_row)
+            .push(input)
+            .push(duration_slider)
+            .push(hysteresis_slider);
+    }
+    Container::new(ret_val)
+        .padding(15)
+        .width(CONTAINERS_WIDTH)
+        .class(ContainerType::BorderedRound)
+}
+
+fn get_quota_notify<'a>(
+    quota_notification: QuotaNotification,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let update = move |quota_notification| {
+        Message::UpdateNotificationSettings(Notification::Quota(quota_notification), false)
+    };
+    let checkbox = Checkbox::new(quota_notification.is_active)
+        .label(daily_quota_translation(language))
+        .on_toggle(move |is_active| {
+            update(QuotaNotification {
+                is_active,
+                ..quota_notification
+            })
+        })
+        .size(18);
+
+    let mut ret_val = Column::new().spacing(15).push(checkbox);
+
+    if quota_notification.is_active {
+        let sound_row = sound_buttons(Notification::Quota(quota_notification), language);
+        let direction_row =
+            row_direction(language, quota_notification.direction, move |direction| {
+                update(QuotaNotification {
+                    direction,
+                    ..quota_notification
+                })
+            });
+        let data_repr_row = row_options(
+            data_representation_translation(language),
+            &DataRepr::ALL,
+            quota_notification.data_repr,
+            |data_repr| data_repr.get_label(language).to_owned(),
+            move |data_repr| {
+                update(QuotaNotification {
+                    data_repr,
+                    ..quota_notification
+                })
+            },
+        );
+        let input = threshold_input(
+            language,
+            quota_notification.threshold,
+            quota_notification.byte_multiple,
+            per_day_translation(language),
+            move |value| update(quota_notification.with_threshold(&value)),
+        );
+        let hysteresis_slider = threshold_slider(
+            hysteresis_translation(language),
+            0..=90,
+            u16::from(quota_notification.hysteresis),
+            move |hysteresis| {
+                update(QuotaNotification {
+                    hysteresis: u8::try_from(hysteresis).unwrap_or_default(),
+                    ..quota_notification
+                })
+            },
+        );
+        ret_val = ret_val
+            .push(sound_row)
+            .push(direction_row)
+            .push(data_repr_row)
+            .push(input)
+            .push(hysteresis_slider);
+    }
+    Container::new(ret_val)
+        .padding(15)
+        .width(CONTAINERS_WIDTH)
+        .class(ContainerType::BorderedRound)
+}
+
+fn get_threshold_rules_notify<'a>(
+    threshold_rule_notification: SimpleNotification,
+    rules: &[ThresholdRule],
+    favorites: &Favorites,
+    search: &SearchParameters,
+    language: Language,
+) -> Container<'a, Message, StyleType> {
+    let checkbox = Checkbox::new(threshold_rule_notification.is_active)
+        .label(custom_thresholds_translation(language))
+        .on_toggle(move |toggled| {
+            Message::UpdateNotificationSettings(
+                if toggled {
+                    Notification::ThresholdRule(SimpleNotification::on(
+                        threshold_rule_notification.sound,
+                    ))
+                } else {
+                    Notification::ThresholdRule(SimpleNotification::off(
+                        threshold_rule_notification.sound,
+                    ))
+                },
+                false,
+            )
+        })
+        .size(18);
+
+    let mut ret_val = Column::new().spacing(15).push(checkbox);
+
+    if threshold_rule_notification.is_active {
+        ret_val = ret_val.push(sound_buttons(
+            Notification::ThresholdRule(threshold_rule_notification),
+            language,
+        ));
+        for (index, rule) in rules.iter().enumerate() {
+            ret_val = ret_val.push(threshold_rule_row(index, rule, language));
+        }
+
+        // favorites and search without a rule yet
+        let mut candidates: Vec<RuleTarget> = favorites
+            .hosts()
+            .iter()
+            .map(|host| FavoriteKey::Host(host.clone()))
+            .chain(
+                favorites
+                    .services()
+                    .iter()
+                    .map(|s| FavoriteKey::Service(*s)),
+            )
+            .chain(
+                favorites
+                    .programs()
+                    .iter()
+                    .map(|program| FavoriteKey::Program(program.clone())),
+            )
+            .map(RuleTarget::Favorite)
+            .filter(|target| !rules.iter().any(|rule| rule.target.eq(target)))
+            .collect();
+        candidates.sort_by_key(RuleTarget::name);
+        let search_target = RuleTarget::Search(Box::new(search.clone()));
+        let is_search_candidate = !SearchParameters::default().eq(search)
+            && !rules.iter().any(|rule| rule.target.eq(&search_target));
+
+        if candidates.is_empty() && !is_search_candidate {
+            if rules.is_empty() {
+                ret_val = ret_val.push(
+                    Text::new(custom_thresholds_hint_translation(language))
+                        .class(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER),
+                );
+            }
+        } else {
+            let mut add_row = Row::new()
+                .spacing(5)
+                .align_y(Alignment::Center)
+                .padding(Padding::ZERO.left(26))
+                .push(Text::new(format!(
+                    "{}:",
+                    add_threshold_translation(language)
+                )));
+            if is_search_candidate {
+                add_row = add_row.push(button_add_rule(
+                    current_filters_translation(language).to_string(),
+                    search_target,
+                ));
+            }
+            for target in candidates {
+                add_row = add_row.push(button_add_rule(target.name(), target));
+            }
+            ret_val = ret_val.push(add_row.wrap().vertical_spacing(5));
+        }
+    }
+    Container::new(ret_val)
+        .padding(15)
+        .width(CONTAINERS_WIDTH)
+        .class(ContainerType::BorderedRound)
+}
+
+fn threshold_rule_row<'a>(
+    index: usize,
+    rule: &ThresholdRule,
+    language: Language,
+) -> Column<'a, Message, StyleType> {
+    let header = Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .padding(Padding::ZERO.left(26))
+        .push(
+            Button::new(
+                Icon::Bin
+                    .to_text()
+                    .size(FONT_SIZE_FOOTER)
+                    .align_x(Alignment::Center)
+                    .align_y(Alignment::Center),
+            )
+            .padding(0)
+            .height(20)
+            .width(20)
+            .on_press(Message::RemoveThresholdRule(index)),
+        )
+        .push(Text::new(rule.target.name()).class(TextType::Title));
+    let data_repr_rule = rule.clone();
+    let data_repr_row = row_options(
+        data_representation_translation(language),
+        &DataRepr::ALL,
+        rule.data_repr,
+        |data_repr| data_repr.get_label(language).to_owned(),
+        move |data_repr| {
+            Message::UpdateThresholdRule(
+                index,
+                ThresholdRule {
+                    data_repr,
+                    ..data_repr_rule.clone()
+                },
+            )
+        },
+    );
+    let threshold_rule = rule.clone();
+    let input = threshold_input(
+        language,
+        rule.threshold,
+        rule.byte_multiple,
+        per_second_translation(language),
+        move |value| Message::UpdateThresholdRule(index, threshold_rule.with_threshold(&value)),
+    );
+    Column::new()
+        .spacing(10)
+        .push(header)
+        .push(data_repr_row)
+        .push(input)
+}
+
+fn button_add_rule<'a>(label: String, target: RuleTarget) -> Button<'a, Message, StyleType> {
+    Button::new(
+        Text::new(label)
+            .size(FONT_SIZE_FOOTER)
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center),
+    )
+    .padding(Padding::ZERO.left(10).right(10))
+    .height(25)
+    .class(ButtonType::BorderedRound)
+    .on_press(Message::AddThresholdRule(target))
+}
+
+fn row_direction<'a>(
+    language: Language,
+    direction: DataDirection,
+    on_press: impl Fn(DataDirection) -> Message,
+) -> row::Wrapping<'a, Message, StyleType> {
+    row_options(
+        direction_translation(language),
+        &DataDirection::ALL,
+        direction,
+        |direction| direction.get_label(language).to_owned(),
+        on_press,
+    )
+}
+
+fn threshold_slider<'a>(
+    label: &str,
+    range: RangeInclusive<u16>,
+    value: u16,
+    on_change: impl Fn(u16) -> Message + 'a,
+) -> Row<'a, Message, StyleType> {
+    Row::new()
+        .spacing(10)
+        .align_y(Alignment::Center)
+        .padding(Padding::ZERO.left(26))
+        .push(Text::new(format!("{label}: {value:^3}")).width(260))
+        .push(Slider::new(range, value, on_change).width(200))
+}
+
 fn get_remote_notifications<'a>(
     remote_notifications: &RemoteNotifications,
     language: Language,
@@ -267,10 +730,27 @@ fn input_group_bytes<'a>(
     bytes_notification: DataNotification,
     language: Language,
 ) -> Container<'a, Message, StyleType> {
-    let mut curr_threshold_str = (bytes_notification.threshold.unwrap_or_default()
-        / bytes_notification.byte_multiple.multiplier())
-    .to_string();
-    curr_threshold_str.push_str(bytes_notification.byte_multiple.get_char());
+    threshold_input(
+        language,
+        bytes_notification.threshold.unwrap_or_default(),
+        bytes_notification.byte_multiple,
+        per_second_translation(language),
+        move |value| {
+            let bytes_notification = DataNotification::from(&value, Some(bytes_notification));
+            Message::UpdateNotificationSettings(Notification::Data(bytes_notification), false)
+        },
+    )
+}
+
+fn threshold_input<'a>(
+    language: Language,
+    threshold: u64,
+    byte_multiple: ByteMultiple,
+    period: &'static str,
+    on_input: impl Fn(String) -> Message + 'a,
+) -> Container<'a, Message, StyleType> {
+    let mut curr_threshold_str = (threshold / byte_multiple.multiplier()).to_string();
+    curr_threshold_str.push_str(byte_multiple.get_char());
     let input_row = Row::new()
         .spacing(5)
         .align_y(Alignment::Center)
@@ -285,15 +765,12 @@ fn input_group_bytes<'a>(
                     &curr_threshold_str
                 },
             )
-            .on_input(move |value| {
-                let bytes_notification = DataNotification::from(&value, Some(bytes_notification));
-                Message::UpdateNotificationSettings(Notification::Data(bytes_notification), false)
-            })
+            .on_input(on_input)
             .padding([2, 5])
             .width(100),
         )
         .push(
-            Text::new(per_second_translation(language))
+            Text::new(period)
                 .align_y(Alignment::Center)
                 .size(FONT_SIZE_FOOTER),
         );
@@ -348,7 +825,13 @@ fn sound_buttons<'a>(
 ) -> row::Wrapping<'a, Message, StyleType> {
     let current_sound = match notification {
         Notification::Data(n) => n.sound,
-        Notification::Favorite(n) | Notification::IpBlacklist(n) => n.sound,
+        Notification::Favorite(n)
+        | Notification::IpBlacklist(n)
+        | Notification::ArpAnomaly(n)
+        | Notification::ThresholdRule(n) => n.sound,
+        Notification::Scan(n) => n.sound,
+        Notification::Rate(n) => n.sound,
+        Notification::Quota(n) => n.sound,
     };
 
     let mut ret_val = Row::new()
@@ -368,6 +851,15 @@ fn sound_buttons<'a>(
             Notification::IpBlacklist(n) => {
                 Notification::IpBlacklist(SimpleNotification { sound: option, ..n })
             }
+            Notification::ArpAnomaly(n) => {
+                Notification::ArpAnomaly(SimpleNotification { sound: option, ..n })
+            }
+            Notification::Scan(n) => Notification::Scan(ScanNotification { sound: option, ..n }),
+            Notification::Rate(n) => Notification::Rate(RateNotification { sound: option, ..n }),
+            Notification::Quota(n) => Notification::Quota(QuotaNotification { sound: option, ..n }),
+            Notification::ThresholdRule(n) => {
+                Notification::ThresholdRule(SimpleNotification { sound: option, ..n })
+            }
         };
         ret_val = ret_val.push(
             Button::new(
@@ -422,22 +914,44 @@ fn row_data_representation<'a>(
     data_notification: DataNotification,
     language: Language,
     data_repr: DataRepr,
+) -> row::Wrapping<'a, Message, StyleType> {
+    row_options(
+        data_representation_translation(language),
+        &DataRepr::ALL,
+        data_repr,
+        |option| option.get_label(language).to_owned(),
+        move |option| {
+            Message::UpdateNotificationSettings(
+                Notification::Data(DataNotification {
+                    data_repr: option,
+                    ..data_notification
+                }),
+                false,
+            )
+        },
+    )
+}
+
+/// Row of buttons to select one of the given options
+fn row_options<'a, T: Copy + PartialEq>(
+    label: &str,
+    options: &[T],
+    selected: T,
+    get_label: impl Fn(T) -> String,
+    on_press: impl Fn(T) -> Message,
 ) -> row::Wrapping<'a, Message, StyleType> {
     let mut ret_val = Row::new()
         .width(Length::Shrink)
         .align_y(Alignment::Center)
         .spacing(5)
         .padding(Padding::ZERO.left(26))
-        .push(Text::new(format!(
-            "{}:",
-            data_representation_translation(language)
-        )));
+        .push(Text::new(format!("{label}:")));
 
-    for option in DataRepr::ALL {
-        let is_active = data_repr.eq(&option);
+    for option in options {
+        let is_active = selected.eq(option);
         ret_val = ret_val.push(
             Button::new(
-                Text::new(option.get_label(language).to_owned())
+                Text::new(get_label(*option))
                     .size(FONT_SIZE_FOOTER)
                     .align_x(Alignment::Center)
                     .align_y(Alignment::Center),
@@ -449,13 +963,7 @@ fn row_data_representation<'a>(
             } else {
                 ButtonType::BorderedRound
             })
-            .on_press(Message::UpdateNotificationSettings(
-                Notification::Data(DataNotification {
-                    data_repr: option,
-                    ..data_notification
-                }),
-                false,
-            )),
+            .on_press(on_press(*option)),
         );
     }
     ret_val.wrap()
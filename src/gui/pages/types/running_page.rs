@@ -1,6 +1,7 @@
 use crate::gui::types::message::Message;
 use crate::translations::translations::{notifications_translation, overview_translation};
 use crate::translations::translations_2::inspect_translation;
+use crate::translations::translations_6::devices_translation;
 use crate::utils::types::icon::Icon;
 use crate::{Language, StyleType};
 use serde::{Deserialize, Serialize};
@@ -13,14 +14,17 @@ pub enum RunningPage {
     Overview,
     /// Inspect page.
     Inspect,
+    /// Devices page.
+    Devices,
     /// Notifications page.
     Notifications,
 }
 
 impl RunningPage {
-    pub const ALL: [RunningPage; 3] = [
+    pub const ALL: [RunningPage; 4] = [
         RunningPage::Overview,
         RunningPage::Inspect,
+        RunningPage::Devices,
         RunningPage::Notifications,
     ];
 
@@ -28,6 +32,7 @@ pub fn get_tab_label(&self, language: Language) -> &str {
         match self {
             RunningPage::Overview => overview_translation(language),
             RunningPage::Inspect => inspect_translation(language),
+            RunningPage::Devices => devices_translation(language),
             RunningPage::Notifications => notifications_translation(language),
         }
     }
@@ -35,7 +40,8 @@ pub fn get_tab_label(&self, language: Language) -> &str {
     pub fn next(self) -> Self {
         match self {
             RunningPage::Overview => RunningPage::Inspect,
-            RunningPage::Inspect => RunningPage::Notifications,
+            RunningPage::Inspect => RunningPage::Devices,
+            RunningPage::Devices => RunningPage::Notifications,
             RunningPage::Notifications => RunningPage::Overview,
         }
     }
@@ -44,7 +50,8 @@ pub fn previous(self) -> Self {
         match self {
             RunningPage::Overview => RunningPage::Notifications,
             RunningPage::Inspect => RunningPage::Overview,
-            RunningPage::Notifications => RunningPage::Inspect,
+            RunningPage::Devices => RunningPage::Inspect,
+            RunningPage::Notifications => RunningPage::Devices,
         }
     }
 
@@ -52,6 +59,7 @@ pub fn icon<'a>(self) -> iced::widget::Text<'a, StyleType> {
         match self {
             RunningPage::Overview => Icon::Overview,
             RunningPage::Inspect => Icon::Inspect,
+            RunningPage::Devices => Icon::Globe,
             RunningPage::Notifications => Icon::Notification,
         }
         .to_text()
@@ -69,14 +77,16 @@ mod tests {
     #[test]
     fn test_previous_running_page() {
         assert_eq!(RunningPage::Overview.previous(), RunningPage::Notifications);
-        assert_eq!(RunningPage::Notifications.previous(), RunningPage::Inspect);
+        assert_eq!(RunningPage::Notifications.previous(), RunningPage::Devices);
+        assert_eq!(RunningPage::Devices.previous(), RunningPage::Inspect);
         assert_eq!(RunningPage::Inspect.previous(), RunningPage::Overview);
     }
 
     #[test]
     fn test_next_running_page() {
         assert_eq!(RunningPage::Overview.next(), RunningPage::Inspect);
-        assert_eq!(RunningPage::Inspect.next(), RunningPage::Notifications);
+        assert_eq!(RunningPage::Inspect.next(), RunningPage::Devices);
+        assert_eq!(RunningPage::Devices.next(), RunningPage::Notifications);
         assert_eq!(RunningPage::Notifications.next(), RunningPage::Overview);
     }
 }
@@ -74,7 +74,13 @@ pub fn waiting_page(sniffer: &Sniffer) -> Option<Container<'_, Message, StyleTyp
             .push(Space::new().height(Length::Fill))
             .push(
                 Container::new(
-                    col_device(language, cs, &sniffer.conf.filters).height(Length::Shrink),
+                    col_device(
+                        language,
+                        cs,
+                        &sniffer.conf.filters,
+                        &sniffer.skipped_adapters,
+                    )
+                    .height(Length::Shrink),
                 )
                 .padding([15, 30])
                 .class(ContainerType::BorderedRound),
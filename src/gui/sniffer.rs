@@ -6,6 +6,7 @@
 use crate::gui::components::modal::{get_clear_all_overlay, get_exit_overlay, modal};
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::pages::connection_details_page::connection_details_page;
+use crate::gui::pages::devices_page::devices_page;
 use crate::gui::pages::initial_page::initial_page;
 use crate::gui::pages::inspect_page::inspect_page;
 use crate::gui::pages::notifications_page::notifications_page;
@@ -29,35 +30,51 @@
 use crate::mmdb::asn::ASN_MMDB;
 use crate::mmdb::country::COUNTRY_MMDB;
 use crate::mmdb::types::mmdb_reader::{MmdbReader, MmdbReaders};
+use crate::networking::flow_export::{FlowExportError, FlowExporter};
+use crate::networking::ip_feeds::{ListUpdate, load_ip_list};
+use crate::networking::manage_packets::get_address_to_lookup;
 use crate::networking::parse_packets::BackendTrafficMessage;
+use crate::networking::parse_packets::PacketCommentRules;
 use crate::networking::parse_packets::parse_packets;
+use crate::networking::scan_detection::ScanDetector;
 use crate::networking::traffic_preview::{TrafficPreview, traffic_preview};
 use crate::networking::types::capture_context::{
-    CaptureContext, CaptureSource, CaptureSourcePicklist, MyPcapImport,
+    CaptureContext, CaptureSource, CaptureSourcePicklist, MyMultiDevice, MyPcapImport,
 };
 use crate::networking::types::combobox_data_states::ComboboxDataStates;
 use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::flow_export_conf::FlowExportConf;
 use crate::networking::types::host::{Host, HostMessage};
 use crate::networking::types::info_traffic::InfoTraffic;
-use crate::networking::types::ip_blacklist::IpBlacklist;
+use crate::networking::types::ip_blacklist::{IpBlacklist, IpListConf, IpLists, SharedIpLists};
 use crate::networking::types::latency::{LatencyStatus, measure_latency};
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::program::Program;
 use crate::networking::types::program_lookup::{ProgramLookup, get_picon, lookup_program};
+use crate::notifications::bandwidth_monitor::BandwidthMonitor;
 use crate::notifications::notify_and_log::notify_and_log;
 use crate::notifications::types::logged_notification::LoggedNotifications;
 use crate::notifications::types::notifications::{DataNotification, Notification};
 use crate::notifications::types::sound::{Sound, play};
-use crate::report::get_report_entries::get_searched_entries;
+use crate::notifications::types::threshold_rule::{RuleTarget, ThresholdRule};
+use crate::report::export_report::{ConnectionRecord, write_connections_to_file};
+use crate::report::get_report_entries::{get_all_searched_entries, get_searched_entries};
+use crate::report::metrics::{MetricsServer, render_metrics};
+use crate::report::types::metrics_conf::MetricsConf;
+use crate::report::types::report_format::ReportFormat;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
+use crate::translations::translations_6::{
+    export_results_translation, invalid_address_translation,
+};
 use crate::translations::types::language::Language;
 use crate::utils::check_updates::set_newer_release_status;
 use crate::utils::error_logger::{ErrorLogger, Location};
 use crate::utils::types::file_info::FileInfo;
 use crate::utils::types::icon::Icon;
+use crate::utils::types::timestamp::Timestamp;
 use crate::utils::types::web_page::WebPage;
-use crate::{StyleType, TrafficChart, location};
+use crate::{SNIFFNET_LOWERCASE, StyleType, TrafficChart, location};
 use async_channel::Receiver;
 use iced::Event::{Keyboard, Window};
 use iced::keyboard::key::Named;
@@ -69,11 +86,11 @@
 use listeners::Process;
 use rfd::FileHandle;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub const FONT_FAMILY_NAME: &str = "Sarasa Mono SC for Sniffnet";
 pub const ICON_FONT_FAMILY_NAME: &str = "Icons for Sniffnet";
@@ -101,12 +118,20 @@ pub struct Sniffer {
     pub addresses_resolved: HashMap<IpAddr, (String, Host)>,
     /// Log of the displayed notifications, with the total number of notifications for this capture
     pub logged_notifications: LoggedNotifications,
+    /// Probes sent by remote hosts, used to detect port scans and host sweeps
+    pub scan_detector: ScanDetector,
+    /// Traffic history used to emit rate and quota notifications
+    pub bandwidth_monitor: BandwidthMonitor,
     /// Reports if a newer release of the software is available on GitHub
     pub newer_release_available: Option<bool>,
     /// Network device to be analyzed, or PCAP file to be imported
     pub capture_source: CaptureSource,
     /// Signals if a pcap error occurred
     pub pcap_error: Option<String>,
+    /// Network adapters skipped because their capture couldn't be started, with the related errors
+    pub skipped_adapters: Vec<String>,
+    /// Error raised when the inspect search results were last exported, if any
+    pub export_results_error: Option<String>,
     /// Messages status
     pub dots_pulse: (String, u8),
     /// Traffic chart displayed in the Overview page
@@ -129,6 +154,14 @@ pub struct Sniffer {
     pub mmdb_readers: MmdbReaders,
     /// IP blacklist
     pub ip_blacklist: IpBlacklist,
+    /// Named IP lists, indexed by the path of their file or by their URL
+    pub ip_lists: HashMap<String, IpBlacklist>,
+    /// Last time each IP list served over HTTP was fetched
+    ip_feeds_fetched: HashMap<String, Instant>,
+    /// IP lists used by the current capture
+    active_ip_lists: SharedIpLists,
+    /// URL of a new IP list, as typed by the user
+    pub ip_list_url: String,
     /// Latest latency measurements by remote address
     pub latency_statuses: HashMap<IpAddr, LatencyStatus>,
     /// Time-related events
@@ -145,6 +178,14 @@ pub struct Sniffer {
     pub freeze_tx: Option<tokio::sync::broadcast::Sender<()>>,
     /// State of the port to program lookups
     pub program_lookup: Option<ProgramLookup>,
+    /// Endpoint exposing the traffic statistics as Prometheus metrics, if running
+    pub metrics_server: Option<MetricsServer>,
+    /// Error raised when the metrics endpoint was last started, if any
+    pub metrics_error: Option<String>,
+    /// Exporter of the flow records of the current capture, if enabled
+    pub flow_exporter: Option<FlowExporter>,
+    /// Error raised when the flow exporter was last created, if any
+    pub flow_export_error: Option<FlowExportError>,
 }
 
 impl Sniffer {
@@ -168,9 +209,13 @@ pub fn new(conf: Conf) -> Self {
             info_traffic: InfoTraffic::default(),
             addresses_resolved: HashMap::new(),
             logged_notifications: LoggedNotifications::default(),
+            scan_detector: ScanDetector::default(),
+            bandwidth_monitor: BandwidthMonitor::default(),
             newer_release_available: None,
             capture_source,
             pcap_error: None,
+            skipped_adapters: Vec::new(),
+            export_results_error: None,
             dots_pulse: (".".to_string(), 0),
             traffic_chart: TrafficChart::new(style, language, data_repr),
             preview_charts,
@@ -185,6 +230,10 @@ pub fn new(conf: Conf) -> Self {
                 asn: Arc::new(MmdbReader::from(&mmdb_asn, ASN_MMDB)),
             },
             ip_blacklist: IpBlacklist::default(), // load it later
+            ip_lists: HashMap::new(),             // load them later
+            ip_feeds_fetched: HashMap::new(),
+            active_ip_lists: SharedIpLists::default(),
+            ip_list_url: String::new(),
             latency_statuses: HashMap::new(),
             timing_events: TimingEvents::default(),
             thumbnail: false,
@@ -193,11 +242,15 @@ pub fn new(conf: Conf) -> Self {
             frozen: false,
             freeze_tx: None,
             program_lookup: None,
+            metrics_server: None,
+            metrics_error: None,
+            flow_exporter: None,
+            flow_export_error: None,
         }
     }
 
     fn keyboard_subscription(&self) -> Subscription<Message> {
-        if self.welcome.is_some() || self.ip_blacklist.is_loading() {
+        if self.welcome.is_some() || self.is_loading_ip_lists() {
             return Subscription::none();
         }
 
@@ -284,6 +337,7 @@ fn window_subscription() -> Subscription<Message> {
         })
     }
 
+    #[allow(clippy::too_many_lines)]
     pub fn update(&mut self, message: Message) -> Task<Message> {
         self.dots_pulse.1 = (self.dots_pulse.1 + 1) % 3;
         match message {
@@ -293,10 +347,12 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
             }
             Message::DeviceSelection(name) => self.device_selection(&name),
             Message::SetCaptureSource(cs_pick) => self.set_capture_source(cs_pick),
+            Message::ToggleDeviceSelection(name) => self.toggle_device_selection(&name),
             Message::ToggleFilters => self.toggle_filters(),
             Message::BpfFilter(value) => self.bpf_filter(value),
             Message::DataReprSelection(unit) => self.data_repr_selection(unit),
             Message::ReportSortSelection(sort) => self.report_sort_selection(sort),
+            Message::ReportFlowsToggle => self.report_flows_toggle(),
             Message::OpenWebPage(web_page) => Self::open_web_page(&web_page),
             Message::Start => return self.start(),
             Message::Reset => return self.reset(),
@@ -322,6 +378,8 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
             Message::CtrlDPressed => self.ctrl_d_pressed(),
             Message::Search(parameters) => self.search(parameters),
             Message::UpdatePageNumber(increment) => self.update_page_number(increment),
+            Message::ExportSearchResults(format) => return self.export_search_results(format),
+            Message::SearchResultsExported(error) => self.export_results_error = error,
             Message::ArrowPressed(increment) => self.arrow_pressed(increment),
             Message::WindowFocused => self.window_focused(),
             Message::GradientsSelection(gradient_type) => self.gradients_selection(gradient_type),
@@ -332,6 +390,20 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
             Message::CustomAsnDb(db) => self.custom_asn_db(db),
             Message::LoadIpBlacklist(path) => return self.load_ip_blacklist(path),
             Message::SetIpBlacklist(blacklist) => self.set_ip_blacklist(blacklist),
+            Message::AddIpList(path) => return self.add_ip_list(path),
+            Message::SetIpList(path, update) => self.set_ip_list(&path, update),
+            Message::IpListUrl(url) => self.ip_list_url = url,
+            Message::UpdateIpList(index, list) => return self.update_ip_list(index, list),
+            Message::RemoveIpList(index) => self.remove_ip_list(index),
+            Message::TunnelDecapsulationToggle => self.tunnel_decapsulation_toggle(),
+            Message::ServerNamesToggle => self.server_names_toggle(),
+            Message::ToggleMetrics => self.toggle_metrics(),
+            Message::UpdateMetricsConf(metrics) => self.update_metrics_conf(metrics),
+            Message::EnableMetrics(address) => self.enable_metrics(address),
+            Message::ToggleFlowExport => self.toggle_flow_export(),
+            Message::UpdateFlowExportConf(flow_export) => {
+                self.update_flow_export_conf(flow_export);
+            }
             Message::QuitWrapper => return self.quit_wrapper(),
             Message::Quit => return self.quit(),
             Message::Welcome => self.welcome(),
@@ -350,6 +422,11 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
             Message::ToggleExportPcap => self.toggle_export_pcap(),
             Message::OutputPcapDir(path) => self.output_pcap_dir(path),
             Message::OutputPcapFile(name) => self.output_pcap_file(&name),
+            Message::OutputPcapRotationSize(value) => self.output_pcap_rotation_size(&value),
+            Message::OutputPcapRotationInterval(value) => {
+                self.output_pcap_rotation_interval(&value);
+            }
+            Message::OutputPcapRingBuffer(value) => self.output_pcap_ring_buffer(&value),
             Message::ToggleThumbnail(triggered_by_resize) => {
                 return self.toggle_thumbnail(triggered_by_resize);
             }
@@ -361,10 +438,13 @@ pub fn update(&mut self, message: Message) -> Task<Message> {
             Message::SetPcapImport(path) => self.set_pcap_import(path),
             Message::PendingHosts(cap_id, host_msgs) => self.pending_hosts(cap_id, host_msgs),
             Message::OfflineGap(cap_id, gap) => self.offline_gap(cap_id, gap),
-            Message::Periodic => self.periodic(),
+            Message::Periodic => return self.periodic(),
             Message::ExpandNotification(id, expand) => self.expand_notification(id, expand),
             Message::ToggleRemoteNotifications => self.toggle_remote_notifications(),
             Message::RemoteNotificationsUrl(url) => self.remote_notifications_url(&url),
+            Message::AddThresholdRule(target) => self.add_threshold_rule(target),
+            Message::UpdateThresholdRule(index, rule) => self.update_threshold_rule(index, rule),
+            Message::RemoveThresholdRule(index) => self.remove_threshold_rule(index),
             Message::Freeze => self.freeze(),
             Message::TrafficPreview(msg) => self.traffic_preview(msg),
         }
@@ -396,6 +476,7 @@ pub fn view(&self) -> Element<'_, Message, StyleType> {
                         match running_page {
                             RunningPage::Overview => overview_page(self),
                             RunningPage::Inspect => inspect_page(self),
+                            RunningPage::Devices => devices_page(self),
                             RunningPage::Notifications => notifications_page(self),
                         }
                     }
@@ -442,7 +523,7 @@ pub fn view(&self) -> Element<'_, Message, StyleType> {
             }
         };
 
-        if self.ip_blacklist.is_loading() {
+        if self.is_loading_ip_lists() {
             let overlay = Into::<Element<Message, StyleType>>::into(center(
                 Icon::get_hourglass(self.dots_pulse.0.len()).size(60),
             ));
@@ -472,12 +553,14 @@ pub fn scale_factor(&self) -> f32 {
 
     fn start_app(&mut self, id: Option<Id>) -> Task<Message> {
         self.id = id;
+        self.apply_metrics_conf();
         let previews_task = self.start_traffic_previews();
         Task::batch([
             Sniffer::register_sigint_handler(),
             Task::perform(set_newer_release_status(), Message::SetNewerReleaseStatus),
             previews_task,
             self.load_ip_blacklist(self.conf.settings.ip_blacklist.clone()),
+            self.load_ip_lists(),
         ])
     }
 
@@ -504,10 +587,14 @@ fn tick_run(
 
     fn set_capture_source(&mut self, cs_pick: CaptureSourcePicklist) {
         self.conf.capture_source_picklist = cs_pick;
-        if cs_pick == CaptureSourcePicklist::File {
-            self.set_pcap_import(self.conf.import_pcap_path.clone());
-        } else {
-            self.device_selection(&self.conf.device.device_name.clone());
+        match cs_pick {
+            CaptureSourcePicklist::Device => {
+                self.device_selection(&self.conf.device.device_name.clone());
+            }
+            CaptureSourcePicklist::Devices => self.multi_device_selection(),
+            CaptureSourcePicklist::File => {
+                self.set_pcap_import(self.conf.import_pcap_path.clone());
+            }
         }
     }
 
@@ -529,6 +616,11 @@ fn report_sort_selection(&mut self, sort: SortType) {
         self.conf.report_sort_type = sort;
     }
 
+    fn report_flows_toggle(&mut self) {
+        self.page_number = 1;
+        self.conf.report_flows = !self.conf.report_flows;
+    }
+
     fn style(&mut self, style: StyleType) {
         self.conf.settings.style = style;
         self.change_charts_style();
@@ -596,6 +688,9 @@ fn search(&mut self, parameters: SearchParameters) {
         combobox_data.asns.1 = self.search.as_name != parameters.as_name;
         combobox_data.domains.1 = self.search.domain != parameters.domain;
         combobox_data.programs.1 = self.search.program != parameters.program;
+        combobox_data.server_names.1 = self.search.server_name != parameters.server_name;
+        combobox_data.tcp_states.1 = self.search.tcp_state != parameters.tcp_state;
+        combobox_data.vlans.1 = self.search.vlan != parameters.vlan;
         self.combobox_data_states.update_states(&parameters);
 
         self.page_number = 1;
@@ -604,6 +699,25 @@ fn search(&mut self, parameters: SearchParameters) {
         self.search = parameters;
     }
 
+    fn export_search_results(&mut self, format: ReportFormat) -> Task<Message> {
+        self.export_results_error = None;
+        // take a snapshot of the results at the moment the export is requested
+        let records: Vec<ConnectionRecord> = get_all_searched_entries(self)
+            .0
+            .into_iter()
+            .map(|(key, val)| {
+                let address_to_lookup = get_address_to_lookup(&key, val.traffic_direction);
+                ConnectionRecord::new(&key, &val, self.addresses_resolved.get(&address_to_lookup))
+            })
+            .collect();
+        Task::future(Self::export_search_results_inner(
+            records,
+            format,
+            self.conf.settings.language,
+        ))
+        .map(Message::SearchResultsExported)
+    }
+
     fn update_page_number(&mut self, increment: bool) {
         if increment {
             if self.page_number < get_searched_entries(self).1.div_ceil(30) {
@@ -683,6 +797,253 @@ fn set_ip_blacklist(&mut self, blacklist: IpBlacklist) {
         self.ip_blacklist = blacklist;
     }
 
+    fn is_loading_ip_lists(&self) -> bool {
+        self.ip_blacklist.is_loading() || self.ip_lists.values().any(IpBlacklist::is_loading)
+    }
+
+    /// Returns the IP lists to check the remote addresses against
+    fn ip_lists_for_capture(&self) -> IpLists {
+        IpLists::new(
+            &self.conf.settings.ip_blacklist,
+            &self.ip_blacklist,
+            &self.conf.settings.ip_lists,
+            &self.ip_lists,
+        )
+    }
+
+    /// Sets the IP lists used by a new capture, returning them
+    fn activate_ip_lists(&mut self) -> SharedIpLists {
+        self.active_ip_lists = Arc::new(RwLock::new(self.ip_lists_for_capture()));
+        self.active_ip_lists.clone()
+    }
+
+    fn load_ip_lists(&mut self) -> Task<Message> {
+        let lists = self.conf.settings.ip_lists.clone();
+        Task::batch(lists.into_iter().map(|list| self.load_ip_list(list)))
+    }
+
+    fn load_ip_list(&mut self, list: IpListConf) -> Task<Message> {
+        let path = list.path.clone();
+        if list.is_url() {
+            // lists served over HTTP are fetched in background, keeping the current copy meanwhile
+            self.ip_feeds_fetched.insert(path.clone(), Instant::now());
+        } else {
+            let mut placeholder = IpBlacklist::default();
+            placeholder.start_loading();
+            self.ip_lists.insert(path.clone(), placeholder);
+        }
+        let etag = self
+            .ip_lists
+            .get(&path)
+            .and_then(IpBlacklist::etag)
+            .cloned();
+        Task::perform(load_ip_list(list, etag), move |update| {
+            Message::SetIpList(path, update)
+        })
+    }
+
+    /// Fetches again the IP lists served over HTTP whose refresh interval has elapsed
+    fn refresh_ip_feeds(&mut self) -> Task<Message> {
+        let due: Vec<IpListConf> = self
+            .conf
+            .settings
+            .ip_lists
+            .iter()
+            .filter(|list| {
+                list.is_url()
+                    && list.refresh_minutes > 0
+                    && self.ip_feeds_fetched.get(&list.path).is_none_or(|fetched| {
+                        fetched.elapsed()
+                            >= Duration::from_secs(60 * u64::from(list.refresh_minutes))
+                    })
+            })
+            .cloned()
+            .collect();
+        Task::batch(due.into_iter().map(|list| self.load_ip_list(list)))
+    }
+
+    fn add_ip_list(&mut self, path: String) -> Task<Message> {
+        let lists = &mut self.conf.settings.ip_lists;
+        if path.is_empty() || lists.iter().any(|list| list.path == path) {
+            return Task::none();
+        }
+        let list = IpListConf::new(path);
+        if list.is_url() {
+            self.ip_list_url.clear();
+        }
+        lists.push(list.clone());
+        self.load_ip_list(list)
+    }
+
+    fn set_ip_list(&mut self, path: &str, update: ListUpdate) {
+        // the list could have been removed in the meantime
+        if let ListUpdate::Loaded(list) = update
+            && self.conf.settings.ip_lists.iter().any(|l| l.path == path)
+        {
+            self.ip_lists.insert(path.to_string(), list);
+            // make the new content available to the running capture
+            if let Ok(mut active_ip_lists) = self.active_ip_lists.write() {
+                *active_ip_lists = self.ip_lists_for_capture();
+            }
+        }
+    }
+
+    fn update_ip_list(&mut self, index: usize, list: IpListConf) -> Task<Message> {
+        let Some(existing) = self.conf.settings.ip_lists.get_mut(index) else {
+            return Task::none();
+        };
+        let format_changed =
+            existing.format != list.format || existing.csv_column != list.csv_column;
+        *existing = list.clone();
+        if format_changed {
+            // the current copy was parsed with the old settings: download it again in full
+            if let Some(current) = self.ip_lists.remove(&list.path) {
+                self.ip_lists
+                    .insert(list.path.clone(), current.with_etag(None));
+            }
+            self.load_ip_list(list)
+        } else {
+            Task::none()
+        }
+    }
+
+    fn remove_ip_list(&mut self, index: usize) {
+        let lists = &mut self.conf.settings.ip_lists;
+        if index < lists.len() {
+            let removed = lists.remove(index);
+            self.ip_lists.remove(&removed.path);
+            self.ip_feeds_fetched.remove(&removed.path);
+        }
+    }
+
+    fn tunnel_decapsulation_toggle(&mut self) {
+        self.conf.settings.decapsulate_tunnels = !self.conf.settings.decapsulate_tunnels;
+    }
+
+    fn server_names_toggle(&mut self) {
+        self.conf.settings.extract_server_names = !self.conf.settings.extract_server_names;
+    }
+
+    fn toggle_metrics(&mut self) {
+        let metrics = &mut self.conf.settings.metrics;
+        metrics.is_active = !metrics.is_active;
+        self.apply_metrics_conf();
+    }
+
+    fn update_metrics_conf(&mut self, metrics: MetricsConf) {
+        self.conf.settings.metrics = metrics;
+        self.publish_metrics();
+    }
+
+    fn enable_metrics(&mut self, address: SocketAddr) {
+        let metrics = &mut self.conf.settings.metrics;
+        metrics.set_socket_address(address);
+        metrics.is_active = true;
+        self.apply_metrics_conf();
+    }
+
+    /// Starts or stops the metrics endpoint, according to the settings
+    fn apply_metrics_conf(&mut self) {
+        let language = self.conf.settings.language;
+        let metrics = &mut self.conf.settings.metrics;
+        let address = metrics.socket_address();
+        if metrics.is_active
+            && address.is_some()
+            && self.metrics_server.as_ref().map(MetricsServer::address) == address
+        {
+            return;
+        }
+        self.metrics_server = None;
+        self.metrics_error = None;
+        if !metrics.is_active {
+            return;
+        }
+        let server = address
+            .ok_or_else(|| {
+                format!(
+                    "{}: {}",
+                    invalid_address_translation(language),
+                    metrics.address
+                )
+            })
+            .and_then(MetricsServer::start);
+        match server {
+            Ok(server) => {
+                self.metrics_server = Some(server);
+                self.publish_metrics();
+            }
+            Err(e) => {
+                metrics.is_active = false;
+                self.metrics_error = Some(e);
+            }
+        }
+    }
+
+    /// Updates the metrics served by the endpoint, if it's running
+    fn publish_metrics(&self) {
+        if let Some(server) = &self.metrics_server {
+            server.set_metrics(render_metrics(
+                &self.info_traffic,
+                self.program_lookup.as_ref().map(ProgramLookup::programs),
+                &self.conf.settings.metrics,
+            ));
+        }
+    }
+
+    fn toggle_flow_export(&mut self) {
+        let flow_export = &mut self.conf.settings.flow_export;
+        flow_export.is_active = !flow_export.is_active;
+        self.flow_export_error = None;
+    }
+
+    fn update_flow_export_conf(&mut self, flow_export: FlowExportConf) {
+        self.conf.settings.flow_export = flow_export;
+        self.flow_export_error = None;
+    }
+
+    /// Creates the flow exporter for a new capture, if the export is enabled
+    fn start_flow_export(&mut self) {
+        self.flow_exporter = None;
+        self.flow_export_error = None;
+        let flow_export = &self.conf.settings.flow_export;
+        if !flow_export.is_active {
+            return;
+        }
+        match FlowExporter::new(flow_export) {
+            Ok(exporter) => self.flow_exporter = Some(exporter),
+            Err(e) => self.flow_export_error = Some(e),
+        }
+    }
+
+    /// Sends the records of the flows whose timeouts expired to the collector
+    fn export_flows(&mut self, msg: &InfoTraffic, no_more_packets: bool) {
+        let now = self.flow_export_now();
+        let Some(exporter) = &mut self.flow_exporter else {
+            return;
+        };
+        exporter.update(&self.info_traffic.map, msg.map.keys(), now);
+        if no_more_packets {
+            exporter.flush(&self.info_traffic.map, now);
+        }
+    }
+
+    /// Sends the records of all the flows not exported yet, and stops the export
+    fn stop_flow_export(&mut self) {
+        let now = self.flow_export_now();
+        if let Some(mut exporter) = self.flow_exporter.take() {
+            exporter.flush(&self.info_traffic.map, now);
+        }
+    }
+
+    /// Current time for the flow exporter: the time of the last packet when reading a file
+    fn flow_export_now(&self) -> Timestamp {
+        if matches!(self.capture_source, CaptureSource::File(_)) {
+            self.info_traffic.last_packet_timestamp
+        } else {
+            Timestamp::now()
+        }
+    }
+
     fn measure_latency(&mut self, ip: IpAddr) -> Task<Message> {
         self.latency_statuses.insert(ip, LatencyStatus::Measuring);
         Task::perform(measure_latency(ip), move |status| {
@@ -742,6 +1103,18 @@ fn output_pcap_file(&mut self, name: &str) {
         self.conf.export_pcap.set_file_name(name);
     }
 
+    fn output_pcap_rotation_size(&mut self, value: &str) {
+        self.conf.export_pcap.set_rotation_megabytes(value);
+    }
+
+    fn output_pcap_rotation_interval(&mut self, value: &str) {
+        self.conf.export_pcap.set_rotation_minutes(value);
+    }
+
+    fn output_pcap_ring_buffer(&mut self, value: &str) {
+        self.conf.export_pcap.set_ring_buffer_files(value);
+    }
+
     fn toggle_thumbnail(&mut self, triggered_by_resize: bool) -> Task<Message> {
         let window_id = self.id.unwrap_or_else(Id::unique);
 
@@ -840,10 +1213,11 @@ fn offline_gap(&mut self, cap_id: usize, gap: u32) {
         }
     }
 
-    fn periodic(&mut self) {
+    fn periodic(&mut self) -> Task<Message> {
         self.update_waiting_dots();
         self.capture_source.set_addresses();
         self.update_threshold();
+        self.refresh_ip_feeds()
     }
 
     fn expand_notification(&mut self, id: usize, expand: bool) {
@@ -873,6 +1247,32 @@ fn remote_notifications_url(&mut self, url: &str) {
             .set_url(url);
     }
 
+    fn add_threshold_rule(&mut self, target: RuleTarget) {
+        let rules = &mut self.conf.settings.notifications.threshold_rules;
+        if !rules.iter().any(|rule| rule.target == target) {
+            rules.push(ThresholdRule::new(target));
+        }
+    }
+
+    fn update_threshold_rule(&mut self, index: usize, rule: ThresholdRule) {
+        if let Some(existing) = self
+            .conf
+            .settings
+            .notifications
+            .threshold_rules
+            .get_mut(index)
+        {
+            *existing = rule;
+        }
+    }
+
+    fn remove_threshold_rule(&mut self, index: usize) {
+        let rules = &mut self.conf.settings.notifications.threshold_rules;
+        if index < rules.len() {
+            rules.remove(index);
+        }
+    }
+
     fn freeze(&mut self) {
         self.frozen = !self.frozen;
         if let Some(tx) = &self.freeze_tx {
@@ -926,6 +1326,8 @@ fn update_threshold(&mut self) {
     fn refresh_data(&mut self, mut msg: InfoTraffic, no_more_packets: bool) {
         self.info_traffic
             .refresh(&mut msg, &mut self.program_lookup);
+        self.publish_metrics();
+        self.export_flows(&msg, no_more_packets);
         if self.info_traffic.tot_data_info.tot_data(DataRepr::Packets) == 0 {
             return;
         }
@@ -936,6 +1338,8 @@ fn refresh_data(&mut self, mut msg: InfoTraffic, no_more_packets: bool) {
             &self.conf.favorites,
             &self.capture_source,
             &self.addresses_resolved,
+            &mut self.scan_detector,
+            &mut self.bandwidth_monitor,
         );
         if self.thumbnail
             || self
@@ -947,6 +1351,21 @@ fn refresh_data(&mut self, mut msg: InfoTraffic, no_more_packets: bool) {
         self.traffic_chart.update_charts_data(&msg, no_more_packets);
 
         // update combobox dropdowns
+        for server_name in msg.map.values().filter_map(|v| v.server_name.as_ref()) {
+            self.combobox_data_states
+                .data
+                .update_server_name(server_name);
+        }
+        for tcp_state in msg
+            .map
+            .keys()
+            .filter_map(|k| self.info_traffic.map.get(k)?.tcp_state)
+        {
+            self.combobox_data_states.data.update_tcp_state(tcp_state);
+        }
+        for key in msg.map.keys() {
+            self.combobox_data_states.data.update_vlan(key.vlan);
+        }
         self.combobox_data_states.update_states(&self.search);
     }
 
@@ -971,6 +1390,7 @@ fn open_web_page(web_page: &WebPage) {
         child.wait().unwrap_or_default();
     }
 
+    #[allow(clippy::too_many_lines)]
     fn start(&mut self) -> Task<Message> {
         if self.is_capture_source_consistent() {
             // close captures preview channel to kill previous preview captures
@@ -982,31 +1402,52 @@ fn start(&mut self) -> Task<Message> {
                 .iter_mut()
                 .for_each(|(_, chart)| *chart = PreviewChart::new(self.conf.settings.style));
 
-            if matches!(&self.capture_source, CaptureSource::Device(_)) {
-                let current_device_name = &self.capture_source.get_name();
-                self.device_selection(current_device_name);
+            match &self.capture_source {
+                CaptureSource::Device(_) => {
+                    let current_device_name = &self.capture_source.get_name();
+                    self.device_selection(current_device_name);
+                }
+                CaptureSource::Devices(_) => self.multi_device_selection(),
+                CaptureSource::File(_) => {}
             }
             let pcap_path = self.conf.export_pcap.full_path();
-            let capture_context =
-                CaptureContext::new(&self.capture_source, pcap_path.as_ref(), &self.conf.filters);
-            self.pcap_error = capture_context.error().map(ToString::to_string);
+            let mut capture_contexts = CaptureContext::new_all(
+                &self.capture_source,
+                pcap_path.as_ref(),
+                self.conf.export_pcap.rotation(),
+                &self.conf.filters,
+                self.conf.settings.extract_server_names,
+            );
+            // adapters that can't be captured are skipped, as long as at least one of them can
+            let language = self.conf.settings.language;
+            self.skipped_adapters = self
+                .capture_source
+                .remove_failed_devices(&mut capture_contexts)
+                .into_iter()
+                .map(|(name, e)| format!("{name}: {}", e.get_message(language)))
+                .collect();
+            let pcap_error = capture_contexts.iter().find_map(CaptureContext::error);
+            self.pcap_error = pcap_error.map(|e| e.get_message(language));
             self.running_page = Some(self.conf.last_opened_page);
 
-            if capture_context.error().is_none() {
+            if pcap_error.is_none() {
                 // no pcap error
+                self.start_flow_export();
                 let curr_cap_id = self.current_capture_rx.0;
                 let mmdb_readers = self.mmdb_readers.clone();
-                let ip_blacklist = self.ip_blacklist.clone();
-                self.capture_source
-                    .set_link_type(capture_context.my_link_type());
+                let ip_lists = self.activate_ip_lists();
+                self.capture_source.set_link_types(&capture_contexts);
                 self.capture_source.set_addresses();
                 let capture_source = self.capture_source.clone();
                 self.traffic_chart
-                    .change_capture_source(matches!(capture_source, CaptureSource::Device(_)));
+                    .change_capture_source(!matches!(capture_source, CaptureSource::File(_)));
                 let (tx, rx) = async_channel::unbounded();
                 let (freeze_tx, freeze_rx) = tokio::sync::broadcast::channel(1_048_575);
                 let freeze_rx2 = freeze_tx.subscribe();
                 let filters = self.conf.filters.clone();
+                let comment_rules = PacketCommentRules::from_conf(&self.conf);
+                let decapsulate_tunnels = self.conf.settings.decapsulate_tunnels;
+                let extract_server_names = self.conf.settings.extract_server_names;
                 let _ = thread::Builder::new()
                     .name("thread_parse_packets".to_string())
                     .spawn(move || {
@@ -1014,9 +1455,12 @@ fn start(&mut self) -> Task<Message> {
                             curr_cap_id,
                             capture_source,
                             &mmdb_readers,
-                            &ip_blacklist,
-                            capture_context,
-                            filters,
+                            &ip_lists,
+                            capture_contexts,
+                            &filters,
+                            &comment_rules,
+                            decapsulate_tunnels,
+                            extract_server_names,
                             &tx,
                             (freeze_rx, freeze_rx2),
                         );
@@ -1025,7 +1469,7 @@ fn start(&mut self) -> Task<Message> {
                 self.current_capture_rx.1 = Some(rx.clone());
                 self.freeze_tx = Some(freeze_tx);
 
-                if matches!(self.capture_source, CaptureSource::Device(_)) {
+                if !matches!(self.capture_source, CaptureSource::File(_)) {
                     let (port_tx, port_rx) = std::sync::mpsc::channel();
                     let (program_tx, program_rx) = std::sync::mpsc::channel();
                     let _ = thread::Builder::new()
@@ -1078,11 +1522,15 @@ fn reset(&mut self) -> Task<Message> {
         } = self.conf.settings;
         // increment capture id to ignore pending messages from previous captures
         self.current_capture_rx = (self.current_capture_rx.0 + 1, None);
+        self.stop_flow_export();
         self.info_traffic = InfoTraffic::default();
         self.addresses_resolved = HashMap::new();
         self.latency_statuses = HashMap::new();
         self.logged_notifications = LoggedNotifications::default();
+        self.scan_detector = ScanDetector::default();
+        self.bandwidth_monitor = BandwidthMonitor::default();
         self.pcap_error = None;
+        self.skipped_adapters = Vec::new();
         self.traffic_chart = TrafficChart::new(style, language, self.conf.data_repr);
         self.modal = None;
         self.settings_page = None;
@@ -1095,6 +1543,7 @@ fn reset(&mut self) -> Task<Message> {
         self.frozen = false;
         self.freeze_tx = None;
         self.program_lookup = None;
+        self.publish_metrics();
         self.start_traffic_previews()
     }
 
@@ -1122,6 +1571,26 @@ fn device_selection(&mut self, name: &str) {
         }
     }
 
+    fn toggle_device_selection(&mut self, name: &str) {
+        let device_names = &mut self.conf.device.device_names;
+        if let Some(pos) = device_names.iter().position(|n| n.eq(name)) {
+            device_names.remove(pos);
+        } else {
+            device_names.push(name.to_string());
+        }
+        self.multi_device_selection();
+    }
+
+    fn multi_device_selection(&mut self) {
+        let devices = self
+            .preview_charts
+            .iter()
+            .filter(|(my_dev, _)| self.conf.device.device_names.contains(my_dev.get_name()))
+            .map(|(my_dev, _)| my_dev.clone())
+            .collect();
+        self.capture_source = CaptureSource::Devices(MyMultiDevice::new(devices));
+    }
+
     fn update_waiting_dots(&mut self) {
         if !self.frozen {
             if self.dots_pulse.0.len() > 2 {
@@ -1197,6 +1666,28 @@ fn update_notifications_settings(&mut self, notification: Notification, emit_sou
                     ip_blacklist_notification;
                 ip_blacklist_notification.sound
             }
+            Notification::ArpAnomaly(arp_anomaly_notification) => {
+                self.conf.settings.notifications.arp_anomaly_notification =
+                    arp_anomaly_notification;
+                arp_anomaly_notification.sound
+            }
+            Notification::Scan(scan_notification) => {
+                self.conf.settings.notifications.scan_notification = scan_notification;
+                scan_notification.sound
+            }
+            Notification::Rate(rate_notification) => {
+                self.conf.settings.notifications.rate_notification = rate_notification;
+                rate_notification.sound
+            }
+            Notification::Quota(quota_notification) => {
+                self.conf.settings.notifications.quota_notification = quota_notification;
+                quota_notification.sound
+            }
+            Notification::ThresholdRule(threshold_rule_notification) => {
+                self.conf.settings.notifications.threshold_rule_notification =
+                    threshold_rule_notification;
+                threshold_rule_notification.sound
+            }
         };
         if emit_sound {
             play(sound, self.conf.settings.notifications.volume);
@@ -1370,6 +1861,26 @@ async fn open_file_inner(old_file: String, file_info: FileInfo, language: Langua
         picked.path().to_string_lossy().to_string()
     }
 
+    /// Returns the error raised when writing the file, if any (nothing is reported if the dialog is dismissed)
+    async fn export_search_results_inner(
+        records: Vec<ConnectionRecord>,
+        format: ReportFormat,
+        language: Language,
+    ) -> Option<String> {
+        let extension = format.extension();
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(export_results_translation(language))
+            .set_directory(std::env::var("HOME").unwrap_or_default())
+            .set_file_name(format!("{SNIFFNET_LOWERCASE}_connections.{extension}"))
+            .add_filter(format.to_string(), &[extension])
+            .save_file()
+            .await?;
+
+        write_connections_to_file(file.path(), &records, format)
+            .err()
+            .map(|e| format!("{}: {e}", file.path().display()))
+    }
+
     fn handle_new_host(&mut self, host_msg: HostMessage) {
         let HostMessage {
             host,
@@ -1404,7 +1915,7 @@ fn handle_program_lookup_result(
                 .update_program(lookup_res.2.as_ref());
 
             // update program lookup state with the new lookup result
-            program_lookup.update(lookup_res, &mut self.info_traffic.map);
+            program_lookup.update(lookup_res, &mut self.info_traffic);
         }
     }
 
@@ -1423,6 +1934,8 @@ fn register_sigint_handler() -> Task<Message> {
     pub fn is_capture_source_consistent(&self) -> bool {
         self.conf.capture_source_picklist == CaptureSourcePicklist::Device
             && matches!(self.capture_source, CaptureSource::Device(_))
+            || self.conf.capture_source_picklist == CaptureSourcePicklist::Devices
+                && matches!(&self.capture_source, CaptureSource::Devices(d) if !d.devices().is_empty())
             || self.conf.capture_source_picklist == CaptureSourcePicklist::File
                 && matches!(self.capture_source, CaptureSource::File(_))
     }
@@ -1460,11 +1973,15 @@ mod tests {
     use crate::gui::types::message::Message;
     use crate::gui::types::settings::Settings;
     use crate::gui::types::timing_events::TimingEvents;
+    use crate::networking::flow_export::{FlowExportError, FlowExporter};
+    use crate::networking::ip_feeds::ListUpdate;
     use crate::networking::types::capture_context::CaptureSourcePicklist;
     use crate::networking::types::config_device::ConfigDevice;
     use crate::networking::types::data_info::DataInfo;
     use crate::networking::types::data_representation::DataRepr;
+    use crate::networking::types::flow_export_conf::FlowExportConf;
     use crate::networking::types::host::Host;
+    use crate::networking::types::ip_blacklist::{FeedFormat, IpBlacklist, IpListConf};
     use crate::networking::types::latency::LatencyStatus;
     use crate::networking::types::program::Program;
     use crate::networking::types::service::Service;
@@ -1476,7 +1993,13 @@ mod tests {
         DataNotification, Notification, Notifications, SimpleNotification,
     };
     use crate::notifications::types::sound::Sound;
+    use crate::notifications::types::threshold_rule::{RuleTarget, ThresholdRule};
+    use crate::report::metrics::MetricsServer;
+    use crate::report::types::metrics_conf::MetricsConf;
+    use crate::report::types::report_format::ReportFormat;
+    use crate::report::types::search_parameters::SearchParameters;
     use crate::report::types::sort_type::SortType;
+    use crate::translations::translations_6::invalid_address_translation;
     use crate::{ByteMultiple, Language, RunningPage, Sniffer, StyleType};
 
     // helpful to clean up files generated from tests
@@ -1643,6 +2166,7 @@ fn test_add_or_remove_favorite() {
 
         let fav_host = FavoriteKey::Host(Host {
             domain: "1.1".to_string(),
+            dns_name: String::new(),
             asn: Default::default(),
             country: Country::US,
         });
@@ -2038,6 +2562,246 @@ fn test_clear_all_notifications() {
         assert_eq!(sniffer.logged_notifications.len(), 0);
     }
 
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_add_update_remove_threshold_rules() {
+        let mut sniffer = Sniffer::new(Conf::default());
+        let service_target = RuleTarget::Favorite(FavoriteKey::Service(Service::Name("https")));
+        let search_target = RuleTarget::Search(Box::new(SearchParameters {
+            program: "backup".to_string(),
+            ..SearchParameters::default()
+        }));
+
+        sniffer.update(Message::AddThresholdRule(service_target.clone()));
+        sniffer.update(Message::AddThresholdRule(search_target.clone()));
+        // a rule for the same target isn't added twice
+        sniffer.update(Message::AddThresholdRule(service_target.clone()));
+        assert_eq!(
+            sniffer.conf.settings.notifications.threshold_rules,
+            vec![
+                ThresholdRule::new(service_target.clone()),
+                ThresholdRule::new(search_target.clone())
+            ]
+        );
+
+        let updated = ThresholdRule::new(search_target).with_threshold("20k");
+        sniffer.update(Message::UpdateThresholdRule(1, updated.clone()));
+        sniffer.update(Message::RemoveThresholdRule(0));
+        sniffer.update(Message::RemoveThresholdRule(5));
+        assert_eq!(
+            sniffer.conf.settings.notifications.threshold_rules,
+            vec![updated]
+        );
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_export_search_results_error() {
+        let mut sniffer = Sniffer::new(Conf::default());
+        assert_eq!(sniffer.export_results_error, None);
+
+        let error = "/root/sniffnet_connections.csv: Permission denied".to_string();
+        sniffer.update(Message::SearchResultsExported(Some(error.clone())));
+        assert_eq!(sniffer.export_results_error, Some(error));
+
+        // the error is cleared when a new export is requested
+        sniffer.update(Message::ExportSearchResults(ReportFormat::Json));
+        assert_eq!(sniffer.export_results_error, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_toggle_metrics() {
+        let mut sniffer = Sniffer::new(Conf::default());
+        assert!(!sniffer.conf.settings.metrics.is_active);
+        assert!(sniffer.metrics_server.is_none());
+
+        // an invalid address isn't bound
+        sniffer.update(Message::UpdateMetricsConf(MetricsConf {
+            address: "localhost".to_string(),
+            ..MetricsConf::default()
+        }));
+        sniffer.update(Message::ToggleMetrics);
+        assert!(!sniffer.conf.settings.metrics.is_active);
+        assert!(sniffer.metrics_server.is_none());
+        assert_eq!(
+            sniffer.metrics_error,
+            Some(format!(
+                "{}: localhost",
+                invalid_address_translation(Language::EN)
+            ))
+        );
+
+        // any free port is bound
+        sniffer.update(Message::UpdateMetricsConf(MetricsConf {
+            port: 0,
+            ..MetricsConf::default()
+        }));
+        sniffer.update(Message::ToggleMetrics);
+        assert!(sniffer.conf.settings.metrics.is_active);
+        assert_eq!(sniffer.metrics_error, None);
+        let address = sniffer.metrics_server.as_ref().unwrap().address();
+        assert!(address.ip().is_loopback());
+        assert_ne!(address.port(), 0);
+
+        // the running endpoint is kept when enabled again on its address
+        sniffer.update(Message::EnableMetrics(address));
+        assert_eq!(
+            sniffer.conf.settings.metrics.socket_address(),
+            Some(address)
+        );
+        assert_eq!(
+            sniffer.metrics_server.as_ref().map(MetricsServer::address),
+            Some(address)
+        );
+
+        // the address is released when the endpoint is stopped
+        sniffer.update(Message::ToggleMetrics);
+        assert!(sniffer.metrics_server.is_none());
+        sniffer.update(Message::ToggleMetrics);
+        assert_eq!(
+            sniffer.metrics_server.as_ref().map(MetricsServer::address),
+            Some(address)
+        );
+        sniffer.update(Message::ToggleMetrics);
+        assert!(!sniffer.conf.settings.metrics.is_active);
+        assert!(sniffer.metrics_server.is_none());
+        assert_eq!(sniffer.metrics_error, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_flow_export() {
+        let mut sniffer = Sniffer::new(Conf::default());
+        sniffer.update(Message::ToggleFlowExport);
+        assert!(sniffer.conf.settings.flow_export.is_active);
+
+        // an invalid collector is reported when the capture starts
+        sniffer.update(Message::UpdateFlowExportConf(FlowExportConf {
+            is_active: true,
+            address: "collector.local".to_string(),
+            ..FlowExportConf::default()
+        }));
+        sniffer.start_flow_export();
+        assert!(sniffer.flow_exporter.is_none());
+        assert_eq!(
+            sniffer.flow_export_error,
+            Some(FlowExportError::InvalidAddress(
+                "collector.local".to_string()
+            ))
+        );
+        assert_eq!(
+            sniffer
+                .flow_export_error
+                .as_ref()
+                .map(|e| e.get_message(Language::IT)),
+            Some("Indirizzo non valido: collector.local".to_string())
+        );
+
+        // the exporter lives as long as the capture
+        sniffer.update(Message::UpdateFlowExportConf(FlowExportConf {
+            is_active: true,
+            port: 9995,
+            ..FlowExportConf::default()
+        }));
+        assert_eq!(sniffer.flow_export_error, None);
+        sniffer.start_flow_export();
+        assert_eq!(
+            sniffer.flow_exporter.as_ref().map(FlowExporter::collector),
+            "127.0.0.1:9995".parse().ok()
+        );
+        sniffer.update(Message::Reset);
+        assert!(sniffer.flow_exporter.is_none());
+
+        // nothing is exported when disabled
+        sniffer.update(Message::ToggleFlowExport);
+        sniffer.start_flow_export();
+        assert!(sniffer.flow_exporter.is_none());
+        assert_eq!(sniffer.flow_export_error, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_add_update_remove_ip_lists() {
+        let mut sniffer = Sniffer::new(Conf::default());
+        let tor_path = "lists/tor_exits.txt".to_string();
+        let allow_path = "lists/internal.txt".to_string();
+
+        sniffer.update(Message::AddIpList(tor_path.clone()));
+        sniffer.update(Message::AddIpList(allow_path.clone()));
+        // the same file isn't added twice, and an empty path is ignored
+        sniffer.update(Message::AddIpList(tor_path.clone()));
+        sniffer.update(Message::AddIpList(String::new()));
+        assert_eq!(
+            sniffer.conf.settings.ip_lists,
+            vec![
+                IpListConf::new(tor_path.clone()),
+                IpListConf::new(allow_path.clone())
+            ]
+        );
+        assert_eq!(sniffer.conf.settings.ip_lists[0].name, "tor_exits");
+        assert!(sniffer.ip_lists.values().all(IpBlacklist::is_loading));
+
+        let updated = IpListConf {
+            is_allowlist: true,
+            ..IpListConf::new(allow_path.clone())
+        };
+        sniffer.update(Message::UpdateIpList(1, updated.clone()));
+        sniffer.update(Message::RemoveIpList(0));
+        sniffer.update(Message::RemoveIpList(5));
+        assert_eq!(sniffer.conf.settings.ip_lists, vec![updated]);
+        assert!(!sniffer.ip_lists.contains_key(&tor_path));
+
+        // content of removed lists is discarded
+        sniffer.update(Message::SetIpList(
+            tor_path.clone(),
+            ListUpdate::Loaded(IpBlacklist::default()),
+        ));
+        sniffer.update(Message::SetIpList(
+            allow_path,
+            ListUpdate::Loaded(IpBlacklist::default()),
+        ));
+        assert_eq!(sniffer.ip_lists.len(), 1);
+        assert!(!sniffer.is_loading_ip_lists());
+
+        // changing the format of a feed discards the ETag of its current copy
+        let feed_path = "https://example.com/drop.txt".to_string();
+        sniffer.update(Message::AddIpList(feed_path.clone()));
+        sniffer.update(Message::SetIpList(
+            feed_path.clone(),
+            ListUpdate::Loaded(IpBlacklist::default().with_etag(Some("\"v1\"".to_string()))),
+        ));
+        let feed = IpListConf {
+            is_allowlist: true,
+            ..IpListConf::new(feed_path.clone())
+        };
+        sniffer.update(Message::UpdateIpList(1, feed.clone()));
+        assert_eq!(
+            sniffer.ip_lists[&feed_path].etag(),
+            Some(&"\"v1\"".to_string())
+        );
+        let csv_feed = IpListConf {
+            format: FeedFormat::Csv,
+            ..feed
+        };
+        sniffer.update(Message::UpdateIpList(1, csv_feed.clone()));
+        assert_eq!(sniffer.ip_lists[&feed_path].etag(), None);
+
+        // the same holds for the CSV column
+        sniffer.update(Message::SetIpList(
+            feed_path.clone(),
+            ListUpdate::Loaded(IpBlacklist::default().with_etag(Some("\"v2\"".to_string()))),
+        ));
+        sniffer.update(Message::UpdateIpList(
+            1,
+            IpListConf {
+                csv_column: 2,
+                ..csv_feed
+            },
+        ));
+        assert_eq!(sniffer.ip_lists[&feed_path].etag(), None);
+    }
+
     #[test]
     #[parallel] // needed to not collide with other tests generating configs files
     fn test_correctly_switch_running_and_settings_pages() {
@@ -2197,6 +2961,11 @@ fn test_conf() {
                     },
                     style: StyleType::DraculaDark,
                     ip_blacklist: "blacklist_file.csv".to_string(),
+                    ip_lists: Vec::new(),
+                    decapsulate_tunnels: true,
+                    extract_server_names: true,
+                    metrics: MetricsConf::default(),
+                    flow_export: FlowExportConf::default(),
                 },
                 favorites: Favorites::from([FavoriteKey::Service(Service::Name("https"))]),
                 host_favorites_filter: true,
@@ -2210,6 +2979,7 @@ fn test_conf() {
                     bpf: "tcp or udp".to_string(),
                 },
                 report_sort_type: SortType::Ascending,
+                report_flows: true,
                 host_sort_type: SortType::Descending,
                 service_sort_type: SortType::Descending,
                 program_sort_type: SortType::Neutral,
@@ -2218,7 +2988,8 @@ fn test_conf() {
                 export_pcap: ExportPcap {
                     enabled: true,
                     file_name: "test.cap".to_string(),
-                    directory: "/".to_string()
+                    directory: "/".to_string(),
+                    ..ExportPcap::default()
                 },
                 import_pcap_path: "/test.pcap".to_string(),
                 data_repr: DataRepr::Bits,
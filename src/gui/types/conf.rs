@@ -40,6 +40,7 @@
 ///   - this is to ensure that fields deserialized correctly but with "weird" values are fixed
 #[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
 #[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Conf {
     /// Capture source picklist, to select the source of the capture
     #[serde(deserialize_with = "deserialize_or_default")]
@@ -68,6 +69,9 @@ pub struct Conf {
     /// Report sort type (inspect page)
     #[serde(deserialize_with = "deserialize_or_default")]
     pub report_sort_type: SortType,
+    /// Whether to merge both directions of the connections into bidirectional flows (inspect page)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub report_flows: bool,
     /// Host favorites filter (overview page)
     #[serde(deserialize_with = "deserialize_or_default")]
     pub host_favorites_filter: bool,
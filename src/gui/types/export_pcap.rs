@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::gui::types::conf::deserialize_or_default;
+use crate::networking::types::pcap_rotation::PcapRotation;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[serde(default)]
@@ -12,6 +14,15 @@ pub struct ExportPcap {
     pub(crate) file_name: String,
     #[serde(deserialize_with = "deserialize_or_default")]
     pub(crate) directory: String,
+    /// Start a new file every N megabytes (0 means never)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub(crate) rotation_megabytes: u32,
+    /// Start a new file every N minutes (0 means never)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub(crate) rotation_minutes: u32,
+    /// Only keep the last N files when rotating (0 means keep all)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub(crate) ring_buffer_files: u32,
 }
 
 impl ExportPcap {
@@ -30,6 +41,18 @@ pub fn set_directory(&mut self, directory: String) {
         self.directory = directory;
     }
 
+    pub fn set_rotation_megabytes(&mut self, value: &str) {
+        self.rotation_megabytes = parse_count(value);
+    }
+
+    pub fn set_rotation_minutes(&mut self, value: &str) {
+        self.rotation_minutes = parse_count(value);
+    }
+
+    pub fn set_ring_buffer_files(&mut self, value: &str) {
+        self.ring_buffer_files = parse_count(value);
+    }
+
     pub fn enabled(&self) -> bool {
         self.enabled
     }
@@ -42,6 +65,28 @@ pub fn directory(&self) -> &str {
         &self.directory
     }
 
+    pub fn rotation_megabytes(&self) -> u32 {
+        self.rotation_megabytes
+    }
+
+    pub fn rotation_minutes(&self) -> u32 {
+        self.rotation_minutes
+    }
+
+    pub fn ring_buffer_files(&self) -> u32 {
+        self.ring_buffer_files
+    }
+
+    pub fn rotation(&self) -> PcapRotation {
+        PcapRotation {
+            file_size: (self.rotation_megabytes > 0)
+                .then(|| u64::from(self.rotation_megabytes) * 1_000_000),
+            file_duration: (self.rotation_minutes > 0)
+                .then(|| Duration::from_secs(u64::from(self.rotation_minutes) * 60)),
+            files_to_keep: (self.ring_buffer_files > 0).then_some(self.ring_buffer_files as usize),
+        }
+    }
+
     pub fn full_path(&self) -> Option<String> {
         if self.enabled {
             let mut full_path = PathBuf::from(&self.directory);
@@ -64,10 +109,21 @@ fn default() -> Self {
             enabled: false,
             file_name: String::from(Self::DEFAULT_FILE_NAME),
             directory: std::env::var("HOME").unwrap_or_default(),
+            rotation_megabytes: 0,
+            rotation_minutes: 0,
+            ring_buffer_files: 0,
         }
     }
 }
 
+/// Parses a non-negative number typed by the user, ignoring non-digit characters
+fn parse_count(value: &str) -> u32 {
+    let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+    digits
+        .parse()
+        .unwrap_or(if digits.is_empty() { 0 } else { u32::MAX })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +227,32 @@ fn test_full_path() {
         export_pcap.set_directory("".to_string());
         assert_eq!(export_pcap.full_path(), Some("sniffnet.pcap".to_string()));
     }
+
+    #[test]
+    fn test_rotation() {
+        let mut export_pcap = ExportPcap::default();
+        assert_eq!(export_pcap.rotation(), PcapRotation::default());
+        assert!(!export_pcap.rotation().is_active());
+
+        export_pcap.set_rotation_megabytes("100");
+        export_pcap.set_rotation_minutes("1h0");
+        export_pcap.set_ring_buffer_files("");
+        assert_eq!(export_pcap.rotation_megabytes(), 100);
+        assert_eq!(export_pcap.rotation_minutes(), 10);
+        assert_eq!(export_pcap.ring_buffer_files(), 0);
+        assert_eq!(
+            export_pcap.rotation(),
+            PcapRotation {
+                file_size: Some(100_000_000),
+                file_duration: Some(Duration::from_secs(600)),
+                files_to_keep: None,
+            }
+        );
+
+        export_pcap.set_rotation_megabytes("0");
+        export_pcap.set_rotation_minutes("");
+        export_pcap.set_ring_buffer_files("99999999999");
+        assert_eq!(export_pcap.ring_buffer_files(), u32::MAX);
+        assert!(!export_pcap.rotation().is_active());
+    }
 }
@@ -315,13 +315,23 @@ pub fn new_entry_search(&self) -> SearchParameters {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FavoriteKey {
     Host(Host),
     Service(Service),
     Program(Program),
 }
 
+impl FavoriteKey {
+    pub fn to_entry_string(&self) -> String {
+        match self {
+            FavoriteKey::Host(host) => host.to_entry_string(),
+            FavoriteKey::Service(service) => service.to_string(),
+            FavoriteKey::Program(program) => program.to_string(),
+        }
+    }
+}
+
 impl From<FavoriteItem> for FavoriteKey {
     fn from(item: FavoriteItem) -> Self {
         match item {
@@ -3,21 +3,26 @@
 use crate::gui::pages::types::settings_page::SettingsPage;
 use crate::gui::styles::types::gradient_type::GradientType;
 use crate::gui::types::favorite::FavoriteKey;
+use crate::networking::ip_feeds::ListUpdate;
 use crate::networking::traffic_preview::TrafficPreview;
 use crate::networking::types::capture_context::CaptureSourcePicklist;
 use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::flow_export_conf::FlowExportConf;
 use crate::networking::types::host::HostMessage;
 use crate::networking::types::info_traffic::InfoTraffic;
-use crate::networking::types::ip_blacklist::IpBlacklist;
+use crate::networking::types::ip_blacklist::{IpBlacklist, IpListConf};
 use crate::networking::types::latency::LatencyStatus;
 use crate::notifications::types::notifications::Notification;
+use crate::notifications::types::threshold_rule::{RuleTarget, ThresholdRule};
+use crate::report::types::metrics_conf::MetricsConf;
+use crate::report::types::report_format::ReportFormat;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
 use crate::utils::types::file_info::FileInfo;
 use crate::utils::types::web_page::WebPage;
 use crate::{Language, StyleType};
 use iced::window;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 
 #[derive(Debug, Clone)]
 /// Messages types that permit reacting to application interactions/subscriptions
@@ -32,6 +37,8 @@ pub enum Message {
     SetCaptureSource(CaptureSourcePicklist),
     /// Select network device
     DeviceSelection(String),
+    /// Add or remove a network device from the ones captured in parallel
+    ToggleDeviceSelection(String),
     /// Toggle BPF filter checkbox
     ToggleFilters,
     /// Change BPF filter string
@@ -40,6 +47,8 @@ pub enum Message {
     DataReprSelection(DataRepr),
     /// Select report sort type to be displayed (inspect page)
     ReportSortSelection(SortType),
+    /// Toggle the bidirectional flows view of the inspect page
+    ReportFlowsToggle,
     /// Select host sort type to be displayed (overview page)
     HostSortSelection(SortType),
     /// Select service sort type to be displayed (overview page)
@@ -98,6 +107,10 @@ pub enum Message {
     Search(SearchParameters),
     /// Update page result number in inspect
     UpdatePageNumber(bool),
+    /// Export all the connections matching the inspect search to file
+    ExportSearchResults(ReportFormat),
+    /// The export of the inspect search results is over, with the error raised, if any
+    SearchResultsExported(Option<String>),
     /// Left (false) or Right (true) arrow key has been pressed
     ArrowPressed(bool),
     /// Emit when the main window be focused
@@ -118,6 +131,30 @@ pub enum Message {
     LoadIpBlacklist(String),
     /// Set new IP blacklist content
     SetIpBlacklist(IpBlacklist),
+    /// Add a named IP list from the file at the given path
+    AddIpList(String),
+    /// Set the content of the named IP list at the given path
+    SetIpList(String, ListUpdate),
+    /// The URL of a new IP list has been edited
+    IpListUrl(String),
+    /// Update the named IP list at the given index
+    UpdateIpList(usize, IpListConf),
+    /// Remove the named IP list at the given index
+    RemoveIpList(usize),
+    /// Toggle the decapsulation of the traffic carried by tunnels
+    TunnelDecapsulationToggle,
+    /// Toggle the extraction of the server names from TLS and QUIC handshakes
+    ServerNamesToggle,
+    /// Start or stop the endpoint exposing the Prometheus metrics
+    ToggleMetrics,
+    /// Update the settings of the metrics endpoint
+    UpdateMetricsConf(MetricsConf),
+    /// Start the metrics endpoint on the given address (from the command line)
+    EnableMetrics(SocketAddr),
+    /// Enable or disable the export of flow records
+    ToggleFlowExport,
+    /// Update the settings of the export of flow records
+    UpdateFlowExportConf(FlowExportConf),
     /// Wrapper around the Quit message
     QuitWrapper,
     /// Save the configurations of the app and quit
@@ -136,6 +173,12 @@ pub enum Message {
     OutputPcapDir(String),
     /// The output PCAP file name has been updated
     OutputPcapFile(String),
+    /// The size (in megabytes) after which a new output PCAP file is started has been updated
+    OutputPcapRotationSize(String),
+    /// The interval (in minutes) after which a new output PCAP file is started has been updated
+    OutputPcapRotationInterval(String),
+    /// The number of output PCAP files to keep has been updated
+    OutputPcapRingBuffer(String),
     /// Toggle thumbnail mode
     ToggleThumbnail(bool),
     /// Drag the window
@@ -162,6 +205,12 @@ pub enum Message {
     ToggleRemoteNotifications,
     /// The remote notifications URL has been updated
     RemoteNotificationsUrl(String),
+    /// Add a threshold rule for the given favorite or search
+    AddThresholdRule(RuleTarget),
+    /// Update the threshold rule at the given index
+    UpdateThresholdRule(usize, ThresholdRule),
+    /// Remove the threshold rule at the given index
+    RemoveThresholdRule(usize),
     /// Pause or resume live capture
     Freeze,
     /// Traffic preview
@@ -2,7 +2,10 @@
 
 use crate::gui::styles::types::gradient_type::GradientType;
 use crate::gui::types::conf::deserialize_or_default;
+use crate::networking::types::flow_export_conf::FlowExportConf;
+use crate::networking::types::ip_blacklist::IpListConf;
 use crate::notifications::types::notifications::Notifications;
+use crate::report::types::metrics_conf::MetricsConf;
 use crate::{Language, StyleType};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -22,6 +25,22 @@ pub struct Settings {
     pub mmdb_asn: String,
     #[serde(deserialize_with = "deserialize_or_default")]
     pub ip_blacklist: String,
+    /// Named IP lists, in addition to the default IP blacklist
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub ip_lists: Vec<IpListConf>,
+    /// Whether to analyze the packets carried by tunnels (VXLAN, Geneve, GRE, and IP-in-IP)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub decapsulate_tunnels: bool,
+    /// Whether to extract the server names from TLS and QUIC handshakes
+    /// (live captures store larger slices of each packet to do so)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub extract_server_names: bool,
+    /// Embedded endpoint exposing the traffic statistics as Prometheus metrics
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub metrics: MetricsConf,
+    /// Export of the connections to a `NetFlow` or IPFIX collector
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub flow_export: FlowExportConf,
     // ---------------------------------------------------------------------------------------------
     #[serde(deserialize_with = "deserialize_or_default")]
     pub notifications: Notifications,
@@ -38,6 +57,11 @@ fn default() -> Self {
             mmdb_country: String::new(),
             mmdb_asn: String::new(),
             ip_blacklist: String::new(),
+            ip_lists: Vec::new(),
+            decapsulate_tunnels: false,
+            extract_server_names: false,
+            metrics: MetricsConf::default(),
+            flow_export: FlowExportConf::default(),
             style_path: String::new(),
             notifications: Notifications::default(),
             style: StyleType::default(),
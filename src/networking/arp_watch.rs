@@ -0,0 +1,280 @@
+//! Module to detect suspicious ARP activity, which is often the symptom of ARP spoofing.
+//!
+//! The MAC address claiming each IPv4 address is remembered, so that it's possible to notice when:
+//! - a known IP address is suddenly claimed by a different MAC address
+//! - two MAC addresses keep claiming the same IP address (spoofing attempt or IP conflict)
+//! - a MAC address floods the network with gratuitous ARP packets
+
+use crate::networking::manage_packets::mac_from_dec_to_hex;
+use crate::networking::types::arp_anomaly::ArpAnomaly;
+use crate::utils::types::bounded_map::BoundedMap;
+use etherparse::{ArpOperation, LaxPacketHeaders, NetHeaders};
+use std::net::Ipv4Addr;
+
+/// Maximum number of IP addresses for which the claiming MAC address is remembered
+const MAX_BINDINGS: usize = 65_536;
+/// Bindings of IP addresses not claimed for longer than this are forgotten
+const BINDING_TIMEOUT_USECS: i64 = 3_600_000_000;
+/// Maximum number of MAC addresses for which gratuitous ARP packets are counted
+const MAX_GRATUITOUS_SENDERS: usize = 16_384;
+/// Two MAC addresses claiming an IP within this interval are considered in conflict
+const DUPLICATE_WINDOW_USECS: i64 = 60_000_000;
+/// Interval over which gratuitous ARP packets are counted
+const FLOOD_WINDOW_USECS: i64 = 10_000_000;
+/// Number of gratuitous ARP packets sent within the flood window to be considered a flood
+const FLOOD_THRESHOLD: usize = 20;
+/// Minimum interval between two anomalies reported for the same IP or MAC address
+const REPORT_COOLDOWN_USECS: i64 = 60_000_000;
+
+/// MAC addresses claiming an IP address
+struct Binding {
+    mac: [u8; 6],
+    last_seen_usecs: i64,
+    /// MAC address which claimed the IP before the current one, along with its last claim
+    previous: Option<([u8; 6], i64)>,
+    last_report_usecs: Option<i64>,
+}
+
+/// Gratuitous ARP packets recently sent by a MAC address
+#[derive(Default)]
+struct GratuitousCount {
+    window_start_usecs: i64,
+    packets: usize,
+    last_report_usecs: Option<i64>,
+}
+
+/// Keeps track of the IP to MAC bindings announced via ARP to detect anomalies
+pub struct ArpWatcher {
+    bindings: BoundedMap<Ipv4Addr, Binding>,
+    gratuitous: BoundedMap<[u8; 6], GratuitousCount>,
+}
+
+impl Default for ArpWatcher {
+    fn default() -> Self {
+        Self {
+            bindings: BoundedMap::new(MAX_BINDINGS, BINDING_TIMEOUT_USECS),
+            // after the cooldown, idle senders have nothing worth remembering
+            gratuitous: BoundedMap::new(MAX_GRATUITOUS_SENDERS, REPORT_COOLDOWN_USECS),
+        }
+    }
+}
+
+impl ArpWatcher {
+    /// Analyzes an ARP packet, returning the anomaly it reveals (if any)
+    pub fn check(
+        &mut self,
+        headers: &LaxPacketHeaders,
+        timestamp_usecs: i64,
+    ) -> Option<ArpAnomaly> {
+        let Some(NetHeaders::Arp(arp)) = &headers.net else {
+            return None;
+        };
+        let arp = arp.try_eth_ipv4().ok()?;
+        let sender_ip = Ipv4Addr::from(arp.sender_ipv4);
+        // ARP probes are sent before an address is assigned
+        if sender_ip.is_unspecified() {
+            return None;
+        }
+
+        let is_gratuitous = arp.sender_ipv4 == arp.target_ipv4
+            || (arp.operation == ArpOperation::REPLY
+                && [[0; 6], [0xff; 6]].contains(&arp.target_mac));
+        let flood = if is_gratuitous {
+            self.count_gratuitous(arp.sender_mac, timestamp_usecs)
+        } else {
+            None
+        };
+
+        self.claim(sender_ip, arp.sender_mac, timestamp_usecs)
+            .or(flood)
+    }
+
+    fn claim(&mut self, ip: Ipv4Addr, mac: [u8; 6], timestamp_usecs: i64) -> Option<ArpAnomaly> {
+        let binding = self
+            .bindings
+            .get_or_insert_with(ip, timestamp_usecs, || Binding {
+                mac,
+                last_seen_usecs: timestamp_usecs,
+                previous: None,
+                last_report_usecs: None,
+            });
+        if binding.mac == mac {
+            binding.last_seen_usecs = timestamp_usecs;
+            return None;
+        }
+
+        // the MAC address which claimed the IP before the current one is claiming it again
+        let is_conflict = binding
+            .previous
+            .is_some_and(|(previous_mac, _)| previous_mac == mac)
+            && timestamp_usecs - binding.last_seen_usecs < DUPLICATE_WINDOW_USECS;
+        let anomaly = if is_conflict {
+            ArpAnomaly::DuplicateIp {
+                ip,
+                mac_addresses: (mac_from_dec_to_hex(binding.mac), mac_from_dec_to_hex(mac)),
+            }
+        } else {
+            ArpAnomaly::MacChanged {
+                ip,
+                previous_mac: mac_from_dec_to_hex(binding.mac),
+                new_mac: mac_from_dec_to_hex(mac),
+            }
+        };
+        binding.previous = Some((binding.mac, binding.last_seen_usecs));
+        binding.mac = mac;
+        binding.last_seen_usecs = timestamp_usecs;
+
+        if is_cooling_down(binding.last_report_usecs, timestamp_usecs) {
+            return None;
+        }
+        binding.last_report_usecs = Some(timestamp_usecs);
+        Some(anomaly)
+    }
+
+    fn count_gratuitous(&mut self, mac: [u8; 6], timestamp_usecs: i64) -> Option<ArpAnomaly> {
+        let count =
+            self.gratuitous
+                .get_or_insert_with(mac, timestamp_usecs, GratuitousCount::default);
+        if timestamp_usecs - count.window_start_usecs >= FLOOD_WINDOW_USECS {
+            count.window_start_usecs = timestamp_usecs;
+            count.packets = 0;
+        }
+        count.packets += 1;
+        if count.packets < FLOOD_THRESHOLD
+            || is_cooling_down(count.last_report_usecs, timestamp_usecs)
+        {
+            return None;
+        }
+        count.last_report_usecs = Some(timestamp_usecs);
+        Some(ArpAnomaly::GratuitousFlood {
+            mac: mac_from_dec_to_hex(mac),
+            packets: count.packets,
+        })
+    }
+}
+
+fn is_cooling_down(last_report_usecs: Option<i64>, timestamp_usecs: i64) -> bool {
+    last_report_usecs.is_some_and(|last| timestamp_usecs - last < REPORT_COOLDOWN_USECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{ArpEthIpv4Packet, PacketBuilder};
+
+    fn arp_packet(
+        operation: ArpOperation,
+        sender: ([u8; 6], [u8; 4]),
+        target: ([u8; 6], [u8; 4]),
+    ) -> Vec<u8> {
+        let arp = ArpEthIpv4Packet {
+            operation,
+            sender_mac: sender.0,
+            sender_ipv4: sender.1,
+            target_mac: target.0,
+            target_ipv4: target.1,
+        };
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2(sender.0, target.0)
+            .arp(arp.into())
+            .write(&mut packet)
+            .unwrap();
+        packet
+    }
+
+    fn check(watcher: &mut ArpWatcher, packet: &[u8], timestamp_usecs: i64) -> Option<ArpAnomaly> {
+        let headers = LaxPacketHeaders::from_ethernet(packet).unwrap();
+        watcher.check(&headers, timestamp_usecs)
+    }
+
+    const GATEWAY_IP: [u8; 4] = [192, 168, 1, 1];
+    const GATEWAY_MAC: [u8; 6] = [0xaa, 0, 0, 0, 0, 1];
+    const ATTACKER_MAC: [u8; 6] = [0xbb, 0, 0, 0, 0, 2];
+    const VICTIM: ([u8; 6], [u8; 4]) = ([0xcc, 0, 0, 0, 0, 3], [192, 168, 1, 20]);
+
+    #[test]
+    fn test_arp_mac_changed_and_duplicate_ip() {
+        let mut watcher = ArpWatcher::default();
+        let genuine = arp_packet(ArpOperation::REPLY, (GATEWAY_MAC, GATEWAY_IP), VICTIM);
+        let spoofed = arp_packet(ArpOperation::REPLY, (ATTACKER_MAC, GATEWAY_IP), VICTIM);
+
+        assert_eq!(check(&mut watcher, &genuine, 0), None);
+        assert_eq!(check(&mut watcher, &genuine, 1_000_000), None);
+        assert_eq!(
+            check(&mut watcher, &spoofed, 2_000_000),
+            Some(ArpAnomaly::MacChanged {
+                ip: Ipv4Addr::from(GATEWAY_IP),
+                previous_mac: "aa:00:00:00:00:01".to_string(),
+                new_mac: "bb:00:00:00:00:02".to_string(),
+            })
+        );
+        // still within the cooldown
+        assert_eq!(check(&mut watcher, &genuine, 3_000_000), None);
+        assert_eq!(check(&mut watcher, &spoofed, 50_000_000), None);
+        // both MAC addresses keep claiming the IP
+        assert_eq!(
+            check(&mut watcher, &genuine, 70_000_000),
+            Some(ArpAnomaly::DuplicateIp {
+                ip: Ipv4Addr::from(GATEWAY_IP),
+                mac_addresses: (
+                    "bb:00:00:00:00:02".to_string(),
+                    "aa:00:00:00:00:01".to_string()
+                ),
+            })
+        );
+    }
+
+    #[test]
+    fn test_arp_gratuitous_flood() {
+        let mut watcher = ArpWatcher::default();
+        let gratuitous = arp_packet(
+            ArpOperation::REQUEST,
+            (ATTACKER_MAC, GATEWAY_IP),
+            ([0xff; 6], GATEWAY_IP),
+        );
+        for i in 0..FLOOD_THRESHOLD - 1 {
+            assert_eq!(check(&mut watcher, &gratuitous, i as i64 * 1_000), None);
+        }
+        assert_eq!(
+            check(&mut watcher, &gratuitous, 100_000),
+            Some(ArpAnomaly::GratuitousFlood {
+                mac: "bb:00:00:00:00:02".to_string(),
+                packets: FLOOD_THRESHOLD,
+            })
+        );
+        assert_eq!(check(&mut watcher, &gratuitous, 200_000), None);
+
+        // probes don't bind addresses, and regular requests aren't gratuitous
+        let probe = arp_packet(
+            ArpOperation::REQUEST,
+            (GATEWAY_MAC, [0; 4]),
+            ([0; 6], GATEWAY_IP),
+        );
+        let request = arp_packet(ArpOperation::REQUEST, VICTIM, ([0; 6], GATEWAY_IP));
+        for i in 0..2 * FLOOD_THRESHOLD {
+            assert_eq!(check(&mut watcher, &probe, i as i64), None);
+            assert_eq!(check(&mut watcher, &request, i as i64), None);
+        }
+    }
+
+    #[test]
+    fn test_arp_bogus_claims_dont_disable_detection() {
+        let mut watcher = ArpWatcher::default();
+        let max = u32::try_from(MAX_BINDINGS).unwrap();
+        // bogus claims filling the table
+        for i in 0..max {
+            assert_eq!(
+                watcher.claim(Ipv4Addr::from(0x0a00_0000 + i), ATTACKER_MAC, 0),
+                None
+            );
+        }
+
+        let genuine = arp_packet(ArpOperation::REPLY, (GATEWAY_MAC, GATEWAY_IP), VICTIM);
+        let spoofed = arp_packet(ArpOperation::REPLY, (ATTACKER_MAC, GATEWAY_IP), VICTIM);
+        assert_eq!(check(&mut watcher, &genuine, 1_000_000), None);
+        assert!(matches!(
+            check(&mut watcher, &spoofed, 2_000_000),
+            Some(ArpAnomaly::MacChanged { .. })
+        ));
+    }
+}
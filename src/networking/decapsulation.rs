@@ -0,0 +1,314 @@
+//! Module to decapsulate the traffic carried by tunnels (VXLAN, Geneve, GRE, and IP-in-IP),
+//! so that the inner packets are attributed to their actual endpoints.
+//!
+//! References:
+//! - VXLAN: <https://www.rfc-editor.org/rfc/rfc7348>
+//! - Geneve: <https://www.rfc-editor.org/rfc/rfc8926>
+//! - GRE: <https://www.rfc-editor.org/rfc/rfc2784> and <https://www.rfc-editor.org/rfc/rfc2890>
+
+use crate::networking::types::tunnel::{Tunnel, TunnelKind};
+use etherparse::{
+    EtherType, IpNumber, LaxPacketHeaders, LaxPayloadSlice, NetHeaders, TransportHeader,
+};
+use std::net::IpAddr;
+
+const VXLAN_PORT: u16 = 4789;
+const GENEVE_PORT: u16 = 6081;
+
+/// Length of both the VXLAN header and the fixed part of the Geneve header
+const HEADER_LEN: usize = 8;
+/// VXLAN flag indicating that the VNI is valid
+const VXLAN_FLAG_VNI: u8 = 0x08;
+
+/// Protocol type of encapsulated Ethernet frames (Transparent Ethernet Bridging)
+const ETHER_TYPE_TEB: u16 = 0x6558;
+
+/// GRE flags indicating the presence of the optional fields
+const GRE_FLAG_CHECKSUM: u16 = 0x8000;
+const GRE_FLAG_KEY: u16 = 0x2000;
+const GRE_FLAG_SEQUENCE: u16 = 0x1000;
+/// Mask of the GRE version (only version 0 carries generic protocols)
+const GRE_VERSION_MASK: u16 = 0x0007;
+
+/// Inner packet carried by a tunnel
+pub struct Decapsulated<'a> {
+    /// Headers of the inner packet
+    pub headers: LaxPacketHeaders<'a>,
+    /// Tunnel carrying the inner packet
+    pub tunnel: Tunnel,
+    /// Bytes of the outer packet, accounted in the same way as the ones of any other packet
+    pub outer_bytes: u128,
+}
+
+/// Returns the inner packet, if the given one is carried by a supported tunnel
+pub fn decapsulate<'a>(headers: &LaxPacketHeaders<'a>) -> Option<Decapsulated<'a>> {
+    let (source, dest, ip_bytes) = match &headers.net {
+        Some(NetHeaders::Ipv4(ipv4, _)) => (
+            IpAddr::from(ipv4.source),
+            IpAddr::from(ipv4.destination),
+            u128::from(ipv4.total_len),
+        ),
+        Some(NetHeaders::Ipv6(ipv6, _)) => (
+            IpAddr::from(ipv6.source),
+            IpAddr::from(ipv6.destination),
+            u128::from(ipv6.payload_length) + 40,
+        ),
+        _ => return None,
+    };
+
+    let (kind, id, inner) = match (&headers.transport, &headers.payload) {
+        (Some(TransportHeader::Udp(udp)), LaxPayloadSlice::Udp { payload, .. }) => {
+            match udp.destination_port {
+                VXLAN_PORT => vxlan(payload).map(|(id, inner)| (TunnelKind::Vxlan, id, inner))?,
+                GENEVE_PORT => {
+                    geneve(payload).map(|(id, inner)| (TunnelKind::Geneve, id, inner))?
+                }
+                _ => return None,
+            }
+        }
+        (None, LaxPayloadSlice::Ip(ip_payload)) if !ip_payload.fragmented => {
+            match ip_payload.ip_number {
+                IpNumber::GRE => {
+                    gre(ip_payload.payload).map(|(id, inner)| (TunnelKind::Gre, id, inner))?
+                }
+                IpNumber::IPV4 | IpNumber::IPV6 => (
+                    TunnelKind::IpInIp,
+                    None,
+                    LaxPacketHeaders::from_ip(ip_payload.payload).ok()?,
+                ),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    // the inner packet must at least have a network header to be analyzed
+    inner.net.as_ref()?;
+
+    Some(Decapsulated {
+        headers: inner,
+        tunnel: Tunnel {
+            kind,
+            source,
+            dest,
+            id,
+        },
+        outer_bytes: headers.link.as_ref().map_or(0, |l| l.header_len() as u128) + ip_bytes,
+    })
+}
+
+/// Parses a VXLAN header, returning the VNI and the encapsulated Ethernet frame
+fn vxlan(payload: &[u8]) -> Option<(Option<u32>, LaxPacketHeaders<'_>)> {
+    let header = payload.get(..HEADER_LEN)?;
+    let id = (header[0] & VXLAN_FLAG_VNI != 0).then(|| read_u24(header, 4));
+    let inner = LaxPacketHeaders::from_ethernet(&payload[HEADER_LEN..]).ok()?;
+    Some((id, inner))
+}
+
+/// Parses a Geneve header, returning the VNI and the encapsulated packet
+fn geneve(payload: &[u8]) -> Option<(Option<u32>, LaxPacketHeaders<'_>)> {
+    let header = payload.get(..HEADER_LEN)?;
+    // only version 0 is defined
+    if header[0] >> 6 != 0 {
+        return None;
+    }
+    let options_len = usize::from(header[0] & 0x3f) * 4;
+    let protocol_type = u16::from_be_bytes([header[2], header[3]]);
+    let inner = payload.get(HEADER_LEN + options_len..)?;
+    Some((
+        Some(read_u24(header, 4)),
+        from_protocol_type(protocol_type, inner)?,
+    ))
+}
+
+/// Parses a GRE header, returning the key and the encapsulated packet
+fn gre(payload: &[u8]) -> Option<(Option<u32>, LaxPacketHeaders<'_>)> {
+    let flags = u16::from_be_bytes(payload.get(..2)?.try_into().ok()?);
+    if flags & GRE_VERSION_MASK != 0 {
+        return None;
+    }
+    let protocol_type = u16::from_be_bytes(payload.get(2..4)?.try_into().ok()?);
+    let mut offset = 4;
+    if flags & GRE_FLAG_CHECKSUM != 0 {
+        offset += 4;
+    }
+    let id = if flags & GRE_FLAG_KEY == 0 {
+        None
+    } else {
+        let key = u32::from_be_bytes(payload.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+        Some(key)
+    };
+    if flags & GRE_FLAG_SEQUENCE != 0 {
+        offset += 4;
+    }
+    let inner = payload.get(offset..)?;
+    Some((id, from_protocol_type(protocol_type, inner)?))
+}
+
+/// Parses a packet whose type is identified by an `EtherType`
+fn from_protocol_type(protocol_type: u16, packet: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    if protocol_type == ETHER_TYPE_TEB {
+        LaxPacketHeaders::from_ethernet(packet).ok()
+    } else {
+        Some(LaxPacketHeaders::from_ether_type(
+            EtherType(protocol_type),
+            packet,
+        ))
+    }
+}
+
+fn read_u24(slice: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([0, slice[offset], slice[offset + 1], slice[offset + 2]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::PacketBuilder;
+
+    const OUTER_SOURCE: [u8; 4] = [10, 0, 0, 1];
+    const OUTER_DEST: [u8; 4] = [10, 0, 0, 2];
+
+    fn inner_ipv4_udp() -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ipv4([192, 168, 10, 5], [192, 168, 20, 7], 64)
+            .udp(40000, 53)
+            .write(&mut packet, &[1, 2, 3, 4])
+            .unwrap();
+        packet
+    }
+
+    fn inner_ethernet_tcp() -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([2, 0, 0, 0, 0, 1], [2, 0, 0, 0, 0, 2])
+            .ipv4([172, 16, 0, 3], [172, 16, 0, 4], 64)
+            .tcp(50000, 8080, 1, 1024)
+            .write(&mut packet, &[])
+            .unwrap();
+        packet
+    }
+
+    fn outer_udp(dest_port: u16, payload: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [3; 6])
+            .ipv4(OUTER_SOURCE, OUTER_DEST, 64)
+            .udp(54321, dest_port)
+            .write(&mut packet, payload)
+            .unwrap();
+        packet
+    }
+
+    fn outer_ip(ip_number: IpNumber, payload: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [3; 6])
+            .ipv4(OUTER_SOURCE, OUTER_DEST, 64)
+            .write(&mut packet, ip_number, payload)
+            .unwrap();
+        packet
+    }
+
+    fn inner_addresses(decapsulated: &Decapsulated) -> (IpAddr, IpAddr) {
+        match &decapsulated.headers.net {
+            Some(NetHeaders::Ipv4(ipv4, _)) => (ipv4.source.into(), ipv4.destination.into()),
+            _ => panic!("inner packet should be IPv4"),
+        }
+    }
+
+    #[test]
+    fn test_decapsulate_vxlan() {
+        let mut payload = vec![VXLAN_FLAG_VNI, 0, 0, 0, 0, 0x30, 0x39, 0];
+        payload.extend(inner_ethernet_tcp());
+        let packet = outer_udp(VXLAN_PORT, &payload);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+
+        let decapsulated = decapsulate(&headers).unwrap();
+        assert_eq!(
+            decapsulated.tunnel,
+            Tunnel {
+                kind: TunnelKind::Vxlan,
+                source: IpAddr::from(OUTER_SOURCE),
+                dest: IpAddr::from(OUTER_DEST),
+                id: Some(12345),
+            }
+        );
+        assert_eq!(
+            inner_addresses(&decapsulated),
+            ([172, 16, 0, 3].into(), [172, 16, 0, 4].into())
+        );
+        assert!(matches!(
+            decapsulated.headers.transport,
+            Some(TransportHeader::Tcp(_))
+        ));
+        assert_eq!(decapsulated.outer_bytes, packet.len() as u128);
+        assert_eq!(
+            decapsulated.tunnel.to_string(),
+            "VXLAN 10.0.0.1 → 10.0.0.2 (VNI 12345)"
+        );
+    }
+
+    #[test]
+    fn test_decapsulate_geneve_with_options() {
+        // one 4 bytes option, protocol type IPv4, VNI 7
+        let mut payload = vec![0x01, 0, 0x08, 0x00, 0, 0, 7, 0, 1, 2, 3, 4];
+        payload.extend(inner_ipv4_udp());
+        let packet = outer_udp(GENEVE_PORT, &payload);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+
+        let decapsulated = decapsulate(&headers).unwrap();
+        assert_eq!(decapsulated.tunnel.kind, TunnelKind::Geneve);
+        assert_eq!(decapsulated.tunnel.id, Some(7));
+        assert_eq!(
+            inner_addresses(&decapsulated),
+            ([192, 168, 10, 5].into(), [192, 168, 20, 7].into())
+        );
+    }
+
+    #[test]
+    fn test_decapsulate_gre_and_ip_in_ip() {
+        // GRE with key 99, carrying IPv4
+        let mut payload = vec![0x20, 0, 0x08, 0x00, 0, 0, 0, 99];
+        payload.extend(inner_ipv4_udp());
+        let packet = outer_ip(IpNumber::GRE, &payload);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let decapsulated = decapsulate(&headers).unwrap();
+        assert_eq!(decapsulated.tunnel.kind, TunnelKind::Gre);
+        assert_eq!(decapsulated.tunnel.id, Some(99));
+        assert_eq!(
+            decapsulated.tunnel.to_string(),
+            "GRE 10.0.0.1 → 10.0.0.2 (key 99)"
+        );
+        assert_eq!(
+            inner_addresses(&decapsulated),
+            ([192, 168, 10, 5].into(), [192, 168, 20, 7].into())
+        );
+
+        let packet = outer_ip(IpNumber::IPV4, &inner_ipv4_udp());
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let decapsulated = decapsulate(&headers).unwrap();
+        assert_eq!(decapsulated.tunnel.kind, TunnelKind::IpInIp);
+        assert_eq!(decapsulated.tunnel.id, None);
+        assert_eq!(
+            decapsulated.tunnel.to_string(),
+            "IP-in-IP 10.0.0.1 → 10.0.0.2"
+        );
+    }
+
+    #[test]
+    fn test_not_decapsulated() {
+        // regular UDP traffic
+        let packet = outer_udp(443, &inner_ipv4_udp());
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert!(decapsulate(&headers).is_none());
+
+        // truncated VXLAN header
+        let packet = outer_udp(VXLAN_PORT, &[VXLAN_FLAG_VNI, 0, 0]);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert!(decapsulate(&headers).is_none());
+
+        // GRE carrying a protocol without a network header (PPP)
+        let packet = outer_ip(IpNumber::GRE, &[0, 0, 0x88, 0x0b, 0xff, 0x03]);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert!(decapsulate(&headers).is_none());
+    }
+}
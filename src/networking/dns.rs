@@ -0,0 +1,275 @@
+//! Module to decode the DNS responses (including mDNS) exchanged in the sniffed traffic.
+//!
+//! This is used to label the remote addresses with the domain names that were actually queried,
+//! which are often more meaningful than the ones obtained via reverse DNS lookups
+//! (e.g., `youtube.com` instead of `1e100.net`).
+//!
+//! Reference: <https://www.rfc-editor.org/rfc/rfc1035>
+
+use etherparse::{LaxPacketHeaders, LaxPayloadSlice, TransportHeader};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const DNS_PORT: u16 = 53;
+const MDNS_PORT: u16 = 5353;
+
+const HEADER_LEN: usize = 12;
+/// Query/Response flag
+const FLAG_RESPONSE: u16 = 0x8000;
+
+const TYPE_A: u16 = 1;
+const TYPE_CNAME: u16 = 5;
+const TYPE_AAAA: u16 = 28;
+
+/// Maximum length of a domain name
+const MAX_NAME_LEN: usize = 255;
+/// Maximum number of compression pointers followed while decoding a name (prevents loops)
+const MAX_POINTERS: usize = 16;
+
+/// Returns the addresses contained in the DNS response carried by a packet (if any),
+/// each with the domain name originally queried to obtain it
+pub fn dns_answers(headers: &LaxPacketHeaders) -> Vec<(IpAddr, String)> {
+    let message = match (&headers.transport, &headers.payload) {
+        (Some(TransportHeader::Udp(udp)), LaxPayloadSlice::Udp { payload, .. })
+            if is_dns_port(udp.source_port) || is_dns_port(udp.destination_port) =>
+        {
+            *payload
+        }
+        (Some(TransportHeader::Tcp(tcp)), LaxPayloadSlice::Tcp { payload, .. })
+            if tcp.source_port == DNS_PORT =>
+        {
+            // DNS messages over TCP are prefixed by their length;
+            // only messages entirely contained in a single segment are decoded
+            let Some(len) = payload
+                .get(..2)
+                .map(|l| usize::from(u16::from_be_bytes([l[0], l[1]])))
+            else {
+                return Vec::new();
+            };
+            let Some(message) = payload.get(2..2 + len) else {
+                return Vec::new();
+            };
+            message
+        }
+        _ => return Vec::new(),
+    };
+    parse_response(message).unwrap_or_default()
+}
+
+fn is_dns_port(port: u16) -> bool {
+    port == DNS_PORT || port == MDNS_PORT
+}
+
+/// Parses a DNS message, returning None if it's not a valid response
+fn parse_response(message: &[u8]) -> Option<Vec<(IpAddr, String)>> {
+    let flags = read_u16(message, 2)?;
+    if flags & FLAG_RESPONSE == 0 {
+        return None;
+    }
+    let questions = read_u16(message, 4)?;
+    let answers = read_u16(message, 6)?;
+    if answers == 0 {
+        return None;
+    }
+
+    let mut offset = HEADER_LEN;
+    for _ in 0..questions {
+        // name, type, and class
+        offset = read_name(message, offset)?.1 + 4;
+    }
+
+    // alias => canonical name
+    let mut cnames = HashMap::new();
+    let mut addresses = Vec::new();
+    for _ in 0..answers {
+        let (name, name_end) = read_name(message, offset)?;
+        let record_type = read_u16(message, name_end)?;
+        // class and TTL are skipped
+        let data_len = usize::from(read_u16(message, name_end + 8)?);
+        let data_start = name_end + 10;
+        let data = message.get(data_start..data_start + data_len)?;
+        match record_type {
+            TYPE_A => {
+                if let Ok(octets) = <[u8; 4]>::try_from(data) {
+                    addresses.push((IpAddr::V4(Ipv4Addr::from(octets)), name));
+                }
+            }
+            TYPE_AAAA => {
+                if let Ok(octets) = <[u8; 16]>::try_from(data) {
+                    addresses.push((IpAddr::V6(Ipv6Addr::from(octets)), name));
+                }
+            }
+            TYPE_CNAME => {
+                let (canonical_name, _) = read_name(message, data_start)?;
+                cnames.insert(canonical_name, name);
+            }
+            _ => {}
+        }
+        offset = data_start + data_len;
+    }
+
+    Some(
+        addresses
+            .into_iter()
+            .map(|(address, name)| (address, queried_name(name, &cnames)))
+            .collect(),
+    )
+}
+
+/// Follows the chain of CNAME records backwards, up to the name originally queried
+fn queried_name(mut name: String, cnames: &HashMap<String, String>) -> String {
+    for _ in 0..cnames.len() {
+        match cnames.get(&name) {
+            Some(alias) => name.clone_from(alias),
+            None => break,
+        }
+    }
+    name
+}
+
+/// Reads the (possibly compressed) domain name starting at the given offset,
+/// returning it in lowercase along with the offset of the following field
+fn read_name(message: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut end = None;
+    let mut pointers = 0;
+    loop {
+        let len = *message.get(offset)?;
+        match len {
+            0 => break,
+            // compression pointer
+            l if l & 0xC0 == 0xC0 => {
+                pointers += 1;
+                if pointers > MAX_POINTERS {
+                    return None;
+                }
+                end.get_or_insert(offset + 2);
+                offset = usize::from(read_u16(message, offset)? & 0x3FFF);
+            }
+            l if l & 0xC0 != 0 => return None,
+            l => {
+                let label = message.get(offset + 1..offset + 1 + usize::from(l))?;
+                if !name.is_empty() {
+                    name.push('.');
+                }
+                name.push_str(&String::from_utf8_lossy(label).to_lowercase());
+                if name.len() > MAX_NAME_LEN {
+                    return None;
+                }
+                offset += 1 + usize::from(l);
+            }
+        }
+    }
+    Some((name, end.unwrap_or(offset + 1)))
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response to a query for `www.youtube.com`, answered with a CNAME and two addresses
+    fn youtube_response() -> Vec<u8> {
+        let mut message = vec![
+            0x12, 0x34, // id
+            0x81, 0x80, // flags: standard response, no error
+            0x00, 0x01, // questions
+            0x00, 0x03, // answers
+            0x00, 0x00, // authority records
+            0x00, 0x00, // additional records
+        ];
+        // question: www.youtube.com, type A, class IN
+        message.extend_from_slice(b"\x03www\x07youtube\x03com\x00\x00\x01\x00\x01");
+        // answer: www.youtube.com (pointer to offset 12) CNAME youtube-ui.l.google.com
+        message.extend_from_slice(&[0xC0, 0x0C, 0x00, 0x05, 0x00, 0x01, 0, 0, 0, 60, 0x00, 0x16]);
+        message.extend_from_slice(b"\x0ayoutube-ui\x01l\x06google\xc0\x18");
+        // answers: youtube-ui.l.google.com (pointer to offset 45) A 142.250.180.14 and AAAA
+        message.extend_from_slice(&[0xC0, 0x2D, 0x00, 0x01, 0x00, 0x01, 0, 0, 0, 60, 0x00, 0x04]);
+        message.extend_from_slice(&[142, 250, 180, 14]);
+        message.extend_from_slice(&[0xC0, 0x2D, 0x00, 0x1C, 0x00, 0x01, 0, 0, 0, 60, 0x00, 0x10]);
+        message.extend_from_slice(&[0x2a, 0x00, 0x14, 0x50, 0x40, 0x02, 0x08, 0x0a]);
+        message.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0x20, 0x0e]);
+        message
+    }
+
+    #[test]
+    fn test_parse_response_follows_cnames() {
+        assert_eq!(
+            parse_response(&youtube_response()),
+            Some(vec![
+                (
+                    IpAddr::V4(Ipv4Addr::new(142, 250, 180, 14)),
+                    "www.youtube.com".to_string()
+                ),
+                (
+                    "2a00:1450:4002:80a::200e".parse().unwrap(),
+                    "www.youtube.com".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_response_mdns_announcement() {
+        // mDNS announcements have no questions, and the cache-flush bit set in the class
+        let mut message = vec![0, 0, 0x84, 0x00, 0, 0, 0, 1, 0, 0, 0, 0];
+        message.extend_from_slice(b"\x0bMacBook-Pro\x05local\x00");
+        message.extend_from_slice(&[0x00, 0x01, 0x80, 0x01, 0, 0, 0, 120, 0x00, 0x04]);
+        message.extend_from_slice(&[192, 168, 1, 10]);
+        assert_eq!(
+            parse_response(&message),
+            Some(vec![(
+                IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+                "macbook-pro.local".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_parse_response_rejects_queries_and_malformed_messages() {
+        let mut query = youtube_response();
+        query[2] = 0x01;
+        assert_eq!(parse_response(&query), None);
+
+        let response = youtube_response();
+        assert_eq!(parse_response(&response[..response.len() - 1]), None);
+        assert_eq!(parse_response(&response[..5]), None);
+
+        // compression pointer to itself
+        let mut looping = vec![0, 0, 0x81, 0x80, 0, 1, 0, 1, 0, 0, 0, 0];
+        looping.extend_from_slice(&[0xC0, 0x0C]);
+        assert_eq!(parse_response(&looping), None);
+    }
+
+    #[test]
+    fn test_dns_answers_over_udp_and_tcp() {
+        let message = youtube_response();
+        let ip = [8, 8, 8, 8];
+        let local_ip = [192, 168, 1, 2];
+
+        let udp = etherparse::PacketBuilder::ipv4(ip, local_ip, 64).udp(53, 40000);
+        let mut packet = Vec::new();
+        udp.write(&mut packet, &message).unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(dns_answers(&headers).len(), 2);
+
+        let tcp = etherparse::PacketBuilder::ipv4(ip, local_ip, 64).tcp(53, 40000, 1, 1024);
+        let mut payload = u16::try_from(message.len()).unwrap().to_be_bytes().to_vec();
+        payload.extend_from_slice(&message);
+        let mut packet = Vec::new();
+        tcp.write(&mut packet, &payload).unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(dns_answers(&headers).len(), 2);
+
+        // not a DNS port
+        let udp = etherparse::PacketBuilder::ipv4(ip, local_ip, 64).udp(443, 40000);
+        let mut packet = Vec::new();
+        udp.write(&mut packet, &message).unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert!(dns_answers(&headers).is_empty());
+    }
+}
@@ -0,0 +1,705 @@
+//! Module to export the analyzed connections to a flow collector,
+//! as `NetFlow` v5, `NetFlow` v9, or IPFIX records sent over UDP.
+//!
+//! Each address:port pair is exported as a unidirectional flow: the packets and bytes not exported yet
+//! are sent when the flow has been active for longer than the active timeout,
+//! when it has been idle for longer than the inactive timeout, or when the capture is over.
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::flow_export_conf::{FlowExportConf, FlowProtocol};
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::translations::translations_6::invalid_address_translation;
+use crate::translations::types::language::Language;
+use crate::utils::types::timestamp::Timestamp;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
+/// Maximum number of records in a `NetFlow` v5 message
+const V5_MAX_RECORDS: usize = 30;
+
+/// Maximum number of records in a `NetFlow` v9 or IPFIX message, to keep it below the usual MTU
+const MAX_RECORDS: usize = 16;
+
+/// Template describing the records of IPv4 flows
+const IPV4_TEMPLATE_ID: u16 = 256;
+
+/// Template describing the records of IPv6 flows
+const IPV6_TEMPLATE_ID: u16 = 257;
+
+/// Exports the connections of a capture to a flow collector
+#[derive(Debug)]
+pub struct FlowExporter {
+    socket: UdpSocket,
+    collector: SocketAddr,
+    protocol: FlowProtocol,
+    /// Active timeout, in microseconds
+    active_timeout: i64,
+    /// Inactive timeout, in microseconds
+    inactive_timeout: i64,
+    /// Export state of each flow seen so far
+    flows: HashMap<AddressPortPair, FlowState>,
+    /// Flows with packets not exported yet
+    pending: HashSet<AddressPortPair>,
+    /// Reference for the uptime of the exporter, needed by `NetFlow`
+    boot: Option<Timestamp>,
+    /// Flows (`NetFlow` v5), messages (`NetFlow` v9), or data records (IPFIX) exported so far
+    sequence: u32,
+}
+
+/// Amount of traffic of a flow already exported
+#[derive(Debug, Default)]
+struct FlowState {
+    exported_packets: u128,
+    exported_bytes: u128,
+    /// Timestamp of the first packet not exported yet
+    record_start: Option<Timestamp>,
+}
+
+/// Traffic of a flow exchanged in a time interval
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FlowRecord {
+    source: IpAddr,
+    destination: IpAddr,
+    source_port: u16,
+    destination_port: u16,
+    protocol: u8,
+    tcp_flags: u8,
+    packets: u64,
+    bytes: u64,
+    start: Timestamp,
+    end: Timestamp,
+}
+
+impl FlowRecord {
+    fn is_ipv6(&self) -> bool {
+        self.source.is_ipv6()
+    }
+}
+
+/// Reasons why the flow records can't be exported
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FlowExportError {
+    /// The address of the collector isn't a valid IP address
+    InvalidAddress(String),
+    /// The socket to send the records couldn't be opened
+    Socket(String),
+}
+
+impl FlowExportError {
+    pub fn get_message(&self, language: Language) -> String {
+        match self {
+            FlowExportError::InvalidAddress(address) => {
+                format!("{}: {address}", invalid_address_translation(language))
+            }
+            FlowExportError::Socket(error) => error.clone(),
+        }
+    }
+}
+
+impl FlowExporter {
+    pub fn new(conf: &FlowExportConf) -> Result<Self, FlowExportError> {
+        let collector = conf
+            .collector()
+            .ok_or_else(|| FlowExportError::InvalidAddress(conf.address.clone()))?;
+        let local_address = if collector.is_ipv4() {
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0)
+        } else {
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0)
+        };
+        let socket =
+            UdpSocket::bind(local_address).map_err(|e| FlowExportError::Socket(e.to_string()))?;
+        Ok(Self {
+            socket,
+            collector,
+            protocol: conf.protocol,
+            active_timeout: secs_to_usecs(conf.active_timeout),
+            inactive_timeout: secs_to_usecs(conf.inactive_timeout),
+            flows: HashMap::new(),
+            pending: HashSet::new(),
+            boot: None,
+            sequence: 0,
+        })
+    }
+
+    pub fn collector(&self) -> SocketAddr {
+        self.collector
+    }
+
+    /// Exports the flows whose timeouts expired
+    ///
+    /// `changed` are the pairs that exchanged packets since the previous update
+    pub fn update<'a>(
+        &mut self,
+        map: &HashMap<AddressPortPair, InfoAddressPortPair>,
+        changed: impl Iterator<Item = &'a AddressPortPair>,
+        now: Timestamp,
+    ) {
+        let records = self.expired_records(map, changed, now, false);
+        self.send(&records, now);
+    }
+
+    /// Exports all the flows with packets not exported yet (e.g., when the capture is over)
+    pub fn flush(&mut self, map: &HashMap<AddressPortPair, InfoAddressPortPair>, now: Timestamp) {
+        let records = self.expired_records(map, std::iter::empty(), now, true);
+        self.send(&records, now);
+    }
+
+    fn expired_records<'a>(
+        &mut self,
+        map: &HashMap<AddressPortPair, InfoAddressPortPair>,
+        changed: impl Iterator<Item = &'a AddressPortPair>,
+        now: Timestamp,
+        flush: bool,
+    ) -> Vec<FlowRecord> {
+        for key in changed {
+            // flows not carried over IP (i.e., ARP) can't be exported
+            if key.protocol.ip_number(key.source.is_ipv6()).is_none() {
+                continue;
+            }
+            let Some(info) = map.get(key) else {
+                continue;
+            };
+            let state = self.flows.entry(*key).or_default();
+            if state.exported_packets >= info.transmitted_packets {
+                continue;
+            }
+            if state.record_start.is_none() {
+                // the first packet of a new flow is known, otherwise the latest one is the best guess
+                state.record_start = Some(if state.exported_packets == 0 {
+                    info.initial_timestamp
+                } else {
+                    info.final_timestamp
+                });
+            }
+            self.pending.insert(*key);
+        }
+        if self.boot.is_none() {
+            self.boot = self.flows.values().filter_map(|s| s.record_start).min();
+        }
+
+        let now_usecs = usecs(now);
+        let (active_timeout, inactive_timeout) = (self.active_timeout, self.inactive_timeout);
+        let mut records = Vec::new();
+        self.pending.retain(|key| {
+            let (Some(info), Some(state)) = (map.get(key), self.flows.get_mut(key)) else {
+                return false;
+            };
+            let start = state.record_start.unwrap_or(info.initial_timestamp);
+            let is_expired = now_usecs - usecs(start) >= active_timeout
+                || now_usecs - usecs(info.final_timestamp) >= inactive_timeout;
+            if !flush && !is_expired {
+                return true;
+            }
+            records.push(FlowRecord {
+                source: key.source,
+                destination: key.dest,
+                source_port: key.sport.unwrap_or_default(),
+                destination_port: key.dport.unwrap_or_default(),
+                protocol: key
+                    .protocol
+                    .ip_number(key.source.is_ipv6())
+                    .unwrap_or_default(),
+                tcp_flags: info.tcp_flags.bits(),
+                packets: saturate(info.transmitted_packets - state.exported_packets),
+                bytes: saturate(info.transmitted_bytes - state.exported_bytes),
+                start,
+                end: info.final_timestamp,
+            });
+            state.exported_packets = info.transmitted_packets;
+            state.exported_bytes = info.transmitted_bytes;
+            state.record_start = None;
+            false
+        });
+        records.sort_by_key(|r| (r.start, r.end));
+        records
+    }
+
+    fn send(&mut self, records: &[FlowRecord], now: Timestamp) {
+        for message in self.messages(records, now) {
+            // collectors can be temporarily unreachable: records are sent on a best-effort basis
+            let _ = self.socket.send_to(&message, self.collector);
+        }
+    }
+
+    fn messages(&mut self, records: &[FlowRecord], now: Timestamp) -> Vec<Vec<u8>> {
+        match self.protocol {
+            FlowProtocol::NetflowV5 => self.netflow_v5_messages(records, now),
+            FlowProtocol::NetflowV9 | FlowProtocol::Ipfix => records
+                .chunks(MAX_RECORDS)
+                .map(|chunk| self.template_based_message(chunk, now))
+                .collect(),
+        }
+    }
+
+    fn netflow_v5_messages(&mut self, records: &[FlowRecord], now: Timestamp) -> Vec<Vec<u8>> {
+        // NetFlow v5 only supports IPv4
+        let records: Vec<&FlowRecord> = records.iter().filter(|r| !r.is_ipv6()).collect();
+        let mut messages = Vec::new();
+        for chunk in records.chunks(V5_MAX_RECORDS) {
+            let mut buf = Vec::with_capacity(24 + 48 * chunk.len());
+            put_u16(&mut buf, 5);
+            put_u16(&mut buf, to_u16(chunk.len()));
+            put_u32(&mut buf, self.uptime(now));
+            put_u32(&mut buf, unix_secs(now));
+            put_u32(&mut buf, unix_nsecs(now));
+            put_u32(&mut buf, self.sequence);
+            // engine type and ID, sampling interval
+            buf.extend_from_slice(&[0; 4]);
+            for record in chunk {
+                put_ip(&mut buf, record.source);
+                put_ip(&mut buf, record.destination);
+                // next hop, input and output interfaces
+                buf.extend_from_slice(&[0; 8]);
+                put_u32(&mut buf, u32::try_from(record.packets).unwrap_or(u32::MAX));
+                put_u32(&mut buf, u32::try_from(record.bytes).unwrap_or(u32::MAX));
+                put_u32(&mut buf, self.uptime(record.start));
+                put_u32(&mut buf, self.uptime(record.end));
+                put_u16(&mut buf, record.source_port);
+                put_u16(&mut buf, record.destination_port);
+                buf.extend_from_slice(&[0, record.tcp_flags, record.protocol, 0]);
+                // AS numbers, masks, and padding
+                buf.extend_from_slice(&[0; 8]);
+            }
+            self.sequence = self.sequence.wrapping_add(u32::from(to_u16(chunk.len())));
+            messages.push(buf);
+        }
+        messages
+    }
+
+    /// Builds a `NetFlow` v9 or IPFIX message, including the templates of the records it carries
+    fn template_based_message(&mut self, records: &[FlowRecord], now: Timestamp) -> Vec<u8> {
+        let is_ipfix = self.protocol == FlowProtocol::Ipfix;
+        let families: Vec<bool> = [false, true]
+            .into_iter()
+            .filter(|is_ipv6| records.iter().any(|r| r.is_ipv6() == *is_ipv6))
+            .collect();
+
+        let mut buf = Vec::new();
+        if is_ipfix {
+            put_u16(&mut buf, 10);
+            // length, set below
+            put_u16(&mut buf, 0);
+            put_u32(&mut buf, unix_secs(now));
+            put_u32(&mut buf, self.sequence);
+        } else {
+            put_u16(&mut buf, 9);
+            put_u16(&mut buf, to_u16(families.len() + records.len()));
+            put_u32(&mut buf, self.uptime(now));
+            put_u32(&mut buf, unix_secs(now));
+            put_u32(&mut buf, self.sequence);
+        }
+        // source ID / observation domain ID
+        put_u32(&mut buf, 0);
+
+        let set_start = buf.len();
+        put_u16(&mut buf, if is_ipfix { 2 } else { 0 });
+        put_u16(&mut buf, 0);
+        for &is_ipv6 in &families {
+            let fields = self.template_fields(is_ipv6);
+            put_u16(&mut buf, template_id(is_ipv6));
+            put_u16(&mut buf, to_u16(fields.len()));
+            for (field_type, length) in fields {
+                put_u16(&mut buf, field_type);
+                put_u16(&mut buf, length);
+            }
+        }
+        set_length(&mut buf, set_start);
+
+        for &is_ipv6 in &families {
+            let set_start = buf.len();
+            put_u16(&mut buf, template_id(is_ipv6));
+            put_u16(&mut buf, 0);
+            for record in records.iter().filter(|r| r.is_ipv6() == is_ipv6) {
+                self.put_record(&mut buf, record);
+            }
+            while (buf.len() - set_start) % 4 != 0 {
+                buf.push(0);
+            }
+            set_length(&mut buf, set_start);
+        }
+
+        if is_ipfix {
+            let length = to_u16(buf.len()).to_be_bytes();
+            buf[2..4].copy_from_slice(&length);
+            self.sequence = self.sequence.wrapping_add(u32::from(to_u16(records.len())));
+        } else {
+            self.sequence = self.sequence.wrapping_add(1);
+        }
+        buf
+    }
+
+    /// Type and length of the fields of the records (`NetFlow` v9 field types match the IPFIX ones)
+    fn template_fields(&self, is_ipv6: bool) -> [(u16, u16); 10] {
+        let (source, destination, address_length) = if is_ipv6 { (27, 28, 16) } else { (8, 12, 4) };
+        // IPFIX uses absolute timestamps, NetFlow v9 uses the uptime of the exporter
+        let (start, end, time_length) = if self.protocol == FlowProtocol::Ipfix {
+            (152, 153, 8)
+        } else {
+            (22, 21, 4)
+        };
+        [
+            (source, address_length),
+            (destination, address_length),
+            // ports, protocol, and TCP flags
+            (7, 2),
+            (11, 2),
+            (4, 1),
+            (6, 1),
+            // packets and bytes
+            (2, 8),
+            (1, 8),
+            (start, time_length),
+            (end, time_length),
+        ]
+    }
+
+    fn put_record(&self, buf: &mut Vec<u8>, record: &FlowRecord) {
+        put_ip(buf, record.source);
+        put_ip(buf, record.destination);
+        put_u16(buf, record.source_port);
+        put_u16(buf, record.destination_port);
+        buf.extend_from_slice(&[record.protocol, record.tcp_flags]);
+        buf.extend_from_slice(&record.packets.to_be_bytes());
+        buf.extend_from_slice(&record.bytes.to_be_bytes());
+        if self.protocol == FlowProtocol::Ipfix {
+            for timestamp in [record.start, record.end] {
+                let millis = u64::try_from(usecs(timestamp) / 1000).unwrap_or_default();
+                buf.extend_from_slice(&millis.to_be_bytes());
+            }
+        } else {
+            put_u32(buf, self.uptime(record.start));
+            put_u32(buf, self.uptime(record.end));
+        }
+    }
+
+    /// Milliseconds elapsed since the boot of the exporter (wrapping around as `NetFlow` expects)
+    fn uptime(&self, timestamp: Timestamp) -> u32 {
+        let boot = self.boot.map(usecs).unwrap_or_default();
+        let millis = (usecs(timestamp) - boot).max(0) / 1000;
+        u32::try_from(millis % (1 << 32)).unwrap_or_default()
+    }
+}
+
+fn template_id(is_ipv6: bool) -> u16 {
+    if is_ipv6 {
+        IPV6_TEMPLATE_ID
+    } else {
+        IPV4_TEMPLATE_ID
+    }
+}
+
+fn secs_to_usecs(secs: u64) -> i64 {
+    i64::try_from(secs)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(1_000_000)
+}
+
+fn usecs(timestamp: Timestamp) -> i64 {
+    timestamp.to_usecs().unwrap_or_default()
+}
+
+fn unix_secs(timestamp: Timestamp) -> u32 {
+    u32::try_from(timestamp.secs()).unwrap_or_default()
+}
+
+fn unix_nsecs(timestamp: Timestamp) -> u32 {
+    u32::try_from(usecs(timestamp).rem_euclid(1_000_000) * 1000).unwrap_or_default()
+}
+
+fn saturate(value: u128) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+fn put_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_ip(buf: &mut Vec<u8>, ip: IpAddr) {
+    match ip {
+        IpAddr::V4(ip) => buf.extend_from_slice(&ip.octets()),
+        IpAddr::V6(ip) => buf.extend_from_slice(&ip.octets()),
+    }
+}
+
+/// Writes the length of the set (or flowset) starting at the given position
+fn set_length(buf: &mut [u8], set_start: usize) {
+    let length = to_u16(buf.len() - set_start).to_be_bytes();
+    buf[set_start + 2..set_start + 4].copy_from_slice(&length);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use crate::networking::types::tcp_state::TcpFlags;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use std::time::Duration;
+
+    fn key(source: &str, dest: &str, protocol: Protocol) -> AddressPortPair {
+        AddressPortPair::new(
+            source.parse().unwrap(),
+            Some(50000),
+            dest.parse().unwrap(),
+            Some(443),
+            protocol,
+        )
+    }
+
+    fn info(packets: u128, bytes: u128, first_secs: i64, last_secs: i64) -> InfoAddressPortPair {
+        InfoAddressPortPair {
+            traffic_direction: TrafficDirection::Outgoing,
+            transmitted_packets: packets,
+            transmitted_bytes: bytes,
+            initial_timestamp: Timestamp::new(first_secs, 0),
+            final_timestamp: Timestamp::new(last_secs, 0),
+            ..InfoAddressPortPair::default()
+        }
+    }
+
+    /// Returns an exporter with the given protocol, and the socket of its collector
+    fn exporter(protocol: FlowProtocol) -> (FlowExporter, UdpSocket) {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let conf = FlowExportConf {
+            is_active: true,
+            port: collector.local_addr().unwrap().port(),
+            protocol,
+            ..FlowExportConf::default()
+        };
+        (FlowExporter::new(&conf).unwrap(), collector)
+    }
+
+    fn receive(collector: &UdpSocket) -> Vec<u8> {
+        let mut buf = [0; 2048];
+        let len = collector.recv(&mut buf).unwrap();
+        buf[..len].to_vec()
+    }
+
+    fn u16_at(buf: &[u8], i: usize) -> u16 {
+        u16::from_be_bytes([buf[i], buf[i + 1]])
+    }
+
+    fn u32_at(buf: &[u8], i: usize) -> u32 {
+        u32::from_be_bytes(buf[i..i + 4].try_into().unwrap())
+    }
+
+    fn u64_at(buf: &[u8], i: usize) -> u64 {
+        u64::from_be_bytes(buf[i..i + 8].try_into().unwrap())
+    }
+
+    /// Map with a TCP flow over IPv4 and a UDP flow over IPv6
+    fn map() -> HashMap<AddressPortPair, InfoAddressPortPair> {
+        let mut tcp = info(5, 500, 1_700_000_000, 1_700_000_002);
+        tcp.tcp_flags = TcpFlags {
+            syn: 1,
+            ack: 4,
+            ..TcpFlags::default()
+        };
+        HashMap::from([
+            (key("192.168.1.2", "8.8.8.8", Protocol::TCP), tcp),
+            (
+                key("fe80::1", "2001:db8::1", Protocol::UDP),
+                info(2, 300, 1_700_000_001, 1_700_000_001),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_flow_export_timeouts() {
+        let (mut exporter, _collector) = exporter(FlowProtocol::Ipfix);
+        let tcp_key = key("192.168.1.2", "8.8.8.8", Protocol::TCP);
+        let arp_key = key("192.168.1.2", "192.168.1.1", Protocol::ARP);
+        let mut map = HashMap::from([
+            (tcp_key, info(5, 500, 100, 101)),
+            (arp_key, info(1, 42, 100, 100)),
+        ]);
+
+        // nothing expired yet
+        let records = exporter.expired_records(
+            &map,
+            [tcp_key, arp_key].iter(),
+            Timestamp::new(105, 0),
+            false,
+        );
+        assert!(records.is_empty());
+
+        // inactive timeout
+        let records =
+            exporter.expired_records(&map, std::iter::empty(), Timestamp::new(116, 0), false);
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            (records[0].packets, records[0].bytes, records[0].protocol),
+            (5, 500, 6)
+        );
+        assert_eq!(
+            (records[0].start, records[0].end),
+            (Timestamp::new(100, 0), Timestamp::new(101, 0))
+        );
+        assert!(
+            exporter
+                .expired_records(&map, std::iter::empty(), Timestamp::new(200, 0), false)
+                .is_empty()
+        );
+
+        // active timeout: only the traffic not exported yet is included
+        for secs in 130..=190 {
+            map.insert(
+                tcp_key,
+                info(5 + u128::try_from(secs - 129).unwrap(), 1000, 100, secs),
+            );
+            let records =
+                exporter.expired_records(&map, [tcp_key].iter(), Timestamp::new(secs, 0), false);
+            assert_eq!(records.is_empty(), secs < 190);
+        }
+        let records =
+            exporter.expired_records(&map, std::iter::empty(), Timestamp::new(190, 0), false);
+        assert!(records.is_empty());
+
+        // flush at the end of the capture
+        map.insert(tcp_key, info(70, 1200, 100, 191));
+        exporter.expired_records(&map, [tcp_key].iter(), Timestamp::new(191, 0), false);
+        let records =
+            exporter.expired_records(&map, std::iter::empty(), Timestamp::new(191, 0), true);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].packets, records[0].bytes), (4, 200));
+        assert_eq!(
+            (records[0].start, records[0].end),
+            (Timestamp::new(191, 0), Timestamp::new(191, 0))
+        );
+    }
+
+    #[test]
+    fn test_flow_export_netflow_v5() {
+        let (mut exporter, collector) = exporter(FlowProtocol::NetflowV5);
+        let map = map();
+        exporter.update(&map, map.keys(), Timestamp::new(1_700_000_020, 250));
+
+        let buf = receive(&collector);
+        // IPv6 flows aren't supported by NetFlow v5
+        assert_eq!(buf.len(), 24 + 48);
+        assert_eq!(u16_at(&buf, 0), 5);
+        assert_eq!(u16_at(&buf, 2), 1);
+        // the uptime refers to the first flow
+        assert_eq!(u32_at(&buf, 4), 20_000);
+        assert_eq!(u32_at(&buf, 8), 1_700_000_020);
+        assert_eq!(u32_at(&buf, 12), 250_000);
+        assert_eq!(u32_at(&buf, 16), 0);
+
+        let record = &buf[24..];
+        assert_eq!(&record[0..4], &[192, 168, 1, 2]);
+        assert_eq!(&record[4..8], &[8, 8, 8, 8]);
+        assert_eq!(u32_at(record, 16), 5);
+        assert_eq!(u32_at(record, 20), 500);
+        assert_eq!(u32_at(record, 24), 0);
+        assert_eq!(u32_at(record, 28), 2000);
+        assert_eq!(u16_at(record, 32), 50000);
+        assert_eq!(u16_at(record, 34), 443);
+        // TCP flags and protocol
+        assert_eq!(&record[36..39], &[0, 0x12, 6]);
+
+        // the sequence counts the exported flows
+        let mut map = map;
+        for info in map.values_mut() {
+            info.transmitted_packets += 1;
+            info.final_timestamp = Timestamp::new(1_700_000_011, 0);
+        }
+        exporter.update(&map, map.keys(), Timestamp::new(1_700_000_040, 0));
+        let buf = receive(&collector);
+        assert_eq!(u32_at(&buf, 16), 1);
+    }
+
+    #[test]
+    fn test_flow_export_netflow_v9() {
+        let (mut exporter, collector) = exporter(FlowProtocol::NetflowV9);
+        let map = map();
+        exporter.update(&map, map.keys(), Timestamp::new(1_700_000_020, 0));
+
+        let buf = receive(&collector);
+        assert_eq!(u16_at(&buf, 0), 9);
+        // two templates and two records
+        assert_eq!(u16_at(&buf, 2), 4);
+        assert_eq!(u32_at(&buf, 4), 20_000);
+        assert_eq!(u32_at(&buf, 8), 1_700_000_020);
+        assert_eq!(u32_at(&buf, 12), 0);
+
+        // template flowset
+        let templates_len = usize::from(u16_at(&buf, 22));
+        assert_eq!(u16_at(&buf, 20), 0);
+        assert_eq!(templates_len, 4 + 2 * (4 + 10 * 4));
+        assert_eq!((u16_at(&buf, 24), u16_at(&buf, 26)), (IPV4_TEMPLATE_ID, 10));
+        assert_eq!((u16_at(&buf, 28), u16_at(&buf, 30)), (8, 4));
+        assert_eq!((u16_at(&buf, 68), u16_at(&buf, 70)), (IPV6_TEMPLATE_ID, 10));
+        assert_eq!((u16_at(&buf, 72), u16_at(&buf, 74)), (27, 16));
+
+        // IPv4 data flowset, padded
+        let v4 = 20 + templates_len;
+        assert_eq!(
+            (u16_at(&buf, v4), u16_at(&buf, v4 + 2)),
+            (IPV4_TEMPLATE_ID, 44)
+        );
+        assert_eq!(&buf[v4 + 4..v4 + 8], &[192, 168, 1, 2]);
+        assert_eq!((u16_at(&buf, v4 + 12), u16_at(&buf, v4 + 14)), (50000, 443));
+        assert_eq!(&buf[v4 + 16..v4 + 18], &[6, 0x12]);
+        assert_eq!(u64_at(&buf, v4 + 18), 5);
+        assert_eq!(u64_at(&buf, v4 + 26), 500);
+        assert_eq!((u32_at(&buf, v4 + 34), u32_at(&buf, v4 + 38)), (0, 2000));
+
+        // IPv6 data flowset
+        let v6 = v4 + 44;
+        assert_eq!(
+            (u16_at(&buf, v6), u16_at(&buf, v6 + 2)),
+            (IPV6_TEMPLATE_ID, 68)
+        );
+        assert_eq!(buf[v6 + 40], 17);
+        assert_eq!(u64_at(&buf, v6 + 50), 300);
+        assert_eq!(buf.len(), v6 + 68);
+    }
+
+    #[test]
+    fn test_flow_export_ipfix() {
+        let (mut exporter, collector) = exporter(FlowProtocol::Ipfix);
+        let mut map = map();
+        exporter.update(&map, map.keys(), Timestamp::new(1_700_000_020, 0));
+
+        let buf = receive(&collector);
+        assert_eq!(u16_at(&buf, 0), 10);
+        assert_eq!(usize::from(u16_at(&buf, 2)), buf.len());
+        assert_eq!(u32_at(&buf, 4), 1_700_000_020);
+        assert_eq!(u32_at(&buf, 8), 0);
+
+        // template set
+        assert_eq!(u16_at(&buf, 16), 2);
+        let templates_len = usize::from(u16_at(&buf, 18));
+        assert_eq!((u16_at(&buf, 20), u16_at(&buf, 22)), (IPV4_TEMPLATE_ID, 10));
+        assert_eq!((u16_at(&buf, 56), u16_at(&buf, 58)), (152, 8));
+        assert_eq!((u16_at(&buf, 60), u16_at(&buf, 62)), (153, 8));
+
+        // IPv4 data set, with absolute timestamps
+        let v4 = 16 + templates_len;
+        assert_eq!(
+            (u16_at(&buf, v4), u16_at(&buf, v4 + 2)),
+            (IPV4_TEMPLATE_ID, 52)
+        );
+        assert_eq!(u64_at(&buf, v4 + 34), 1_700_000_000_000);
+        assert_eq!(u64_at(&buf, v4 + 42), 1_700_000_002_000);
+
+        // only IPv6 records: the sequence counts the data records
+        let v6_key = key("fe80::1", "2001:db8::1", Protocol::UDP);
+        map.insert(v6_key, info(3, 400, 1_700_000_001, 1_700_000_011));
+        exporter.update(&map, [v6_key].iter(), Timestamp::new(1_700_000_040, 0));
+        let buf = receive(&collector);
+        assert_eq!(u32_at(&buf, 8), 2);
+        assert_eq!((u16_at(&buf, 16), u16_at(&buf, 18)), (2, 4 + 4 + 10 * 4));
+        assert_eq!(u16_at(&buf, 20), IPV6_TEMPLATE_ID);
+    }
+}
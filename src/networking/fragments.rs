@@ -0,0 +1,263 @@
+//! Module to attribute IP fragments to the connection of the datagram they belong to.
+//!
+//! Only the first fragment of a datagram carries the transport header:
+//! its ports are remembered, keyed by the IP identification, so that the following fragments
+//! can be accounted to the same address:port pair.
+//! Fragments arriving before the first one of their datagram are accounted without ports.
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::utils::types::bounded_map::BoundedMap;
+use etherparse::{IpNumber, LaxPacketHeaders, LaxPayloadSlice, NetHeaders};
+
+/// Maximum number of tracked datagrams (the oldest ones are forgotten to make room for new ones)
+const MAX_DATAGRAMS: usize = 16_384;
+/// Datagrams idle for longer than this are forgotten
+const REASSEMBLY_TIMEOUT_USECS: i64 = 30_000_000;
+
+/// Relevant fields of an IPv4 or IPv6 fragment
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fragment {
+    identification: u32,
+    /// Source and destination ports, only known for the first fragment of TCP, UDP, and SCTP datagrams
+    ports: Option<(u16, u16)>,
+}
+
+impl Fragment {
+    /// Returns the fragment carried by a packet (if any)
+    pub fn from_headers(headers: &LaxPacketHeaders) -> Option<Self> {
+        let LaxPayloadSlice::Ip(ip_payload) = &headers.payload else {
+            return None;
+        };
+        let (identification, offset) = match &headers.net {
+            Some(NetHeaders::Ipv4(ipv4, _)) if ipv4.is_fragmenting_payload() => {
+                (u32::from(ipv4.identification), ipv4.fragment_offset.value())
+            }
+            Some(NetHeaders::Ipv6(_, exts)) => {
+                let fragment = exts
+                    .fragment
+                    .as_ref()
+                    .filter(|f| f.is_fragmenting_payload())?;
+                (fragment.identification, fragment.fragment_offset.value())
+            }
+            _ => return None,
+        };
+        let has_ports =
+            [IpNumber::TCP, IpNumber::UDP, IpNumber::SCTP].contains(&ip_payload.ip_number);
+        let ports = match ip_payload.payload {
+            [s1, s2, d1, d2, ..] if offset == 0 && has_ports => Some((
+                u16::from_be_bytes([*s1, *s2]),
+                u16::from_be_bytes([*d1, *d2]),
+            )),
+            _ => None,
+        };
+        Some(Self {
+            identification,
+            ports,
+        })
+    }
+}
+
+/// Identifies the datagram a fragment belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct DatagramId {
+    /// Address:port pair of the fragment, without ports
+    key: AddressPortPair,
+    identification: u32,
+}
+
+/// Keeps track of the fragmented datagrams to account all their fragments to the right connection
+pub struct FragmentTracker {
+    /// Address:port pair of each datagram
+    datagrams: BoundedMap<DatagramId, AddressPortPair>,
+}
+
+impl Default for FragmentTracker {
+    fn default() -> Self {
+        Self {
+            datagrams: BoundedMap::new(MAX_DATAGRAMS, REASSEMBLY_TIMEOUT_USECS),
+        }
+    }
+}
+
+impl FragmentTracker {
+    /// Returns the address:port pair a fragment has to be accounted to
+    ///
+    /// `key` is the address:port pair of the fragment as analyzed from its headers (i.e., without ports)
+    pub fn attribute(
+        &mut self,
+        key: AddressPortPair,
+        fragment: &Fragment,
+        timestamp_usecs: i64,
+    ) -> AddressPortPair {
+        let id = DatagramId {
+            key,
+            identification: fragment.identification,
+        };
+        if let Some((sport, dport)) = fragment.ports {
+            let key = AddressPortPair {
+                sport: Some(sport),
+                dport: Some(dport),
+                ..key
+            };
+            self.datagrams.insert(id, key, timestamp_usecs);
+            return key;
+        }
+        self.datagrams
+            .get_mut(&id, timestamp_usecs)
+            .map_or(key, |datagram_key| *datagram_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use etherparse::{IpFragOffset, Ipv4Header, Ipv6FragmentHeader, Ipv6Header, PacketBuilder};
+    use std::net::IpAddr;
+
+    /// Returns the fragments of an IPv4 UDP datagram carrying 32 bytes of payload
+    fn ipv4_fragments(identification: u16) -> (Vec<u8>, Vec<u8>) {
+        let mut datagram = Vec::new();
+        PacketBuilder::ipv4([10, 1, 1, 1], [10, 2, 2, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut datagram, &[7; 32])
+            .unwrap();
+        let (ipv4, udp_datagram) = Ipv4Header::from_slice(&datagram).unwrap();
+        let (first_part, second_part) = udp_datagram.split_at(24);
+
+        let fragment = |offset: u16, more_fragments: bool, payload: &[u8]| {
+            let mut header = ipv4.clone();
+            header.identification = identification;
+            header.more_fragments = more_fragments;
+            header.fragment_offset = IpFragOffset::try_new(offset).unwrap();
+            header.set_payload_len(payload.len()).unwrap();
+            header.header_checksum = header.calc_header_checksum();
+            let mut packet = header.to_bytes().to_vec();
+            packet.extend_from_slice(payload);
+            packet
+        };
+        (
+            fragment(0, true, first_part),
+            fragment(3, false, second_part),
+        )
+    }
+
+    fn portless_key(headers: &LaxPacketHeaders) -> AddressPortPair {
+        let Some(NetHeaders::Ipv4(ipv4, _)) = &headers.net else {
+            panic!("fragments should be IPv4");
+        };
+        AddressPortPair::new(
+            IpAddr::from(ipv4.source),
+            None,
+            IpAddr::from(ipv4.destination),
+            None,
+            Protocol::UDP,
+        )
+    }
+
+    #[test]
+    fn test_fragment_from_headers() {
+        let (first, second) = ipv4_fragments(1234);
+
+        let headers = LaxPacketHeaders::from_ip(&first).unwrap();
+        assert!(headers.transport.is_none());
+        assert_eq!(
+            Fragment::from_headers(&headers),
+            Some(Fragment {
+                identification: 1234,
+                ports: Some((5000, 6000))
+            })
+        );
+
+        let headers = LaxPacketHeaders::from_ip(&second).unwrap();
+        assert_eq!(
+            Fragment::from_headers(&headers),
+            Some(Fragment {
+                identification: 1234,
+                ports: None
+            })
+        );
+
+        // IPv6 fragments carry the identification in an extension header
+        let ipv6 = Ipv6Header {
+            payload_length: 24,
+            next_header: IpNumber::IPV6_FRAGMENTATION_HEADER,
+            hop_limit: 64,
+            source: [1; 16],
+            destination: [2; 16],
+            ..Default::default()
+        };
+        let fragment_header = Ipv6FragmentHeader::new(
+            IpNumber::UDP,
+            IpFragOffset::try_new(100).unwrap(),
+            false,
+            98_765,
+        );
+        let mut packet = ipv6.to_bytes().to_vec();
+        packet.extend_from_slice(&fragment_header.to_bytes());
+        packet.extend_from_slice(&[7; 16]);
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(
+            Fragment::from_headers(&headers),
+            Some(Fragment {
+                identification: 98_765,
+                ports: None
+            })
+        );
+
+        // not a fragment
+        let mut packet = Vec::new();
+        PacketBuilder::ipv4([10, 1, 1, 1], [10, 2, 2, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[7; 32])
+            .unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(Fragment::from_headers(&headers), None);
+    }
+
+    #[test]
+    fn test_attribute_fragments() {
+        let mut tracker = FragmentTracker::default();
+        let (first, second) = ipv4_fragments(1);
+
+        let headers = LaxPacketHeaders::from_ip(&first).unwrap();
+        let key = portless_key(&headers);
+        let fragment = Fragment::from_headers(&headers).unwrap();
+        let full_key = tracker.attribute(key, &fragment, 1_000);
+        assert_eq!((full_key.sport, full_key.dport), (Some(5000), Some(6000)));
+
+        let headers = LaxPacketHeaders::from_ip(&second).unwrap();
+        let fragment = Fragment::from_headers(&headers).unwrap();
+        assert_eq!(tracker.attribute(key, &fragment, 2_000), full_key);
+
+        // fragments of an unknown datagram are left without ports
+        let (_, second) = ipv4_fragments(2);
+        let headers = LaxPacketHeaders::from_ip(&second).unwrap();
+        let fragment = Fragment::from_headers(&headers).unwrap();
+        assert_eq!(tracker.attribute(key, &fragment, 3_000), key);
+    }
+
+    #[test]
+    fn test_fragment_flood_is_bounded() {
+        let mut tracker = FragmentTracker::default();
+        let (first, _) = ipv4_fragments(1);
+        let key = portless_key(&LaxPacketHeaders::from_ip(&first).unwrap());
+        let first_fragment = |identification| Fragment {
+            identification,
+            ports: Some((5000, 6000)),
+        };
+        let next_fragment = |identification| Fragment {
+            identification,
+            ports: None,
+        };
+
+        // first fragments never followed by the rest of their datagram
+        let max = u32::try_from(MAX_DATAGRAMS).unwrap();
+        for identification in 0..2 * max {
+            tracker.attribute(key, &first_fragment(identification), 0);
+        }
+        assert_eq!(tracker.attribute(key, &next_fragment(max - 1), 0), key);
+        let full_key = tracker.attribute(key, &next_fragment(2 * max - 1), 0);
+        assert_eq!((full_key.sport, full_key.dport), (Some(5000), Some(6000)));
+    }
+}
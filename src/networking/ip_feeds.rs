@@ -0,0 +1,198 @@
+//! Module to load the named IP lists, either from local files or from HTTP(S) URLs.
+//!
+//! Lists served over HTTP are fetched again periodically; their `ETag` is used to avoid
+//! downloading unchanged content, and the last good copy is kept if a fetch fails.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+
+use crate::SNIFFNET_LOWERCASE;
+use crate::location;
+use crate::networking::types::ip_blacklist::{IpBlacklist, IpListConf};
+use crate::utils::error_logger::{ErrorLogger, Location};
+use crate::utils::formatted_strings::APP_VERSION;
+
+/// Maximum time to wait for a list to be downloaded
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Client used to fetch the IP lists; it's shared to reuse the underlying connection pool
+static FEEDS_CLIENT: LazyLock<Option<reqwest::Client>> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .user_agent(format!("{SNIFFNET_LOWERCASE}-{APP_VERSION}"))
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .log_err(location!())
+        .ok()
+});
+
+/// Outcome of loading an IP list
+#[derive(Clone, Debug)]
+pub enum ListUpdate {
+    /// New content of the list
+    Loaded(IpBlacklist),
+    /// The list didn't change or couldn't be fetched: the previous copy should be kept
+    Unchanged,
+}
+
+/// Loads the given list; `etag` is the `ETag` of the copy currently available, if any
+pub async fn load_ip_list(conf: IpListConf, etag: Option<String>) -> ListUpdate {
+    if conf.is_url() {
+        return fetch_ip_list(&conf, etag).await;
+    }
+    let list = match tokio::fs::read_to_string(&conf.path).await {
+        Ok(content) => IpBlacklist::parse(&content, conf.format, conf.csv_column),
+        Err(_) => IpBlacklist::default(),
+    };
+    ListUpdate::Loaded(list)
+}
+
+async fn fetch_ip_list(conf: &IpListConf, etag: Option<String>) -> ListUpdate {
+    let Some(client) = FEEDS_CLIENT.as_ref() else {
+        return ListUpdate::Unchanged;
+    };
+    let mut request = client.get(&conf.path);
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    // feeds can be temporarily unreachable: failures aren't logged as errors
+    let Ok(response) = request.send().await else {
+        return ListUpdate::Unchanged;
+    };
+    if response.status() == StatusCode::NOT_MODIFIED || !response.status().is_success() {
+        return ListUpdate::Unchanged;
+    }
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let Ok(content) = response.text().await else {
+        return ListUpdate::Unchanged;
+    };
+
+    let list = IpBlacklist::parse(&content, conf.format, conf.csv_column);
+    if list.is_invalid() {
+        // keep the last good copy
+        return ListUpdate::Unchanged;
+    }
+    ListUpdate::Loaded(list.with_etag(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::types::ip_blacklist::FeedFormat;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{IpAddr, Ipv4Addr, TcpListener};
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves the given responses (status, optional `ETag`, body) to consecutive requests,
+    /// returning the URL of the server and a channel receiving the `If-None-Match` headers sent
+    fn serve(
+        responses: Vec<(u16, Option<&'static str>, &'static str)>,
+    ) -> (String, mpsc::Receiver<Option<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.txt", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, etag, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut if_none_match = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("if-none-match")
+                    {
+                        if_none_match = Some(value.trim().to_string());
+                    }
+                }
+                tx.send(if_none_match).unwrap();
+                let etag_header = etag.map(|e| format!("ETag: {e}\r\n")).unwrap_or_default();
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\n{etag_header}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ip_list_with_etag_and_fallback() {
+        let (url, requests) = serve(vec![
+            (
+                200,
+                Some("\"v1\""),
+                "; Spamhaus DROP\n1.10.16.0/20 ; SBL256894\n8.8.8.8\n",
+            ),
+            (304, None, ""),
+            (500, None, "oops"),
+            (200, Some("\"v2\""), "<html>not a list</html>"),
+            (200, None, "9.9.9.9\n"),
+        ]);
+        let conf = IpListConf::new(url);
+        assert!(conf.is_url());
+
+        // first fetch
+        let ListUpdate::Loaded(list) = load_ip_list(conf.clone(), None).await else {
+            panic!("the list should be loaded");
+        };
+        assert_eq!(requests.recv().unwrap(), None);
+        assert_eq!(list.etag(), Some(&"\"v1\"".to_string()));
+        assert!(list.contains(&IpAddr::V4(Ipv4Addr::new(1, 10, 20, 1))));
+        assert!(list.contains(&IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8))));
+
+        // not modified
+        let update = load_ip_list(conf.clone(), list.etag().cloned()).await;
+        assert!(matches!(update, ListUpdate::Unchanged));
+        assert_eq!(requests.recv().unwrap(), Some("\"v1\"".to_string()));
+
+        // server error and invalid content: the last good copy is kept
+        let update = load_ip_list(conf.clone(), list.etag().cloned()).await;
+        assert!(matches!(update, ListUpdate::Unchanged));
+        let update = load_ip_list(conf.clone(), list.etag().cloned()).await;
+        assert!(matches!(update, ListUpdate::Unchanged));
+
+        // new content
+        let ListUpdate::Loaded(list) = load_ip_list(conf, list.etag().cloned()).await else {
+            panic!("the list should be loaded");
+        };
+        assert_eq!(list.etag(), None);
+        assert!(list.contains(&IpAddr::V4(Ipv4Addr::new(9, 9, 9, 9))));
+        assert!(!list.contains(&IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8))));
+    }
+
+    #[tokio::test]
+    async fn test_load_ip_list_from_file_with_format() {
+        let conf = IpListConf {
+            format: FeedFormat::Csv,
+            csv_column: 2,
+            ..IpListConf::new("resources/test/ip_feed.csv".to_string())
+        };
+        assert!(!conf.is_url());
+        let ListUpdate::Loaded(list) = load_ip_list(conf, None).await else {
+            panic!("local lists are always loaded");
+        };
+        assert_eq!(
+            list.imported_items_info(),
+            Some("(IPs: 2, CIDRs: 1)".to_string())
+        );
+
+        let conf = IpListConf::new("resources/test/not_existing.txt".to_string());
+        let ListUpdate::Loaded(list) = load_ip_list(conf, None).await else {
+            panic!("local lists are always loaded");
+        };
+        assert!(list.is_invalid());
+    }
+}
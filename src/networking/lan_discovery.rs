@@ -0,0 +1,372 @@
+//! Module to learn about the devices of the local network from the messages they exchange:
+//! - ARP packets bind MAC addresses to IPv4 addresses
+//! - DHCP messages carry the hostname of the clients and the addresses assigned to them
+//! - mDNS responses and `NetBIOS` name registrations carry the names announced by the devices
+//!
+//! References:
+//! - <https://www.rfc-editor.org/rfc/rfc2131> (DHCP)
+//! - <https://www.rfc-editor.org/rfc/rfc6762> (mDNS)
+//! - <https://www.rfc-editor.org/rfc/rfc1002> (`NetBIOS` name service)
+
+use crate::networking::dns::dns_answers;
+use crate::networking::manage_packets::mac_from_dec_to_hex;
+use etherparse::{
+    ArpHardwareId, EtherType, LaxPacketHeaders, LaxPayloadSlice, LinkHeader, NetHeaders,
+    TransportHeader,
+};
+use std::net::{IpAddr, Ipv4Addr};
+
+const DHCP_SERVER_PORT: u16 = 67;
+const DHCP_CLIENT_PORT: u16 = 68;
+const MDNS_PORT: u16 = 5353;
+const NETBIOS_NAME_PORT: u16 = 137;
+
+/// Offset of the options in DHCP messages (after the magic cookie)
+const DHCP_OPTIONS_OFFSET: usize = 240;
+const DHCP_MAGIC_COOKIE: [u8; 4] = [0x63, 0x82, 0x53, 0x63];
+const DHCP_OPTION_HOSTNAME: u8 = 12;
+const DHCP_OPTION_MESSAGE_TYPE: u8 = 53;
+const DHCP_ACK: u8 = 5;
+
+const NETBIOS_OPCODE_REGISTRATION: u16 = 5;
+const NETBIOS_OPCODE_REFRESH: [u16; 2] = [8, 9];
+/// Length of an encoded `NetBIOS` name, including its length byte and the terminating zero
+const NETBIOS_NAME_LEN: usize = 34;
+
+/// Information about a device of the local network carried by a packet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceAnnouncement {
+    pub mac_address: String,
+    pub ip_address: Option<IpAddr>,
+    pub hostname: Option<String>,
+}
+
+/// Returns the information about a device of the local network carried by a packet (if any)
+pub fn device_announcement(headers: &LaxPacketHeaders) -> Option<DeviceAnnouncement> {
+    if let Some(NetHeaders::Arp(arp)) = &headers.net {
+        return arp_announcement(arp);
+    }
+
+    let (Some(TransportHeader::Udp(udp)), LaxPayloadSlice::Udp { payload, .. }) =
+        (&headers.transport, &headers.payload)
+    else {
+        return None;
+    };
+    let ports = [udp.source_port, udp.destination_port];
+    if ports.contains(&DHCP_SERVER_PORT) && ports.contains(&DHCP_CLIENT_PORT) {
+        return dhcp_announcement(payload);
+    }
+
+    // mDNS and NetBIOS announcements are sent by the devices themselves
+    let Some(LinkHeader::Ethernet2(ethernet)) = &headers.link else {
+        return None;
+    };
+    let mac_address = mac_from_dec_to_hex(ethernet.source);
+    let source = match &headers.net {
+        Some(NetHeaders::Ipv4(ipv4, _)) => IpAddr::from(ipv4.source),
+        Some(NetHeaders::Ipv6(ipv6, _)) => IpAddr::from(ipv6.source),
+        _ => return None,
+    };
+    if udp.source_port == MDNS_PORT {
+        // only the names of the device sending the response are considered
+        let hostname = dns_answers(headers)
+            .into_iter()
+            .find(|(address, _)| *address == source)
+            .and_then(|(_, name)| name.strip_suffix(".local").map(ToString::to_string))?;
+        return Some(DeviceAnnouncement {
+            mac_address,
+            ip_address: Some(source),
+            hostname: Some(hostname),
+        });
+    }
+    if udp.source_port == NETBIOS_NAME_PORT {
+        let (hostname, ip_address) = netbios_name(payload)?;
+        return Some(DeviceAnnouncement {
+            mac_address,
+            ip_address: Some(IpAddr::V4(ip_address)),
+            hostname: Some(hostname),
+        });
+    }
+    None
+}
+
+fn arp_announcement(arp: &etherparse::ArpPacket) -> Option<DeviceAnnouncement> {
+    if arp.hw_addr_type != ArpHardwareId::ETHERNET || arp.proto_addr_type != EtherType::IPV4 {
+        return None;
+    }
+    let mac_address = <[u8; 6]>::try_from(arp.sender_hw_addr()).ok()?;
+    let ip_address = Ipv4Addr::from(<[u8; 4]>::try_from(arp.sender_protocol_addr()).ok()?);
+    Some(DeviceAnnouncement {
+        mac_address: mac_from_dec_to_hex(mac_address),
+        // ARP probes are sent before an address is assigned
+        ip_address: (!ip_address.is_unspecified()).then_some(IpAddr::V4(ip_address)),
+        hostname: None,
+    })
+}
+
+fn dhcp_announcement(message: &[u8]) -> Option<DeviceAnnouncement> {
+    // hardware type must be Ethernet, with 6 bytes long addresses
+    let [op, 1, 6, ..] = message else {
+        return None;
+    };
+    if message.get(236..DHCP_OPTIONS_OFFSET)? != DHCP_MAGIC_COOKIE {
+        return None;
+    }
+    let mac_address = <[u8; 6]>::try_from(message.get(28..34)?).ok()?;
+
+    let mut hostname = None;
+    let mut message_type = None;
+    let mut options = message.get(DHCP_OPTIONS_OFFSET..)?;
+    while let [code, rest @ ..] = options {
+        match code {
+            // padding
+            0 => options = rest,
+            // end
+            255 => break,
+            _ => {
+                let [len, rest @ ..] = rest else {
+                    break;
+                };
+                let Some(value) = rest.get(..usize::from(*len)) else {
+                    break;
+                };
+                match *code {
+                    DHCP_OPTION_HOSTNAME => {
+                        let name = String::from_utf8_lossy(value);
+                        let name = name.trim_end_matches('\0').trim();
+                        hostname = (!name.is_empty()).then(|| name.to_string());
+                    }
+                    DHCP_OPTION_MESSAGE_TYPE => message_type = value.first().copied(),
+                    _ => {}
+                }
+                options = &rest[value.len()..];
+            }
+        }
+    }
+
+    // the address in use is confirmed by the client (ciaddr) or assigned by the server (yiaddr)
+    let address_offset = match (op, message_type) {
+        (1, _) => 12,
+        (2, Some(DHCP_ACK)) => 16,
+        _ => return None,
+    };
+    let ip_address =
+        Ipv4Addr::from(<[u8; 4]>::try_from(message.get(address_offset..address_offset + 4)?).ok()?);
+
+    Some(DeviceAnnouncement {
+        mac_address: mac_from_dec_to_hex(mac_address),
+        ip_address: (!ip_address.is_unspecified()).then_some(IpAddr::V4(ip_address)),
+        hostname,
+    })
+}
+
+/// Returns the unique name and address registered (or claimed in a positive response)
+/// by a `NetBIOS` name service message
+fn netbios_name(message: &[u8]) -> Option<(String, Ipv4Addr)> {
+    let [_, _, f1, f2, ..] = message else {
+        return None;
+    };
+    let flags = u16::from_be_bytes([*f1, *f2]);
+    let is_response = flags & 0x8000 != 0;
+    let opcode = (flags >> 11) & 0x0f;
+    let rcode = flags & 0x0f;
+    let name_end = 12 + NETBIOS_NAME_LEN;
+    // offset of the data of the resource record with the address
+    let data_offset = if !is_response
+        && (opcode == NETBIOS_OPCODE_REGISTRATION || NETBIOS_OPCODE_REFRESH.contains(&opcode))
+    {
+        // question, followed by an additional record with a compressed name
+        name_end + 4 + 12
+    } else if is_response && opcode == 0 && rcode == 0 {
+        // answer
+        name_end + 10
+    } else {
+        return None;
+    };
+
+    let [0x20, encoded @ .., 0x00] = message.get(12..name_end)? else {
+        return None;
+    };
+    let mut decoded = encoded.chunks_exact(2).map(|pair| {
+        pair[0]
+            .wrapping_sub(b'A')
+            .wrapping_shl(4)
+            .wrapping_add(pair[1].wrapping_sub(b'A'))
+    });
+    let name: Vec<u8> = decoded.by_ref().take(15).collect();
+    let suffix = decoded.next()?;
+    let [nb_flags, _, a, b, c, d, ..] = message.get(data_offset..)? else {
+        return None;
+    };
+    // group names (e.g., workgroups) and names of services other than workstation and server are skipped
+    let is_group = nb_flags & 0x80 != 0;
+    if is_group || ![0x00, 0x20].contains(&suffix) {
+        return None;
+    }
+    let name = String::from_utf8_lossy(&name).trim_end().to_string();
+    (!name.is_empty()).then_some((name, Ipv4Addr::new(*a, *b, *c, *d)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{ArpOperation, ArpPacket, PacketBuilder};
+
+    fn udp_packet(
+        source: [u8; 4],
+        destination: [u8; 4],
+        ports: (u16, u16),
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([0xb8, 0x27, 0xeb, 1, 2, 3], [0xff; 6])
+            .ipv4(source, destination, 64)
+            .udp(ports.0, ports.1)
+            .write(&mut packet, payload)
+            .unwrap();
+        packet
+    }
+
+    fn dhcp_message(op: u8, yiaddr: [u8; 4], options: &[u8]) -> Vec<u8> {
+        let mut message = vec![0; DHCP_OPTIONS_OFFSET];
+        message[..3].copy_from_slice(&[op, 1, 6]);
+        message[16..20].copy_from_slice(&yiaddr);
+        message[28..34].copy_from_slice(&[0xdc, 0xa6, 0x32, 4, 5, 6]);
+        message[236..240].copy_from_slice(&DHCP_MAGIC_COOKIE);
+        message.extend_from_slice(options);
+        message.push(255);
+        message
+    }
+
+    fn encode_netbios_name(name: &str, suffix: u8) -> Vec<u8> {
+        let mut padded = format!("{name:<15}").into_bytes();
+        padded.push(suffix);
+        let mut encoded = vec![0x20];
+        for byte in padded {
+            encoded.extend([b'A' + (byte >> 4), b'A' + (byte & 0x0f)]);
+        }
+        encoded.push(0x00);
+        encoded
+    }
+
+    #[test]
+    fn test_arp_announcement() {
+        let arp = ArpPacket::new(
+            ArpHardwareId::ETHERNET,
+            EtherType::IPV4,
+            ArpOperation::REQUEST,
+            &[0xb8, 0x27, 0xeb, 1, 2, 3],
+            &[192, 168, 1, 10],
+            &[0; 6],
+            &[192, 168, 1, 1],
+        )
+        .unwrap();
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([0xb8, 0x27, 0xeb, 1, 2, 3], [0xff; 6])
+            .arp(arp)
+            .write(&mut packet)
+            .unwrap();
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            device_announcement(&headers),
+            Some(DeviceAnnouncement {
+                mac_address: "b8:27:eb:01:02:03".to_string(),
+                ip_address: Some("192.168.1.10".parse().unwrap()),
+                hostname: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_dhcp_announcement() {
+        // request carrying the hostname
+        let message = dhcp_message(1, [0; 4], &[53, 1, 3, 12, 5, b'l', b'a', b'p', b't', b'p']);
+        let packet = udp_packet([0; 4], [255; 4], (68, 67), &message);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            device_announcement(&headers),
+            Some(DeviceAnnouncement {
+                mac_address: "dc:a6:32:04:05:06".to_string(),
+                ip_address: None,
+                hostname: Some("laptp".to_string()),
+            })
+        );
+
+        // acknowledgement assigning the address
+        let message = dhcp_message(2, [192, 168, 1, 20], &[53, 1, DHCP_ACK]);
+        let packet = udp_packet([192, 168, 1, 1], [255; 4], (67, 68), &message);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            device_announcement(&headers),
+            Some(DeviceAnnouncement {
+                mac_address: "dc:a6:32:04:05:06".to_string(),
+                ip_address: Some("192.168.1.20".parse().unwrap()),
+                hostname: None,
+            })
+        );
+
+        // offer
+        let message = dhcp_message(2, [192, 168, 1, 20], &[53, 1, 2]);
+        let packet = udp_packet([192, 168, 1, 1], [255; 4], (67, 68), &message);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(device_announcement(&headers), None);
+    }
+
+    #[test]
+    fn test_mdns_announcement() {
+        let mut message = vec![0, 0, 0x84, 0x00, 0, 0, 0, 1, 0, 0, 0, 0];
+        message.extend_from_slice(
+            b"\x0braspberrypi\x05local\x00\x00\x01\x80\x01\x00\x00\x00\x78\x00\x04",
+        );
+        message.extend_from_slice(&[192, 168, 1, 10]);
+        let packet = udp_packet([192, 168, 1, 10], [224, 0, 0, 251], (5353, 5353), &message);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            device_announcement(&headers),
+            Some(DeviceAnnouncement {
+                mac_address: "b8:27:eb:01:02:03".to_string(),
+                ip_address: Some("192.168.1.10".parse().unwrap()),
+                hostname: Some("raspberrypi".to_string()),
+            })
+        );
+
+        // response about another device
+        let packet = udp_packet([192, 168, 1, 11], [224, 0, 0, 251], (5353, 5353), &message);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(device_announcement(&headers), None);
+    }
+
+    #[test]
+    fn test_netbios_announcement() {
+        let registration = |name: &str, suffix: u8, nb_flags: u8| {
+            // registration request, broadcast
+            let mut message = vec![0x12, 0x34, 0x29, 0x10, 0, 1, 0, 0, 0, 0, 0, 1];
+            message.extend(encode_netbios_name(name, suffix));
+            message.extend_from_slice(&[0x00, 0x20, 0x00, 0x01]);
+            message.extend_from_slice(&[0xc0, 0x0c, 0x00, 0x20, 0x00, 0x01, 0, 0, 0x0e, 0x10]);
+            message.extend_from_slice(&[0x00, 0x06, nb_flags, 0x00, 192, 168, 1, 30]);
+            udp_packet([192, 168, 1, 30], [192, 168, 1, 255], (137, 137), &message)
+        };
+
+        let packet = registration("DESKTOP-42", 0x00, 0x00);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            device_announcement(&headers),
+            Some(DeviceAnnouncement {
+                mac_address: "b8:27:eb:01:02:03".to_string(),
+                ip_address: Some("192.168.1.30".parse().unwrap()),
+                hostname: Some("DESKTOP-42".to_string()),
+            })
+        );
+
+        // workgroup
+        let packet = registration("WORKGROUP", 0x00, 0x80);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(device_announcement(&headers), None);
+
+        // master browser
+        let packet = registration("DESKTOP-42", 0x1d, 0x00);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(device_announcement(&headers), None);
+    }
+}
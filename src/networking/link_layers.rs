@@ -0,0 +1,309 @@
+//! Module to decode the link layers which aren't supported by `etherparse`
+//! (PPP, PPP over Ethernet, NFLOG, IEEE 802.11, and IP over InfiniBand),
+//! so that the packets they carry can be analyzed.
+
+use etherparse::{EtherType, Ethernet2Header, LaxPacketHeaders, LaxPayloadSlice, LinkHeader};
+
+const ETHER_TYPE_PPPOE_SESSION: EtherType = EtherType(0x8864);
+const ETHER_TYPE_MPLS_UNICAST: EtherType = EtherType(0x8847);
+const ETHER_TYPE_MPLS_MULTICAST: EtherType = EtherType(0x8848);
+
+/// Type of the NFLOG attribute containing the logged packet
+const NFULA_PAYLOAD: u16 = 9;
+
+/// Returns the ether type and payload of a PPP frame
+///
+/// Reference: <https://www.rfc-editor.org/rfc/rfc1661>
+fn ppp_payload(frame: &[u8]) -> Option<(EtherType, &[u8])> {
+    // address and control fields are only present in HDLC-like framing
+    let frame = frame.strip_prefix(&[0xff, 0x03]).unwrap_or(frame);
+    // protocol field may be compressed to a single byte (odd values)
+    let (protocol, payload) = match frame {
+        [p, rest @ ..] if p & 0x01 == 1 => (u16::from(*p), rest),
+        [p1, p2, rest @ ..] => (u16::from_be_bytes([*p1, *p2]), rest),
+        _ => return None,
+    };
+    let ether_type = match protocol {
+        0x0021 => EtherType::IPV4,
+        0x0057 => EtherType::IPV6,
+        0x0281 => ETHER_TYPE_MPLS_UNICAST,
+        0x0283 => ETHER_TYPE_MPLS_MULTICAST,
+        // link and network control protocols aren't analyzed
+        _ => return None,
+    };
+    Some((ether_type, payload))
+}
+
+/// Returns the ether type and payload of a PPP over Ethernet session frame
+///
+/// Reference: <https://www.rfc-editor.org/rfc/rfc2516>
+fn pppoe_payload(frame: &[u8]) -> Option<(EtherType, &[u8])> {
+    // version and type must be 1, and code must be 0 for session data
+    let [0x11, 0x00, _, _, l1, l2, ppp_frame @ ..] = frame else {
+        return None;
+    };
+    let len = usize::from(u16::from_be_bytes([*l1, *l2])).min(ppp_frame.len());
+    ppp_payload(&ppp_frame[..len])
+}
+
+/// Returns the headers of a PPP frame (link types `PPP`, `PPP_HDLC`, and `PPP_WITH_DIR`)
+pub fn from_ppp(packet: &[u8], has_direction: bool) -> Option<LaxPacketHeaders<'_>> {
+    let frame = if has_direction {
+        packet.get(1..)?
+    } else {
+        packet
+    };
+    let (ether_type, payload) = ppp_payload(frame)?;
+    Some(LaxPacketHeaders::from_ether_type(ether_type, payload))
+}
+
+/// Returns the headers of a PPP over Ethernet frame without Ethernet header (link type `PPP_ETHER`)
+pub fn from_pppoe(packet: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    let (ether_type, payload) = pppoe_payload(packet)?;
+    Some(LaxPacketHeaders::from_ether_type(ether_type, payload))
+}
+
+/// Strips the PPP over Ethernet session header of packets carried over Ethernet (if any),
+/// returning the headers of the packets it carries.
+///
+/// Packets that aren't PPP over Ethernet sessions, or carrying something else than IP or MPLS, are returned unchanged.
+pub fn strip_pppoe(headers: LaxPacketHeaders<'_>) -> LaxPacketHeaders<'_> {
+    let LaxPayloadSlice::Ether(ether_payload) = &headers.payload else {
+        return headers;
+    };
+    if ether_payload.ether_type != ETHER_TYPE_PPPOE_SESSION {
+        return headers;
+    }
+    let Some((ether_type, payload)) = pppoe_payload(ether_payload.payload) else {
+        return headers;
+    };
+    let mut inner = LaxPacketHeaders::from_ether_type(ether_type, payload);
+    inner.link = headers.link;
+    inner.link_exts = headers.link_exts;
+    inner
+}
+
+/// Returns the headers of a packet logged by the Linux netfilter (link type `NFLOG`)
+///
+/// Reference: <https://www.tcpdump.org/linktypes/LINKTYPE_NFLOG.html>
+pub fn from_nflog(packet: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    // header: address family (1 byte), version (1 byte), and resource ID (2 bytes)
+    let mut tlvs = packet.get(4..)?;
+    // TLV length and type are in host byte order (libpcap swaps them when needed)
+    while let [l1, l2, t1, t2, ..] = tlvs {
+        let len = usize::from(u16::from_ne_bytes([*l1, *l2]));
+        // the upper bits of the type are flags
+        let tlv_type = u16::from_ne_bytes([*t1, *t2]) & 0x7fff;
+        if len < 4 || len > tlvs.len() {
+            return None;
+        }
+        if tlv_type == NFULA_PAYLOAD {
+            return LaxPacketHeaders::from_ip(&tlvs[4..len]).ok();
+        }
+        // TLVs are padded to a multiple of 4 bytes
+        tlvs = tlvs.get(len.next_multiple_of(4)..)?;
+    }
+    None
+}
+
+/// Returns the headers of an IEEE 802.11 frame preceded by a radiotap header (link type `IEEE802_11_RADIOTAP`)
+///
+/// Reference: <https://www.radiotap.org/>
+pub fn from_radiotap(packet: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    let [_, _, l1, l2, ..] = packet else {
+        return None;
+    };
+    let len = usize::from(u16::from_le_bytes([*l1, *l2]));
+    from_ieee80211(packet.get(len..)?)
+}
+
+/// Returns the headers of an IEEE 802.11 data frame (link type `IEEE802_11`)
+///
+/// Source and destination MAC addresses are reported in an Ethernet header,
+/// like when the frame is bridged to a wired network.
+/// Encrypted frames can't be analyzed.
+pub fn from_ieee80211(frame: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    let [fc1, fc2, ..] = frame else {
+        return None;
+    };
+    let frame_type = (fc1 >> 2) & 0x03;
+    let subtype = fc1 >> 4;
+    let is_protected = fc2 & 0x40 != 0;
+    // only data frames with a payload (i.e., not null function) are analyzed
+    if frame_type != 2 || subtype & 0x04 != 0 || is_protected {
+        return None;
+    }
+
+    let to_ds = fc2 & 0x01 != 0;
+    let from_ds = fc2 & 0x02 != 0;
+    let is_qos = subtype & 0x08 != 0;
+    let has_ht_control = is_qos && fc2 & 0x80 != 0;
+    let header_len = 24
+        + if to_ds && from_ds { 6 } else { 0 }
+        + if is_qos { 2 } else { 0 }
+        + if has_ht_control { 4 } else { 0 };
+
+    let address = |i: usize| -> Option<[u8; 6]> {
+        let start = if i < 3 { 4 + 6 * i } else { 24 };
+        frame.get(start..start + 6)?.try_into().ok()
+    };
+    let (destination, source) = match (to_ds, from_ds) {
+        (false, false) => (address(0)?, address(1)?),
+        (true, false) => (address(2)?, address(1)?),
+        (false, true) => (address(0)?, address(2)?),
+        (true, true) => (address(2)?, address(3)?),
+    };
+
+    // the payload is encapsulated in an LLC header with a SNAP extension
+    let [0xaa, 0xaa, 0x03, _, _, _, e1, e2, payload @ ..] = frame.get(header_len..)? else {
+        return None;
+    };
+    let ether_type = EtherType(u16::from_be_bytes([*e1, *e2]));
+    let mut headers = LaxPacketHeaders::from_ether_type(ether_type, payload);
+    headers.link = Some(LinkHeader::Ethernet2(Ethernet2Header {
+        source,
+        destination,
+        ether_type,
+    }));
+    Some(headers)
+}
+
+/// Returns the headers of an IP over InfiniBand packet (link type `IPOIB`)
+pub fn from_ipoib(packet: &[u8]) -> Option<LaxPacketHeaders<'_>> {
+    // header: protocol type (2 bytes) and reserved (2 bytes)
+    let [p1, p2, _, _, payload @ ..] = packet else {
+        return None;
+    };
+    Some(LaxPacketHeaders::from_ether_type(
+        EtherType(u16::from_be_bytes([*p1, *p2])),
+        payload,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{NetHeaders, PacketBuilder, TransportHeader};
+
+    fn ip_packet() -> Vec<u8> {
+        let mut packet = Vec::new();
+        PacketBuilder::ipv4([10, 1, 1, 1], [10, 2, 2, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[7; 8])
+            .unwrap();
+        packet
+    }
+
+    fn assert_ip_packet(headers: Option<LaxPacketHeaders>) {
+        let headers = headers.expect("headers should be decoded");
+        let Some(NetHeaders::Ipv4(ipv4, _)) = headers.net else {
+            panic!("payload should be IPv4");
+        };
+        assert_eq!(ipv4.destination, [10, 2, 2, 2]);
+        let Some(TransportHeader::Udp(udp)) = headers.transport else {
+            panic!("payload should be UDP");
+        };
+        assert_eq!(udp.destination_port, 6000);
+    }
+
+    fn pppoe_session(ppp_frame: &[u8]) -> Vec<u8> {
+        let len = u16::try_from(ppp_frame.len()).unwrap().to_be_bytes();
+        let mut frame = vec![0x11, 0x00, 0x12, 0x34, len[0], len[1]];
+        frame.extend_from_slice(ppp_frame);
+        frame
+    }
+
+    #[test]
+    fn test_from_ppp() {
+        let mut frame = vec![0xff, 0x03, 0x00, 0x21];
+        frame.extend(ip_packet());
+        assert_ip_packet(from_ppp(&frame, false));
+
+        // compressed protocol field, preceded by the direction
+        let mut frame = vec![0x01, 0x21];
+        frame.extend(ip_packet());
+        assert_ip_packet(from_ppp(&frame, true));
+
+        // LCP
+        assert!(from_ppp(&[0xc0, 0x21, 0x01, 0x01, 0x00, 0x04], false).is_none());
+    }
+
+    #[test]
+    fn test_from_pppoe() {
+        let mut ppp_frame = vec![0x00, 0x21];
+        ppp_frame.extend(ip_packet());
+        assert_ip_packet(from_pppoe(&pppoe_session(&ppp_frame)));
+
+        // PPPoE discovery
+        assert!(from_pppoe(&[0x11, 0x09, 0x00, 0x00, 0x00, 0x00]).is_none());
+    }
+
+    #[test]
+    fn test_strip_pppoe() {
+        let mut ppp_frame = vec![0x00, 0x21];
+        ppp_frame.extend(ip_packet());
+        let mut packet = vec![3, 3, 3, 3, 3, 3, 1, 1, 1, 1, 1, 1, 0x88, 0x64];
+        packet.extend(pppoe_session(&ppp_frame));
+
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert!(headers.net.is_none());
+        let headers = strip_pppoe(headers);
+        assert!(headers.link.is_some());
+        assert_ip_packet(Some(headers));
+    }
+
+    #[test]
+    fn test_from_nflog() {
+        let ip_packet = ip_packet();
+        let tlv = |tlv_type: u16, value: &[u8]| {
+            let len = u16::try_from(value.len() + 4).unwrap();
+            let mut tlv = len.to_ne_bytes().to_vec();
+            tlv.extend(tlv_type.to_ne_bytes());
+            tlv.extend_from_slice(value);
+            tlv.resize(tlv.len().next_multiple_of(4), 0);
+            tlv
+        };
+        let mut packet = vec![2, 0, 0, 1];
+        // NFULA_PACKET_HDR, NFULA_PREFIX, and NFULA_PAYLOAD
+        packet.extend(tlv(1, &[0x08, 0x00, 0x01, 0x00]));
+        packet.extend(tlv(10, b"DROP\0"));
+        packet.extend(tlv(NFULA_PAYLOAD, &ip_packet));
+        assert_ip_packet(from_nflog(&packet));
+
+        // no payload
+        assert!(from_nflog(&packet[..20]).is_none());
+    }
+
+    #[test]
+    fn test_from_radiotap() {
+        // minimal radiotap header (no fields present)
+        let mut packet = vec![0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00];
+        // QoS data frame from the distribution system: address 1 is the destination, address 3 the source
+        packet.extend([0x88, 0x02, 0x00, 0x00]);
+        packet.extend([0x0a; 6]);
+        packet.extend([0x0b; 6]);
+        packet.extend([0x0c; 6]);
+        packet.extend([0x00, 0x00, 0x00, 0x00]);
+        packet.extend([0xaa, 0xaa, 0x03, 0x00, 0x00, 0x00, 0x08, 0x00]);
+        packet.extend(ip_packet());
+
+        let headers = from_radiotap(&packet);
+        let Some(Some(LinkHeader::Ethernet2(ethernet))) = headers.as_ref().map(|h| &h.link) else {
+            panic!("MAC addresses should be reported");
+        };
+        assert_eq!(ethernet.destination, [0x0a; 6]);
+        assert_eq!(ethernet.source, [0x0c; 6]);
+        assert_ip_packet(headers);
+
+        // protected frame
+        packet[9] |= 0x40;
+        assert!(from_radiotap(&packet).is_none());
+    }
+
+    #[test]
+    fn test_from_ipoib() {
+        let mut packet = vec![0x08, 0x00, 0x00, 0x00];
+        packet.extend(ip_packet());
+        assert_ip_packet(from_ipoib(&packet));
+    }
+}
@@ -2,7 +2,8 @@
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 use etherparse::{
-    ArpHardwareId, EtherType, LaxPacketHeaders, LinkHeader, NetHeaders, TransportHeader,
+    ArpHardwareId, EtherType, IpNumber, LaxPacketHeaders, LaxPayloadSlice, LinkHeader, NetHeaders,
+    TransportHeader,
 };
 use pcap::Address;
 
@@ -12,14 +13,19 @@
 use crate::networking::types::bogon::is_bogon;
 use crate::networking::types::capture_context::CaptureSource;
 use crate::networking::types::icmp_type::{IcmpType, IcmpTypeV4, IcmpTypeV6};
+use crate::networking::types::igmp_type::IgmpType;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::info_traffic::InfoTraffic;
-use crate::networking::types::ip_blacklist::IpBlacklist;
+use crate::networking::types::ip_blacklist::SharedIpLists;
+use crate::networking::types::mpls_labels::MplsLabels;
 use crate::networking::types::program::Program;
 use crate::networking::types::service::Service;
 use crate::networking::types::service_query::ServiceQuery;
+use crate::networking::types::tcp_metrics::TcpMetrics;
+use crate::networking::types::tcp_state::TcpFlags;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::networking::types::traffic_type::TrafficType;
+use crate::networking::types::vlan_ids::VlanIds;
 use std::fmt::Write;
 use std::time::Instant;
 
@@ -27,12 +33,15 @@
 
 /// Calls methods to analyze link, network, and transport headers.
 /// Returns the relevant collected information.
+#[allow(clippy::similar_names)]
 pub fn analyze_headers(
     headers: LaxPacketHeaders,
     mac_addresses: &mut (Option<String>, Option<String>),
     exchanged_bytes: &mut u128,
     icmp_type: &mut IcmpType,
     arp_type: &mut ArpType,
+    igmp_type: &mut IgmpType,
+    tcp_flags: &mut TcpFlags,
 ) -> Option<AddressPortPair> {
     let mut retval = AddressPortPair::default();
 
@@ -44,6 +53,7 @@ pub fn analyze_headers(
     );
 
     let is_arp = matches!(&headers.net, Some(NetHeaders::Arp(_)));
+    retval.vlan = VlanIds::from_link_exts(&headers.link_exts);
 
     if !analyze_network_header(
         headers.net,
@@ -58,10 +68,13 @@ pub fn analyze_headers(
     if !is_arp
         && !analyze_transport_header(
             headers.transport,
+            &headers.payload,
             &mut retval.sport,
             &mut retval.dport,
             &mut retval.protocol,
             icmp_type,
+            igmp_type,
+            tcp_flags,
         )
     {
         return None;
@@ -166,12 +179,16 @@ fn analyze_network_header(
 /// This function analyzes the transport layer header passed as parameter and updates variables
 /// passed by reference on the basis of the packet header content.
 /// Returns false if packet has to be skipped.
+#[allow(clippy::too_many_arguments, clippy::similar_names)]
 fn analyze_transport_header(
     transport_header: Option<TransportHeader>,
+    payload: &LaxPayloadSlice,
     port1: &mut Option<u16>,
     port2: &mut Option<u16>,
     protocol: &mut Protocol,
     icmp_type: &mut IcmpType,
+    igmp_type: &mut IgmpType,
+    tcp_flags: &mut TcpFlags,
 ) -> bool {
     match transport_header {
         Some(TransportHeader::Udp(udp_header)) => {
@@ -184,6 +201,7 @@ fn analyze_transport_header(
             *port1 = Some(tcp_header.source_port);
             *port2 = Some(tcp_header.destination_port);
             *protocol = Protocol::TCP;
+            *tcp_flags = TcpFlags::from_header(&tcp_header);
             true
         }
         Some(TransportHeader::Icmpv4(icmpv4_header)) => {
@@ -200,12 +218,52 @@ fn analyze_transport_header(
             *icmp_type = IcmpTypeV6::from_etherparse(&icmpv6_header.icmp_type);
             true
         }
-        Some(TransportHeader::Igmp(_)) => {
-            #[allow(clippy::match_same_arms)]
-            // TODO!
-            false
+        Some(TransportHeader::Igmp(igmp_header)) => {
+            *port1 = None;
+            *port2 = None;
+            *protocol = Protocol::IGMP;
+            *igmp_type = IgmpType::from_etherparse(&igmp_header.igmp_type);
+            true
         }
-        None => false,
+        None => match payload {
+            // ports of fragments are assigned later on, based on the first fragment of their datagram
+            LaxPayloadSlice::Ip(ip_payload) if ip_payload.fragmented => {
+                *port1 = None;
+                *port2 = None;
+                *protocol = match ip_payload.ip_number {
+                    IpNumber::TCP => Protocol::TCP,
+                    IpNumber::UDP => Protocol::UDP,
+                    IpNumber::ICMP | IpNumber::IPV6_ICMP => Protocol::ICMP,
+                    IpNumber::IGMP => Protocol::IGMP,
+                    IpNumber(number) => Protocol::Other(number),
+                };
+                true
+            }
+            // protocols not decoded by etherparse are identified by their IP number;
+            // the ones that should have been decoded are malformed, so they're skipped
+            LaxPayloadSlice::Ip(ip_payload)
+                if ![
+                    IpNumber::TCP,
+                    IpNumber::UDP,
+                    IpNumber::ICMP,
+                    IpNumber::IPV6_ICMP,
+                    IpNumber::IGMP,
+                ]
+                .contains(&ip_payload.ip_number) =>
+            {
+                (*port1, *port2) = match (ip_payload.ip_number, ip_payload.payload) {
+                    // SCTP common header starts with source and destination ports
+                    (IpNumber::SCTP, [s1, s2, d1, d2, ..]) => (
+                        Some(u16::from_be_bytes([*s1, *s2])),
+                        Some(u16::from_be_bytes([*d1, *d2])),
+                    ),
+                    _ => (None, None),
+                };
+                *protocol = Protocol::Other(ip_payload.ip_number.0);
+                true
+            }
+            _ => false,
+        },
     }
 }
 
@@ -214,7 +272,10 @@ pub fn get_service(
     traffic_direction: TrafficDirection,
     my_interface_addresses: &[Address],
 ) -> Service {
-    if key.protocol == Protocol::ICMP || key.protocol == Protocol::ARP {
+    if matches!(
+        key.protocol,
+        Protocol::ICMP | Protocol::ARP | Protocol::IGMP
+    ) {
         return Service::NotApplicable;
     }
 
@@ -261,7 +322,7 @@ pub fn get_service(
 }
 
 /// Function to insert the source and destination of a packet into the map containing the analyzed traffic
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::similar_names)]
 pub fn modify_or_insert_in_map(
     info_traffic_msg: &mut InfoTraffic,
     key: &AddressPortPair,
@@ -269,12 +330,15 @@ pub fn modify_or_insert_in_map(
     mac_addresses: (Option<String>, Option<String>),
     icmp_type: IcmpType,
     arp_type: ArpType,
+    igmp_type: IgmpType,
+    tcp_flags: TcpFlags,
     exchanged_bytes: u128,
-    ip_blacklist: &IpBlacklist,
+    ip_lists: &SharedIpLists,
+    adapter: Option<&String>,
 ) -> (TrafficDirection, Service) {
     let mut traffic_direction = TrafficDirection::default();
     let mut service = Service::Unknown;
-    let mut is_blacklisted = false;
+    let mut blacklist_match = None;
 
     if !info_traffic_msg.map.contains_key(key) {
         // first occurrence of key (in this time interval)
@@ -294,7 +358,10 @@ pub fn modify_or_insert_in_map(
         service = get_service(key, traffic_direction, my_interface_addresses);
         // check if the remote address is blacklisted
         let address_to_lookup = get_address_to_lookup(key, traffic_direction);
-        is_blacklisted = ip_blacklist.contains(&address_to_lookup);
+        blacklist_match = ip_lists
+            .read()
+            .ok()
+            .and_then(|lists| lists.lookup(&address_to_lookup));
     }
 
     let timestamp = info_traffic_msg.last_packet_timestamp;
@@ -318,6 +385,18 @@ pub fn modify_or_insert_in_map(
                     .and_modify(|n| *n += 1)
                     .or_insert(1);
             }
+            if key.protocol.eq(&Protocol::IGMP) {
+                info.igmp_types
+                    .entry(igmp_type)
+                    .and_modify(|n| *n += 1)
+                    .or_insert(1);
+            }
+            info.tcp_flags.add(tcp_flags);
+            if let Some(adapter) = adapter
+                && !info.adapters.contains(adapter)
+            {
+                info.adapters.push(adapter.clone());
+            }
         })
         .or_insert_with(|| InfoAddressPortPair {
             mac_address1: mac_addresses.0,
@@ -339,8 +418,21 @@ pub fn modify_or_insert_in_map(
             } else {
                 HashMap::new()
             },
-            is_blacklisted,
+            igmp_types: if key.protocol.eq(&Protocol::IGMP) {
+                HashMap::from([(igmp_type, 1)])
+            } else {
+                HashMap::new()
+            },
+            blacklist_match,
             program: Program::NotApplicable,
+            adapters: adapter.into_iter().cloned().collect(),
+            server_name: None,
+            tcp_flags,
+            tcp_state: None,
+            tcp_metrics: TcpMetrics::default(),
+            tunnel: None,
+            mpls_labels: MplsLabels::default(),
+            fragments: 0,
         });
 
     (new_info.traffic_direction, new_info.service)
@@ -496,7 +588,7 @@ pub fn is_my_address(local_address: &IpAddr, my_interface_addresses: &Vec<Addres
 }
 
 /// Converts a MAC address in its hexadecimal form
-fn mac_from_dec_to_hex(mac_dec: [u8; 6]) -> String {
+pub fn mac_from_dec_to_hex(mac_dec: [u8; 6]) -> String {
     let mut mac_hex = String::with_capacity(17);
     for n in &mac_dec {
         let _ = write!(mac_hex, "{n:02x}:");
@@ -538,13 +630,18 @@ mod tests {
     use crate::Protocol;
     use crate::Service;
     use crate::networking::manage_packets::{
-        get_service, get_traffic_direction, get_traffic_type, is_local_connection,
+        analyze_headers, get_service, get_traffic_direction, get_traffic_type, is_local_connection,
         mac_from_dec_to_hex,
     };
     use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::arp_type::ArpType;
+    use crate::networking::types::icmp_type::IcmpType;
+    use crate::networking::types::igmp_type::IgmpType;
     use crate::networking::types::service_query::ServiceQuery;
+    use crate::networking::types::tcp_state::TcpFlags;
     use crate::networking::types::traffic_direction::TrafficDirection;
     use crate::networking::types::traffic_type::TrafficType;
+    use etherparse::{IpNumber, LaxPacketHeaders, PacketBuilder};
 
     include!(concat!(env!("OUT_DIR"), "/services.rs"));
 
@@ -1523,7 +1620,14 @@ fn test_get_service_different_tcp_udp() {
 
     #[test]
     fn test_get_service_not_applicable() {
-        for p in [Protocol::TCP, Protocol::UDP, Protocol::ICMP, Protocol::ARP] {
+        for p in [
+            Protocol::TCP,
+            Protocol::UDP,
+            Protocol::ICMP,
+            Protocol::ARP,
+            Protocol::IGMP,
+            Protocol::Other(47),
+        ] {
             for d in [TrafficDirection::Incoming, TrafficDirection::Outgoing] {
                 for (p1, p2) in [(None, Some(443)), (None, None), (Some(443), None)] {
                     let key = AddressPortPair::new(
@@ -1539,6 +1643,49 @@ fn test_get_service_not_applicable() {
         }
     }
 
+    fn analyze_ipv4_packet(ip_number: IpNumber, payload: &[u8]) -> (AddressPortPair, IgmpType) {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1, 2, 3, 4, 5, 6], [1, 0, 94, 0, 0, 251])
+            .ipv4([192, 168, 1, 2], [224, 0, 0, 251], 1)
+            .write(&mut packet, ip_number, payload)
+            .unwrap();
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let mut igmp_type = IgmpType::default();
+        let key = analyze_headers(
+            headers,
+            &mut (None, None),
+            &mut 0,
+            &mut IcmpType::default(),
+            &mut ArpType::default(),
+            &mut igmp_type,
+            &mut TcpFlags::default(),
+        )
+        .unwrap();
+        (key, igmp_type)
+    }
+
+    #[test]
+    fn test_analyze_headers_igmp_and_other_ip_protocols() {
+        // IGMPv2 membership report for 224.0.0.251
+        let (key, igmp_type) =
+            analyze_ipv4_packet(IpNumber::IGMP, &[0x16, 0, 0xfa, 0x04, 224, 0, 0, 251]);
+        assert_eq!(key.protocol, Protocol::IGMP);
+        assert_eq!((key.sport, key.dport), (None, None));
+        assert_eq!(igmp_type, IgmpType::MembershipReportV2);
+
+        let (key, igmp_type) = analyze_ipv4_packet(IpNumber(89), &[2, 1, 0, 44]);
+        assert_eq!(key.protocol, Protocol::Other(89));
+        assert_eq!(key.protocol.to_string(), "OSPF");
+        assert_eq!((key.sport, key.dport), (None, None));
+        assert_eq!(igmp_type, IgmpType::Unknown);
+
+        let (key, _) = analyze_ipv4_packet(IpNumber::SCTP, &[0x0b, 0x59, 0x0e, 0x10, 0, 0]);
+        assert_eq!(key.protocol, Protocol::Other(132));
+        assert_eq!((key.sport, key.dport), (Some(2905), Some(3600)));
+
+        assert_eq!(Protocol::Other(253).to_string(), "IP-253");
+    }
+
     #[test]
     fn test_get_service_unknown() {
         let unknown_port_1 = Some(39332);
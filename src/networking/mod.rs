@@ -1,4 +1,17 @@
+pub mod arp_watch;
+pub mod decapsulation;
+pub mod dns;
+pub mod flow_export;
+pub mod fragments;
+pub mod ip_feeds;
+pub mod lan_discovery;
+pub mod link_layers;
 pub mod manage_packets;
+pub mod mpls;
 pub mod parse_packets;
+pub mod pcapng;
+pub mod scan_detection;
+pub mod server_name;
+pub mod tcp_analysis;
 pub mod traffic_preview;
 pub mod types;
@@ -0,0 +1,108 @@
+//! Module to strip the MPLS label stack of packets, which isn't decoded by `etherparse`,
+//! so that the IP packets it carries can be analyzed.
+//!
+//! Reference: <https://www.rfc-editor.org/rfc/rfc3032>
+
+use crate::networking::types::mpls_labels::MplsLabels;
+use etherparse::{EtherType, LaxPacketHeaders, LaxPayloadSlice};
+
+const ETHER_TYPE_MPLS_UNICAST: EtherType = EtherType(0x8847);
+const ETHER_TYPE_MPLS_MULTICAST: EtherType = EtherType(0x8848);
+
+/// Length of a label stack entry
+const LABEL_LEN: usize = 4;
+/// Maximum number of labels decoded (deeper stacks aren't used in practice)
+const MAX_LABELS: usize = 8;
+
+/// Strips the MPLS label stack of a packet (if any),
+/// returning the headers of the IP packet it carries along with the labels.
+///
+/// Packets without labels, or carrying something else than IP, are returned unchanged.
+pub fn strip_mpls(headers: LaxPacketHeaders<'_>) -> (LaxPacketHeaders<'_>, MplsLabels) {
+    let LaxPayloadSlice::Ether(ether_payload) = &headers.payload else {
+        return (headers, MplsLabels::default());
+    };
+    if ![ETHER_TYPE_MPLS_UNICAST, ETHER_TYPE_MPLS_MULTICAST].contains(&ether_payload.ether_type) {
+        return (headers, MplsLabels::default());
+    }
+
+    let mut labels = Vec::new();
+    let mut rest = ether_payload.payload;
+    let mut bottom_of_stack = false;
+    while !bottom_of_stack && labels.len() < MAX_LABELS {
+        let Some(entry) = rest.get(..LABEL_LEN) else {
+            return (headers, MplsLabels::default());
+        };
+        labels.push(u32::from_be_bytes([0, entry[0], entry[1], entry[2]]) >> 4);
+        bottom_of_stack = entry[2] & 0x01 != 0;
+        rest = &rest[LABEL_LEN..];
+    }
+
+    // there's no protocol field: the IP version is the first nibble of the carried packet
+    let is_ip = bottom_of_stack && matches!(rest.first().map(|b| b >> 4), Some(4 | 6));
+    match LaxPacketHeaders::from_ip(rest) {
+        Ok(mut inner) if is_ip => {
+            inner.link = headers.link;
+            inner.link_exts = headers.link_exts;
+            (inner, MplsLabels(labels))
+        }
+        _ => (headers, MplsLabels::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{NetHeaders, PacketBuilder};
+
+    fn mpls_packet(label_stack: &[u8]) -> Vec<u8> {
+        let mut ip_packet = Vec::new();
+        PacketBuilder::ipv4([10, 1, 1, 1], [10, 2, 2, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut ip_packet, &[0; 4])
+            .unwrap();
+        let mut packet = vec![3, 3, 3, 3, 3, 3, 1, 1, 1, 1, 1, 1, 0x88, 0x47];
+        packet.extend_from_slice(label_stack);
+        packet.extend(ip_packet);
+        packet
+    }
+
+    #[test]
+    fn test_strip_mpls_label_stack() {
+        // labels 16001 and 24005 (bottom of stack), TTL 64
+        let packet = mpls_packet(&[0x03, 0xe8, 0x10, 0x40, 0x05, 0xdc, 0x51, 0x40]);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert!(headers.net.is_none());
+
+        let (headers, labels) = strip_mpls(headers);
+        assert_eq!(labels, MplsLabels(vec![16001, 24005]));
+        assert_eq!(labels.to_string(), "16001 / 24005");
+        assert!(headers.link.is_some());
+        let Some(NetHeaders::Ipv4(ipv4, _)) = headers.net else {
+            panic!("MPLS payload should be IPv4");
+        };
+        assert_eq!(ipv4.destination, [10, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_strip_mpls_unchanged() {
+        // missing bottom of stack
+        let packet = mpls_packet(&[0x03, 0xe8, 0x10, 0x40]);
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let (headers, labels) = strip_mpls(headers);
+        assert!(labels.is_empty());
+        assert!(headers.net.is_none());
+
+        // not MPLS
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [3; 6])
+            .ipv4([10, 1, 1, 1], [10, 2, 2, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[])
+            .unwrap();
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let (headers, labels) = strip_mpls(headers);
+        assert!(labels.is_empty());
+        assert!(headers.net.is_some());
+    }
+}
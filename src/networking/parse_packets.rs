@@ -1,33 +1,55 @@
 //! Module containing functions executed by the thread in charge of parsing sniffed packets
 
+use crate::gui::types::conf::Conf;
+use crate::gui::types::favorite::Favorites;
 use crate::gui::types::filters::Filters;
 use crate::location;
 use crate::mmdb::asn::get_asn;
 use crate::mmdb::country::get_country;
 use crate::mmdb::types::mmdb_reader::MmdbReaders;
+use crate::networking::arp_watch::ArpWatcher;
+use crate::networking::decapsulation::{Decapsulated, decapsulate};
+use crate::networking::dns::dns_answers;
+use crate::networking::fragments::{Fragment, FragmentTracker};
+use crate::networking::lan_discovery::{DeviceAnnouncement, device_announcement};
+use crate::networking::link_layers::{
+    from_ieee80211, from_ipoib, from_nflog, from_ppp, from_pppoe, from_radiotap, strip_pppoe,
+};
 use crate::networking::manage_packets::{
     analyze_headers, get_address_to_lookup, get_traffic_type, is_local_connection,
     modify_or_insert_in_map,
 };
+use crate::networking::mpls::strip_mpls;
+use crate::networking::server_name::{ServerNameExtractor, TransportPayload};
+use crate::networking::tcp_analysis::{TcpAnalyzer, TcpSegment};
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::arp_type::ArpType;
 use crate::networking::types::bogon::is_bogon;
-use crate::networking::types::capture_context::{CaptureContext, CaptureSource, CaptureType};
+use crate::networking::types::capture_context::{
+    CaptureContext, CaptureSource, CaptureType, PacketOwned,
+};
+use crate::networking::types::capture_writer::CaptureWriter;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::{Host, HostMessage};
 use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::igmp_type::IgmpType;
 use crate::networking::types::info_traffic::InfoTraffic;
-use crate::networking::types::ip_blacklist::IpBlacklist;
+use crate::networking::types::ip_blacklist::{BlacklistMatch, SharedIpLists};
+use crate::networking::types::lan_device::{LanDevice, is_unicast_mac};
+use crate::networking::types::mpls_labels::MplsLabels;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::service::Service;
+use crate::networking::types::tcp_state::TcpFlags;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::utils::error_logger::{ErrorLogger, Location};
 use crate::utils::formatted_strings::get_domain_from_r_dns;
+use crate::utils::types::bounded_map::BoundedMap;
 use crate::utils::types::timestamp::Timestamp;
 use async_channel::Sender;
 use dns_lookup::lookup_addr;
 use etherparse::{EtherType, LaxPacketHeaders};
-use pcap::{Address, Packet, PacketHeader};
+use pcap::{Address, Linktype};
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::thread;
@@ -35,35 +57,70 @@
 use tokio::sync::broadcast::Receiver;
 
 const REVERSE_DNS_LOOKUP_THREADS: usize = 5;
+/// Maximum number of addresses for which the queried domain name is remembered until they're resolved
+const MAX_DNS_NAMES: usize = 16_384;
+/// Domain names queried for addresses not resolved within this interval are forgotten
+const DNS_NAME_TIMEOUT_USECS: i64 = 3_600_000_000;
 
 /// The calling thread enters a loop in which it waits for network packets
-#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+#[allow(
+    clippy::too_many_lines,
+    clippy::too_many_arguments,
+    clippy::similar_names
+)]
 pub fn parse_packets(
     cap_id: usize,
     mut cs: CaptureSource,
     mmdb_readers: &MmdbReaders,
-    ip_blacklist: &IpBlacklist,
-    capture_context: CaptureContext,
-    filters: Filters,
+    ip_lists: &SharedIpLists,
+    capture_contexts: Vec<CaptureContext>,
+    filters: &Filters,
+    comment_rules: &PacketCommentRules,
+    decapsulate_tunnels: bool,
+    extract_server_names: bool,
     tx: &Sender<BackendTrafficMessage>,
     freeze_rxs: (Receiver<()>, Receiver<()>),
 ) {
-    let (mut freeze_rx, mut freeze_rx_2) = freeze_rxs;
+    let (mut freeze_rx, freeze_rx_2) = freeze_rxs;
 
-    let my_link_type = capture_context.my_link_type();
-    if !my_link_type.is_supported() {
-        return;
+    // each source (i.e., each network adapter) is captured by a dedicated thread
+    let (pcap_tx, pcap_rx) = std::sync::mpsc::sync_channel(10_000);
+    let device_names: Vec<String> = cs.get_device_names().into_iter().cloned().collect();
+    let mut sources = Vec::new();
+    for (i, capture_context) in capture_contexts.into_iter().enumerate() {
+        let my_link_type = capture_context.my_link_type();
+        if !my_link_type.is_supported() {
+            continue;
+        }
+        let (Some(cap), writer) = capture_context.consume() else {
+            continue;
+        };
+        let source_id = sources.len();
+        let pcap_tx = pcap_tx.clone();
+        let mut freeze_rx_2 = freeze_rx_2.resubscribe();
+        let filters = filters.clone();
+        let _ = thread::Builder::new()
+            .name(format!("thread_packet_stream_{source_id}"))
+            .spawn(move || packet_stream(cap, source_id, &pcap_tx, &mut freeze_rx_2, &filters))
+            .log_err(location!());
+        sources.push(SourceState {
+            my_link_type,
+            writer,
+            adapter: device_names.get(i).cloned(),
+            dropped_packets: 0,
+        });
     }
 
-    let (Some(cap), mut savefile) = capture_context.consume() else {
+    if sources.is_empty() {
         return;
-    };
+    }
 
     let mut info_traffic_msg = InfoTraffic::default();
 
     let (lookup_request_tx, lookup_request_rx) = async_channel::unbounded();
     let (lookup_result_tx, lookup_result_rx) = std::sync::mpsc::channel();
-    let mut resolutions_state = AddressesResolutionState::new(lookup_request_tx, lookup_result_rx);
+    let mut resolutions_state =
+        AddressesResolutionState::new(lookup_request_tx, lookup_result_rx, ip_lists.clone());
     // a pool of threads shares the request queue, so one slow blocking lookup doesn't stall the others
     for i in 0..REVERSE_DNS_LOOKUP_THREADS {
         let lookup_request_rx = lookup_request_rx.clone();
@@ -77,15 +134,14 @@ pub fn parse_packets(
             .log_err(location!());
     }
 
+    let mut server_names = ServerNameExtractor::default();
+    let mut tcp_analyzer = TcpAnalyzer::default();
+    let mut fragment_tracker = FragmentTracker::default();
+    let mut arp_watcher = ArpWatcher::default();
+
     // instant of the first parsed packet plus multiples of 1 second (only used in live captures)
     let mut first_packet_ticks = None;
 
-    let (pcap_tx, pcap_rx) = std::sync::mpsc::sync_channel(10_000);
-    let _ = thread::Builder::new()
-        .name("thread_packet_stream".to_string())
-        .spawn(move || packet_stream(cap, &pcap_tx, &mut freeze_rx_2, &filters))
-        .log_err(location!());
-
     loop {
         // check if we need to freeze the parsing
         if freeze_rx.try_recv().is_ok() {
@@ -95,15 +151,15 @@ pub fn parse_packets(
             first_packet_ticks = Some(Instant::now());
         }
 
-        let (packet_res, cap_stats) = pcap_rx
+        let (source_id, packet_res, cap_stats) = pcap_rx
             .recv_timeout(Duration::from_millis(150))
-            .unwrap_or((Err(pcap::Error::TimeoutExpired), None));
+            .unwrap_or((0, Err(pcap::Error::TimeoutExpired), None));
 
         if tx.is_closed() {
             return;
         }
 
-        if matches!(cs, CaptureSource::Device(_)) {
+        if matches!(cs, CaptureSource::Device(_) | CaptureSource::Devices(_)) {
             maybe_send_tick_run_live(
                 cap_id,
                 &mut info_traffic_msg,
@@ -118,10 +174,12 @@ pub fn parse_packets(
             Err(e) => {
                 if e == pcap::Error::NoMorePackets {
                     // send a message including data from the last interval (only happens in offline captures)
+                    let new_hosts = resolutions_state.new_hosts_to_send();
+                    resolutions_state.mark_blacklisted_hosts(&mut info_traffic_msg);
                     let _ = tx.send_blocking(BackendTrafficMessage::TickRun(
                         cap_id,
                         info_traffic_msg,
-                        resolutions_state.new_hosts_to_send(),
+                        new_hosts,
                         true,
                     ));
                     // wait until there is still some IP address waiting for resolution
@@ -137,12 +195,21 @@ pub fn parse_packets(
                 }
             }
             Ok(packet) => {
-                if let Some(headers) = get_sniffable_headers(&packet.data, my_link_type) {
-                    #[allow(clippy::useless_conversion)]
-                    let secs = i64::from(packet.header.ts.tv_sec);
-                    #[allow(clippy::useless_conversion)]
-                    let usecs = i64::from(packet.header.ts.tv_usec);
-                    let next_packet_timestamp = Timestamp::new(secs, usecs);
+                let Some(source) = sources.get_mut(source_id) else {
+                    continue;
+                };
+                // packets of pcapng imports carry the link type and name of their own interface
+                let (my_link_type, adapter) = match &packet.interface {
+                    Some(interface) => (
+                        MyLinkType::from_pcap_link_type(interface.link_type),
+                        interface.name.as_ref(),
+                    ),
+                    None => (source.my_link_type, source.adapter.as_ref()),
+                };
+                if let Some((headers, mpls_labels)) =
+                    get_sniffable_headers(&packet.data, my_link_type)
+                {
+                    let next_packet_timestamp = packet.timestamp;
 
                     if matches!(cs, CaptureSource::File(_)) {
                         maybe_send_tick_run_offline(
@@ -162,6 +229,33 @@ pub fn parse_packets(
                     let mut mac_addresses = (None, None);
                     let mut icmp_type = IcmpType::default();
                     let mut arp_type = ArpType::default();
+                    let mut igmp_type = IgmpType::default();
+                    let mut tcp_flags = TcpFlags::default();
+
+                    // analyze the packets carried by tunnels, accounting the bytes of the outer ones
+                    let (headers, tunnel, outer_bytes) =
+                        match decapsulate_tunnels.then(|| decapsulate(&headers)).flatten() {
+                            Some(Decapsulated {
+                                headers,
+                                tunnel,
+                                outer_bytes,
+                            }) => (headers, Some(tunnel), Some(outer_bytes)),
+                            None => (headers, None, None),
+                        };
+
+                    // remember the domain names queried to obtain each address
+                    if let Some(timestamp_usecs) = next_packet_timestamp.to_usecs() {
+                        resolutions_state.learn_dns_names(dns_answers(&headers), timestamp_usecs);
+                    }
+                    let payload = TransportPayload::from_headers(&headers);
+                    let tcp_segment = TcpSegment::from_headers(&headers);
+                    let fragment = Fragment::from_headers(&headers);
+                    let announcement = device_announcement(&headers);
+                    if let Some(timestamp_usecs) = next_packet_timestamp.to_usecs()
+                        && let Some(anomaly) = arp_watcher.check(&headers, timestamp_usecs)
+                    {
+                        info_traffic_msg.arp_anomalies.push(anomaly);
+                    }
 
                     let key_option = analyze_headers(
                         headers,
@@ -169,19 +263,30 @@ pub fn parse_packets(
                         &mut exchanged_bytes,
                         &mut icmp_type,
                         &mut arp_type,
+                        &mut igmp_type,
+                        &mut tcp_flags,
                     );
 
-                    let Some(key) = key_option else {
+                    let Some(mut key) = key_option else {
                         continue;
                     };
-
-                    // save this packet to PCAP file
-                    if let Some(file) = savefile.as_mut() {
-                        file.write(&Packet {
-                            header: &packet.header,
-                            data: &packet.data,
-                        });
+                    if let Some(fragment) = &fragment
+                        && let Some(timestamp_usecs) = next_packet_timestamp.to_usecs()
+                    {
+                        key = fragment_tracker.attribute(key, fragment, timestamp_usecs);
+                    }
+                    if let Some(outer_bytes) = outer_bytes {
+                        exchanged_bytes = outer_bytes;
                     }
+
+                    update_lan_devices(
+                        &mut info_traffic_msg,
+                        &mac_addresses,
+                        announcement,
+                        exchanged_bytes,
+                        next_packet_timestamp,
+                    );
+
                     // update the map
                     let (traffic_direction, service) = modify_or_insert_in_map(
                         &mut info_traffic_msg,
@@ -190,16 +295,73 @@ pub fn parse_packets(
                         mac_addresses,
                         icmp_type,
                         arp_type,
+                        igmp_type,
+                        tcp_flags,
                         exchanged_bytes,
-                        ip_blacklist,
+                        ip_lists,
+                        adapter,
                     );
 
+                    if let Some(info) = info_traffic_msg.map.get_mut(&key) {
+                        if tunnel.is_some() {
+                            info.tunnel = tunnel;
+                        }
+                        if !mpls_labels.is_empty() {
+                            info.mpls_labels = mpls_labels;
+                        }
+                        if fragment.is_some() {
+                            info.fragments += 1;
+                        }
+                    }
+
+                    if extract_server_names
+                        && let Some(timestamp_usecs) = next_packet_timestamp.to_usecs()
+                        && let Some(server_name) =
+                            server_names.server_name(&key, payload, timestamp_usecs)
+                        && let Some(info) = info_traffic_msg.map.get_mut(&key)
+                    {
+                        info.server_name = Some(server_name);
+                    }
+
+                    if let Some(segment) = tcp_segment
+                        && let Some(timestamp_usecs) = next_packet_timestamp.to_usecs()
+                        && let Some(info) = info_traffic_msg.map.get_mut(&key)
+                    {
+                        let is_outgoing = traffic_direction == TrafficDirection::Outgoing;
+                        info.tcp_metrics.add(tcp_analyzer.analyze(
+                            &key,
+                            &segment,
+                            timestamp_usecs,
+                            is_outgoing,
+                        ));
+                    }
+
+                    let address_to_lookup = get_address_to_lookup(&key, traffic_direction);
+
+                    // save this packet to file
+                    if let Some(writer) = source.writer.as_mut() {
+                        writer.write(&packet, || {
+                            let blacklist_match = info_traffic_msg
+                                .map
+                                .get(&key)
+                                .and_then(|info| info.blacklist_match.as_ref());
+                            comment_rules.comment(
+                                blacklist_match,
+                                &address_to_lookup,
+                                resolutions_state
+                                    .addresses_resolved
+                                    .get(&address_to_lookup)
+                                    .map(|resolved| &resolved.host),
+                                service,
+                            )
+                        });
+                    }
+
                     info_traffic_msg
                         .tot_data_info
                         .add_packet(exchanged_bytes, traffic_direction);
 
                     // check the rDNS status of this address and act accordingly
-                    let address_to_lookup = get_address_to_lookup(&key, traffic_direction);
                     let mut r_dns_waiting_resolution = false;
                     let r_dns_already_resolved = resolutions_state
                         .addresses_resolved
@@ -244,8 +406,8 @@ pub fn parse_packets(
                             let host = resolutions_state
                                 .addresses_resolved
                                 .get(&address_to_lookup)
-                                .unwrap_or(&Host::default())
-                                .clone();
+                                .map(|resolved| resolved.host.clone())
+                                .unwrap_or_default();
                             info_traffic_msg
                                 .hosts
                                 .entry(host)
@@ -294,7 +456,9 @@ pub fn parse_packets(
 
                     // update dropped packets number
                     if let Some(stats) = cap_stats {
-                        info_traffic_msg.dropped_packets = stats.dropped;
+                        source.dropped_packets = stats.dropped;
+                        info_traffic_msg.dropped_packets =
+                            sources.iter().map(|s| s.dropped_packets).sum();
                     }
                 }
             }
@@ -302,11 +466,12 @@ pub fn parse_packets(
     }
 }
 
+/// Returns the headers of a packet, along with its MPLS labels (if any)
 pub(super) fn get_sniffable_headers(
     packet: &[u8],
     my_link_type: MyLinkType,
-) -> Option<LaxPacketHeaders<'_>> {
-    match my_link_type {
+) -> Option<(LaxPacketHeaders<'_>, MplsLabels)> {
+    let headers = match my_link_type {
         MyLinkType::Ethernet(_) | MyLinkType::Unsupported(_) | MyLinkType::NotYetAssigned => {
             LaxPacketHeaders::from_ethernet(packet).ok()
         }
@@ -316,6 +481,47 @@ pub(super) fn get_sniffable_headers(
         MyLinkType::LinuxSll(_) => from_linux_sll(packet, true),
         MyLinkType::LinuxSll2(_) => from_linux_sll(packet, false),
         MyLinkType::Null(_) | MyLinkType::Loop(_) => from_null(packet),
+        MyLinkType::Ppp(l) => from_ppp(packet, l == Linktype::PPP_WITH_DIR),
+        MyLinkType::PppoE(_) => from_pppoe(packet),
+        MyLinkType::Nflog(_) => from_nflog(packet),
+        MyLinkType::Ieee80211(l) if l == Linktype::IEEE802_11_RADIOTAP => from_radiotap(packet),
+        MyLinkType::Ieee80211(_) => from_ieee80211(packet),
+        MyLinkType::IpOverIb(_) => from_ipoib(packet),
+    }?;
+    Some(strip_mpls(strip_pppoe(headers)))
+}
+
+/// Updates the inventory of the devices of the local network with the info carried by a packet
+fn update_lan_devices(
+    info_traffic_msg: &mut InfoTraffic,
+    mac_addresses: &(Option<String>, Option<String>),
+    announcement: Option<DeviceAnnouncement>,
+    exchanged_bytes: u128,
+    timestamp: Timestamp,
+) {
+    for mac_address in [&mac_addresses.0, &mac_addresses.1].into_iter().flatten() {
+        if is_unicast_mac(mac_address) {
+            info_traffic_msg
+                .devices
+                .entry(mac_address.clone())
+                .or_insert_with(|| LanDevice::new(mac_address, timestamp))
+                .add_packet(exchanged_bytes, timestamp);
+        }
+    }
+
+    if let Some(announcement) = announcement
+        && is_unicast_mac(&announcement.mac_address)
+    {
+        let device = info_traffic_msg
+            .devices
+            .entry(announcement.mac_address.clone())
+            .or_insert_with(|| LanDevice::new(&announcement.mac_address, timestamp));
+        if let Some(ip_address) = announcement.ip_address {
+            device.add_ip_address(ip_address);
+        }
+        if announcement.hostname.is_some() {
+            device.hostname = announcement.hostname;
+        }
     }
 }
 
@@ -398,6 +604,8 @@ fn reverse_dns_lookups(
         };
         let new_host = Host {
             domain: get_domain_from_r_dns(rdns.clone()),
+            // filled in by the parsing thread, which decodes the DNS responses
+            dns_name: String::new(),
             asn,
             country,
         };
@@ -427,20 +635,33 @@ pub struct AddressesResolutionState {
     lookup_result_rx: std::sync::mpsc::Receiver<HostMessage>,
     /// Map of the addresses waiting for a rDNS resolution; used to NOT send multiple rDNS for the same address
     addresses_waiting_resolution: HashMap<IpAddr, DataInfo>,
-    /// Map of the resolved addresses with the corresponding host
-    addresses_resolved: HashMap<IpAddr, Host>,
+    /// Map of the resolved addresses with the corresponding host message (without data)
+    addresses_resolved: HashMap<IpAddr, HostMessage>,
+    /// Map of the addresses seen in DNS responses before being resolved, with the domain name that was queried
+    dns_names: BoundedMap<IpAddr, String>,
+    /// Resolved hosts whose queried domain name changed since the last message
+    relabeled_hosts: HashMap<IpAddr, HostMessage>,
+    /// Lists used to check the resolved hosts against domain, ASN, and country entries
+    ip_lists: SharedIpLists,
+    /// Map of the addresses whose resolved host is blacklisted, with the matching list
+    blacklisted_hosts: HashMap<IpAddr, BlacklistMatch>,
 }
 
 impl AddressesResolutionState {
     fn new(
         lookup_request_tx: async_channel::Sender<(AddressPortPair, TrafficDirection, Vec<Address>)>,
         lookup_result_rx: std::sync::mpsc::Receiver<HostMessage>,
+        ip_lists: SharedIpLists,
     ) -> Self {
         Self {
             lookup_request_tx,
             lookup_result_rx,
             addresses_waiting_resolution: HashMap::new(),
             addresses_resolved: HashMap::new(),
+            dns_names: BoundedMap::new(MAX_DNS_NAMES, DNS_NAME_TIMEOUT_USECS),
+            relabeled_hosts: HashMap::new(),
+            ip_lists,
+            blacklisted_hosts: HashMap::new(),
         }
     }
 
@@ -455,14 +676,67 @@ fn new_hosts_to_send(&mut self) -> Vec<HostMessage> {
                 .unwrap_or_default();
             // overwrite the host message with the collected data
             host_msg.data_info_host.data_info = other_data;
+            // label the host with the domain name queried to reach it, if seen in a DNS response
+            if let Some(dns_name) = self.dns_names.remove(&address_to_lookup) {
+                host_msg.host.dns_name = dns_name;
+            }
+            // check the newly resolved host against the blacklists
+            self.check_blacklists(address_to_lookup, &host_msg.host);
             // insert the newly resolved host in the collection of resolved addresses
-            self.addresses_resolved
-                .insert(address_to_lookup, host_msg.host.clone());
+            let mut resolved = host_msg.clone();
+            resolved.data_info_host.data_info = DataInfo::default();
+            self.addresses_resolved.insert(address_to_lookup, resolved);
 
             new_hosts.push(host_msg);
         }
+        // send again the hosts labeled with a different domain name since their resolution
+        new_hosts.extend(self.relabeled_hosts.drain().map(|(_, host_msg)| host_msg));
         new_hosts
     }
+
+    /// Labels the hosts with the domain names queried to obtain their addresses
+    fn learn_dns_names(&mut self, answers: Vec<(IpAddr, String)>, timestamp_usecs: i64) {
+        for (address, dns_name) in answers {
+            let Some(resolved) = self.addresses_resolved.get_mut(&address) else {
+                self.dns_names.insert(address, dns_name, timestamp_usecs);
+                continue;
+            };
+            if resolved.host.dns_name != dns_name {
+                resolved.host.dns_name = dns_name;
+                let host_msg = resolved.clone();
+                self.check_blacklists(address, &host_msg.host);
+                self.relabeled_hosts.insert(address, host_msg);
+            }
+        }
+    }
+
+    /// Checks a resolved host against the blacklists
+    fn check_blacklists(&mut self, address: IpAddr, host: &Host) {
+        let list_match = self
+            .ip_lists
+            .read()
+            .ok()
+            .and_then(|lists| lists.lookup_host(&address, host));
+        match list_match {
+            Some(list_match) => self.blacklisted_hosts.insert(address, list_match),
+            None => self.blacklisted_hosts.remove(&address),
+        };
+    }
+
+    /// Marks the connections exchanged with blacklisted hosts which weren't already blacklisted by address
+    fn mark_blacklisted_hosts(&self, info_traffic_msg: &mut InfoTraffic) {
+        if self.blacklisted_hosts.is_empty() {
+            return;
+        }
+        for (key, info) in info_traffic_msg
+            .map
+            .iter_mut()
+            .filter(|(_, info)| info.blacklist_match.is_none())
+        {
+            let address_to_lookup = get_address_to_lookup(key, info.traffic_direction);
+            info.blacklist_match = self.blacklisted_hosts.get(&address_to_lookup).cloned();
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -483,10 +757,12 @@ fn maybe_send_tick_run_live(
     if first_packet_ticks.is_some_and(|i| i.elapsed() >= Duration::from_secs(1)) {
         *first_packet_ticks =
             first_packet_ticks.and_then(|i| i.checked_add(Duration::from_secs(1)));
+        let new_hosts = resolutions_state.new_hosts_to_send();
+        resolutions_state.mark_blacklisted_hosts(info_traffic_msg);
         let _ = tx.send_blocking(BackendTrafficMessage::TickRun(
             cap_id,
             info_traffic_msg.take_but_leave_something(),
-            resolutions_state.new_hosts_to_send(),
+            new_hosts,
             false,
         ));
         cs.set_addresses();
@@ -506,10 +782,12 @@ fn maybe_send_tick_run_offline(
     if info_traffic_msg.last_packet_timestamp.secs() < next_packet_timestamp.secs() {
         let diff_secs =
             next_packet_timestamp.secs() - info_traffic_msg.last_packet_timestamp.secs();
+        let new_hosts = resolutions_state.new_hosts_to_send();
+        resolutions_state.mark_blacklisted_hosts(info_traffic_msg);
         let _ = tx.send_blocking(BackendTrafficMessage::TickRun(
             cap_id,
             info_traffic_msg.take_but_leave_something(),
-            resolutions_state.new_hosts_to_send(),
+            new_hosts,
             false,
         ));
         if diff_secs > 1 {
@@ -522,9 +800,66 @@ fn maybe_send_tick_run_offline(
     }
 }
 
+/// Rules to comment the packets exported to pcapng files,
+/// based on the notifications enabled when the capture is started
+#[derive(Clone, Default)]
+pub struct PacketCommentRules {
+    blacklist: bool,
+    favorites: Option<Favorites>,
+}
+
+impl PacketCommentRules {
+    pub fn from_conf(conf: &Conf) -> Self {
+        let notifications = &conf.settings.notifications;
+        Self {
+            blacklist: notifications.ip_blacklist_notification.is_active,
+            favorites: notifications
+                .favorite_notification
+                .is_active
+                .then(|| conf.favorites.clone()),
+        }
+    }
+
+    /// Returns the comment for a packet exchanged with the given remote address
+    /// (None if the packet didn't match any notification)
+    fn comment(
+        &self,
+        blacklist_match: Option<&BlacklistMatch>,
+        address: &IpAddr,
+        host: Option<&Host>,
+        service: Service,
+    ) -> Option<String> {
+        let mut comments = Vec::new();
+        if self.blacklist
+            && let Some(blacklist_match) = blacklist_match
+        {
+            comments.push(format!("Blacklisted IP: {address} ({blacklist_match})"));
+        }
+        if let Some(favorites) = &self.favorites {
+            if let Some(host) = host.filter(|h| favorites.contains_host(h)) {
+                comments.push(format!("Favorite host: {}", host.domain));
+            }
+            if favorites.contains_service(&service) {
+                comments.push(format!("Favorite service: {service}"));
+            }
+        }
+        (!comments.is_empty()).then(|| comments.join("; "))
+    }
+}
+
+/// State of one of the sources captured in parallel
+struct SourceState {
+    my_link_type: MyLinkType,
+    writer: Option<CaptureWriter>,
+    /// Name of the network adapter (None when importing PCAP files)
+    adapter: Option<String>,
+    dropped_packets: u32,
+}
+
 fn packet_stream(
     mut cap: CaptureType,
-    tx: &std::sync::mpsc::SyncSender<(Result<PacketOwned, pcap::Error>, Option<pcap::Stat>)>,
+    source_id: usize,
+    tx: &std::sync::mpsc::SyncSender<(usize, Result<PacketOwned, pcap::Error>, Option<pcap::Stat>)>,
     freeze_rx: &mut Receiver<()>,
     filters: &Filters,
 ) {
@@ -540,17 +875,61 @@ fn packet_stream(
         }
 
         let packet_res = cap.next_packet();
-        let packet_owned = packet_res.map(|p| PacketOwned {
-            header: *p.header,
-            data: p.data.into(),
-        });
-        if tx.send((packet_owned, cap.stats().ok())).is_err() {
+        if tx.send((source_id, packet_res, cap.stats().ok())).is_err() {
             return;
         }
     }
 }
 
-struct PacketOwned {
-    header: PacketHeader,
-    data: Box<[u8]>,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host_msg(address: IpAddr) -> HostMessage {
+        HostMessage {
+            host: Host {
+                domain: "1e100.net".to_string(),
+                ..Host::default()
+            },
+            data_info_host: DataInfoHost {
+                data_info: DataInfo::new_with_first_packet(100, TrafficDirection::Outgoing),
+                ..DataInfoHost::default()
+            },
+            address_to_lookup: address,
+            rdns: "1e100.net".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hosts_labeled_with_dns_names() {
+        let (lookup_request_tx, _lookup_request_rx) = async_channel::unbounded();
+        let (lookup_result_tx, lookup_result_rx) = std::sync::mpsc::channel();
+        let mut state = AddressesResolutionState::new(
+            lookup_request_tx,
+            lookup_result_rx,
+            SharedIpLists::default(),
+        );
+        let address = IpAddr::from([142, 250, 180, 14]);
+
+        // answer seen before the resolution
+        state.learn_dns_names(vec![(address, "www.youtube.com".to_string())], 0);
+        lookup_result_tx.send(host_msg(address)).unwrap();
+        let new_hosts = state.new_hosts_to_send();
+        assert_eq!(new_hosts.len(), 1);
+        assert_eq!(new_hosts[0].host.dns_name, "www.youtube.com");
+
+        // answer seen after the resolution: the host is sent again, without data
+        state.learn_dns_names(vec![(address, "www.youtube.com".to_string())], 1);
+        assert!(state.new_hosts_to_send().is_empty());
+        state.learn_dns_names(vec![(address, "youtube.com".to_string())], 2);
+        let new_hosts = state.new_hosts_to_send();
+        assert_eq!(new_hosts.len(), 1);
+        assert_eq!(new_hosts[0].host.dns_name, "youtube.com");
+        assert_eq!(new_hosts[0].data_info_host.data_info, DataInfo::default());
+        assert_eq!(
+            state.addresses_resolved[&address].host.dns_name,
+            "youtube.com"
+        );
+        assert!(state.new_hosts_to_send().is_empty());
+    }
 }
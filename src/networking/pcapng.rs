@@ -0,0 +1,652 @@
+//! Module to read and write captures in the pcapng format.
+//!
+//! Differently from classic PCAP files, a pcapng file can contain packets from several
+//! network interfaces (each with its own link type), and carries additional metadata
+//! such as the application that wrote it, the BPF filter in use, and comments for single packets.
+//!
+//! Reference: <https://www.ietf.org/archive/id/draft-ietf-opsawg-pcapng-02.html>
+
+use crate::utils::types::timestamp::Timestamp;
+use pcap::Linktype;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Block type of the Section Header Block, also used as magic number of pcapng files
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const OBSOLETE_PACKET_BLOCK: u32 = 0x0000_0002;
+const SIMPLE_PACKET_BLOCK: u32 = 0x0000_0003;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+const OPT_END_OF_OPT: u16 = 0;
+const OPT_COMMENT: u16 = 1;
+const SHB_OS: u16 = 3;
+const SHB_USER_APPL: u16 = 4;
+const IF_NAME: u16 = 2;
+const IF_TSRESOL: u16 = 9;
+const IF_FILTER: u16 = 11;
+
+/// Blocks larger than this are considered corrupted (as done by libpcap)
+const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Returns true if the file at the given path starts with a pcapng Section Header Block
+pub fn is_pcapng_file(path: &str) -> bool {
+    let mut magic = [0; 4];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|()| u32::from_le_bytes(magic) == SECTION_HEADER_BLOCK)
+}
+
+/// Returns true if the given output path has the pcapng extension
+pub fn is_pcapng_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pcapng"))
+}
+
+/// Writes packets from one or more interfaces to a pcapng section
+pub struct PcapngWriter<W: Write> {
+    writer: W,
+    /// Body of the Section Header Block and of the Interface Description Blocks,
+    /// written again at the beginning of each new file when rotating
+    section_header: Vec<u8>,
+    interface_descriptions: Vec<Vec<u8>>,
+    bytes_written: u64,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    /// Writes the Section Header Block, including the application name and an optional comment
+    pub fn new(mut writer: W, user_appl: &str, comment: Option<&str>) -> io::Result<Self> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        body.extend_from_slice(&1_u16.to_le_bytes()); // major version
+        body.extend_from_slice(&0_u16.to_le_bytes()); // minor version
+        body.extend_from_slice(&(-1_i64).to_le_bytes()); // section length not specified
+        let mut options = Options::default();
+        if let Some(comment) = comment {
+            options.push(OPT_COMMENT, comment.as_bytes());
+        }
+        options.push(SHB_OS, std::env::consts::OS.as_bytes());
+        options.push(SHB_USER_APPL, user_appl.as_bytes());
+        body.extend(options.finish());
+        let bytes_written = write_block(&mut writer, SECTION_HEADER_BLOCK, &body)?;
+        Ok(Self {
+            writer,
+            section_header: body,
+            interface_descriptions: Vec::new(),
+            bytes_written,
+        })
+    }
+
+    /// Continues writing to a new file, starting it with the same section and interfaces
+    pub fn restart(&mut self, mut writer: W) -> io::Result<()> {
+        let mut bytes_written =
+            write_block(&mut writer, SECTION_HEADER_BLOCK, &self.section_header)?;
+        for body in &self.interface_descriptions {
+            bytes_written += write_block(&mut writer, INTERFACE_DESCRIPTION_BLOCK, body)?;
+        }
+        self.writer.flush()?;
+        self.writer = writer;
+        self.bytes_written = bytes_written;
+        Ok(())
+    }
+
+    /// Size of the current file
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Writes an Interface Description Block, returning the identifier of the new interface
+    pub fn add_interface(
+        &mut self,
+        link_type: Linktype,
+        snaplen: u32,
+        name: Option<&str>,
+        filter: Option<&str>,
+    ) -> io::Result<u32> {
+        let mut body = Vec::new();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        body.extend_from_slice(&(link_type.0 as u16).to_le_bytes());
+        body.extend_from_slice(&0_u16.to_le_bytes()); // reserved
+        body.extend_from_slice(&snaplen.to_le_bytes());
+        let mut options = Options::default();
+        if let Some(name) = name {
+            options.push(IF_NAME, name.as_bytes());
+        }
+        if let Some(filter) = filter {
+            // the first byte specifies the filter type (0 means libpcap filter string)
+            let mut value = vec![0];
+            value.extend_from_slice(filter.as_bytes());
+            options.push(IF_FILTER, &value);
+        }
+        body.extend(options.finish());
+        self.bytes_written += write_block(&mut self.writer, INTERFACE_DESCRIPTION_BLOCK, &body)?;
+        self.interface_descriptions.push(body);
+        u32::try_from(self.interface_descriptions.len() - 1)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many interfaces"))
+    }
+
+    /// Writes an Enhanced Packet Block, with timestamp in microseconds and an optional comment
+    pub fn write_packet(
+        &mut self,
+        interface_id: u32,
+        timestamp: Timestamp,
+        orig_len: u32,
+        data: &[u8],
+        comment: Option<&str>,
+    ) -> io::Result<()> {
+        let caplen = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "packet too large"))?;
+        #[allow(clippy::cast_sign_loss)]
+        let ts = timestamp.to_usecs().unwrap_or_default().max(0) as u64;
+        let mut body = Vec::with_capacity(data.len() + 32);
+        body.extend_from_slice(&interface_id.to_le_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        body.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        body.extend_from_slice(&(ts as u32).to_le_bytes());
+        body.extend_from_slice(&caplen.to_le_bytes());
+        body.extend_from_slice(&orig_len.to_le_bytes());
+        body.extend_from_slice(data);
+        body.resize(padded_len(body.len()), 0);
+        if let Some(comment) = comment {
+            let mut options = Options::default();
+            options.push(OPT_COMMENT, comment.as_bytes());
+            body.extend(options.finish());
+        }
+        self.bytes_written += write_block(&mut self.writer, ENHANCED_PACKET_BLOCK, &body)?;
+        Ok(())
+    }
+}
+
+/// Interface described in a pcapng file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcapngInterface {
+    pub link_type: Linktype,
+    pub snaplen: u32,
+    pub name: Option<String>,
+    /// Number of timestamp units per second
+    ts_units: u64,
+}
+
+/// Packet read from a pcapng file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcapngPacket {
+    pub interface: Arc<PcapngInterface>,
+    pub timestamp: Timestamp,
+    pub orig_len: u32,
+    pub data: Vec<u8>,
+}
+
+/// Reads packets from a pcapng file, possibly made of multiple sections and interfaces
+pub struct PcapngReader<R: Read> {
+    reader: R,
+    big_endian: bool,
+    /// Interfaces of the current section
+    interfaces: Vec<Arc<PcapngInterface>>,
+    /// Block read in advance while looking for the interfaces at the beginning of the file
+    pending_block: Option<(u32, Vec<u8>)>,
+}
+
+impl<R: Read> PcapngReader<R> {
+    /// Reads the first Section Header Block and the Interface Description Blocks following it
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut block_type = [0; 4];
+        reader.read_exact(&mut block_type)?;
+        if u32::from_le_bytes(block_type) != SECTION_HEADER_BLOCK {
+            return Err(invalid_data("not a pcapng file"));
+        }
+        let mut pcapng_reader = Self {
+            reader,
+            big_endian: false,
+            interfaces: Vec::new(),
+            pending_block: None,
+        };
+        pcapng_reader.read_section_header()?;
+        // interfaces are usually described right after the section header
+        while let Some((block_type, body)) = pcapng_reader.read_block()? {
+            if block_type == INTERFACE_DESCRIPTION_BLOCK {
+                pcapng_reader.parse_interface(&body)?;
+            } else {
+                pcapng_reader.pending_block = Some((block_type, body));
+                break;
+            }
+        }
+        Ok(pcapng_reader)
+    }
+
+    /// Interfaces of the current section found so far
+    pub fn interfaces(&self) -> &[Arc<PcapngInterface>] {
+        &self.interfaces
+    }
+
+    /// Returns the next packet, or None when the file is over
+    pub fn next_packet(&mut self) -> io::Result<Option<PcapngPacket>> {
+        loop {
+            let block = match self.pending_block.take() {
+                Some(block) => Some(block),
+                None => self.read_block()?,
+            };
+            let Some((block_type, body)) = block else {
+                return Ok(None);
+            };
+            match block_type {
+                INTERFACE_DESCRIPTION_BLOCK => self.parse_interface(&body)?,
+                ENHANCED_PACKET_BLOCK => return self.parse_enhanced_packet(&body).map(Some),
+                SIMPLE_PACKET_BLOCK => return self.parse_simple_packet(&body).map(Some),
+                OBSOLETE_PACKET_BLOCK => return self.parse_obsolete_packet(&body).map(Some),
+                // other blocks (statistics, name resolution, ...) are ignored
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the rest of a Section Header Block, whose type has already been read
+    fn read_section_header(&mut self) -> io::Result<()> {
+        let mut header = [0; 8];
+        self.reader.read_exact(&mut header)?;
+        let magic = [header[4], header[5], header[6], header[7]];
+        self.big_endian = match u32::from_le_bytes(magic) {
+            BYTE_ORDER_MAGIC => false,
+            m if m.swap_bytes() == BYTE_ORDER_MAGIC => true,
+            _ => return Err(invalid_data("invalid byte-order magic")),
+        };
+        let total_len = self.u32([header[0], header[1], header[2], header[3]]) as usize;
+        // a new section has its own interfaces
+        self.interfaces.clear();
+        self.skip_block_rest(total_len, 12)
+    }
+
+    /// Reads the next block, returning its type and body (None when the file is over)
+    fn read_block(&mut self) -> io::Result<Option<(u32, Vec<u8>)>> {
+        let mut block_type = [0; 4];
+        match self.reader.read_exact(&mut block_type) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        if u32::from_le_bytes(block_type) == SECTION_HEADER_BLOCK {
+            self.read_section_header()?;
+            return self.read_block();
+        }
+        let block_type = self.u32(block_type);
+        let mut total_len = [0; 4];
+        self.reader.read_exact(&mut total_len)?;
+        let total_len = self.u32(total_len) as usize;
+        if !(12..=MAX_BLOCK_SIZE).contains(&total_len) || !total_len.is_multiple_of(4) {
+            return Err(invalid_data("invalid block length"));
+        }
+        let mut body = vec![0; total_len - 8];
+        self.reader.read_exact(&mut body)?;
+        // remove the trailing copy of the block length
+        body.truncate(total_len - 12);
+        Ok(Some((block_type, body)))
+    }
+
+    fn skip_block_rest(&mut self, total_len: usize, already_read: usize) -> io::Result<()> {
+        if total_len < already_read + 4 || total_len > MAX_BLOCK_SIZE {
+            return Err(invalid_data("invalid block length"));
+        }
+        let mut rest = vec![0; total_len - already_read];
+        self.reader.read_exact(&mut rest)
+    }
+
+    fn parse_interface(&mut self, body: &[u8]) -> io::Result<()> {
+        if body.len() < 8 {
+            return Err(invalid_data("interface description block too short"));
+        }
+        let link_type = self.u16([body[0], body[1]]);
+        let snaplen = self.u32([body[4], body[5], body[6], body[7]]);
+        let mut interface = PcapngInterface {
+            link_type: Linktype(i32::from(link_type)),
+            snaplen,
+            name: None,
+            ts_units: 1_000_000,
+        };
+        for (code, value) in self.options(&body[8..]) {
+            match code {
+                IF_NAME => interface.name = Some(String::from_utf8_lossy(value).to_string()),
+                IF_TSRESOL => {
+                    if let Some(&resol) = value.first() {
+                        interface.ts_units = ts_units_from_resol(resol);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.interfaces.push(Arc::new(interface));
+        Ok(())
+    }
+
+    fn parse_enhanced_packet(&self, body: &[u8]) -> io::Result<PcapngPacket> {
+        if body.len() < 20 {
+            return Err(invalid_data("enhanced packet block too short"));
+        }
+        let interface_id = self.u32([body[0], body[1], body[2], body[3]]);
+        let ts_high = self.u32([body[4], body[5], body[6], body[7]]);
+        let ts_low = self.u32([body[8], body[9], body[10], body[11]]);
+        let caplen = self.u32([body[12], body[13], body[14], body[15]]) as usize;
+        let orig_len = self.u32([body[16], body[17], body[18], body[19]]);
+        let data = body
+            .get(20..20 + caplen)
+            .ok_or_else(|| invalid_data("invalid captured length"))?;
+        let ts = (u64::from(ts_high) << 32) | u64::from(ts_low);
+        let interface = self.interface(interface_id)?;
+        Ok(PcapngPacket {
+            timestamp: timestamp(ts, interface.ts_units),
+            interface,
+            orig_len,
+            data: data.to_vec(),
+        })
+    }
+
+    fn parse_obsolete_packet(&self, body: &[u8]) -> io::Result<PcapngPacket> {
+        if body.len() < 20 {
+            return Err(invalid_data("packet block too short"));
+        }
+        let interface_id = u32::from(self.u16([body[0], body[1]]));
+        let ts_high = self.u32([body[4], body[5], body[6], body[7]]);
+        let ts_low = self.u32([body[8], body[9], body[10], body[11]]);
+        let caplen = self.u32([body[12], body[13], body[14], body[15]]) as usize;
+        let orig_len = self.u32([body[16], body[17], body[18], body[19]]);
+        let data = body
+            .get(20..20 + caplen)
+            .ok_or_else(|| invalid_data("invalid captured length"))?;
+        let ts = (u64::from(ts_high) << 32) | u64::from(ts_low);
+        let interface = self.interface(interface_id)?;
+        Ok(PcapngPacket {
+            timestamp: timestamp(ts, interface.ts_units),
+            interface,
+            orig_len,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Simple packets always belong to the first interface and have no timestamp
+    fn parse_simple_packet(&self, body: &[u8]) -> io::Result<PcapngPacket> {
+        if body.len() < 4 {
+            return Err(invalid_data("simple packet block too short"));
+        }
+        let interface = self.interface(0)?;
+        let snaplen = interface.snaplen;
+        let orig_len = self.u32([body[0], body[1], body[2], body[3]]);
+        let mut caplen = orig_len.min(u32::try_from(body.len() - 4).unwrap_or(u32::MAX));
+        if snaplen > 0 {
+            caplen = caplen.min(snaplen);
+        }
+        Ok(PcapngPacket {
+            interface,
+            timestamp: Timestamp::default(),
+            orig_len,
+            data: body[4..4 + caplen as usize].to_vec(),
+        })
+    }
+
+    fn interface(&self, interface_id: u32) -> io::Result<Arc<PcapngInterface>> {
+        self.interfaces
+            .get(interface_id as usize)
+            .cloned()
+            .ok_or_else(|| invalid_data("packet of an unknown interface"))
+    }
+
+    /// Iterates over the options contained in the given slice
+    fn options<'a>(&self, mut bytes: &'a [u8]) -> impl Iterator<Item = (u16, &'a [u8])> {
+        let big_endian = self.big_endian;
+        std::iter::from_fn(move || {
+            if bytes.len() < 4 {
+                return None;
+            }
+            let read_u16 = if big_endian {
+                u16::from_be_bytes
+            } else {
+                u16::from_le_bytes
+            };
+            let code = read_u16([bytes[0], bytes[1]]);
+            let len = read_u16([bytes[2], bytes[3]]) as usize;
+            if code == OPT_END_OF_OPT {
+                return None;
+            }
+            let value = bytes.get(4..4 + len)?;
+            bytes = bytes.get(4 + padded_len(len)..).unwrap_or_default();
+            Some((code, value))
+        })
+    }
+
+    fn u16(&self, bytes: [u8; 2]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: [u8; 4]) -> u32 {
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+/// Options of a block, each one padded to 32 bits
+#[derive(Default)]
+struct Options {
+    bytes: Vec<u8>,
+}
+
+impl Options {
+    fn push(&mut self, code: u16, value: &[u8]) {
+        // option values are limited to 65535 bytes
+        let value = &value[..value.len().min(usize::from(u16::MAX))];
+        self.bytes.extend_from_slice(&code.to_le_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        self.bytes
+            .extend_from_slice(&(value.len() as u16).to_le_bytes());
+        self.bytes.extend_from_slice(value);
+        self.bytes.resize(padded_len(self.bytes.len()), 0);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if !self.bytes.is_empty() {
+            self.bytes.extend_from_slice(&OPT_END_OF_OPT.to_le_bytes());
+            self.bytes.extend_from_slice(&0_u16.to_le_bytes());
+        }
+        self.bytes
+    }
+}
+
+/// Writes a block, returning its total length
+fn write_block(writer: &mut impl Write, block_type: u32, body: &[u8]) -> io::Result<u64> {
+    let total_len = u32::try_from(body.len() + 12)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "block too large"))?;
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&total_len.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.write_all(&total_len.to_le_bytes())?;
+    Ok(u64::from(total_len))
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(4) * 4
+}
+
+/// Converts the value of the `if_tsresol` option to the number of timestamp units per second
+fn ts_units_from_resol(resol: u8) -> u64 {
+    let exponent = u32::from(resol & 0x7F);
+    let units = if resol & 0x80 == 0 {
+        10_u64.checked_pow(exponent)
+    } else {
+        2_u64.checked_pow(exponent)
+    };
+    units.unwrap_or(1_000_000)
+}
+
+fn timestamp(ts: u64, ts_units: u64) -> Timestamp {
+    let secs = ts / ts_units;
+    let usecs = u128::from(ts % ts_units) * 1_000_000 / u128::from(ts_units);
+    Timestamp::new(
+        i64::try_from(secs).unwrap_or(i64::MAX),
+        i64::try_from(usecs).unwrap_or_default(),
+    )
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> (Vec<Arc<PcapngInterface>>, Vec<PcapngPacket>) {
+        let mut reader = PcapngReader::new(bytes).unwrap();
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            packets.push(packet);
+        }
+        (reader.interfaces().to_vec(), packets)
+    }
+
+    #[test]
+    fn test_pcapng_round_trip_multiple_interfaces() {
+        let mut writer = PcapngWriter::new(Vec::new(), "Sniffnet", Some("tcp")).unwrap();
+        let eth = writer
+            .add_interface(Linktype::ETHERNET, 65535, Some("eth0"), Some("tcp"))
+            .unwrap();
+        let raw = writer
+            .add_interface(Linktype(12), 65535, Some("tun0"), None)
+            .unwrap();
+        assert_eq!((eth, raw), (0, 1));
+        writer
+            .write_packet(
+                eth,
+                Timestamp::new(1_700_000_000, 123_456),
+                60,
+                &[1; 5],
+                None,
+            )
+            .unwrap();
+        writer
+            .write_packet(
+                raw,
+                Timestamp::new(1_700_000_001, 7),
+                3,
+                &[2; 3],
+                Some("bad"),
+            )
+            .unwrap();
+        let bytes = writer.writer;
+        assert_eq!(bytes.len() % 4, 0);
+
+        let (interfaces, packets) = read_all(&bytes);
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].link_type, Linktype::ETHERNET);
+        assert_eq!(interfaces[0].name.as_deref(), Some("eth0"));
+        assert_eq!(interfaces[1].link_type, Linktype(12));
+        assert_eq!(interfaces[1].name.as_deref(), Some("tun0"));
+        assert_eq!(
+            packets,
+            vec![
+                PcapngPacket {
+                    interface: interfaces[0].clone(),
+                    timestamp: Timestamp::new(1_700_000_000, 123_456),
+                    orig_len: 60,
+                    data: vec![1; 5],
+                },
+                PcapngPacket {
+                    interface: interfaces[1].clone(),
+                    timestamp: Timestamp::new(1_700_000_001, 7),
+                    orig_len: 3,
+                    data: vec![2; 3],
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pcapng_writer_restart() {
+        let mut writer = PcapngWriter::new(Vec::new(), "Sniffnet", None).unwrap();
+        let id = writer
+            .add_interface(Linktype::ETHERNET, 65535, Some("eth0"), None)
+            .unwrap();
+        writer
+            .write_packet(id, Timestamp::new(1, 0), 4, &[1; 4], None)
+            .unwrap();
+        assert_eq!(writer.bytes_written(), writer.writer.len() as u64);
+
+        writer.restart(Vec::new()).unwrap();
+        assert_eq!(writer.bytes_written(), writer.writer.len() as u64);
+        writer
+            .write_packet(id, Timestamp::new(2, 0), 4, &[2; 4], None)
+            .unwrap();
+
+        let (interfaces, packets) = read_all(&writer.writer);
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(interfaces[0].name.as_deref(), Some("eth0"));
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].data, vec![2; 4]);
+    }
+
+    #[test]
+    fn test_pcapng_big_endian_and_ts_resolution() {
+        let mut bytes = Vec::new();
+        // section header block
+        bytes.extend_from_slice(&SECTION_HEADER_BLOCK.to_be_bytes());
+        bytes.extend_from_slice(&28_u32.to_be_bytes());
+        bytes.extend_from_slice(&BYTE_ORDER_MAGIC.to_be_bytes());
+        bytes.extend_from_slice(&[0, 1, 0, 0]);
+        bytes.extend_from_slice(&(-1_i64).to_be_bytes());
+        bytes.extend_from_slice(&28_u32.to_be_bytes());
+        // interface description block with nanosecond resolution
+        bytes.extend_from_slice(&INTERFACE_DESCRIPTION_BLOCK.to_be_bytes());
+        bytes.extend_from_slice(&32_u32.to_be_bytes());
+        bytes.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0xFF, 0xFF]);
+        bytes.extend_from_slice(&[0, 9, 0, 1, 9, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&32_u32.to_be_bytes());
+        // enhanced packet block
+        let ts: u64 = 2_500_000_000;
+        bytes.extend_from_slice(&ENHANCED_PACKET_BLOCK.to_be_bytes());
+        bytes.extend_from_slice(&36_u32.to_be_bytes());
+        bytes.extend_from_slice(&0_u32.to_be_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.extend_from_slice(&((ts >> 32) as u32).to_be_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.extend_from_slice(&(ts as u32).to_be_bytes());
+        bytes.extend_from_slice(&2_u32.to_be_bytes());
+        bytes.extend_from_slice(&2_u32.to_be_bytes());
+        bytes.extend_from_slice(&[0xAB, 0xCD, 0, 0]);
+        bytes.extend_from_slice(&36_u32.to_be_bytes());
+
+        let (interfaces, packets) = read_all(&bytes);
+        assert_eq!(interfaces[0].link_type, Linktype::ETHERNET);
+        assert_eq!(interfaces[0].snaplen, 65535);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].timestamp, Timestamp::new(2, 500_000));
+        assert_eq!(packets[0].data, vec![0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn test_pcapng_invalid_files() {
+        assert!(PcapngReader::new(&[0xD4, 0xC3, 0xB2, 0xA1, 0, 0, 0, 0][..]).is_err());
+        assert!(PcapngReader::new(&[][..]).is_err());
+    }
+
+    #[test]
+    fn test_is_pcapng_path() {
+        assert!(is_pcapng_path("/tmp/sniffnet.pcapng"));
+        assert!(is_pcapng_path("capture.PCAPNG"));
+        assert!(!is_pcapng_path("sniffnet.pcap"));
+        assert!(!is_pcapng_path("pcapng"));
+    }
+
+    #[test]
+    fn test_ts_units_from_resol() {
+        assert_eq!(ts_units_from_resol(6), 1_000_000);
+        assert_eq!(ts_units_from_resol(9), 1_000_000_000);
+        assert_eq!(ts_units_from_resol(0x80 | 10), 1024);
+    }
+}
@@ -0,0 +1,310 @@
+//! Module to detect reconnaissance activity: remote hosts probing many local ports (port scans)
+//! or many local hosts (host sweeps) within a sliding window.
+//!
+//! Probes are TCP connection attempts (SYN) and ICMP echo requests coming from a remote address.
+//! Probes answered with a reset, or whose handshake is never completed, are counted as well,
+//! since they're typical of stealth scans.
+
+use crate::Protocol;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::icmp_type::{IcmpType, IcmpTypeV4, IcmpTypeV6};
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::info_traffic::InfoTraffic;
+use crate::networking::types::scan::{Scan, ScanKind};
+use crate::networking::types::tcp_state::TcpFlags;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::utils::types::bounded_map::BoundedMap;
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+
+/// Probes older than this are forgotten
+const WINDOW_SECS: i64 = 60;
+/// Maximum number of remote addresses whose probes are remembered
+/// (the oldest ones are forgotten to make room for new ones, e.g., during spoofed-source floods)
+const MAX_SOURCES: usize = 16_384;
+/// Minimum number of ports or hosts to be probed for a scan to be reported
+/// (thresholds read from the configuration file are never lower)
+const MIN_THRESHOLD: u16 = 2;
+
+/// Local host and port (if any) probed by a remote address
+type Target = (IpAddr, Option<u16>);
+
+/// Probe sent to a local target
+#[derive(Default)]
+struct Probe {
+    last_seen_secs: i64,
+    /// Flags of the TCP segments sent by the remote address
+    flags: TcpFlags,
+    /// Whether the probe was answered with a reset
+    reset: bool,
+}
+
+/// Keeps track of the probes sent by each remote address to detect scans
+pub struct ScanDetector {
+    probes: BoundedMap<IpAddr, HashMap<Target, Probe>>,
+}
+
+impl Default for ScanDetector {
+    fn default() -> Self {
+        Self {
+            probes: BoundedMap::new(MAX_SOURCES, WINDOW_SECS * 1_000_000),
+        }
+    }
+}
+
+impl ScanDetector {
+    /// Analyzes the traffic of the last interval, returning the scans detected.
+    ///
+    /// A remote address is reported when it probed at least `ports_threshold` distinct local ports
+    /// or `hosts_threshold` distinct local hosts within the sliding window;
+    /// its probes are then forgotten so that it's reported again only if the activity continues.
+    pub fn update(
+        &mut self,
+        msg: &InfoTraffic,
+        ports_threshold: u16,
+        hosts_threshold: u16,
+    ) -> Vec<Scan> {
+        let now_secs = msg.last_packet_timestamp.secs();
+        let ports_threshold = usize::from(ports_threshold.max(MIN_THRESHOLD));
+        let hosts_threshold = usize::from(hosts_threshold.max(MIN_THRESHOLD));
+
+        // probes first, so that resets answering probes of the same interval are recognized
+        for (key, val) in &msg.map {
+            if val.traffic_direction == TrafficDirection::Incoming {
+                self.add_probe(key, val, now_secs);
+            }
+        }
+        for (key, val) in &msg.map {
+            if val.traffic_direction == TrafficDirection::Outgoing {
+                self.add_reset(key, val, now_secs);
+            }
+        }
+
+        self.probes.retain(|_, targets| {
+            targets.retain(|_, probe| now_secs - probe.last_seen_secs < WINDOW_SECS);
+            !targets.is_empty()
+        });
+
+        let mut scans = Vec::new();
+        self.probes.retain(|source, targets| {
+            let ports: BTreeSet<u16> = targets.keys().filter_map(|(_, port)| *port).collect();
+            let hosts: BTreeSet<IpAddr> = targets.keys().map(|(host, _)| *host).collect();
+            let kind = if ports.len() >= ports_threshold {
+                ScanKind::PortScan
+            } else if hosts.len() >= hosts_threshold {
+                ScanKind::HostSweep
+            } else {
+                return true;
+            };
+            scans.push(Scan {
+                source: *source,
+                kind,
+                ports: ports.into_iter().collect(),
+                hosts: hosts.into_iter().collect(),
+                syn_only: targets
+                    .values()
+                    .filter(|p| p.flags.syn > 0 && p.flags.ack == 0)
+                    .count(),
+                resets: targets.values().filter(|p| p.reset).count(),
+            });
+            false
+        });
+        scans
+    }
+
+    fn add_probe(&mut self, key: &AddressPortPair, val: &InfoAddressPortPair, now_secs: i64) {
+        let target = (key.dest, key.dport);
+        let is_probe = match key.protocol {
+            Protocol::TCP => val.tcp_flags.syn > 0,
+            Protocol::ICMP => val.icmp_types.keys().any(|icmp_type| {
+                matches!(
+                    icmp_type,
+                    IcmpType::V4(IcmpTypeV4::Echo) | IcmpType::V6(IcmpTypeV6::EchoRequest)
+                )
+            }),
+            _ => false,
+        };
+        // further segments of a known probe are considered too (e.g., the completion of the handshake)
+        let is_known = self
+            .probes
+            .get(&key.source)
+            .is_some_and(|targets| targets.contains_key(&target));
+        if !is_probe && !is_known {
+            return;
+        }
+        let probe = self
+            .probes
+            .get_or_insert_with(key.source, now_secs.saturating_mul(1_000_000), HashMap::new)
+            .entry(target)
+            .or_default();
+        probe.last_seen_secs = now_secs;
+        probe.flags.add(val.tcp_flags);
+    }
+
+    fn add_reset(&mut self, key: &AddressPortPair, val: &InfoAddressPortPair, now_secs: i64) {
+        if key.protocol != Protocol::TCP || val.tcp_flags.rst == 0 {
+            return;
+        }
+        if let Some(probe) = self
+            .probes
+            .get_mut(&key.dest, now_secs.saturating_mul(1_000_000))
+            .and_then(|targets| targets.get_mut(&(key.source, key.sport)))
+        {
+            probe.reset = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::types::timestamp::Timestamp;
+
+    const SCANNER: [u8; 4] = [203, 0, 113, 7];
+
+    /// Traffic of an interval in which the scanner sent a SYN to each of the given targets,
+    /// which answered with a reset
+    fn syn_probes(targets: &[([u8; 4], u16)], secs: i64) -> InfoTraffic {
+        let mut msg = InfoTraffic {
+            last_packet_timestamp: Timestamp::new(secs, 0),
+            ..InfoTraffic::default()
+        };
+        for (host, port) in targets {
+            let key = AddressPortPair::new(
+                IpAddr::from(SCANNER),
+                Some(40_000),
+                IpAddr::from(*host),
+                Some(*port),
+                Protocol::TCP,
+            );
+            let syn = InfoAddressPortPair {
+                traffic_direction: TrafficDirection::Incoming,
+                tcp_flags: TcpFlags {
+                    syn: 1,
+                    ..TcpFlags::default()
+                },
+                ..InfoAddressPortPair::default()
+            };
+            let rst = InfoAddressPortPair {
+                traffic_direction: TrafficDirection::Outgoing,
+                tcp_flags: TcpFlags {
+                    rst: 1,
+                    ..TcpFlags::default()
+                },
+                ..InfoAddressPortPair::default()
+            };
+            msg.map.insert(key.reversed(), rst);
+            msg.map.insert(key, syn);
+        }
+        msg
+    }
+
+    #[test]
+    fn test_detect_port_scan() {
+        let mut detector = ScanDetector::default();
+        let first_half: Vec<([u8; 4], u16)> = (1..=10).map(|p| ([192, 168, 1, 2], p)).collect();
+        let second_half: Vec<([u8; 4], u16)> = (11..=20).map(|p| ([192, 168, 1, 2], p)).collect();
+
+        assert!(
+            detector
+                .update(&syn_probes(&first_half, 0), 20, 10)
+                .is_empty()
+        );
+        let scans = detector.update(&syn_probes(&second_half, 30), 20, 10);
+        assert_eq!(
+            scans,
+            vec![Scan {
+                source: IpAddr::from(SCANNER),
+                kind: ScanKind::PortScan,
+                ports: (1..=20).collect(),
+                hosts: vec![IpAddr::from([192, 168, 1, 2])],
+                syn_only: 20,
+                resets: 20,
+            }]
+        );
+        // the probes of reported scans are forgotten
+        assert!(
+            detector
+                .update(&syn_probes(&first_half, 31), 20, 10)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_spoofed_sources_are_bounded() {
+        let mut detector = ScanDetector::default();
+        let first_half: Vec<([u8; 4], u16)> = (1..=10).map(|p| ([192, 168, 1, 2], p)).collect();
+        let second_half: Vec<([u8; 4], u16)> = (11..=20).map(|p| ([192, 168, 1, 2], p)).collect();
+        assert!(
+            detector
+                .update(&syn_probes(&first_half, 0), 20, 10)
+                .is_empty()
+        );
+
+        // a single SYN from each of many spoofed sources
+        let mut flood = syn_probes(&[], 1);
+        let max = u32::try_from(MAX_SOURCES).unwrap();
+        for i in 0..max {
+            let key = AddressPortPair::new(
+                IpAddr::from((0x0a00_0000 + i).to_be_bytes()),
+                Some(40_000),
+                IpAddr::from([192, 168, 1, 2]),
+                Some(80),
+                Protocol::TCP,
+            );
+            let syn = InfoAddressPortPair {
+                traffic_direction: TrafficDirection::Incoming,
+                tcp_flags: TcpFlags {
+                    syn: 1,
+                    ..TcpFlags::default()
+                },
+                ..InfoAddressPortPair::default()
+            };
+            flood.map.insert(key, syn);
+        }
+        assert!(detector.update(&flood, 20, 10).is_empty());
+
+        // the oldest source made room for the spoofed ones
+        assert!(!detector.probes.contains_key(&IpAddr::from(SCANNER)));
+        assert!(
+            detector
+                .update(&syn_probes(&second_half, 2), 20, 10)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_single_probe_isnt_a_scan_with_invalid_thresholds() {
+        let mut detector = ScanDetector::default();
+        let probe = [([192, 168, 1, 2], 22)];
+
+        assert!(detector.update(&syn_probes(&probe, 0), 0, 0).is_empty());
+        assert!(detector.update(&syn_probes(&probe, 1), 1, 1).is_empty());
+        let scans = detector.update(&syn_probes(&[([192, 168, 1, 3], 80)], 2), 0, 0);
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].kind, ScanKind::PortScan);
+    }
+
+    #[test]
+    fn test_detect_host_sweep_within_window() {
+        let mut detector = ScanDetector::default();
+        let hosts: Vec<([u8; 4], u16)> = (1..=10).map(|h| ([192, 168, 1, h], 22)).collect();
+
+        // probes falling out of the window aren't considered
+        assert!(
+            detector
+                .update(&syn_probes(&hosts[..5], 0), 20, 10)
+                .is_empty()
+        );
+        assert!(
+            detector
+                .update(&syn_probes(&hosts[5..], 90), 20, 10)
+                .is_empty()
+        );
+        let scans = detector.update(&syn_probes(&hosts[..5], 100), 20, 10);
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].kind, ScanKind::HostSweep);
+        assert_eq!(scans[0].ports, vec![22]);
+        assert_eq!(scans[0].hosts.len(), 10);
+    }
+}
@@ -0,0 +1,809 @@
+//! Module to extract the server names (SNI) from the TLS and QUIC handshakes in the sniffed traffic.
+//!
+//! The Server Name Indication is sent in clear by the client in the TLS `ClientHello`.
+//! Over TCP, the `ClientHello` may span multiple segments, which are reassembled by sequence number until it's complete.
+//! Over QUIC, it's carried in the CRYPTO frames of Initial packets, which are protected
+//! with keys derived from the Destination Connection ID (and therefore decryptable by any observer).
+//!
+//! References:
+//! - <https://www.rfc-editor.org/rfc/rfc8446> (TLS 1.3)
+//! - <https://www.rfc-editor.org/rfc/rfc9001> (Using TLS to Secure QUIC)
+//! - <https://www.rfc-editor.org/rfc/rfc9369> (QUIC Version 2)
+
+use crate::Protocol;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::utils::types::bounded_map::BoundedMap;
+use aws_lc_rs::aead::quic::{AES_128, HeaderProtectionKey};
+use aws_lc_rs::aead::{AES_128_GCM, Aad, LessSafeKey, Nonce, UnboundKey};
+use aws_lc_rs::hkdf;
+use etherparse::{LaxPacketHeaders, LaxPayloadSlice, TransportHeader};
+use std::collections::BTreeMap;
+
+const TLS_HANDSHAKE_RECORD: u8 = 0x16;
+const TLS_CLIENT_HELLO: u8 = 0x01;
+const TLS_EXT_SERVER_NAME: u16 = 0x0000;
+const TLS_SERVER_NAME_HOST: u8 = 0x00;
+
+const QUIC_V1: u32 = 0x0000_0001;
+const QUIC_V2: u32 = 0x6b33_43cf;
+const QUIC_V1_INITIAL_SALT: [u8; 20] = [
+    0x38, 0x76, 0x2c, 0xf7, 0xf5, 0x59, 0x34, 0xb3, 0x4d, 0x17, 0x9a, 0xe6, 0xa4, 0xc8, 0x0c, 0xad,
+    0xcc, 0xbb, 0x7f, 0x0a,
+];
+const QUIC_V2_INITIAL_SALT: [u8; 20] = [
+    0x0d, 0xed, 0xe3, 0xde, 0xf7, 0x00, 0xa6, 0xdb, 0x81, 0x93, 0x81, 0xbe, 0x6e, 0x26, 0x9d, 0xcb,
+    0xf9, 0xbd, 0x2e, 0xd9,
+];
+const QUIC_MAX_CID_LEN: usize = 20;
+const QUIC_FRAME_PADDING: u64 = 0x00;
+const QUIC_FRAME_PING: u64 = 0x01;
+const QUIC_FRAME_ACK: u64 = 0x02;
+const QUIC_FRAME_ACK_ECN: u64 = 0x03;
+const QUIC_FRAME_CRYPTO: u64 = 0x06;
+
+/// Handshakes larger than this are not collected any further
+const MAX_HANDSHAKE_LEN: usize = 16 * 1024;
+/// Maximum number of incomplete handshakes waiting for further packets (the oldest ones are dropped)
+const MAX_PENDING_HANDSHAKES: usize = 1024;
+/// Incomplete handshakes without further packets for longer than this are dropped
+const PENDING_TIMEOUT_USECS: i64 = 10_000_000;
+
+/// Extracts the server names from the handshakes of the sniffed connections
+pub struct ServerNameExtractor {
+    /// Handshakes whose `ClientHello` has only been partially received so far
+    pending: BoundedMap<AddressPortPair, PendingHandshake>,
+}
+
+impl Default for ServerNameExtractor {
+    fn default() -> Self {
+        Self {
+            pending: BoundedMap::new(MAX_PENDING_HANDSHAKES, PENDING_TIMEOUT_USECS),
+        }
+    }
+}
+
+enum PendingHandshake {
+    /// TLS records received over TCP, by offset from the first segment
+    Tls {
+        initial_seq: u32,
+        segments: BTreeMap<usize, Vec<u8>>,
+    },
+    /// Data of the CRYPTO frames received in QUIC Initial packets, by offset
+    Quic(BTreeMap<usize, Vec<u8>>),
+}
+
+/// Payload carried by the transport layer of a packet
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransportPayload<'a> {
+    data: &'a [u8],
+    /// Sequence number of the first byte (TCP only)
+    tcp_seq: Option<u32>,
+}
+
+impl<'a> TransportPayload<'a> {
+    /// Returns the payload carried by the transport layer of a packet (empty if not TCP or UDP)
+    pub fn from_headers(headers: &LaxPacketHeaders<'a>) -> Self {
+        let data = match headers.payload {
+            LaxPayloadSlice::Tcp { payload, .. } | LaxPayloadSlice::Udp { payload, .. } => payload,
+            _ => &[],
+        };
+        let tcp_seq = match &headers.transport {
+            Some(TransportHeader::Tcp(tcp)) => Some(tcp.sequence_number),
+            _ => None,
+        };
+        Self { data, tcp_seq }
+    }
+}
+
+/// Outcome of parsing a (possibly partial) `ClientHello`
+#[derive(Debug, PartialEq)]
+enum ClientHello {
+    ServerName(String),
+    /// Complete message without server name, or not a `ClientHello` at all
+    NoServerName,
+    Incomplete,
+}
+
+impl ServerNameExtractor {
+    /// Returns the server name indicated by the client, if the given packet completes a `ClientHello`
+    pub fn server_name(
+        &mut self,
+        key: &AddressPortPair,
+        payload: TransportPayload,
+        timestamp_usecs: i64,
+    ) -> Option<String> {
+        if payload.data.is_empty() {
+            return None;
+        }
+        match key.protocol {
+            Protocol::TCP => self.tls_server_name(key, payload, timestamp_usecs),
+            Protocol::UDP => self.quic_server_name(key, payload.data, timestamp_usecs),
+            _ => None,
+        }
+    }
+
+    fn tls_server_name(
+        &mut self,
+        key: &AddressPortPair,
+        payload: TransportPayload,
+        timestamp_usecs: i64,
+    ) -> Option<String> {
+        let seq = payload.tcp_seq?;
+        let (records, collected_len, new_handshake) =
+            match self.pending.get_mut(key, timestamp_usecs) {
+                Some(PendingHandshake::Tls {
+                    initial_seq,
+                    segments,
+                }) => {
+                    // segments preceding the first one aren't part of the handshake
+                    let offset =
+                        usize::try_from(seq.wrapping_sub(*initial_seq)).unwrap_or(usize::MAX);
+                    if offset >= MAX_HANDSHAKE_LEN {
+                        return None;
+                    }
+                    // retransmitted segments replace the data already received at the same offset
+                    segments.insert(offset, payload.data.to_vec());
+                    (contiguous_stream(segments), collected_len(segments), None)
+                }
+                None if payload.data[0] == TLS_HANDSHAKE_RECORD => {
+                    let handshake = PendingHandshake::Tls {
+                        initial_seq: seq,
+                        segments: BTreeMap::from([(0, payload.data.to_vec())]),
+                    };
+                    (payload.data.to_vec(), payload.data.len(), Some(handshake))
+                }
+                Some(PendingHandshake::Quic(_)) | None => return None,
+            };
+        let client_hello = parse_client_hello(&tls_handshake(&records));
+        self.update_pending(
+            key,
+            client_hello,
+            collected_len,
+            new_handshake,
+            timestamp_usecs,
+        )
+    }
+
+    fn quic_server_name(
+        &mut self,
+        key: &AddressPortPair,
+        datagram: &[u8],
+        timestamp_usecs: i64,
+    ) -> Option<String> {
+        let frames = quic_initial_crypto_frames(datagram)?;
+        let (stream, collected_len, new_handshake) =
+            match self.pending.get_mut(key, timestamp_usecs) {
+                Some(PendingHandshake::Quic(fragments)) => {
+                    fragments.extend(frames);
+                    (contiguous_stream(fragments), collected_len(fragments), None)
+                }
+                Some(PendingHandshake::Tls { .. }) => return None,
+                None => {
+                    let fragments = BTreeMap::from_iter(frames);
+                    let stream = contiguous_stream(&fragments);
+                    let len = collected_len(&fragments);
+                    (stream, len, Some(PendingHandshake::Quic(fragments)))
+                }
+            };
+        let client_hello = parse_client_hello(&stream);
+        self.update_pending(
+            key,
+            client_hello,
+            collected_len,
+            new_handshake,
+            timestamp_usecs,
+        )
+    }
+
+    /// Keeps waiting for the rest of an incomplete handshake (storing it, if new),
+    /// or forgets it once the `ClientHello` is complete or too much data was collected
+    fn update_pending(
+        &mut self,
+        key: &AddressPortPair,
+        client_hello: ClientHello,
+        collected_len: usize,
+        new_handshake: Option<PendingHandshake>,
+        timestamp_usecs: i64,
+    ) -> Option<String> {
+        match client_hello {
+            ClientHello::Incomplete if collected_len < MAX_HANDSHAKE_LEN => {
+                if let Some(handshake) = new_handshake {
+                    self.pending.insert(*key, handshake, timestamp_usecs);
+                }
+                None
+            }
+            ClientHello::ServerName(name) => {
+                self.pending.remove(key);
+                Some(name)
+            }
+            ClientHello::NoServerName | ClientHello::Incomplete => {
+                self.pending.remove(key);
+                None
+            }
+        }
+    }
+}
+
+/// Amount of data collected for a handshake, whose parts are stored by offset
+fn collected_len(parts: &BTreeMap<usize, Vec<u8>>) -> usize {
+    parts.values().map(Vec::len).sum()
+}
+
+/// Concatenates the bodies of consecutive TLS handshake records
+fn tls_handshake(records: &[u8]) -> Vec<u8> {
+    let mut handshake = Vec::new();
+    let mut offset = 0;
+    while records.get(offset) == Some(&TLS_HANDSHAKE_RECORD) {
+        let Some(len) = read_u16(records, offset + 3) else {
+            break;
+        };
+        let start = offset + 5;
+        let end = (start + usize::from(len)).min(records.len());
+        handshake.extend_from_slice(&records[start..end]);
+        offset = start + usize::from(len);
+    }
+    handshake
+}
+
+/// Parses the (possibly partial) `ClientHello` at the beginning of a TLS handshake
+fn parse_client_hello(handshake: &[u8]) -> ClientHello {
+    match handshake.first() {
+        None => return ClientHello::Incomplete,
+        Some(&TLS_CLIENT_HELLO) => {}
+        Some(_) => return ClientHello::NoServerName,
+    }
+    let msg_len = read_u24(handshake, 1).map(|len| 4 + len);
+    let is_complete = msg_len.is_some_and(|len| handshake.len() >= len);
+    let msg = &handshake[..msg_len.unwrap_or(0).min(handshake.len())];
+    match server_name_extension(msg) {
+        Some(name) => ClientHello::ServerName(name),
+        None if is_complete => ClientHello::NoServerName,
+        None => ClientHello::Incomplete,
+    }
+}
+
+/// Returns the host name in the Server Name extension, if included in the available bytes
+fn server_name_extension(msg: &[u8]) -> Option<String> {
+    // message type and length, legacy version, and random
+    let mut offset = 4 + 2 + 32;
+    // legacy session ID
+    offset += 1 + usize::from(*msg.get(offset)?);
+    // cipher suites
+    offset += 2 + usize::from(read_u16(msg, offset)?);
+    // legacy compression methods
+    offset += 1 + usize::from(*msg.get(offset)?);
+    // extensions length
+    offset += 2;
+    loop {
+        let ext_type = read_u16(msg, offset)?;
+        let ext_len = usize::from(read_u16(msg, offset + 2)?);
+        if ext_type == TLS_EXT_SERVER_NAME {
+            let ext = msg.get(offset + 4..offset + 4 + ext_len)?;
+            // server name list length, name type, and name length
+            if ext.get(2) != Some(&TLS_SERVER_NAME_HOST) {
+                return None;
+            }
+            let name_len = usize::from(read_u16(ext, 3)?);
+            let name = std::str::from_utf8(ext.get(5..5 + name_len)?).ok()?;
+            return Some(name.to_lowercase());
+        }
+        offset += 4 + ext_len;
+    }
+}
+
+/// Returns the data of the CRYPTO frames (with their offsets) in a client's QUIC Initial packet
+fn quic_initial_crypto_frames(datagram: &[u8]) -> Option<Vec<(usize, Vec<u8>)>> {
+    // long header with fixed bit set
+    let first_byte = *datagram.first()?;
+    if first_byte & 0xC0 != 0xC0 {
+        return None;
+    }
+    let version = u32::from_be_bytes(datagram.get(1..5)?.try_into().ok()?);
+    let (salt, labels, initial_type) = match version {
+        QUIC_V1 => (&QUIC_V1_INITIAL_SALT, QUIC_V1_LABELS, 0b00),
+        QUIC_V2 => (&QUIC_V2_INITIAL_SALT, QUIC_V2_LABELS, 0b01),
+        _ => return None,
+    };
+    if (first_byte & 0x30) >> 4 != initial_type {
+        return None;
+    }
+
+    let dcid_len = usize::from(*datagram.get(5)?);
+    if dcid_len > QUIC_MAX_CID_LEN {
+        return None;
+    }
+    let dcid = datagram.get(6..6 + dcid_len)?;
+    let mut offset = 6 + dcid_len;
+    let scid_len = usize::from(*datagram.get(offset)?);
+    offset += 1 + scid_len;
+    let token_len = read_varint(datagram, &mut offset)?;
+    offset = offset.checked_add(usize::try_from(token_len).ok()?)?;
+    let len = usize::try_from(read_varint(datagram, &mut offset)?).ok()?;
+    let pn_offset = offset;
+    let packet = datagram.get(..pn_offset.checked_add(len)?)?;
+
+    let keys = QuicInitialKeys::new(salt, labels, dcid)?;
+    let payload = keys.unprotect(packet, pn_offset)?;
+    Some(crypto_frames(&payload))
+}
+
+/// Labels used to derive the packet protection keys from the client's Initial secret
+#[derive(Clone, Copy)]
+struct QuicLabels {
+    key: &'static [u8],
+    iv: &'static [u8],
+    hp: &'static [u8],
+}
+
+const QUIC_V1_LABELS: QuicLabels = QuicLabels {
+    key: b"quic key",
+    iv: b"quic iv",
+    hp: b"quic hp",
+};
+const QUIC_V2_LABELS: QuicLabels = QuicLabels {
+    key: b"quicv2 key",
+    iv: b"quicv2 iv",
+    hp: b"quicv2 hp",
+};
+
+/// Keys protecting the Initial packets sent by a QUIC client
+struct QuicInitialKeys {
+    key: [u8; 16],
+    iv: [u8; 12],
+    hp: [u8; 16],
+}
+
+impl QuicInitialKeys {
+    fn new(salt: &[u8], labels: QuicLabels, dcid: &[u8]) -> Option<Self> {
+        let initial_secret = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(dcid);
+        let client_secret: [u8; 32] = hkdf_expand_label(&initial_secret, b"client in")?;
+        let client_secret = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &client_secret);
+        Some(Self {
+            key: hkdf_expand_label(&client_secret, labels.key)?,
+            iv: hkdf_expand_label(&client_secret, labels.iv)?,
+            hp: hkdf_expand_label(&client_secret, labels.hp)?,
+        })
+    }
+
+    /// Removes header protection and decrypts the payload of a packet
+    fn unprotect(&self, packet: &[u8], pn_offset: usize) -> Option<Vec<u8>> {
+        let sample = packet.get(pn_offset + 4..pn_offset + 20)?;
+        let mask = HeaderProtectionKey::new(&AES_128, &self.hp)
+            .ok()?
+            .new_mask(sample)
+            .ok()?;
+        let first_byte = packet[0] ^ (mask[0] & 0x0F);
+        let pn_len = usize::from(first_byte & 0x03) + 1;
+        let mut header = packet.get(..pn_offset + pn_len)?.to_vec();
+        header[0] = first_byte;
+        let mut packet_number = [0; 8];
+        for i in 0..pn_len {
+            header[pn_offset + i] ^= mask[1 + i];
+            packet_number[8 - pn_len + i] = header[pn_offset + i];
+        }
+
+        let mut nonce = self.iv;
+        for (n, pn) in nonce[4..].iter_mut().zip(packet_number) {
+            *n ^= pn;
+        }
+        let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &self.key).ok()?);
+        let mut payload = packet[pn_offset + pn_len..].to_vec();
+        let plaintext_len = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(&header),
+                &mut payload,
+            )
+            .ok()?
+            .len();
+        payload.truncate(plaintext_len);
+        Some(payload)
+    }
+}
+
+/// Length of the keying material output by HKDF
+struct OkmLen(usize);
+
+impl hkdf::KeyType for OkmLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// `HKDF-Expand-Label` function defined by TLS 1.3, with empty context
+fn hkdf_expand_label<const N: usize>(secret: &hkdf::Prk, label: &[u8]) -> Option<[u8; N]> {
+    let len = u16::try_from(N).ok()?.to_be_bytes();
+    let label_len = [u8::try_from(b"tls13 ".len() + label.len()).ok()?];
+    let info: [&[u8]; 5] = [&len, &label_len, b"tls13 ", label, &[0]];
+    let mut out = [0; N];
+    secret.expand(&info, OkmLen(N)).ok()?.fill(&mut out).ok()?;
+    Some(out)
+}
+
+/// Returns the CRYPTO frames found in the decrypted payload of an Initial packet
+fn crypto_frames(payload: &[u8]) -> Vec<(usize, Vec<u8>)> {
+    let mut frames = Vec::new();
+    let mut offset = 0;
+    while let Some(frame_type) = read_varint(payload, &mut offset) {
+        match frame_type {
+            QUIC_FRAME_PADDING | QUIC_FRAME_PING => {}
+            QUIC_FRAME_ACK | QUIC_FRAME_ACK_ECN => {
+                // largest acknowledged, delay, range count, and first range
+                let (Some(_), Some(_), Some(range_count), Some(_)) = (
+                    read_varint(payload, &mut offset),
+                    read_varint(payload, &mut offset),
+                    read_varint(payload, &mut offset),
+                    read_varint(payload, &mut offset),
+                ) else {
+                    break;
+                };
+                // gap and length of each further range, then ECN counts
+                let further_fields = 2 * range_count
+                    + if frame_type == QUIC_FRAME_ACK_ECN {
+                        3
+                    } else {
+                        0
+                    };
+                for _ in 0..further_fields {
+                    if read_varint(payload, &mut offset).is_none() {
+                        return frames;
+                    }
+                }
+            }
+            QUIC_FRAME_CRYPTO => {
+                let (Some(crypto_offset), Some(len)) = (
+                    read_varint(payload, &mut offset),
+                    read_varint(payload, &mut offset),
+                ) else {
+                    break;
+                };
+                let (Ok(crypto_offset), Ok(len)) =
+                    (usize::try_from(crypto_offset), usize::try_from(len))
+                else {
+                    break;
+                };
+                let Some(data) = payload.get(offset..offset + len) else {
+                    break;
+                };
+                frames.push((crypto_offset, data.to_vec()));
+                offset += len;
+            }
+            // other frames are not expected before the handshake is complete
+            _ => break,
+        }
+    }
+    frames
+}
+
+/// Returns the data of the CRYPTO stream received without gaps from its beginning
+fn contiguous_stream(fragments: &BTreeMap<usize, Vec<u8>>) -> Vec<u8> {
+    let mut stream = Vec::new();
+    for (offset, data) in fragments {
+        if *offset > stream.len() {
+            break;
+        }
+        if let Some(new_data) = data.get(stream.len() - offset..) {
+            stream.extend_from_slice(new_data);
+        }
+    }
+    stream
+}
+
+/// Reads a QUIC variable-length integer, advancing the offset
+fn read_varint(data: &[u8], offset: &mut usize) -> Option<u64> {
+    let first_byte = *data.get(*offset)?;
+    let len = 1 << (first_byte >> 6);
+    let bytes = data.get(*offset..*offset + len)?;
+    *offset += len;
+    Some(
+        bytes[1..]
+            .iter()
+            .fold(u64::from(first_byte & 0x3F), |acc, b| {
+                (acc << 8) | u64::from(*b)
+            }),
+    )
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u24(data: &[u8], offset: usize) -> Option<usize> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(usize::from(bytes[0]) << 16 | usize::from(bytes[1]) << 8 | usize::from(bytes[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn key(protocol: Protocol) -> AddressPortPair {
+        AddressPortPair::new(
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            Some(50000),
+            IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+            Some(443),
+            protocol,
+        )
+    }
+
+    /// Builds a `ClientHello` handshake message with the given server name,
+    /// placed after an extension of the given size
+    fn client_hello(server_name: &str, padding_len: usize) -> Vec<u8> {
+        let mut extensions = Vec::new();
+        // padding extension
+        extensions.extend_from_slice(&[0x00, 0x15]);
+        extensions.extend_from_slice(&u16::try_from(padding_len).unwrap().to_be_bytes());
+        extensions.extend(std::iter::repeat_n(0, padding_len));
+        // server name extension
+        let name_len = u16::try_from(server_name.len()).unwrap();
+        extensions.extend_from_slice(&[0x00, 0x00]);
+        extensions.extend_from_slice(&(name_len + 5).to_be_bytes());
+        extensions.extend_from_slice(&(name_len + 3).to_be_bytes());
+        extensions.push(0x00);
+        extensions.extend_from_slice(&name_len.to_be_bytes());
+        extensions.extend_from_slice(server_name.as_bytes());
+
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0xAB; 32]);
+        // session ID, cipher suites, compression methods
+        body.extend_from_slice(&[0x00, 0x00, 0x02, 0x13, 0x01, 0x01, 0x00]);
+        body.extend_from_slice(&u16::try_from(extensions.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(&extensions);
+
+        let len = u32::try_from(body.len()).unwrap().to_be_bytes();
+        let mut msg = vec![TLS_CLIENT_HELLO, len[1], len[2], len[3]];
+        msg.extend_from_slice(&body);
+        msg
+    }
+
+    fn tls_record(handshake: &[u8]) -> Vec<u8> {
+        let mut record = vec![TLS_HANDSHAKE_RECORD, 0x03, 0x01];
+        record.extend_from_slice(&u16::try_from(handshake.len()).unwrap().to_be_bytes());
+        record.extend_from_slice(handshake);
+        record
+    }
+
+    #[test]
+    fn test_parse_client_hello() {
+        let msg = client_hello("Example.com", 10);
+        assert_eq!(
+            parse_client_hello(&msg),
+            ClientHello::ServerName("example.com".to_string())
+        );
+        assert_eq!(parse_client_hello(&msg[..50]), ClientHello::Incomplete);
+        assert_eq!(
+            parse_client_hello(&[0x02, 0, 0, 0]),
+            ClientHello::NoServerName
+        );
+    }
+
+    fn tcp(seq: u32, data: &[u8]) -> TransportPayload<'_> {
+        TransportPayload {
+            data,
+            tcp_seq: Some(seq),
+        }
+    }
+
+    fn udp(data: &[u8]) -> TransportPayload<'_> {
+        TransportPayload {
+            data,
+            tcp_seq: None,
+        }
+    }
+
+    #[test]
+    fn test_tls_server_name_across_segments() {
+        let mut extractor = ServerNameExtractor::default();
+        let key = key(Protocol::TCP);
+        let record = tls_record(&client_hello("www.sniffnet.net", 1500));
+        let (first, second) = record.split_at(1000);
+        assert_eq!(
+            extractor.server_name(&key, tcp(u32::MAX - 9, first), 0),
+            None
+        );
+        assert_eq!(
+            extractor.server_name(&key, tcp(990, second), 1),
+            Some("www.sniffnet.net".to_string())
+        );
+        assert!(extractor.pending.get_mut(&key, 1).is_none());
+
+        // application data is ignored
+        assert_eq!(
+            extractor.server_name(&key, tcp(5000, &[0x17, 0x03, 0x03, 0, 1, 0]), 2),
+            None
+        );
+        assert!(extractor.pending.get_mut(&key, 2).is_none());
+    }
+
+    #[test]
+    fn test_tls_server_name_retransmitted_and_out_of_order_segments() {
+        let mut extractor = ServerNameExtractor::default();
+        let key = key(Protocol::TCP);
+        let record = tls_record(&client_hello("www.sniffnet.net", 3000));
+        let segments: Vec<(u32, &[u8])> = record
+            .chunks(1000)
+            .zip((100..).step_by(1000))
+            .map(|(data, seq)| (seq, data))
+            .collect();
+        assert_eq!(segments.len(), 4);
+
+        // the first segment is retransmitted, the third one arrives before the second one
+        for (seq, data) in [segments[0], segments[0], segments[2], segments[1]] {
+            assert_eq!(extractor.server_name(&key, tcp(seq, data), 0), None);
+        }
+        // a segment preceding the handshake is ignored
+        assert_eq!(extractor.server_name(&key, tcp(50, &[0; 50]), 0), None);
+        // a retransmission covering two segments
+        assert_eq!(
+            extractor.server_name(&key, tcp(1100, &record[1000..2500]), 0),
+            None
+        );
+        assert_eq!(
+            extractor.server_name(&key, tcp(segments[3].0, segments[3].1), 0),
+            Some("www.sniffnet.net".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pending_handshakes_are_bounded() {
+        let mut extractor = ServerNameExtractor::default();
+        let record = tls_record(&client_hello("www.sniffnet.net", 1500));
+        let (first, second) = record.split_at(1000);
+        let key_with_port = |port| AddressPortPair {
+            sport: Some(port),
+            ..key(Protocol::TCP)
+        };
+
+        // handshakes never completed don't prevent collecting new ones
+        let max = u16::try_from(MAX_PENDING_HANDSHAKES).unwrap();
+        for port in 0..2 * max {
+            assert_eq!(
+                extractor.server_name(&key_with_port(port), tcp(0, first), 0),
+                None
+            );
+        }
+        assert!(
+            extractor
+                .pending
+                .get_mut(&key_with_port(max - 1), 0)
+                .is_none()
+        );
+        assert_eq!(
+            extractor.server_name(&key_with_port(2 * max - 1), tcp(1000, second), 0),
+            Some("www.sniffnet.net".to_string())
+        );
+
+        // stale handshakes are dropped
+        assert_eq!(
+            extractor.server_name(&key_with_port(0), tcp(0, first), 0),
+            None
+        );
+        let later = PENDING_TIMEOUT_USECS + 1;
+        assert_eq!(
+            extractor.server_name(&key_with_port(1), tcp(0, first), later),
+            None
+        );
+        assert!(
+            extractor
+                .pending
+                .get_mut(&key_with_port(0), later)
+                .is_none()
+        );
+        assert!(
+            extractor
+                .pending
+                .get_mut(&key_with_port(1), later)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_quic_initial_keys() {
+        // RFC 9001, Appendix A.1
+        let dcid = [0x83, 0x94, 0xc8, 0xf0, 0x3e, 0x51, 0x57, 0x08];
+        let keys = QuicInitialKeys::new(&QUIC_V1_INITIAL_SALT, QUIC_V1_LABELS, &dcid).unwrap();
+        assert_eq!(
+            keys.key,
+            [
+                0x1f, 0x36, 0x96, 0x13, 0xdd, 0x76, 0xd5, 0x46, 0x77, 0x30, 0xef, 0xcb, 0xe3, 0xb1,
+                0xa2, 0x2d
+            ]
+        );
+        assert_eq!(
+            keys.iv,
+            [
+                0xfa, 0x04, 0x4b, 0x2f, 0x42, 0xa3, 0xfd, 0x3b, 0x46, 0xfb, 0x25, 0x5c
+            ]
+        );
+        assert_eq!(
+            keys.hp,
+            [
+                0x9f, 0x50, 0x44, 0x9e, 0x04, 0xa0, 0xe8, 0x10, 0x28, 0x3a, 0x1e, 0x99, 0x33, 0xad,
+                0xed, 0xd2
+            ]
+        );
+    }
+
+    /// Builds a client's Initial packet carrying the given CRYPTO frames, protected as a real client would
+    fn quic_initial(dcid: &[u8], packet_number: u32, frames: &[(usize, &[u8])]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        for (offset, data) in frames {
+            payload.push(0x06);
+            payload.extend_from_slice(&(0x4000 | u16::try_from(*offset).unwrap()).to_be_bytes());
+            payload.extend_from_slice(&(0x4000 | u16::try_from(data.len()).unwrap()).to_be_bytes());
+            payload.extend_from_slice(data);
+        }
+        // padding
+        payload.extend(std::iter::repeat_n(
+            0,
+            1100_usize.saturating_sub(payload.len()),
+        ));
+
+        let mut packet = vec![
+            0xC3,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            u8::try_from(dcid.len()).unwrap(),
+        ];
+        packet.extend_from_slice(dcid);
+        // no source connection ID, no token
+        packet.extend_from_slice(&[0x00, 0x00]);
+        let len = 4 + payload.len() + 16;
+        packet.extend_from_slice(&(0x4000 | u16::try_from(len).unwrap()).to_be_bytes());
+        let pn_offset = packet.len();
+        packet.extend_from_slice(&packet_number.to_be_bytes());
+
+        let keys = QuicInitialKeys::new(&QUIC_V1_INITIAL_SALT, QUIC_V1_LABELS, dcid).unwrap();
+        let mut nonce = keys.iv;
+        for (n, pn) in nonce[8..].iter_mut().zip(packet_number.to_be_bytes()) {
+            *n ^= pn;
+        }
+        LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &keys.key).unwrap())
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(&packet),
+                &mut payload,
+            )
+            .unwrap();
+        packet.extend_from_slice(&payload);
+
+        let mask = HeaderProtectionKey::new(&AES_128, &keys.hp)
+            .unwrap()
+            .new_mask(&packet[pn_offset + 4..pn_offset + 20])
+            .unwrap();
+        packet[0] ^= mask[0] & 0x0F;
+        for i in 0..4 {
+            packet[pn_offset + i] ^= mask[1 + i];
+        }
+        packet
+    }
+
+    #[test]
+    fn test_quic_server_name_across_initial_packets() {
+        let mut extractor = ServerNameExtractor::default();
+        let key = key(Protocol::UDP);
+        let dcid = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88];
+        let hello = client_hello("quic.sniffnet.net", 1200);
+        let (first, second) = hello.split_at(900);
+
+        // CRYPTO frames out of order inside the first packet
+        let packet_1 = quic_initial(&dcid, 0, &[(500, &first[500..]), (0, &first[..500])]);
+        let packet_2 = quic_initial(&dcid, 1, &[(900, second)]);
+        assert_eq!(extractor.server_name(&key, udp(&packet_1), 0), None);
+        assert_eq!(
+            extractor.server_name(&key, udp(&packet_2), 1),
+            Some("quic.sniffnet.net".to_string())
+        );
+
+        // tampered packets can't be decrypted
+        let mut tampered = quic_initial(&dcid, 2, &[(0, &hello[..500])]);
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0xFF;
+        assert_eq!(quic_initial_crypto_frames(&tampered), None);
+    }
+}
@@ -0,0 +1,393 @@
+//! Module to passively measure the performance of TCP connections
+//! (round trip time, retransmissions, duplicate acknowledgements, and zero window events).
+//!
+//! Round trip times are sampled in two ways:
+//! - from the three-way handshake, as the time elapsed between the SYN and the final ACK
+//! - from the data sent by the local host, as the time elapsed until the remote host acknowledges it
+//!
+//! Both samples correspond to a full round trip, regardless of the capture point in the first case,
+//! and assuming the capture is taking place on the local host in the second one.
+//! Segments that have been retransmitted are never sampled (Karn's algorithm).
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::tcp_metrics::TcpMetrics;
+use crate::utils::types::bounded_map::BoundedMap;
+use etherparse::{LaxPacketHeaders, LaxPayloadSlice, NetHeaders, TransportHeader};
+use std::collections::VecDeque;
+
+/// Maximum number of segments per connection waiting to be acknowledged
+const MAX_UNACKED: usize = 64;
+/// Maximum number of tracked connections (the oldest ones are forgotten to make room for new ones)
+const MAX_CONNECTIONS: usize = 65_536;
+/// Connections idle for longer than this are forgotten
+const IDLE_TIMEOUT_USECS: i64 = 300_000_000;
+
+/// Relevant fields of a TCP segment
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TcpSegment {
+    seq: u32,
+    ack: Option<u32>,
+    window: u16,
+    syn: bool,
+    fin: bool,
+    rst: bool,
+    payload_len: u32,
+}
+
+impl TcpSegment {
+    /// Returns the TCP segment carried by a packet (if any)
+    pub fn from_headers(headers: &LaxPacketHeaders) -> Option<Self> {
+        let Some(TransportHeader::Tcp(tcp)) = &headers.transport else {
+            return None;
+        };
+        let payload_len = match &headers.payload {
+            LaxPayloadSlice::Tcp {
+                payload,
+                incomplete: false,
+            } => payload.len(),
+            // the packet was truncated: get the payload length from the network header
+            _ => match &headers.net {
+                Some(NetHeaders::Ipv4(ipv4, exts)) => usize::from(ipv4.total_len)
+                    .saturating_sub(ipv4.header_len() + exts.header_len() + tcp.header_len()),
+                Some(NetHeaders::Ipv6(ipv6, exts)) => usize::from(ipv6.payload_length)
+                    .saturating_sub(exts.header_len() + tcp.header_len()),
+                _ => 0,
+            },
+        };
+        Some(Self {
+            seq: tcp.sequence_number,
+            ack: tcp.ack.then_some(tcp.acknowledgment_number),
+            window: tcp.window_size,
+            syn: tcp.syn,
+            fin: tcp.fin,
+            rst: tcp.rst,
+            payload_len: u32::try_from(payload_len).unwrap_or_default(),
+        })
+    }
+
+    /// Amount of sequence numbers consumed by the segment
+    fn len(&self) -> u32 {
+        self.payload_len + u32::from(self.syn) + u32::from(self.fin)
+    }
+
+    fn zero_window(&self) -> bool {
+        self.window == 0 && !self.rst
+    }
+}
+
+/// State of one of the two directions of a connection
+#[derive(Default)]
+struct Side {
+    /// Sequence number following the highest one sent
+    next_seq: Option<u32>,
+    /// Last acknowledgement number sent, with the advertised window
+    last_ack: Option<(u32, u16)>,
+    /// Data waiting to be acknowledged: expected acknowledgement number and timestamp
+    unacked: VecDeque<(u32, i64)>,
+    fin: bool,
+}
+
+#[derive(Default)]
+struct Connection {
+    sides: [Side; 2],
+    /// Side that sent the SYN, with its timestamp (until the handshake is completed)
+    syn: Option<(usize, i64)>,
+}
+
+/// Keeps track of the TCP connections to measure their performance
+pub struct TcpAnalyzer {
+    connections: BoundedMap<AddressPortPair, Connection>,
+}
+
+impl Default for TcpAnalyzer {
+    fn default() -> Self {
+        Self {
+            connections: BoundedMap::new(MAX_CONNECTIONS, IDLE_TIMEOUT_USECS),
+        }
+    }
+}
+
+impl TcpAnalyzer {
+    /// Analyzes a TCP segment, returning the metrics it contributes to its direction of the connection
+    ///
+    /// `is_outgoing` tells whether the segment was sent by the local host
+    pub fn analyze(
+        &mut self,
+        key: &AddressPortPair,
+        segment: &TcpSegment,
+        timestamp_usecs: i64,
+        is_outgoing: bool,
+    ) -> TcpMetrics {
+        let reversed = key.reversed();
+        let (conn_key, side) = if self.connections.contains_key(key) {
+            (*key, 0)
+        } else if self.connections.contains_key(&reversed) {
+            (reversed, 1)
+        } else {
+            (*key, 0)
+        };
+        let conn =
+            self.connections
+                .get_or_insert_with(conn_key, timestamp_usecs, Connection::default);
+
+        let mut metrics = TcpMetrics::default();
+        if segment.zero_window() {
+            metrics.zero_windows = 1;
+        }
+        if segment.payload_len > 0 {
+            metrics.data_segments = 1;
+        }
+
+        if segment.syn && segment.ack.is_none() {
+            conn.syn = Some((side, timestamp_usecs));
+        }
+        if let Some((syn_side, syn_usecs)) = conn.syn
+            && syn_side == side
+            && !segment.syn
+            && segment.ack.is_some()
+            && segment.ack == conn.sides[1 - side].next_seq
+        {
+            // final ACK of the handshake
+            conn.syn = None;
+            if let Ok(rtt) = u64::try_from(timestamp_usecs - syn_usecs) {
+                metrics.add_rtt_sample(rtt);
+            }
+        }
+
+        let sender = &mut conn.sides[side];
+        if segment.len() > 0 {
+            let seq_end = segment.seq.wrapping_add(segment.len());
+            match sender.next_seq {
+                Some(next_seq) if !seq_after(seq_end, next_seq) => {
+                    // keep-alive segments carry (at most) one byte that was already acknowledged
+                    let is_keep_alive =
+                        segment.payload_len <= 1 && segment.seq == next_seq.wrapping_sub(1);
+                    if !is_keep_alive {
+                        metrics.retransmissions = u64::from(segment.payload_len > 0);
+                        // the retransmitted data must not be sampled
+                        sender.unacked.retain(|&(expected, _)| {
+                            !seq_after(expected, segment.seq) || seq_after(expected, seq_end)
+                        });
+                    }
+                    if segment.syn {
+                        conn.syn = None;
+                    }
+                }
+                _ => {
+                    sender.next_seq = Some(seq_end);
+                    if is_outgoing && segment.payload_len > 0 {
+                        if sender.unacked.len() >= MAX_UNACKED {
+                            sender.unacked.pop_front();
+                        }
+                        sender.unacked.push_back((seq_end, timestamp_usecs));
+                    }
+                }
+            }
+        }
+        sender.fin |= segment.fin;
+
+        if let Some(ack) = segment.ack {
+            let is_pure_ack =
+                segment.payload_len == 0 && !segment.syn && !segment.fin && !segment.rst;
+            let is_duplicate = is_pure_ack && sender.last_ack == Some((ack, segment.window));
+            sender.last_ack = Some((ack, segment.window));
+
+            let receiver = &mut conn.sides[1 - side];
+            if is_duplicate && receiver.next_seq.is_some_and(|next| seq_after(next, ack)) {
+                metrics.duplicate_acks = 1;
+            }
+            while let Some(&(expected, sent_usecs)) = receiver.unacked.front() {
+                if seq_after(expected, ack) {
+                    break;
+                }
+                receiver.unacked.pop_front();
+                if expected == ack
+                    && let Ok(rtt) = u64::try_from(timestamp_usecs - sent_usecs)
+                {
+                    metrics.add_rtt_sample(rtt);
+                }
+            }
+        }
+
+        if segment.rst || conn.sides.iter().all(|s| s.fin) {
+            self.connections.remove(&conn_key);
+        }
+
+        metrics
+    }
+}
+
+/// Whether sequence number `a` comes after `b`, taking wrap-around into account
+fn seq_after(a: u32, b: u32) -> bool {
+    a != b && a.wrapping_sub(b) < 1 << 31
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use std::net::IpAddr;
+
+    fn client_key() -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.2".parse::<IpAddr>().unwrap(),
+            Some(50000),
+            "8.8.8.8".parse::<IpAddr>().unwrap(),
+            Some(443),
+            Protocol::TCP,
+        )
+    }
+
+    fn segment(seq: u32, ack: Option<u32>, payload_len: u32) -> TcpSegment {
+        TcpSegment {
+            seq,
+            ack,
+            window: 1024,
+            payload_len,
+            ..TcpSegment::default()
+        }
+    }
+
+    fn syn(seq: u32, ack: Option<u32>) -> TcpSegment {
+        TcpSegment {
+            syn: true,
+            ..segment(seq, ack, 0)
+        }
+    }
+
+    #[test]
+    fn test_handshake_rtt() {
+        let mut analyzer = TcpAnalyzer::default();
+        let client = client_key();
+        let server = client.reversed();
+
+        assert!(
+            analyzer
+                .analyze(&client, &syn(100, None), 0, true)
+                .is_empty()
+        );
+        assert!(
+            analyzer
+                .analyze(&server, &syn(900, Some(101)), 25_000, false)
+                .is_empty()
+        );
+        let metrics = analyzer.analyze(&client, &segment(101, Some(901), 0), 25_100, true);
+        assert_eq!(metrics.rtt_samples, 1);
+        assert_eq!(metrics.rtt_min_usecs, Some(25_100));
+    }
+
+    #[test]
+    fn test_handshake_rtt_after_syn_flood() {
+        let mut analyzer = TcpAnalyzer::default();
+        let client = client_key();
+        let server = client.reversed();
+
+        analyzer.analyze(&client, &syn(100, None), 0, true);
+        // half-open connections never completed fill the analyzer
+        for port in 0..=u16::MAX {
+            let flood_key = AddressPortPair {
+                source: "8.8.4.4".parse::<IpAddr>().unwrap(),
+                dport: Some(port),
+                ..server
+            };
+            analyzer.analyze(&flood_key, &syn(0, None), 1_000, false);
+        }
+        assert!(!analyzer.connections.contains_key(&client));
+
+        // a new connection is still tracked
+        analyzer.analyze(&client, &syn(100, None), 2_000, true);
+        analyzer.analyze(&server, &syn(900, Some(101)), 27_000, false);
+        let metrics = analyzer.analyze(&client, &segment(101, Some(901), 0), 27_100, true);
+        assert_eq!(metrics.rtt_min_usecs, Some(25_100));
+    }
+
+    #[test]
+    fn test_data_rtt_and_retransmissions() {
+        let mut analyzer = TcpAnalyzer::default();
+        let client = client_key();
+        let server = client.reversed();
+
+        // two segments sent by the local host, the second one is then retransmitted
+        let first = analyzer.analyze(&client, &segment(1, Some(1), 100), 0, true);
+        assert_eq!((first.data_segments, first.retransmissions), (1, 0));
+        analyzer.analyze(&client, &segment(101, Some(1), 100), 1_000, true);
+        let retransmission = analyzer.analyze(&client, &segment(101, Some(1), 100), 50_000, true);
+        assert_eq!(retransmission.retransmissions, 1);
+
+        // the first one is sampled, the retransmitted one isn't
+        let ack = analyzer.analyze(&server, &segment(1, Some(101), 0), 20_000, false);
+        assert_eq!(ack.rtt_samples, 1);
+        assert_eq!(ack.avg_rtt_usecs(), Some(20_000));
+        let ack = analyzer.analyze(&server, &segment(1, Some(201), 0), 70_000, false);
+        assert_eq!(ack.rtt_samples, 0);
+
+        // keep-alive
+        let keep_alive = analyzer.analyze(&client, &segment(200, Some(1), 1), 90_000, true);
+        assert_eq!(keep_alive.retransmissions, 0);
+    }
+
+    #[test]
+    fn test_duplicate_acks_and_zero_window() {
+        let mut analyzer = TcpAnalyzer::default();
+        let client = client_key();
+        let server = client.reversed();
+
+        analyzer.analyze(&server, &segment(1, Some(1), 1000), 0, false);
+        analyzer.analyze(&server, &segment(1001, Some(1), 1000), 10, false);
+        // the first segment is lost: the receiver keeps acknowledging the same data
+        assert_eq!(
+            analyzer
+                .analyze(&client, &segment(1, Some(1), 0), 20, true)
+                .duplicate_acks,
+            0
+        );
+        assert_eq!(
+            analyzer
+                .analyze(&client, &segment(1, Some(1), 0), 30, true)
+                .duplicate_acks,
+            1
+        );
+        // after everything is acknowledged, repeated ACKs are not duplicates
+        analyzer.analyze(&client, &segment(1, Some(2001), 0), 40, true);
+        assert_eq!(
+            analyzer
+                .analyze(&client, &segment(1, Some(2001), 0), 50, true)
+                .duplicate_acks,
+            0
+        );
+
+        let zero_window = TcpSegment {
+            window: 0,
+            ..segment(1, Some(2001), 0)
+        };
+        assert_eq!(
+            analyzer
+                .analyze(&client, &zero_window, 60, true)
+                .zero_windows,
+            1
+        );
+    }
+
+    #[test]
+    fn test_seq_after_wraps_around() {
+        assert!(seq_after(10, 5));
+        assert!(!seq_after(5, 10));
+        assert!(!seq_after(5, 5));
+        assert!(seq_after(3, u32::MAX - 3));
+        assert!(!seq_after(u32::MAX - 3, 3));
+    }
+
+    #[test]
+    fn test_segment_from_truncated_packet() {
+        let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 2], [8, 8, 8, 8], 64)
+            .tcp(50000, 443, 1, 1024)
+            .ack(7);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[0; 1000]).unwrap();
+        packet.truncate(200);
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        let segment = TcpSegment::from_headers(&headers).unwrap();
+        assert_eq!(segment.payload_len, 1000);
+        assert_eq!(segment.ack, Some(7));
+    }
+}
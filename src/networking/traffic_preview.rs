@@ -5,8 +5,11 @@
 use crate::networking::types::arp_type::ArpType;
 use crate::networking::types::capture_context::{CaptureContext, CaptureSource, CaptureType};
 use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::igmp_type::IgmpType;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::pcap_rotation::PcapRotation;
+use crate::networking::types::tcp_state::TcpFlags;
 use crate::utils::error_logger::{ErrorLogger, Location};
 use async_channel::Sender;
 use pcap::{Device, Stat};
@@ -45,13 +48,15 @@ pub fn traffic_preview(tx: &Sender<TrafficPreview>) {
         if let Ok(packet) = packet_res {
             let dev_info = packet.dev_info;
             let my_link_type = dev_info.my_link_type;
-            if let Some(headers) = get_sniffable_headers(&packet.data, my_link_type)
+            if let Some((headers, _)) = get_sniffable_headers(&packet.data, my_link_type)
                 && analyze_headers(
                     headers,
                     &mut (None, None),
                     &mut 0,
                     &mut IcmpType::default(),
                     &mut ArpType::default(),
+                    &mut IgmpType::default(),
+                    &mut TcpFlags::default(),
                 )
                 .is_some()
             {
@@ -79,7 +84,13 @@ fn handle_devices_and_previews(
         data.insert(dev_name.clone(), 0);
         traffic_preview.data.push((my_dev.clone(), 0));
         let capture_source = CaptureSource::Device(my_dev);
-        let capture_context = CaptureContext::new(&capture_source, None, &Filters::default());
+        let capture_context = CaptureContext::new(
+            &capture_source,
+            None,
+            PcapRotation::default(),
+            &Filters::default(),
+            false,
+        );
         let my_link_type = capture_context.my_link_type();
         if !my_link_type.is_supported() {
             continue;
@@ -114,7 +125,7 @@ fn packet_stream(
     loop {
         let packet_res = cap.next_packet();
         let packet_owned = packet_res.map(|p| PacketOwned {
-            data: p.data.into(),
+            data: p.data,
             dev_info: dev_info.clone(),
         });
         if tx.send((packet_owned, cap.stats().ok())).is_err() {
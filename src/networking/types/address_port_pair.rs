@@ -1,6 +1,7 @@
 //! Module defining the `AddressPortPair` struct, which represents a network address:port pair.
 
 use crate::Protocol;
+use crate::networking::types::vlan_ids::VlanIds;
 use std::net::{IpAddr, Ipv4Addr};
 
 /// Struct representing a network address:port pair.
@@ -16,6 +17,22 @@ pub struct AddressPortPair {
     pub dport: Option<u16>,
     ///  Transport layer protocol carried through the associate address:port pair (TCP or UPD).
     pub protocol: Protocol,
+    /// IDs of the VLAN the traffic belongs to (overlapping address spaces may be in use on different VLANs)
+    pub vlan: VlanIds,
+}
+
+impl AddressPortPair {
+    /// Returns the address:port pair of the opposite direction
+    pub fn reversed(&self) -> Self {
+        AddressPortPair {
+            source: self.dest,
+            sport: self.dport,
+            dest: self.source,
+            dport: self.sport,
+            protocol: self.protocol,
+            vlan: self.vlan,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -33,6 +50,7 @@ pub fn new(
             dest,
             dport,
             protocol,
+            vlan: VlanIds::default(),
         }
     }
 }
@@ -45,6 +63,7 @@ fn default() -> Self {
             sport: None,
             dport: None,
             protocol: Protocol::ARP,
+            vlan: VlanIds::default(),
         }
     }
 }
@@ -0,0 +1,77 @@
+//! Module defining the `ArpAnomaly` enum, representing suspicious ARP activity.
+
+use crate::translations::translations_6::{
+    arp_duplicate_ip_translation, arp_mac_changed_translation, gratuitous_arp_flood_translation,
+};
+use crate::translations::types::language::Language;
+use std::fmt;
+use std::net::Ipv4Addr;
+
+/// Suspicious ARP activity, possibly caused by ARP spoofing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArpAnomaly {
+    /// A known IP address is now claimed by a different MAC address
+    MacChanged {
+        ip: Ipv4Addr,
+        previous_mac: String,
+        new_mac: String,
+    },
+    /// Two MAC addresses are claiming the same IP address at the same time
+    DuplicateIp {
+        ip: Ipv4Addr,
+        mac_addresses: (String, String),
+    },
+    /// A MAC address is sending an unusual amount of gratuitous ARP packets
+    GratuitousFlood { mac: String, packets: usize },
+}
+
+impl ArpAnomaly {
+    pub fn title(&self, language: Language) -> &'static str {
+        match self {
+            ArpAnomaly::MacChanged { .. } => arp_mac_changed_translation(language),
+            ArpAnomaly::DuplicateIp { .. } => arp_duplicate_ip_translation(language),
+            ArpAnomaly::GratuitousFlood { .. } => gratuitous_arp_flood_translation(language),
+        }
+    }
+
+    /// MAC addresses involved in the anomaly
+    pub fn mac_addresses(&self) -> Vec<&str> {
+        match self {
+            ArpAnomaly::MacChanged {
+                previous_mac,
+                new_mac,
+                ..
+            } => vec![previous_mac, new_mac],
+            ArpAnomaly::DuplicateIp {
+                mac_addresses: (mac_1, mac_2),
+                ..
+            } => vec![mac_1, mac_2],
+            ArpAnomaly::GratuitousFlood { mac, .. } => vec![mac],
+        }
+    }
+
+    /// IP address involved in the anomaly (if any)
+    pub fn ip(&self) -> Option<Ipv4Addr> {
+        match self {
+            ArpAnomaly::MacChanged { ip, .. } | ArpAnomaly::DuplicateIp { ip, .. } => Some(*ip),
+            ArpAnomaly::GratuitousFlood { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ArpAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArpAnomaly::MacChanged {
+                ip,
+                previous_mac,
+                new_mac,
+            } => write!(f, "{ip}: {previous_mac} → {new_mac}"),
+            ArpAnomaly::DuplicateIp {
+                ip,
+                mac_addresses: (mac_1, mac_2),
+            } => write!(f, "{ip}: {mac_1} / {mac_2}"),
+            ArpAnomaly::GratuitousFlood { mac, packets } => write!(f, "{mac}: {packets} ARP"),
+        }
+    }
+}
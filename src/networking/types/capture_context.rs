@@ -1,57 +1,203 @@
 use crate::gui::types::conf::Conf;
 use crate::gui::types::filters::Filters;
 use crate::location;
+use crate::networking::pcapng::{PcapngInterface, PcapngReader, is_pcapng_file, is_pcapng_path};
+use crate::networking::types::capture_writer::{
+    CaptureWriter, PcapFile, PcapngFile, SharedPcapngFile,
+};
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::pcap_rotation::PcapRotation;
 use crate::translations::translations::network_adapter_translation;
 use crate::translations::translations_4::capture_file_translation;
+use crate::translations::translations_6::{
+    network_adapters_translation, not_a_network_adapter_translation,
+    separate_capture_per_adapter_translation,
+};
 use crate::translations::types::language::Language;
 use crate::utils::error_logger::{ErrorLogger, Location};
-use pcap::{Active, Address, Capture, Device, Error, Packet, Savefile, Stat};
+use crate::utils::types::timestamp::Timestamp;
+use pcap::{Active, Address, BpfProgram, Capture, Device, Error, Packet, PacketHeader, Stat};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 pub enum CaptureContext {
     Live(Live),
     LiveWithSavefile(LiveWithSavefile),
+    LiveWithPcapng(LiveWithPcapng),
     Offline(Offline),
-    Error(String),
+    OfflinePcapng(PcapngImport),
+    Error(CaptureError),
+}
+
+/// Error preventing a capture from starting
+#[derive(Debug)]
+pub enum CaptureError {
+    /// Error raised by libpcap or while creating the output file
+    Pcap(String),
+    /// The source opened as a network adapter isn't one
+    NotAnAdapter,
+    /// A single capture was requested for multiple network adapters
+    MultipleAdapters,
+}
+
+impl CaptureError {
+    pub fn get_message(&self, language: Language) -> String {
+        match self {
+            CaptureError::Pcap(error) => error.clone(),
+            CaptureError::NotAnAdapter => not_a_network_adapter_translation(language).to_string(),
+            CaptureError::MultipleAdapters => {
+                separate_capture_per_adapter_translation(language).to_string()
+            }
+        }
+    }
+}
+
+impl From<Error> for CaptureError {
+    fn from(error: Error) -> Self {
+        CaptureError::Pcap(error.to_string())
+    }
 }
 
 impl CaptureContext {
-    pub fn new(source: &CaptureSource, pcap_out_path: Option<&String>, filters: &Filters) -> Self {
-        let mut cap_type = match CaptureType::from_source(source, pcap_out_path) {
+    pub fn new(
+        source: &CaptureSource,
+        pcap_out_path: Option<&String>,
+        rotation: PcapRotation,
+        filters: &Filters,
+        server_names: bool,
+    ) -> Self {
+        let full_packets = pcap_out_path.is_some();
+        let cap_type = match CaptureType::open(source, full_packets, server_names, filters) {
             Ok(c) => c,
-            Err(e) => return Self::Error(e.to_string()),
+            Err(e) => return Self::Error(e),
         };
 
-        // only apply BPF filter if it is active, and return an error if it fails to apply
-        if filters.is_some_filter_active()
-            && let Err(e) = cap_type.set_bpf(filters.bpf())
-        {
-            return Self::Error(e.to_string());
-        }
-
         let cap = match cap_type {
             CaptureType::Live(cap) => cap,
             CaptureType::Offline(cap) => return Self::new_offline(cap),
+            CaptureType::OfflinePcapng(import) => return Self::OfflinePcapng(import),
         };
 
         if let Some(out_path) = pcap_out_path {
-            let savefile_res = cap.savefile(out_path);
-            match savefile_res {
-                Ok(s) => Self::new_live_with_savefile(cap, s),
-                Err(e) => Self::Error(e.to_string()),
+            let pcap_file_res = PcapFile::new(&cap, out_path, rotation);
+            match pcap_file_res {
+                Ok(f) => Self::new_live_with_savefile(cap, f),
+                Err(e) => Self::Error(CaptureError::Pcap(e.to_string())),
             }
         } else {
             Self::new_live(cap)
         }
     }
 
+    /// Builds a capture context for each of the sources to be captured in parallel
+    /// (a single one, unless capturing from multiple network adapters)
+    pub fn new_all(
+        source: &CaptureSource,
+        pcap_out_path: Option<&String>,
+        rotation: PcapRotation,
+        filters: &Filters,
+        server_names: bool,
+    ) -> Vec<Self> {
+        if let Some(out_path) = pcap_out_path.filter(|path| is_pcapng_path(path)) {
+            match source {
+                CaptureSource::Device(device) => {
+                    return Self::new_all_with_pcapng(
+                        std::slice::from_ref(device),
+                        out_path,
+                        rotation,
+                        filters,
+                    );
+                }
+                CaptureSource::Devices(multi_device) => {
+                    return Self::new_all_with_pcapng(
+                        &multi_device.devices,
+                        out_path,
+                        rotation,
+                        filters,
+                    );
+                }
+                CaptureSource::File(_) => {}
+            }
+        }
+
+        match source {
+            CaptureSource::Devices(multi_device) => multi_device
+                .devices
+                .iter()
+                .map(|device| {
+                    let device_out_path =
+                        pcap_out_path.map(|path| pcap_path_for_device(path, device.get_name()));
+                    Self::new(
+                        &CaptureSource::Device(device.clone()),
+                        device_out_path.as_ref(),
+                        rotation,
+                        filters,
+                        server_names,
+                    )
+                })
+                .collect(),
+            _ => vec![Self::new(
+                source,
+                pcap_out_path,
+                rotation,
+                filters,
+                server_names,
+            )],
+        }
+    }
+
+    /// Builds the capture contexts of the given devices, all writing to the same pcapng file
+    /// (with an Interface Description Block for each device)
+    fn new_all_with_pcapng(
+        devices: &[MyDevice],
+        out_path: &str,
+        rotation: PcapRotation,
+        filters: &Filters,
+    ) -> Vec<Self> {
+        let bpf = filters.is_some_filter_active().then(|| filters.bpf());
+        let mut writer = match PcapngFile::new(out_path, rotation, bpf) {
+            Ok(writer) => writer,
+            Err(e) => return vec![Self::Error(CaptureError::Pcap(e.to_string()))],
+        };
+
+        let mut caps = Vec::new();
+        for device in devices {
+            let source = CaptureSource::Device(device.clone());
+            let cap_res = match CaptureType::open(&source, true, true, filters) {
+                Ok(CaptureType::Live(cap)) => writer
+                    .add_interface(cap.get_datalink(), device.get_name(), bpf)
+                    .map(|interface_id| (cap, interface_id))
+                    .map_err(|e| CaptureError::Pcap(e.to_string())),
+                Ok(_) => Err(CaptureError::NotAnAdapter),
+                Err(e) => Err(e),
+            };
+            caps.push(cap_res);
+        }
+
+        let writer: SharedPcapngFile = Arc::new(Mutex::new(writer));
+        caps.into_iter()
+            .map(|cap_res| match cap_res {
+                Ok((cap, interface_id)) => Self::LiveWithPcapng(LiveWithPcapng {
+                    live: Live { cap },
+                    writer: writer.clone(),
+                    interface_id,
+                }),
+                Err(e) => Self::Error(e),
+            })
+            .collect()
+    }
+
     fn new_live(cap: Capture<Active>) -> Self {
         Self::Live(Live { cap })
     }
 
-    fn new_live_with_savefile(cap: Capture<Active>, savefile: Savefile) -> Self {
+    fn new_live_with_savefile(cap: Capture<Active>, savefile: PcapFile) -> Self {
         Self::LiveWithSavefile(LiveWithSavefile {
             live: Live { cap },
             savefile,
@@ -62,31 +208,49 @@ fn new_offline(cap: Capture<pcap::Offline>) -> Self {
         Self::Offline(Offline { cap })
     }
 
-    pub fn error(&self) -> Option<&str> {
+    pub fn error(&self) -> Option<&CaptureError> {
         match self {
             Self::Error(e) => Some(e),
             _ => None,
         }
     }
 
-    pub fn consume(self) -> (Option<CaptureType>, Option<Savefile>) {
+    pub fn consume(self) -> (Option<CaptureType>, Option<CaptureWriter>) {
         match self {
             Self::Live(on) => (Some(CaptureType::Live(on.cap)), None),
-            Self::LiveWithSavefile(onws) => {
-                (Some(CaptureType::Live(onws.live.cap)), Some(onws.savefile))
-            }
+            Self::LiveWithSavefile(onws) => (
+                Some(CaptureType::Live(onws.live.cap)),
+                Some(CaptureWriter::Pcap(onws.savefile)),
+            ),
+            Self::LiveWithPcapng(onwp) => (
+                Some(CaptureType::Live(onwp.live.cap)),
+                Some(CaptureWriter::Pcapng(onwp.writer, onwp.interface_id)),
+            ),
             Self::Offline(off) => (Some(CaptureType::Offline(off.cap)), None),
+            Self::OfflinePcapng(import) => (Some(CaptureType::OfflinePcapng(import)), None),
             Self::Error(_) => (None, None),
         }
     }
 
+    /// Returns the link type of the capture
+    /// (for pcapng imports, the first supported link type among the file interfaces)
     pub fn my_link_type(&self) -> MyLinkType {
         match self {
             Self::Live(on) => MyLinkType::from_pcap_link_type(on.cap.get_datalink()),
             Self::LiveWithSavefile(onws) => {
                 MyLinkType::from_pcap_link_type(onws.live.cap.get_datalink())
             }
+            Self::LiveWithPcapng(onwp) => {
+                MyLinkType::from_pcap_link_type(onwp.live.cap.get_datalink())
+            }
             Self::Offline(off) => MyLinkType::from_pcap_link_type(off.cap.get_datalink()),
+            Self::OfflinePcapng(import) => import
+                .reader
+                .interfaces()
+                .iter()
+                .map(|interface| MyLinkType::from_pcap_link_type(interface.link_type))
+                .find(|link_type| link_type.is_supported())
+                .unwrap_or_default(),
             Self::Error(_) => MyLinkType::default(),
         }
     }
@@ -98,23 +262,132 @@ pub struct Live {
 
 pub struct LiveWithSavefile {
     live: Live,
-    savefile: Savefile,
+    savefile: PcapFile,
+}
+
+pub struct LiveWithPcapng {
+    live: Live,
+    writer: SharedPcapngFile,
+    interface_id: u32,
 }
 
 pub struct Offline {
     cap: Capture<pcap::Offline>,
 }
 
+/// Import of a pcapng file, whose interfaces can have different link types
+pub struct PcapngImport {
+    reader: PcapngReader<BufReader<File>>,
+    bpf: Option<String>,
+    /// BPF programs compiled for each of the link types found in the file
+    bpf_programs: HashMap<i32, BpfProgram>,
+}
+
+impl PcapngImport {
+    fn open(path: &str) -> Result<Self, Error> {
+        let reader = File::open(path)
+            .and_then(|file| PcapngReader::new(BufReader::new(file)))
+            .map_err(|e| Error::PcapError(e.to_string()))?;
+        Ok(Self {
+            reader,
+            bpf: None,
+            bpf_programs: HashMap::new(),
+        })
+    }
+
+    /// Compiles the filter for the link types known so far, to report invalid filters early
+    fn set_bpf(&mut self, bpf: &str) -> Result<(), Error> {
+        self.bpf = Some(bpf.to_string());
+        let link_types: Vec<_> = self
+            .reader
+            .interfaces()
+            .iter()
+            .map(|interface| interface.link_type)
+            .collect();
+        for link_type in link_types {
+            self.bpf_program(link_type)?;
+        }
+        Ok(())
+    }
+
+    fn bpf_program(&mut self, link_type: pcap::Linktype) -> Result<&BpfProgram, Error> {
+        let Some(bpf) = &self.bpf else {
+            return Err(Error::PcapError("No BPF filter".to_string()));
+        };
+        match self.bpf_programs.entry(link_type.0) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let program = Capture::dead(link_type)?.compile(bpf, true)?;
+                Ok(entry.insert(program))
+            }
+        }
+    }
+
+    fn next_packet(&mut self) -> Result<PacketOwned, Error> {
+        loop {
+            // stop when the file is over, or when the rest of it can't be read (e.g., truncated file)
+            let Ok(Some(packet)) = self.reader.next_packet() else {
+                return Err(Error::NoMorePackets);
+            };
+            if self.bpf.is_some() {
+                let matches_filter = self
+                    .bpf_program(packet.interface.link_type)
+                    .is_ok_and(|program| program.filter(&packet.data));
+                if !matches_filter {
+                    continue;
+                }
+            }
+            return Ok(PacketOwned {
+                header: None,
+                timestamp: packet.timestamp,
+                len: packet.orig_len,
+                data: packet.data.into(),
+                interface: Some(packet.interface),
+            });
+        }
+    }
+}
+
+/// Packet returned by a capture, owning its data
+pub struct PacketOwned {
+    /// Header reported by libpcap, needed to write PCAP files (None for pcapng imports)
+    pub header: Option<PacketHeader>,
+    pub timestamp: Timestamp,
+    /// Original length of the packet (may be larger than the captured data)
+    pub len: u32,
+    pub data: Box<[u8]>,
+    /// Interface of the pcapng file the packet belongs to (None unless importing pcapng files)
+    pub interface: Option<Arc<PcapngInterface>>,
+}
+
+impl From<Packet<'_>> for PacketOwned {
+    fn from(packet: Packet<'_>) -> Self {
+        #[allow(clippy::useless_conversion)]
+        let secs = i64::from(packet.header.ts.tv_sec);
+        #[allow(clippy::useless_conversion)]
+        let usecs = i64::from(packet.header.ts.tv_usec);
+        Self {
+            header: Some(*packet.header),
+            timestamp: Timestamp::new(secs, usecs),
+            len: packet.header.len,
+            data: packet.data.into(),
+            interface: None,
+        }
+    }
+}
+
 pub enum CaptureType {
     Live(Capture<Active>),
     Offline(Capture<pcap::Offline>),
+    OfflinePcapng(PcapngImport),
 }
 
 impl CaptureType {
-    pub fn next_packet(&mut self) -> Result<Packet<'_>, Error> {
+    pub fn next_packet(&mut self) -> Result<PacketOwned, Error> {
         match self {
-            Self::Live(on) => on.next_packet(),
-            Self::Offline(off) => off.next_packet(),
+            Self::Live(on) => on.next_packet().map(PacketOwned::from),
+            Self::Offline(off) => off.next_packet().map(PacketOwned::from),
+            Self::OfflinePcapng(import) => import.next_packet(),
         }
     }
 
@@ -122,27 +395,60 @@ pub fn stats(&mut self) -> Result<Stat, Error> {
         match self {
             Self::Live(on) => on.stats(),
             Self::Offline(off) => off.stats(),
+            Self::OfflinePcapng(_) => Err(Error::PcapError(
+                "Statistics not available for pcapng imports".to_string(),
+            )),
+        }
+    }
+
+    /// Opens the capture and applies the BPF filter (if active)
+    fn open(
+        source: &CaptureSource,
+        full_packets: bool,
+        server_names: bool,
+        filters: &Filters,
+    ) -> Result<Self, CaptureError> {
+        let mut cap_type = Self::from_source(source, full_packets, server_names)?;
+        // only apply BPF filter if it is active, and return an error if it fails to apply
+        if filters.is_some_filter_active() {
+            cap_type.set_bpf(filters.bpf())?;
         }
+        Ok(cap_type)
     }
 
-    fn from_source(source: &CaptureSource, pcap_out_path: Option<&String>) -> Result<Self, Error> {
+    fn from_source(
+        source: &CaptureSource,
+        full_packets: bool,
+        server_names: bool,
+    ) -> Result<Self, CaptureError> {
         match source {
             CaptureSource::Device(device) => {
                 let inactive = Capture::from_device(device.to_pcap_device())?;
+                let (buffer_size, snaplen) = if server_names {
+                    // keep whole TLS and QUIC handshakes to extract the server names
+                    (16_000_000, 1600) // 16MB buffer -> 10k packets of 1600 bytes
+                } else {
+                    (2_000_000, 200) // 2MB buffer -> 10k packets of 200 bytes
+                };
                 let cap = inactive
                     .promisc(false)
-                    .buffer_size(2_000_000) // 2MB buffer -> 10k packets of 200 bytes
-                    .snaplen(if pcap_out_path.is_some() {
+                    .buffer_size(buffer_size)
+                    .snaplen(if full_packets {
                         i32::from(u16::MAX)
                     } else {
-                        200 // limit stored packets slice dimension (to keep more in the buffer)
+                        snaplen // limit stored packets slice dimension (to keep more in the buffer)
                     })
                     .immediate_mode(false)
                     .timeout(150) // ensure UI is updated even if no packets are captured
                     .open()?;
                 Ok(Self::Live(cap))
             }
+            CaptureSource::File(file) if is_pcapng_file(&file.path) => {
+                Ok(Self::OfflinePcapng(PcapngImport::open(&file.path)?))
+            }
             CaptureSource::File(file) => Ok(Self::Offline(Capture::from_file(&file.path)?)),
+            // each device of a multi-device source has its own capture context
+            CaptureSource::Devices(_) => Err(CaptureError::MultipleAdapters),
         }
     }
 
@@ -150,6 +456,7 @@ fn set_bpf(&mut self, bpf: &str) -> Result<(), Error> {
         match self {
             Self::Live(cap) => cap.filter(bpf, true),
             Self::Offline(cap) => cap.filter(bpf, true),
+            Self::OfflinePcapng(import) => import.set_bpf(bpf),
         }
     }
 
@@ -173,6 +480,7 @@ pub fn resume(&mut self, filters: &Filters) {
 #[derive(Clone)]
 pub enum CaptureSource {
     Device(MyDevice),
+    Devices(MyMultiDevice),
     File(MyPcapImport),
 }
 
@@ -183,6 +491,10 @@ pub fn from_conf(conf: &Conf) -> Self {
                 let device = conf.device.to_my_device();
                 Self::Device(device)
             }
+            CaptureSourcePicklist::Devices => {
+                let devices = conf.device.to_my_devices();
+                Self::Devices(MyMultiDevice::new(devices))
+            }
             CaptureSourcePicklist::File => {
                 let path = conf.import_pcap_path.clone();
                 Self::File(MyPcapImport::new(path))
@@ -193,6 +505,7 @@ pub fn from_conf(conf: &Conf) -> Self {
     pub fn title(&self, language: Language) -> &str {
         match self {
             Self::Device(_) => network_adapter_translation(language),
+            Self::Devices(_) => network_adapters_translation(language),
             Self::File(_) => capture_file_translation(language),
         }
     }
@@ -200,58 +513,202 @@ pub fn title(&self, language: Language) -> &str {
     pub fn get_addresses(&self) -> &Vec<Address> {
         match self {
             Self::Device(device) => device.get_addresses(),
+            Self::Devices(multi_device) => &multi_device.addresses,
             Self::File(file) => &file.addresses,
         }
     }
 
     pub fn set_addresses(&mut self) {
-        if let Self::Device(my_device) = self {
-            let mut addresses = Vec::new();
-            for dev in Device::list().log_err(location!()).unwrap_or_default() {
-                if matches!(
-                    my_device.get_link_type(),
-                    MyLinkType::LinuxSll(_) | MyLinkType::LinuxSll2(_)
-                ) {
-                    addresses.extend(dev.addresses);
-                } else if dev.name.eq(my_device.get_name()) {
-                    addresses.extend(dev.addresses);
-                    break;
+        match self {
+            Self::Device(my_device) => {
+                let dev_list = Device::list().log_err(location!()).unwrap_or_default();
+                set_device_addresses(my_device, &dev_list);
+            }
+            Self::Devices(multi_device) => {
+                let dev_list = Device::list().log_err(location!()).unwrap_or_default();
+                for my_device in &mut multi_device.devices {
+                    set_device_addresses(my_device, &dev_list);
                 }
+                multi_device.update_addresses();
             }
-            my_device.set_addresses(addresses);
+            Self::File(_) => {}
         }
     }
 
+    /// Returns the link type of the source
+    /// (for multiple devices, the link type of the first supported one)
     pub fn get_link_type(&self) -> MyLinkType {
         match self {
             Self::Device(device) => device.get_link_type(),
+            Self::Devices(multi_device) => multi_device
+                .devices
+                .iter()
+                .map(MyDevice::get_link_type)
+                .find(|link_type| link_type.is_supported())
+                .unwrap_or_default(),
             Self::File(file) => file.link_type,
         }
     }
 
-    pub fn set_link_type(&mut self, link_type: MyLinkType) {
+    /// Sets the link types of the source, given the capture contexts built with `CaptureContext::new_all`
+    pub fn set_link_types(&mut self, capture_contexts: &[CaptureContext]) {
+        let mut link_types = capture_contexts.iter().map(CaptureContext::my_link_type);
         match self {
-            Self::Device(device) => device.set_link_type(link_type),
-            Self::File(file) => file.link_type = link_type,
+            Self::Device(device) => device.set_link_type(link_types.next().unwrap_or_default()),
+            Self::Devices(multi_device) => {
+                for (device, link_type) in multi_device.devices.iter_mut().zip(link_types) {
+                    device.set_link_type(link_type);
+                }
+            }
+            Self::File(file) => file.link_type = link_types.next().unwrap_or_default(),
+        }
+    }
+
+    /// Removes the network adapters whose capture couldn't be started, together with their capture contexts,
+    /// returning their names and errors (nothing is removed if none of the captures could be started)
+    pub fn remove_failed_devices(
+        &mut self,
+        capture_contexts: &mut Vec<CaptureContext>,
+    ) -> Vec<(String, CaptureError)> {
+        let Self::Devices(multi_device) = self else {
+            return Vec::new();
+        };
+        if capture_contexts.iter().all(|c| c.error().is_some()) {
+            return Vec::new();
+        }
+        let mut failed = Vec::new();
+        let mut devices = Vec::new();
+        let mut contexts = Vec::new();
+        for (device, capture_context) in multi_device
+            .devices
+            .drain(..)
+            .zip(capture_contexts.drain(..))
+        {
+            if let CaptureContext::Error(e) = capture_context {
+                failed.push((device.get_name().clone(), e));
+            } else {
+                devices.push(device);
+                contexts.push(capture_context);
+            }
         }
+        multi_device.devices = devices;
+        multi_device.update_addresses();
+        *capture_contexts = contexts;
+        failed
     }
 
     pub fn get_name(&self) -> String {
         match self {
             Self::Device(device) => device.get_name().clone(),
+            Self::Devices(multi_device) => multi_device
+                .devices
+                .iter()
+                .map(|device| device.get_name().as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
             Self::File(file) => file.path.clone(),
         }
     }
 
+    /// Returns the names of the network adapters being captured (empty for files)
+    pub fn get_device_names(&self) -> Vec<&String> {
+        match self {
+            Self::Device(device) => vec![device.get_name()],
+            Self::Devices(multi_device) => multi_device
+                .devices
+                .iter()
+                .map(MyDevice::get_name)
+                .collect(),
+            Self::File(_) => Vec::new(),
+        }
+    }
+
     #[cfg(target_os = "windows")]
     pub fn get_desc(&self) -> Option<String> {
         match self {
             Self::Device(device) => device.get_desc().cloned(),
+            Self::Devices(multi_device) => Some(
+                multi_device
+                    .devices
+                    .iter()
+                    .map(|device| device.get_desc().unwrap_or(device.get_name()).as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            ),
             Self::File(_) => None,
         }
     }
 }
 
+fn set_device_addresses(my_device: &mut MyDevice, dev_list: &[Device]) {
+    let mut addresses = Vec::new();
+    for dev in dev_list {
+        if matches!(
+            my_device.get_link_type(),
+            MyLinkType::LinuxSll(_) | MyLinkType::LinuxSll2(_)
+        ) {
+            addresses.extend(dev.addresses.clone());
+        } else if dev.name.eq(my_device.get_name()) {
+            addresses.extend(dev.addresses.clone());
+            break;
+        }
+    }
+    my_device.set_addresses(addresses);
+}
+
+/// Returns the path of the PCAP file dedicated to the given device,
+/// used when capturing from multiple network adapters
+fn pcap_path_for_device(pcap_out_path: &str, device_name: &str) -> String {
+    let sanitized_name: String = device_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || ['-', '_', '.'].contains(c))
+        .collect();
+    let path = Path::new(pcap_out_path);
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{stem}_{sanitized_name}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{sanitized_name}"),
+    };
+    path.with_file_name(file_name).to_string_lossy().to_string()
+}
+
+/// Multiple network adapters captured in parallel
+#[derive(Clone)]
+pub struct MyMultiDevice {
+    devices: Vec<MyDevice>,
+    addresses: Vec<Address>, // union of the devices addresses
+}
+
+impl MyMultiDevice {
+    pub fn new(devices: Vec<MyDevice>) -> Self {
+        let mut multi_device = Self {
+            devices,
+            addresses: vec![],
+        };
+        multi_device.update_addresses();
+        multi_device
+    }
+
+    pub fn devices(&self) -> &Vec<MyDevice> {
+        &self.devices
+    }
+
+    fn update_addresses(&mut self) {
+        self.addresses = Vec::new();
+        for device in &self.devices {
+            for address in device.get_addresses() {
+                if !self.addresses.iter().any(|a| a.addr == address.addr) {
+                    self.addresses.push(address.clone());
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct MyPcapImport {
     path: String,
@@ -273,5 +730,6 @@ pub fn new(path: String) -> Self {
 pub enum CaptureSourcePicklist {
     #[default]
     Device,
+    Devices,
     File,
 }
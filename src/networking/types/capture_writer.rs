@@ -0,0 +1,148 @@
+use crate::SNIFFNET_TITLECASE;
+use crate::location;
+use crate::networking::pcapng::PcapngWriter;
+use crate::networking::types::capture_context::PacketOwned;
+use crate::networking::types::pcap_rotation::{FileRotation, PcapRotation};
+use crate::utils::error_logger::{ErrorLogger, Location};
+use crate::utils::formatted_strings::APP_VERSION;
+use pcap::{Active, Capture, Error, Linktype, Packet, PacketHeader, Savefile};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::sync::{Arc, Mutex};
+
+/// Length of the global header of classic PCAP files
+const PCAP_FILE_HEADER_LEN: u64 = 24;
+/// Length of the header preceding each packet in classic PCAP files
+const PCAP_RECORD_HEADER_LEN: u64 = 16;
+
+/// pcapng file shared by the captures of all the network adapters
+pub type SharedPcapngFile = Arc<Mutex<PcapngFile>>;
+
+/// Destination of the captured packets, when exporting them to file
+pub enum CaptureWriter {
+    /// Classic PCAP file (one for each network adapter)
+    Pcap(PcapFile),
+    /// pcapng file shared by all the network adapters, with the interface identifier of this one
+    Pcapng(SharedPcapngFile, u32),
+}
+
+impl CaptureWriter {
+    /// Writes the packet, attaching the given comment if supported by the file format
+    pub fn write(&mut self, packet: &PacketOwned, comment: impl FnOnce() -> Option<String>) {
+        match self {
+            Self::Pcap(pcap_file) => {
+                if let Some(header) = &packet.header {
+                    pcap_file.write(header, &packet.data);
+                }
+            }
+            Self::Pcapng(pcapng_file, interface_id) => {
+                if let Ok(mut pcapng_file) = pcapng_file.lock() {
+                    let _ = pcapng_file
+                        .write(*interface_id, packet, comment().as_deref())
+                        .log_err(location!());
+                }
+            }
+        }
+    }
+}
+
+/// Classic PCAP file where the packets of a network adapter are exported
+pub struct PcapFile {
+    savefile: Savefile,
+    link_type: Linktype,
+    bytes_written: u64,
+    rotation: Option<FileRotation>,
+}
+
+impl PcapFile {
+    pub fn new(cap: &Capture<Active>, path: &str, rotation: PcapRotation) -> Result<Self, Error> {
+        let mut rotation = FileRotation::new(path, rotation);
+        let savefile = match rotation.as_mut() {
+            Some(rotation) => rotation.rotate(|path| cap.savefile(path))?,
+            None => cap.savefile(path)?,
+        };
+        Ok(Self {
+            savefile,
+            link_type: cap.get_datalink(),
+            bytes_written: PCAP_FILE_HEADER_LEN,
+            rotation,
+        })
+    }
+
+    fn write(&mut self, header: &PacketHeader, data: &[u8]) {
+        if let Some(rotation) = &mut self.rotation
+            && rotation.is_due(self.bytes_written)
+        {
+            let link_type = self.link_type;
+            // savefiles only need the link type of the capture they belong to;
+            // if the new file can't be created, keep writing to the current one
+            let savefile_res = rotation
+                .rotate(|path| Capture::dead(link_type).and_then(|cap| cap.savefile(path)))
+                .log_err(location!());
+            if let Ok(savefile) = savefile_res {
+                self.savefile = savefile;
+                self.bytes_written = PCAP_FILE_HEADER_LEN;
+            }
+        }
+        self.savefile.write(&Packet { header, data });
+        self.bytes_written += PCAP_RECORD_HEADER_LEN + u64::from(header.caplen);
+    }
+}
+
+/// pcapng file where the packets of all the network adapters are exported
+pub struct PcapngFile {
+    writer: PcapngWriter<BufWriter<File>>,
+    rotation: Option<FileRotation>,
+}
+
+impl PcapngFile {
+    /// Creates the file, whose section header includes Sniffnet version and the BPF filter
+    pub fn new(path: &str, rotation: PcapRotation, bpf: Option<&str>) -> io::Result<Self> {
+        let mut rotation = FileRotation::new(path, rotation);
+        let file = match rotation.as_mut() {
+            Some(rotation) => rotation.rotate(|path| File::create(path))?,
+            None => File::create(path)?,
+        };
+        let user_appl = format!("{SNIFFNET_TITLECASE} {APP_VERSION}");
+        let comment = bpf.map(|bpf| format!("BPF filter: {bpf}"));
+        let writer = PcapngWriter::new(BufWriter::new(file), &user_appl, comment.as_deref())?;
+        Ok(Self { writer, rotation })
+    }
+
+    /// Describes a new network adapter, returning its interface identifier
+    pub fn add_interface(
+        &mut self,
+        link_type: Linktype,
+        name: &str,
+        bpf: Option<&str>,
+    ) -> io::Result<u32> {
+        self.writer
+            .add_interface(link_type, u32::from(u16::MAX), Some(name), bpf)
+    }
+
+    fn write(
+        &mut self,
+        interface_id: u32,
+        packet: &PacketOwned,
+        comment: Option<&str>,
+    ) -> io::Result<()> {
+        if let Some(rotation) = &mut self.rotation
+            && rotation.is_due(self.writer.bytes_written())
+        {
+            let writer = &mut self.writer;
+            // if the new file can't be created, keep writing to the current one
+            let _ = rotation
+                .rotate(|path| {
+                    File::create(path).and_then(|file| writer.restart(BufWriter::new(file)))
+                })
+                .log_err(location!());
+        }
+        self.writer.write_packet(
+            interface_id,
+            packet.timestamp,
+            packet.len,
+            &packet.data,
+            comment,
+        )
+    }
+}
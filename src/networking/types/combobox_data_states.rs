@@ -3,6 +3,8 @@
 
 use crate::countries::types::country::Country;
 use crate::networking::types::host::Host;
+use crate::networking::types::tcp_state::TcpState;
+use crate::networking::types::vlan_ids::VlanIds;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::utils::types::case_insensitive_string::CaseInsensitiveString;
 use iced::widget::combo_box;
@@ -53,6 +55,30 @@ pub fn update_states(&mut self, search: &SearchParameters) {
             );
             data.programs.1 = false;
         }
+
+        if data.server_names.1 {
+            states.server_names = combo_box::State::with_selection(
+                data.server_names.0.iter().map(|c| c.0.clone()).collect(),
+                Some(&search.server_name),
+            );
+            data.server_names.1 = false;
+        }
+
+        if data.tcp_states.1 {
+            states.tcp_states = combo_box::State::with_selection(
+                data.tcp_states.0.iter().map(|c| c.0.clone()).collect(),
+                Some(&search.tcp_state),
+            );
+            data.tcp_states.1 = false;
+        }
+
+        if data.vlans.1 {
+            states.vlans = combo_box::State::with_selection(
+                data.vlans.0.iter().map(|c| c.0.clone()).collect(),
+                Some(&search.vlan),
+            );
+            data.vlans.1 = false;
+        }
     }
 }
 
@@ -62,10 +88,21 @@ pub struct ComboboxData {
     pub asns: (BTreeSet<CaseInsensitiveString>, bool),
     pub countries: (BTreeSet<CaseInsensitiveString>, bool),
     pub programs: (BTreeSet<CaseInsensitiveString>, bool),
+    pub server_names: (BTreeSet<CaseInsensitiveString>, bool),
+    pub tcp_states: (BTreeSet<CaseInsensitiveString>, bool),
+    pub vlans: (BTreeSet<CaseInsensitiveString>, bool),
 }
 
 impl ComboboxData {
     pub fn update_host(&mut self, host: &Host) {
+        if !host.dns_name.is_empty() {
+            self.domains.1 = self
+                .domains
+                .0
+                .insert(CaseInsensitiveString(host.dns_name.clone()))
+                || self.domains.1;
+        }
+
         if !host.domain.is_empty() && host.domain.parse::<IpAddr>().is_err() {
             self.domains.1 = self
                 .domains
@@ -102,6 +139,29 @@ pub fn update_program(&mut self, program: Option<&Process>) {
                 || self.programs.1;
         }
     }
+
+    pub fn update_server_name(&mut self, server_name: &str) {
+        self.server_names.1 = self
+            .server_names
+            .0
+            .insert(CaseInsensitiveString(server_name.to_string()))
+            || self.server_names.1;
+    }
+
+    pub fn update_tcp_state(&mut self, tcp_state: TcpState) {
+        self.tcp_states.1 = self
+            .tcp_states
+            .0
+            .insert(CaseInsensitiveString(tcp_state.to_string()))
+            || self.tcp_states.1;
+    }
+
+    pub fn update_vlan(&mut self, vlan: VlanIds) {
+        if vlan.is_tagged() {
+            self.vlans.1 =
+                self.vlans.0.insert(CaseInsensitiveString(vlan.to_string())) || self.vlans.1;
+        }
+    }
 }
 
 #[derive(Default)]
@@ -110,4 +170,7 @@ pub struct ComboboxStates {
     pub asns: combo_box::State<String>,
     pub countries: combo_box::State<String>,
     pub programs: combo_box::State<String>,
+    pub server_names: combo_box::State<String>,
+    pub tcp_states: combo_box::State<String>,
+    pub vlans: combo_box::State<String>,
 }
@@ -8,6 +8,9 @@
 pub struct ConfigDevice {
     #[serde(deserialize_with = "deserialize_or_default")]
     pub device_name: String,
+    /// Names of the devices selected for a multi-adapter capture
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub device_names: Vec<String>,
 }
 
 impl Default for ConfigDevice {
@@ -22,6 +25,7 @@ fn default() -> Self {
                     flags: DeviceFlags::empty(),
                 })
                 .name,
+            device_names: Vec::new(),
         }
     }
 }
@@ -41,4 +45,13 @@ pub fn to_my_device(&self) -> MyDevice {
         });
         MyDevice::from_pcap_device(standard_device)
     }
+
+    pub fn to_my_devices(&self) -> Vec<MyDevice> {
+        Device::list()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|device| self.device_names.contains(&device.name))
+            .map(MyDevice::from_pcap_device)
+            .collect()
+    }
 }
@@ -0,0 +1,386 @@
+//! Module defining the `InfoFlow` struct, which merges the traffic exchanged
+//! in both directions between two address:port pairs.
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::program::Program;
+use crate::networking::types::service::Service;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use std::collections::HashMap;
+
+/// Amount of traffic exchanged in one direction of a flow
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlowCounters {
+    pub packets: u128,
+    pub bytes: u128,
+}
+
+impl FlowCounters {
+    pub fn data(&self, data_repr: DataRepr) -> u128 {
+        match data_repr {
+            DataRepr::Packets => self.packets,
+            DataRepr::Bytes => self.bytes,
+            DataRepr::Bits => self.bytes * 8,
+        }
+    }
+}
+
+/// Bidirectional flow.
+///
+/// Flows are oriented from the local host to the remote one, so that upstream traffic is the one
+/// sent by the local host; when this can't be determined, they're oriented from their initiator.
+#[derive(Clone, Debug)]
+pub struct InfoFlow {
+    /// Info about the flow as a whole (total data, first and last seen, service, program, ...)
+    pub info: InfoAddressPortPair,
+    /// Traffic from the flow source to the flow destination
+    pub upstream: FlowCounters,
+    /// Traffic from the flow destination to the flow source
+    pub downstream: FlowCounters,
+}
+
+impl InfoFlow {
+    /// Builds the flow the given address:port pair belongs to, returning it along with its key
+    pub fn from_map(
+        map: &HashMap<AddressPortPair, InfoAddressPortPair>,
+        key: &AddressPortPair,
+    ) -> Option<(AddressPortPair, Self)> {
+        let (forward_key, forward, backward) = orientation(map, key)?;
+        Some((forward_key, Self::new(forward, backward)))
+    }
+
+    /// Builds all the flows of the given map, each one along with its key
+    pub fn all_from_map(
+        map: &HashMap<AddressPortPair, InfoAddressPortPair>,
+    ) -> impl Iterator<Item = (AddressPortPair, Self)> {
+        map.keys().filter_map(|key| {
+            let (forward_key, forward, backward) = orientation(map, key)?;
+            // build each flow only once: from its forward direction, if seen
+            (forward_key == *key || forward.is_none())
+                .then(|| (forward_key, Self::new(forward, backward)))
+        })
+    }
+
+    fn new(forward: Option<&InfoAddressPortPair>, backward: Option<&InfoAddressPortPair>) -> Self {
+        let counters = |val: Option<&InfoAddressPortPair>| {
+            val.map_or_else(FlowCounters::default, |val| FlowCounters {
+                packets: val.transmitted_packets,
+                bytes: val.transmitted_bytes,
+            })
+        };
+        let upstream = counters(forward);
+        let downstream = counters(backward);
+
+        let mut info = match (forward, backward) {
+            (Some(forward), _) => forward.clone(),
+            (None, Some(backward)) => {
+                let mut info = backward.clone();
+                std::mem::swap(&mut info.mac_address1, &mut info.mac_address2);
+                info.traffic_direction = backward.traffic_direction.reversed();
+                info
+            }
+            (None, None) => InfoAddressPortPair::default(),
+        };
+
+        if let (Some(_), Some(backward)) = (forward, backward) {
+            merge_backward(&mut info, backward);
+        }
+
+        Self {
+            info,
+            upstream,
+            downstream,
+        }
+    }
+
+    /// Data info of the flow, where upstream traffic is accounted in the direction of the flow
+    pub fn data_info(&self) -> DataInfo {
+        let mut data_info = DataInfo::default();
+        data_info.add_packets(
+            self.upstream.packets,
+            self.upstream.bytes,
+            self.info.traffic_direction,
+            self.info.final_instant,
+        );
+        data_info.add_packets(
+            self.downstream.packets,
+            self.downstream.bytes,
+            self.info.traffic_direction.reversed(),
+            self.info.final_instant,
+        );
+        data_info
+    }
+
+    /// Time elapsed between the first and the last packet of the flow, in microseconds
+    pub fn duration_usecs(&self) -> i64 {
+        self.info
+            .final_timestamp
+            .to_usecs()
+            .zip(self.info.initial_timestamp.to_usecs())
+            .map_or(0, |(last, first)| last.saturating_sub(first).max(0))
+    }
+}
+
+/// Key and directions (forward and backward) of the flow the given address:port pair belongs to
+fn orientation<'a>(
+    map: &'a HashMap<AddressPortPair, InfoAddressPortPair>,
+    key: &AddressPortPair,
+) -> Option<(
+    AddressPortPair,
+    Option<&'a InfoAddressPortPair>,
+    Option<&'a InfoAddressPortPair>,
+)> {
+    let reversed = key.reversed();
+    let (forward_key, forward, backward) = match (map.get(key), map.get(&reversed)) {
+        (None, None) => return None,
+        (Some(val), None) => {
+            if is_upstream(key, val, None) {
+                (*key, Some(val), None)
+            } else {
+                (reversed, None, Some(val))
+            }
+        }
+        (None, Some(rev_val)) => {
+            if is_upstream(&reversed, rev_val, None) {
+                (reversed, Some(rev_val), None)
+            } else {
+                (*key, None, Some(rev_val))
+            }
+        }
+        (Some(val), Some(rev_val)) => {
+            if is_upstream(key, val, Some((&reversed, rev_val))) {
+                (*key, Some(val), Some(rev_val))
+            } else {
+                (reversed, Some(rev_val), Some(val))
+            }
+        }
+    };
+    let backward = backward.filter(|_| forward_key != forward_key.reversed());
+    Some((forward_key, forward, backward))
+}
+
+/// Whether the given direction of a connection is the upstream one
+/// (the reversed direction is also passed, if it has been seen)
+fn is_upstream(
+    key: &AddressPortPair,
+    val: &InfoAddressPortPair,
+    reversed: Option<(&AddressPortPair, &InfoAddressPortPair)>,
+) -> bool {
+    match reversed {
+        // both directions look the same (e.g., traffic between two local hosts):
+        // the flow is oriented from the host that sent the first packet
+        // (ties are broken by the whole key, so that exactly one direction is the upstream one)
+        Some((rev_key, rev_val)) if rev_val.traffic_direction == val.traffic_direction => {
+            (val.initial_timestamp, key.sport, key.source, key.dest)
+                <= (
+                    rev_val.initial_timestamp,
+                    rev_key.sport,
+                    rev_key.source,
+                    rev_key.dest,
+                )
+        }
+        _ => val.traffic_direction == TrafficDirection::Outgoing,
+    }
+}
+
+/// Merges the info about the downstream direction into the ones of the whole flow
+fn merge_backward(info: &mut InfoAddressPortPair, backward: &InfoAddressPortPair) {
+    info.transmitted_bytes += backward.transmitted_bytes;
+    info.transmitted_packets += backward.transmitted_packets;
+    info.initial_timestamp = info.initial_timestamp.min(backward.initial_timestamp);
+    info.final_timestamp = info.final_timestamp.max(backward.final_timestamp);
+    info.final_instant = info.final_instant.max(backward.final_instant);
+    if info.mac_address1.is_none() {
+        info.mac_address1.clone_from(&backward.mac_address2);
+    }
+    if info.mac_address2.is_none() {
+        info.mac_address2.clone_from(&backward.mac_address1);
+    }
+    if info.service == Service::Unknown {
+        info.service = backward.service;
+    }
+    if matches!(info.program, Program::Unknown | Program::NotApplicable) {
+        info.program.clone_from(&backward.program);
+    }
+    if info.server_name.is_none() {
+        info.server_name.clone_from(&backward.server_name);
+    }
+    if info.tunnel.is_none() {
+        info.tunnel = backward.tunnel;
+    }
+    if info.mpls_labels.is_empty() {
+        info.mpls_labels.clone_from(&backward.mpls_labels);
+    }
+    info.fragments += backward.fragments;
+    if info.blacklist_match.is_none() {
+        info.blacklist_match.clone_from(&backward.blacklist_match);
+    }
+    info.tcp_flags.add(backward.tcp_flags);
+    info.tcp_metrics.add(backward.tcp_metrics);
+    for (icmp_type, count) in &backward.icmp_types {
+        *info.icmp_types.entry(*icmp_type).or_default() += count;
+    }
+    for (arp_type, count) in &backward.arp_types {
+        *info.arp_types.entry(*arp_type).or_default() += count;
+    }
+    for (igmp_type, count) in &backward.igmp_types {
+        *info.igmp_types.entry(*igmp_type).or_default() += count;
+    }
+    for adapter in &backward.adapters {
+        if !info.adapters.contains(adapter) {
+            info.adapters.push(adapter.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use crate::utils::types::timestamp::Timestamp;
+    use std::net::IpAddr;
+
+    fn connection() -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.2".parse::<IpAddr>().unwrap(),
+            Some(50000),
+            "8.8.8.8".parse::<IpAddr>().unwrap(),
+            Some(443),
+            Protocol::TCP,
+        )
+    }
+
+    fn info(
+        traffic_direction: TrafficDirection,
+        packets: u128,
+        bytes: u128,
+        first_secs: i64,
+        last_secs: i64,
+    ) -> InfoAddressPortPair {
+        InfoAddressPortPair {
+            traffic_direction,
+            transmitted_packets: packets,
+            transmitted_bytes: bytes,
+            initial_timestamp: Timestamp::new(first_secs, 0),
+            final_timestamp: Timestamp::new(last_secs, 0),
+            ..InfoAddressPortPair::default()
+        }
+    }
+
+    #[test]
+    fn test_flow_merges_both_directions() {
+        let outgoing = connection();
+        let incoming = outgoing.reversed();
+        let map = HashMap::from([
+            (outgoing, info(TrafficDirection::Outgoing, 10, 1000, 5, 9)),
+            (incoming, info(TrafficDirection::Incoming, 20, 30000, 6, 12)),
+        ]);
+
+        for key in [outgoing, incoming] {
+            let (flow_key, flow) = InfoFlow::from_map(&map, &key).unwrap();
+            assert_eq!(flow_key, outgoing);
+            assert_eq!(
+                flow.upstream,
+                FlowCounters {
+                    packets: 10,
+                    bytes: 1000
+                }
+            );
+            assert_eq!(
+                flow.downstream,
+                FlowCounters {
+                    packets: 20,
+                    bytes: 30000
+                }
+            );
+            assert_eq!(flow.info.transmitted_packets, 30);
+            assert_eq!(flow.info.transmitted_bytes, 31000);
+            assert_eq!(flow.info.initial_timestamp, Timestamp::new(5, 0));
+            assert_eq!(flow.info.final_timestamp, Timestamp::new(12, 0));
+            assert_eq!(flow.info.traffic_direction, TrafficDirection::Outgoing);
+            assert_eq!(flow.duration_usecs(), 7_000_000);
+        }
+    }
+
+    #[test]
+    fn test_flow_with_incoming_direction_only() {
+        let incoming = connection().reversed();
+        let map = HashMap::from([(incoming, info(TrafficDirection::Incoming, 3, 180, 1, 2))]);
+
+        let (flow_key, flow) = InfoFlow::from_map(&map, &incoming).unwrap();
+        assert_eq!(flow_key, connection());
+        assert_eq!(flow.upstream, FlowCounters::default());
+        assert_eq!(flow.downstream.packets, 3);
+        assert_eq!(flow.info.traffic_direction, TrafficDirection::Outgoing);
+
+        assert!(InfoFlow::from_map(&map, &AddressPortPair::default()).is_none());
+    }
+
+    #[test]
+    fn test_all_flows_from_map() {
+        let outgoing = connection();
+        let incoming_only = AddressPortPair {
+            sport: Some(53),
+            dport: Some(50001),
+            ..outgoing.reversed()
+        };
+        let map = HashMap::from([
+            (outgoing, info(TrafficDirection::Outgoing, 10, 1000, 5, 9)),
+            (
+                outgoing.reversed(),
+                info(TrafficDirection::Incoming, 20, 30000, 6, 12),
+            ),
+            (
+                incoming_only,
+                info(TrafficDirection::Incoming, 3, 180, 1, 2),
+            ),
+        ]);
+
+        let mut flows: Vec<(AddressPortPair, InfoFlow)> = InfoFlow::all_from_map(&map).collect();
+        flows.sort_by_key(|(key, _)| key.sport);
+        assert_eq!(flows.len(), 2);
+        assert_eq!(flows[0].0, outgoing);
+        assert_eq!(flows[0].1.info.transmitted_packets, 30);
+        assert_eq!(flows[1].0, incoming_only.reversed());
+        assert_eq!(flows[1].1.downstream.packets, 3);
+    }
+
+    #[test]
+    fn test_flow_between_local_hosts_is_oriented_from_initiator() {
+        let first = connection();
+        let second = first.reversed();
+        let map = HashMap::from([
+            (first, info(TrafficDirection::Outgoing, 1, 60, 3, 3)),
+            (second, info(TrafficDirection::Outgoing, 1, 60, 2, 2)),
+        ]);
+
+        let (flow_key, flow) = InfoFlow::from_map(&map, &first).unwrap();
+        assert_eq!(flow_key, second);
+        assert_eq!(flow.info.initial_timestamp, Timestamp::new(2, 0));
+        assert_eq!(flow.info.final_timestamp, Timestamp::new(3, 0));
+    }
+
+    #[test]
+    fn test_flow_with_symmetric_directions_is_emitted_once() {
+        let first = AddressPortPair::new(
+            "192.168.1.2".parse::<IpAddr>().unwrap(),
+            Some(5353),
+            "192.168.1.3".parse::<IpAddr>().unwrap(),
+            Some(5353),
+            Protocol::UDP,
+        );
+        let second = first.reversed();
+        let map = HashMap::from([
+            (first, info(TrafficDirection::Outgoing, 1, 60, 2, 2)),
+            (second, info(TrafficDirection::Outgoing, 1, 60, 2, 2)),
+        ]);
+
+        let (flow_key, _) = InfoFlow::from_map(&map, &first).unwrap();
+        assert_eq!(InfoFlow::from_map(&map, &second).unwrap().0, flow_key);
+        let flows: Vec<(AddressPortPair, InfoFlow)> = InfoFlow::all_from_map(&map).collect();
+        assert_eq!(flows.len(), 1);
+        assert_eq!(flows[0].1.info.transmitted_packets, 2);
+    }
+}
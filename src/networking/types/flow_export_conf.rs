@@ -0,0 +1,97 @@
+use crate::gui::types::conf::deserialize_or_default;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+
+/// Settings of the export of the connections to a flow collector
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct FlowExportConf {
+    /// Whether flow records are exported during the captures
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_active: bool,
+    /// IP address of the collector
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub address: String,
+    /// UDP port of the collector
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub port: u16,
+    /// Protocol used to export the flow records
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub protocol: FlowProtocol,
+    /// Seconds after which the records of long-lived flows are exported, even if still active
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub active_timeout: u64,
+    /// Seconds without packets after which the records of a flow are exported
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub inactive_timeout: u64,
+}
+
+impl Default for FlowExportConf {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            address: "127.0.0.1".to_string(),
+            port: 2055,
+            protocol: FlowProtocol::default(),
+            active_timeout: 60,
+            inactive_timeout: 15,
+        }
+    }
+}
+
+impl FlowExportConf {
+    /// Socket address of the collector, if the configured address is valid
+    pub fn collector(&self) -> Option<SocketAddr> {
+        let ip = self.address.trim().parse::<IpAddr>().ok()?;
+        Some(SocketAddr::new(ip, self.port))
+    }
+}
+
+/// Protocol used to export the flow records
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FlowProtocol {
+    /// `NetFlow` version 5, for legacy collectors (IPv4 flows only)
+    NetflowV5,
+    /// `NetFlow` version 9 (RFC 3954)
+    NetflowV9,
+    /// IP Flow Information Export (RFC 7011)
+    #[default]
+    Ipfix,
+}
+
+impl FlowProtocol {
+    pub const ALL: [FlowProtocol; 3] = [
+        FlowProtocol::NetflowV5,
+        FlowProtocol::NetflowV9,
+        FlowProtocol::Ipfix,
+    ];
+}
+
+impl fmt::Display for FlowProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowProtocol::NetflowV5 => write!(f, "NetFlow v5"),
+            FlowProtocol::NetflowV9 => write!(f, "NetFlow v9"),
+            FlowProtocol::Ipfix => write!(f, "IPFIX"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_export_conf_collector() {
+        let mut conf = FlowExportConf::default();
+        assert_eq!(conf.collector(), "127.0.0.1:2055".parse().ok());
+
+        conf.address = " 2001:db8::5 ".to_string();
+        conf.port = 4739;
+        assert_eq!(conf.collector(), "[2001:db8::5]:4739".parse().ok());
+
+        conf.address = "collector.local".to_string();
+        assert_eq!(conf.collector(), None);
+    }
+}
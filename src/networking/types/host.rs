@@ -3,23 +3,50 @@
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::utils::formatted_strings::clip_text;
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 use std::net::IpAddr;
 
 /// Struct to represent a network host
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
+///
+/// The queried domain name is just a label: it's not part of the host identity,
+/// so that hosts (and the favorites and rules referring to them) don't change when it's learned
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct Host {
     /// Hostname (domain). Obtained from the reverse DNS.
     pub domain: String,
+    /// Domain name queried to obtain the address of this host. Obtained from the sniffed DNS responses.
+    #[serde(default)]
+    pub dns_name: String,
     /// Autonomous System which operates the host
     pub asn: Asn,
     /// Country
     pub country: Country,
 }
 
+impl PartialEq for Host {
+    fn eq(&self, other: &Self) -> bool {
+        self.domain == other.domain && self.asn == other.asn && self.country == other.country
+    }
+}
+
+impl Eq for Host {}
+
+impl Hash for Host {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.domain.hash(state);
+        self.asn.hash(state);
+        self.country.hash(state);
+    }
+}
+
 impl Host {
     /// Used in the host bars
     pub fn to_entry_string(&self) -> String {
-        let mut ret_val = self.domain.clone();
+        let mut ret_val = if self.dns_name.is_empty() {
+            self.domain.clone()
+        } else {
+            format!("{} ({})", self.dns_name, self.domain)
+        };
         if !self.asn.name.is_empty() {
             ret_val.push_str(" - ");
             ret_val.push_str(&self.asn.name);
@@ -29,7 +56,11 @@ pub fn to_entry_string(&self) -> String {
 
     /// Used in the blacklist notifications
     pub fn to_blacklist_string(&self, ip: IpAddr) -> String {
-        let domain = &self.domain;
+        let domain = if self.dns_name.is_empty() {
+            &self.domain
+        } else {
+            &self.dns_name
+        };
         let asn = &self.asn.name;
         let mut info = String::new();
 
@@ -67,7 +98,11 @@ pub struct ThumbnailHost {
 impl ThumbnailHost {
     /// Constructor from a Host
     pub fn from_host(host: &Host, max_text_chars: usize) -> Self {
-        let domain = &host.domain;
+        let domain = if host.dns_name.is_empty() {
+            &host.domain
+        } else {
+            &host.dns_name
+        };
         let asn = &host.asn.name;
         let unclipped =
             if asn.is_empty() || (!domain.trim().is_empty() && domain.parse::<IpAddr>().is_err()) {
@@ -94,11 +129,13 @@ pub struct HostMessage {
 mod tests {
     use crate::networking::types::asn::Asn;
     use crate::networking::types::host::{Host, ThumbnailHost};
+    use std::collections::HashSet;
     use std::net::IpAddr;
 
     fn host_for_tests(domain: &str, asn: &str) -> Host {
         Host {
             domain: domain.to_string(),
+            dns_name: String::new(),
             asn: Asn {
                 name: asn.to_string(),
                 code: "512".to_string(),
@@ -143,6 +180,35 @@ fn test_host_to_entry_string() {
         assert_eq!(host.to_entry_string(), "  - GOOGLE");
     }
 
+    #[test]
+    fn test_host_with_dns_name() {
+        let mut host = host_for_tests("1e100.net", "GOOGLE");
+        host.dns_name = "www.youtube.com".to_string();
+        assert_eq!(
+            host.to_entry_string(),
+            "www.youtube.com (1e100.net) - GOOGLE"
+        );
+        assert_eq!(
+            host.to_blacklist_string(IpAddr::from([142, 250, 180, 14])),
+            "142.250.180.14 (www.youtube.com - GOOGLE)"
+        );
+        assert_eq!(ThumbnailHost::from_host(&host, 26).text, "www.youtube.com");
+
+        let mut host = host_for_tests("142.250.180.14", "");
+        host.dns_name = "www.youtube.com".to_string();
+        assert_eq!(host.to_entry_string(), "www.youtube.com (142.250.180.14)");
+    }
+
+    #[test]
+    fn test_host_identity_ignores_dns_name() {
+        let host = host_for_tests("1e100.net", "GOOGLE");
+        let mut labeled = host.clone();
+        labeled.dns_name = "www.youtube.com".to_string();
+        assert_eq!(host, labeled);
+        let hosts = HashSet::from([host]);
+        assert!(hosts.contains(&labeled));
+    }
+
     #[test]
     fn test_host_to_blacklist_string() {
         let ip = IpAddr::from([8, 8, 8, 8]);
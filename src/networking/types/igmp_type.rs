@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use std::fmt::Write;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub enum IgmpType {
+    MembershipQuery,
+    MembershipReportV1,
+    MembershipReportV2,
+    MembershipReportV3,
+    LeaveGroup,
+    #[default]
+    Unknown,
+}
+
+impl IgmpType {
+    pub fn from_etherparse(igmp_type: &etherparse::IgmpType) -> IgmpType {
+        match igmp_type {
+            etherparse::IgmpType::MembershipQuery(_)
+            | etherparse::IgmpType::MembershipQueryWithSources(_) => Self::MembershipQuery,
+            etherparse::IgmpType::MembershipReportV1(_) => Self::MembershipReportV1,
+            etherparse::IgmpType::MembershipReportV2(_) => Self::MembershipReportV2,
+            etherparse::IgmpType::MembershipReportV3(_) => Self::MembershipReportV3,
+            etherparse::IgmpType::LeaveGroup(_) => Self::LeaveGroup,
+            etherparse::IgmpType::Unknown(_) => Self::Unknown,
+        }
+    }
+
+    pub fn pretty_print_types(map: &HashMap<IgmpType, usize>) -> String {
+        let mut ret_val = String::new();
+
+        let mut vec: Vec<(&IgmpType, &usize)> = map.iter().collect();
+        vec.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        for (igmp_type, n) in vec {
+            let _ = writeln!(ret_val, "   {igmp_type} ({n})");
+        }
+        ret_val
+    }
+}
+
+impl Display for IgmpType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                IgmpType::MembershipQuery => "Membership query",
+                IgmpType::MembershipReportV1 => "Membership report (v1)",
+                IgmpType::MembershipReportV2 => "Membership report (v2)",
+                IgmpType::MembershipReportV3 => "Membership report (v3)",
+                IgmpType::LeaveGroup => "Leave group",
+                IgmpType::Unknown => "?",
+            }
+        )
+    }
+}
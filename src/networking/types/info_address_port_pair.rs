@@ -6,8 +6,14 @@
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_representation::DataRepr;
 use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::igmp_type::IgmpType;
+use crate::networking::types::ip_blacklist::BlacklistMatch;
+use crate::networking::types::mpls_labels::MplsLabels;
 use crate::networking::types::program::Program;
+use crate::networking::types::tcp_metrics::TcpMetrics;
+use crate::networking::types::tcp_state::{TcpFlags, TcpState};
 use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::networking::types::tunnel::Tunnel;
 use crate::report::types::sort_type::SortType;
 use crate::utils::types::timestamp::Timestamp;
 use std::cmp::Ordering;
@@ -41,13 +47,36 @@ pub struct InfoAddressPortPair {
     pub icmp_types: HashMap<IcmpType, usize>,
     /// Types of the ARP operations, with the relative count (this is empty if not ARP)
     pub arp_types: HashMap<ArpType, usize>,
-    /// Whether the remote address is blacklisted
-    pub is_blacklisted: bool,
+    /// Types of the IGMP messages exchanged, with the relative count (this is empty if not IGMP)
+    pub igmp_types: HashMap<IgmpType, usize>,
+    /// Blacklist containing the remote address, if any
+    pub blacklist_match: Option<BlacklistMatch>,
     /// The program associated to this pair
     pub program: Program,
+    /// Network adapters on which this pair has been seen (empty when importing PCAP files)
+    pub adapters: Vec<String>,
+    /// Server name indicated by the client in the TLS or QUIC handshake
+    pub server_name: Option<String>,
+    /// Flags of the TCP segments sent in this direction (this is empty if not TCP)
+    pub tcp_flags: TcpFlags,
+    /// State of the TCP connection, considering the segments sent in both directions
+    pub tcp_state: Option<TcpState>,
+    /// Performance indicators measured from the TCP segments sent in this direction
+    pub tcp_metrics: TcpMetrics,
+    /// Tunnel carrying this pair, if its traffic has been decapsulated
+    pub tunnel: Option<Tunnel>,
+    /// MPLS label stack of the latest packet sent in this direction (this is empty if not MPLS)
+    pub mpls_labels: MplsLabels,
+    /// Amount of IP fragments transmitted between the pair
+    pub fragments: usize,
 }
 
 impl InfoAddressPortPair {
+    /// Whether the remote address is blacklisted
+    pub fn is_blacklisted(&self) -> bool {
+        self.blacklist_match.is_some()
+    }
+
     pub fn refresh(&mut self, other: &Self) {
         // self.program MUST NOT be refreshed here
         self.transmitted_bytes += other.transmitted_bytes;
@@ -55,7 +84,7 @@ pub fn refresh(&mut self, other: &Self) {
         self.final_timestamp = other.final_timestamp;
         self.final_instant = other.final_instant;
         self.service = other.service;
-        self.is_blacklisted = other.is_blacklisted;
+        self.blacklist_match.clone_from(&other.blacklist_match);
         self.traffic_direction = other.traffic_direction;
         for (icmp_type, count) in &other.icmp_types {
             self.icmp_types
@@ -69,6 +98,29 @@ pub fn refresh(&mut self, other: &Self) {
                 .and_modify(|v| *v += count)
                 .or_insert(*count);
         }
+        for (igmp_type, count) in &other.igmp_types {
+            self.igmp_types
+                .entry(*igmp_type)
+                .and_modify(|v| *v += count)
+                .or_insert(*count);
+        }
+        if other.server_name.is_some() {
+            self.server_name.clone_from(&other.server_name);
+        }
+        if other.tunnel.is_some() {
+            self.tunnel = other.tunnel;
+        }
+        if !other.mpls_labels.is_empty() {
+            self.mpls_labels.clone_from(&other.mpls_labels);
+        }
+        self.fragments += other.fragments;
+        self.tcp_flags.add(other.tcp_flags);
+        self.tcp_metrics.add(other.tcp_metrics);
+        for adapter in &other.adapters {
+            if !self.adapters.contains(adapter) {
+                self.adapters.push(adapter.clone());
+            }
+        }
     }
 
     pub fn transmitted_data(&self, data_repr: DataRepr) -> u128 {
@@ -117,8 +169,17 @@ fn default() -> Self {
             traffic_direction: TrafficDirection::default(),
             icmp_types: HashMap::new(),
             arp_types: HashMap::new(),
-            is_blacklisted: false,
+            igmp_types: HashMap::new(),
+            blacklist_match: None,
             program: Program::default(),
+            adapters: Vec::new(),
+            server_name: None,
+            tcp_flags: TcpFlags::default(),
+            tcp_state: None,
+            tcp_metrics: TcpMetrics::default(),
+            tunnel: None,
+            mpls_labels: MplsLabels::default(),
+            fragments: 0,
         }
     }
 }
@@ -191,4 +252,22 @@ fn test_info_address_port_pair_data() {
             Ordering::Greater
         );
     }
+
+    #[test]
+    fn test_info_address_port_pair_refresh_adapters() {
+        let mut pair = InfoAddressPortPair {
+            adapters: vec!["eth0".to_string()],
+            ..Default::default()
+        };
+        let other = InfoAddressPortPair {
+            adapters: vec!["tun0".to_string(), "eth0".to_string()],
+            ..Default::default()
+        };
+
+        pair.refresh(&other);
+        assert_eq!(pair.adapters, vec!["eth0".to_string(), "tun0".to_string()]);
+
+        pair.refresh(&InfoAddressPortPair::default());
+        assert_eq!(pair.adapters, vec!["eth0".to_string(), "tun0".to_string()]);
+    }
 }
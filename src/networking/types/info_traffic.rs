@@ -1,15 +1,21 @@
-use crate::Service;
-use crate::networking::manage_packets::get_local_port;
+use crate::networking::manage_packets::{get_address_to_lookup, get_local_port};
 use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::arp_anomaly::ArpAnomaly;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::data_representation::DataRepr;
 use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::lan_device::LanDevice;
 use crate::networking::types::program_lookup::ProgramLookup;
+use crate::networking::types::tcp_metrics::TcpMetrics;
+use crate::networking::types::tcp_state::TcpFlags;
+use crate::networking::types::vlan_ids::VlanIds;
 use crate::utils::types::timestamp::Timestamp;
+use crate::{Protocol, Service};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::net::IpAddr;
 
 /// Struct containing overall traffic statistics and data.
 #[derive(Debug, Default, Clone)]
@@ -26,6 +32,14 @@ pub struct InfoTraffic {
     pub services: HashMap<Service, DataInfo>,
     /// Map of the hosts with their data info
     pub hosts: HashMap<Host, DataInfoHost>,
+    /// Performance of the TCP traffic exchanged with each remote address
+    pub hosts_tcp_metrics: HashMap<IpAddr, TcpMetrics>,
+    /// Map of the VLANs with their data info (untagged traffic included)
+    pub vlans: HashMap<VlanIds, DataInfo>,
+    /// Map of the devices of the local network, identified by their MAC address
+    pub devices: HashMap<String, LanDevice>,
+    /// Suspicious ARP activity detected in the last interval
+    pub arp_anomalies: Vec<ArpAnomaly>,
 }
 
 impl InfoTraffic {
@@ -74,6 +88,32 @@ pub fn refresh(&mut self, msg: &mut Self, program_lookup_opt: &mut Option<Progra
             }
         }
 
+        for (key, value) in msg.map.iter().filter(|(k, _)| k.protocol == Protocol::TCP) {
+            self.refresh_tcp_state(key);
+            if !value.tcp_metrics.is_empty() {
+                let address = get_address_to_lookup(key, value.traffic_direction);
+                self.hosts_tcp_metrics
+                    .entry(address)
+                    .or_default()
+                    .add(value.tcp_metrics);
+            }
+        }
+
+        for (key, value) in &msg.map {
+            let data_info = value.data_info();
+            self.vlans
+                .entry(key.vlan)
+                .and_modify(|x| x.refresh(data_info))
+                .or_insert(data_info);
+        }
+
+        for (mac_address, device) in &msg.devices {
+            self.devices
+                .entry(mac_address.clone())
+                .and_modify(|x| x.refresh(device))
+                .or_insert_with(|| device.clone());
+        }
+
         for (key, value) in &msg.services {
             self.services
                 .entry(*key)
@@ -89,6 +129,23 @@ pub fn refresh(&mut self, msg: &mut Self, program_lookup_opt: &mut Option<Progra
         }
     }
 
+    /// Updates the state of a TCP connection, considering the segments sent in both directions
+    fn refresh_tcp_state(&mut self, key: &AddressPortPair) {
+        let reversed = key.reversed();
+        let mut tcp_flags = TcpFlags::default();
+        for k in [key, &reversed] {
+            if let Some(info) = self.map.get(k) {
+                tcp_flags.add(info.tcp_flags);
+            }
+        }
+        let tcp_state = tcp_flags.state();
+        for k in [key, &reversed] {
+            if let Some(info) = self.map.get_mut(k) {
+                info.tcp_state = tcp_state;
+            }
+        }
+    }
+
     pub fn get_thumbnail_data(&self, data_repr: DataRepr) -> (u128, u128, u128) {
         let incoming = self.tot_data_info.incoming_data(data_repr);
         let outgoing = self.tot_data_info.outgoing_data(data_repr);
@@ -1,15 +1,26 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::net::IpAddr;
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 use ipnet::IpNet;
 use prefix_trie::joint::set::JointPrefixSet;
+use serde::{Deserialize, Serialize};
+
+use crate::countries::types::country::Country;
+use crate::gui::types::conf::deserialize_or_default;
+use crate::networking::types::host::Host;
 
 #[derive(Clone, Default, Debug)]
 pub struct IpBlacklist {
     ips: Arc<HashSet<IpAddr>>,
     cidrs: Arc<JointPrefixSet<IpNet>>,
+    /// Entries matching the resolved hosts rather than the addresses
+    hosts: Arc<HostRules>,
     is_loading: bool,
+    /// `ETag` of the HTTP response the list was fetched from, if any
+    etag: Option<String>,
 }
 
 impl IpBlacklist {
@@ -17,32 +28,106 @@ pub async fn from_file(path: String) -> Self {
         let Ok(buf) = tokio::fs::read_to_string(&path).await else {
             return IpBlacklist::default();
         };
+        IpBlacklist::parse(&buf, FeedFormat::Plain, 0)
+    }
+
+    /// Parses the content of a list in the given format
+    /// (`csv_column` is the 1-based index of the CSV column containing the addresses)
+    pub fn parse(content: &str, format: FeedFormat, csv_column: usize) -> Self {
         let mut ips = HashSet::new();
         let mut cidrs = JointPrefixSet::new();
-        for line in buf.lines() {
-            let Some(first) = line.split_whitespace().next() else {
-                continue;
-            };
-
-            if let Ok(ip) = first.parse::<IpAddr>() {
+        let mut hosts = HostRules::default();
+        // returns false if the value isn't an address
+        let mut insert = |value: &str| {
+            let value = value.trim();
+            if let Ok(ip) = value.parse::<IpAddr>() {
                 ips.insert(ip);
-            } else if let Ok(cidr) = first.parse::<IpNet>() {
+            } else if let Ok(cidr) = value.parse::<IpNet>() {
                 cidrs.insert(cidr);
+            } else {
+                return false;
+            }
+            true
+        };
+
+        match format {
+            FeedFormat::Plain => {
+                for line in content.lines() {
+                    // both '#' and ';' start a comment
+                    let Some(first) = line
+                        .split(['#', ';'])
+                        .next()
+                        .and_then(|l| l.split_whitespace().next())
+                    else {
+                        continue;
+                    };
+                    insert(first);
+                }
+            }
+            FeedFormat::Csv => {
+                for line in content.lines().filter(|l| !l.trim_start().starts_with('#')) {
+                    if let Some(value) = line.split(',').nth(csv_column.max(1) - 1) {
+                        insert(value.trim().trim_matches('"'));
+                    }
+                }
+            }
+            FeedFormat::Stix => {
+                for value in stix_addresses(content) {
+                    insert(&value);
+                }
+            }
+            FeedFormat::Hosts => {
+                for line in content.lines() {
+                    let mut tokens = line
+                        .split('#')
+                        .next()
+                        .unwrap_or_default()
+                        .split_whitespace();
+                    let Some(first) = tokens.next() else {
+                        continue;
+                    };
+                    let names: Vec<&str> = tokens.collect();
+                    if first.parse::<IpAddr>().is_ok() && !names.is_empty() {
+                        // hosts file line: the address is just a sinkhole, the names are blacklisted
+                        for name in names {
+                            hosts.insert_hosts_file_name(name);
+                        }
+                    } else if !insert(first) {
+                        hosts.insert(first);
+                    }
+                }
             }
         }
+
         IpBlacklist {
             ips: Arc::new(ips),
             cidrs: Arc::new(cidrs),
+            hosts: Arc::new(hosts),
             is_loading: false,
+            etag: None,
         }
     }
 
+    #[must_use]
+    pub fn with_etag(self, etag: Option<String>) -> Self {
+        Self { etag, ..self }
+    }
+
+    pub fn etag(&self) -> Option<&String> {
+        self.etag.as_ref()
+    }
+
     pub fn contains(&self, ip: &IpAddr) -> bool {
         self.ips.contains(ip) || self.cidrs.get_lpm(&IpNet::from(*ip)).is_some()
     }
 
+    /// Whether the given host matches one of the domain, ASN, or country entries
+    pub fn contains_host(&self, host: &Host) -> bool {
+        self.hosts.matches(host)
+    }
+
     pub fn is_invalid(&self) -> bool {
-        self.ips.is_empty() && self.cidrs.is_empty() && !self.is_loading
+        self.ips.is_empty() && self.cidrs.is_empty() && self.hosts.is_empty() && !self.is_loading
     }
 
     pub fn is_loading(&self) -> bool {
@@ -50,12 +135,22 @@ pub fn is_loading(&self) -> bool {
     }
 
     pub fn imported_items_info(&self) -> Option<String> {
-        match (self.ips.len(), self.cidrs.len()) {
-            (0, 0) => None,
-            (ips, 0) => Some(format!("(IPs: {ips})")),
-            (0, cidrs) => Some(format!("(CIDRs: {cidrs})")),
-            (ips, cidrs) => Some(format!("(IPs: {ips}, CIDRs: {cidrs})")),
-        }
+        let counts = [
+            ("IPs", self.ips.len()),
+            ("CIDRs", self.cidrs.len()),
+            (
+                "domains",
+                self.hosts.domains.len() + self.hosts.suffixes.len(),
+            ),
+            ("ASNs", self.hosts.asns.len()),
+            ("countries", self.hosts.countries.len()),
+        ];
+        let items: Vec<String> = counts
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(label, count)| format!("{label}: {count}"))
+            .collect();
+        (!items.is_empty()).then(|| format!("({})", items.join(", ")))
     }
 
     pub fn start_loading(&mut self) {
@@ -63,9 +158,389 @@ pub fn start_loading(&mut self) {
     }
 }
 
+/// Blacklist entries matching the resolved hosts, useful for infrastructure that rotates addresses
+#[derive(Default, Debug)]
+struct HostRules {
+    /// Domains matched exactly
+    domains: HashSet<String>,
+    /// Domains matched together with all their subdomains
+    suffixes: HashSet<String>,
+    /// ASN codes
+    asns: HashSet<String>,
+    countries: HashSet<Country>,
+}
+
+impl HostRules {
+    /// Inserts an entry: `AS` followed by a number is an ASN, a two letters code is a country,
+    /// and anything else is a domain (`*.example.com` and `.example.com` also match its subdomains)
+    fn insert(&mut self, entry: &str) {
+        let entry = entry.trim().trim_end_matches('.');
+        if let Some(code) = entry
+            .strip_prefix("AS")
+            .or_else(|| entry.strip_prefix("as"))
+            .filter(|code| !code.is_empty() && code.chars().all(|c| c.is_ascii_digit()))
+        {
+            self.asns.insert(code.to_string());
+        } else if entry.len() == 2 && entry.chars().all(|c| c.is_ascii_alphabetic()) {
+            let country = Country::from_str(&entry.to_ascii_uppercase());
+            if country != Country::ZZ {
+                self.countries.insert(country);
+            }
+        } else if let Some(suffix) = entry.strip_prefix("*.").or_else(|| entry.strip_prefix('.')) {
+            if !suffix.is_empty() {
+                self.suffixes.insert(suffix.to_ascii_lowercase());
+            }
+        } else if !entry.is_empty() {
+            self.domains.insert(entry.to_ascii_lowercase());
+        }
+    }
+
+    /// Inserts a name listed in a hosts file, skipping the local ones
+    fn insert_hosts_file_name(&mut self, name: &str) {
+        let is_local =
+            !name.contains('.') || name.starts_with("localhost") || name.parse::<IpAddr>().is_ok();
+        if !is_local {
+            self.domains
+                .insert(name.trim_end_matches('.').to_ascii_lowercase());
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+            && self.suffixes.is_empty()
+            && self.asns.is_empty()
+            && self.countries.is_empty()
+    }
+
+    fn matches(&self, host: &Host) -> bool {
+        self.matches_domain(&host.domain)
+            || self.matches_domain(&host.dns_name)
+            || self.asns.contains(&host.asn.code)
+            || self.countries.contains(&host.country)
+    }
+
+    fn matches_domain(&self, domain: &str) -> bool {
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        if domain.is_empty() {
+            return false;
+        }
+        if self.domains.contains(&domain) {
+            return true;
+        }
+        // check the domain and all its parents against the suffixes
+        let mut rest = domain.as_str();
+        loop {
+            if self.suffixes.contains(rest) {
+                return true;
+            }
+            match rest.split_once('.') {
+                Some((_, parent)) => rest = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Extracts the addresses from a STIX 2.1 bundle, considering the patterns of the valid indicators
+/// and the `ipv4-addr` and `ipv6-addr` objects
+fn stix_addresses(content: &str) -> Vec<String> {
+    let Ok(bundle) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let objects = bundle["objects"].as_array().map_or(&[][..], Vec::as_slice);
+    let mut addresses = Vec::new();
+    for object in objects {
+        match object["type"].as_str() {
+            Some("indicator") => {
+                let is_stix_pattern = object["pattern_type"].as_str().is_none_or(|t| t == "stix");
+                let is_revoked = object["revoked"].as_bool().unwrap_or_default();
+                if !is_stix_pattern || is_revoked {
+                    continue;
+                }
+                let pattern = object["pattern"].as_str().unwrap_or_default();
+                // e.g., [ipv4-addr:value = '198.51.100.1' OR ipv4-addr:value ISSUBSET '203.0.113.0/24']
+                for (index, _) in pattern.match_indices("-addr:value") {
+                    let rest = &pattern[index..];
+                    let Some(start) = rest.find('\'') else {
+                        continue;
+                    };
+                    let value = &rest[start + 1..];
+                    if let Some(end) = value.find('\'') {
+                        addresses.push(value[..end].to_string());
+                    }
+                }
+            }
+            Some("ipv4-addr" | "ipv6-addr") => {
+                if let Some(value) = object["value"].as_str() {
+                    addresses.push(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    addresses
+}
+
+/// Format of the content of an IP list
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FeedFormat {
+    /// One address or CIDR per line, with `#` or `;` comments
+    /// (e.g., Spamhaus DROP lists and `FireHOL` netsets)
+    #[default]
+    Plain,
+    /// Comma-separated values, with the addresses in a given column
+    Csv,
+    /// STIX 2.1 bundle of indicators
+    Stix,
+    /// One domain, ASN (e.g., `AS64500`), or country code per line; hosts files are accepted too
+    Hosts,
+}
+
+impl FeedFormat {
+    pub const ALL: [FeedFormat; 4] = [
+        FeedFormat::Plain,
+        FeedFormat::Csv,
+        FeedFormat::Stix,
+        FeedFormat::Hosts,
+    ];
+}
+
+impl fmt::Display for FeedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            FeedFormat::Plain => "Text / DROP / netset",
+            FeedFormat::Csv => "CSV",
+            FeedFormat::Stix => "STIX 2.1",
+            FeedFormat::Hosts => "Domains / ASNs / countries",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Kind of addresses listed by an IP list
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ListCategory {
+    Malware,
+    Tor,
+    Scanner,
+    Spam,
+    #[default]
+    Other,
+}
+
+impl ListCategory {
+    pub const ALL: [ListCategory; 5] = [
+        ListCategory::Malware,
+        ListCategory::Tor,
+        ListCategory::Scanner,
+        ListCategory::Spam,
+        ListCategory::Other,
+    ];
+}
+
+impl fmt::Display for ListCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            ListCategory::Malware => "Malware",
+            ListCategory::Tor => "Tor",
+            ListCategory::Scanner => "Scanner",
+            ListCategory::Spam => "Spam",
+            ListCategory::Other => "Other",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Severity of a hit on an IP list
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum ListSeverity {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl ListSeverity {
+    pub const ALL: [ListSeverity; 3] =
+        [ListSeverity::Low, ListSeverity::Medium, ListSeverity::High];
+}
+
+impl fmt::Display for ListSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            ListSeverity::Low => "Low",
+            ListSeverity::Medium => "Medium",
+            ListSeverity::High => "High",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Named IP list, in addition to the default IP blacklist
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpListConf {
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub name: String,
+    /// Path of the file containing the list, or HTTP(S) URL from which it's fetched
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub path: String,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub format: FeedFormat,
+    /// 1-based index of the column containing the addresses (only used for CSV lists)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub csv_column: usize,
+    /// Minutes between two fetches of a list served over HTTP (0 to never refresh it)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub refresh_minutes: u32,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub category: ListCategory,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub severity: ListSeverity,
+    /// Whether the list contains trusted addresses, which must never be reported as blacklisted
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_allowlist: bool,
+}
+
+impl Default for IpListConf {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            path: String::new(),
+            format: FeedFormat::default(),
+            csv_column: 1,
+            refresh_minutes: 60,
+            category: ListCategory::default(),
+            severity: ListSeverity::default(),
+            is_allowlist: false,
+        }
+    }
+}
+
+impl IpListConf {
+    pub fn new(path: String) -> Self {
+        Self {
+            name: list_name_from_path(&path),
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the list is fetched over HTTP
+    pub fn is_url(&self) -> bool {
+        self.path.starts_with("http://") || self.path.starts_with("https://")
+    }
+}
+
+/// Returns the name of a list given the path of its file (i.e., the file name without extension)
+fn list_name_from_path(path: &str) -> String {
+    Path::new(path).file_stem().map_or_else(
+        || path.to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    )
+}
+
+/// Blacklist containing a remote address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlacklistMatch {
+    pub name: Arc<str>,
+    pub category: ListCategory,
+    pub severity: ListSeverity,
+}
+
+impl fmt::Display for BlacklistMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {})", self.name, self.category, self.severity)
+    }
+}
+
+/// IP lists shared with the thread parsing packets, so that they can be refreshed during a capture
+pub type SharedIpLists = Arc<RwLock<IpLists>>;
+
+/// All the IP lists against which remote addresses are checked
+#[derive(Clone, Default, Debug)]
+pub struct IpLists {
+    blacklists: Vec<(BlacklistMatch, IpBlacklist)>,
+    allowlists: Vec<IpBlacklist>,
+}
+
+impl IpLists {
+    /// Collects the default blacklist (loaded from `default_path`) and the named lists
+    /// (loaded lists are indexed by their path)
+    pub fn new(
+        default_path: &str,
+        default: &IpBlacklist,
+        named: &[IpListConf],
+        loaded: &HashMap<String, IpBlacklist>,
+    ) -> Self {
+        let mut ip_lists = IpLists::default();
+        if !default_path.is_empty() {
+            let default_match = BlacklistMatch {
+                name: Arc::from(list_name_from_path(default_path)),
+                category: ListCategory::default(),
+                severity: ListSeverity::default(),
+            };
+            ip_lists.blacklists.push((default_match, default.clone()));
+        }
+        for conf in named {
+            let Some(list) = loaded.get(&conf.path) else {
+                continue;
+            };
+            if conf.is_allowlist {
+                ip_lists.allowlists.push(list.clone());
+            } else {
+                let list_match = BlacklistMatch {
+                    name: Arc::from(conf.name.as_str()),
+                    category: conf.category,
+                    severity: conf.severity,
+                };
+                ip_lists.blacklists.push((list_match, list.clone()));
+            }
+        }
+        ip_lists
+    }
+
+    /// Returns the most severe blacklist containing the given address,
+    /// or None if the address isn't blacklisted or is allowlisted
+    pub fn lookup(&self, ip: &IpAddr) -> Option<BlacklistMatch> {
+        if self.allowlists.iter().any(|list| list.contains(ip)) {
+            return None;
+        }
+        self.most_severe(|list| list.contains(ip))
+    }
+
+    /// Returns the most severe blacklist matching the host resolved for the given address,
+    /// or None if the host isn't blacklisted or either the host or the address is allowlisted
+    pub fn lookup_host(&self, ip: &IpAddr, host: &Host) -> Option<BlacklistMatch> {
+        if self
+            .allowlists
+            .iter()
+            .any(|list| list.contains(ip) || list.contains_host(host))
+        {
+            return None;
+        }
+        self.most_severe(|list| list.contains_host(host))
+    }
+
+    fn most_severe(&self, is_hit: impl Fn(&IpBlacklist) -> bool) -> Option<BlacklistMatch> {
+        self.blacklists
+            .iter()
+            .filter(|(_, list)| is_hit(list))
+            .map(|(list_match, _)| list_match)
+            // keep the first of the lists with the highest severity
+            .rev()
+            .max_by_key(|list_match| list_match.severity)
+            .cloned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::networking::types::asn::Asn;
+    use rstest::rstest;
     use std::net::Ipv4Addr;
 
     #[tokio::test]
@@ -196,4 +671,214 @@ async fn test_ip_blacklist_real_cidr_ranges() {
         assert!(!blacklist.contains(&IpAddr::V4(Ipv4Addr::new(209, 186, 237, 0))));
         assert!(!blacklist.contains(&IpAddr::V4(Ipv4Addr::new(209, 233, 160, 0))));
     }
+
+    #[rstest]
+    #[case::spamhaus_drop("resources/test/ip_feed_drop.txt", FeedFormat::Plain, "(CIDRs: 2)")]
+    #[case::firehol_netset(
+        "resources/test/ip_feed_netset.txt",
+        FeedFormat::Plain,
+        "(IPs: 1, CIDRs: 2)"
+    )]
+    #[case::csv("resources/test/ip_feed.csv", FeedFormat::Csv, "(IPs: 2, CIDRs: 1)")]
+    #[case::stix(
+        "resources/test/ip_feed_stix.json",
+        FeedFormat::Stix,
+        "(IPs: 3, CIDRs: 1)"
+    )]
+    fn test_ip_blacklist_parse_formats(
+        #[case] path: &str,
+        #[case] format: FeedFormat,
+        #[case] expected_info: &str,
+    ) {
+        let content = std::fs::read_to_string(path).unwrap();
+        let blacklist = IpBlacklist::parse(&content, format, 2);
+        assert_eq!(
+            blacklist.imported_items_info(),
+            Some(expected_info.to_string())
+        );
+    }
+
+    #[test]
+    fn test_ip_blacklist_parse_stix() {
+        let content = std::fs::read_to_string("resources/test/ip_feed_stix.json").unwrap();
+        let blacklist = IpBlacklist::parse(&content, FeedFormat::Stix, 0);
+
+        assert!(blacklist.contains(&IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1))));
+        assert!(blacklist.contains(&IpAddr::V4(Ipv4Addr::new(203, 0, 113, 77))));
+        assert!(blacklist.contains(&"2001:db8::66".parse::<IpAddr>().unwrap()));
+        assert!(blacklist.contains(&IpAddr::V4(Ipv4Addr::new(198, 51, 100, 3))));
+        // revoked indicators and non-STIX patterns are ignored
+        assert!(!blacklist.contains(&IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10))));
+        assert!(!blacklist.contains(&IpAddr::V4(Ipv4Addr::new(192, 0, 2, 20))));
+
+        // not a bundle
+        assert!(IpBlacklist::parse("8.8.8.8", FeedFormat::Stix, 0).is_invalid());
+    }
+
+    fn host(domain: &str, dns_name: &str, asn_code: &str, country: Country) -> Host {
+        Host {
+            domain: domain.to_string(),
+            dns_name: dns_name.to_string(),
+            asn: Asn {
+                code: asn_code.to_string(),
+                name: String::new(),
+            },
+            country,
+        }
+    }
+
+    #[test]
+    fn test_ip_blacklist_parse_hosts() {
+        let content = std::fs::read_to_string("resources/test/host_blacklist.txt").unwrap();
+        let blacklist = IpBlacklist::parse(&content, FeedFormat::Hosts, 0);
+        assert_eq!(
+            blacklist.imported_items_info(),
+            Some("(IPs: 1, domains: 5, ASNs: 2, countries: 1)".to_string())
+        );
+        assert!(blacklist.contains(&IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7))));
+        // sinkhole addresses of hosts files aren't blacklisted
+        assert!(!blacklist.contains(&IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0))));
+
+        let blacklisted = [
+            host("tracker.example.com", "", "1", Country::US),
+            host("C2.Example.Net.", "", "1", Country::US),
+            host("a.b.rotating-cdn.example", "", "1", Country::US),
+            host("rotating-cdn.example", "", "1", Country::US),
+            host("host.dyn.example.org", "", "1", Country::US),
+            // the name queried via DNS is considered too
+            host("1e100.net", "ads.example.com", "1", Country::US),
+            host("", "", "64500", Country::US),
+            host("", "", "64501", Country::US),
+            host("", "", "1", Country::KP),
+        ];
+        for host in &blacklisted {
+            assert!(blacklist.contains_host(host), "{host:?}");
+        }
+
+        let not_blacklisted = [
+            host("localhost", "", "1", Country::US),
+            host("example.com", "", "1", Country::US),
+            host("www.c2.example.net", "", "1", Country::US),
+            host("notrotating-cdn.example", "", "1", Country::US),
+            host("", "", "645001", Country::ZZ),
+        ];
+        for host in &not_blacklisted {
+            assert!(!blacklist.contains_host(host), "{host:?}");
+        }
+    }
+
+    #[test]
+    fn test_ip_lists_lookup_host() {
+        let conf = |name: &str, is_allowlist: bool| IpListConf {
+            name: name.to_string(),
+            path: name.to_string(),
+            format: FeedFormat::Hosts,
+            is_allowlist,
+            ..IpListConf::default()
+        };
+        let named = vec![
+            conf("bad domains", false),
+            conf("bad networks", false),
+            conf("trusted", true),
+        ];
+        let loaded = HashMap::from([
+            (
+                "bad domains".to_string(),
+                IpBlacklist::parse("*.evil.example\nbackup.example.com", FeedFormat::Hosts, 0),
+            ),
+            (
+                "bad networks".to_string(),
+                IpBlacklist::parse("AS64500", FeedFormat::Hosts, 0),
+            ),
+            (
+                "trusted".to_string(),
+                IpBlacklist::parse("backup.example.com\n10.0.0.0/8", FeedFormat::Hosts, 0),
+            ),
+        ]);
+        let ip_lists = IpLists::new("", &IpBlacklist::default(), &named, &loaded);
+        let remote = IpAddr::V4(Ipv4Addr::new(198, 51, 100, 1));
+
+        let hit = ip_lists.lookup_host(&remote, &host("x.evil.example", "", "1", Country::US));
+        assert_eq!(
+            hit.map(|m| m.name.to_string()),
+            Some("bad domains".to_string())
+        );
+        let hit = ip_lists.lookup_host(&remote, &host("", "", "64500", Country::US));
+        assert_eq!(
+            hit.map(|m| m.name.to_string()),
+            Some("bad networks".to_string())
+        );
+        // host lists don't match addresses
+        assert_eq!(ip_lists.lookup(&remote), None);
+
+        // allowlisted hosts and addresses override blacklist hits
+        let host_allowed = host("backup.example.com", "", "64500", Country::US);
+        assert_eq!(ip_lists.lookup_host(&remote, &host_allowed), None);
+        let address_allowed = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        let hit = ip_lists.lookup_host(
+            &address_allowed,
+            &host("x.evil.example", "", "1", Country::US),
+        );
+        assert_eq!(hit, None);
+    }
+
+    #[tokio::test]
+    async fn test_ip_lists_lookup() {
+        let malware = IpListConf {
+            name: "C2 servers".to_string(),
+            path: "resources/test/ip_blacklist_valid.txt".to_string(),
+            category: ListCategory::Malware,
+            severity: ListSeverity::Medium,
+            ..IpListConf::default()
+        };
+        let scanners = IpListConf {
+            category: ListCategory::Scanner,
+            ..IpListConf::new("resources/test/ip_blacklist_valid_with_cidr.txt".to_string())
+        };
+        let allowlist = IpListConf {
+            is_allowlist: true,
+            ..IpListConf::new("resources/test/ip_blacklist_valid_cidr_only.txt".to_string())
+        };
+        assert_eq!(scanners.name, "ip_blacklist_valid_with_cidr");
+        let named = vec![malware, scanners, allowlist];
+        let mut loaded = HashMap::new();
+        for conf in &named {
+            loaded.insert(
+                conf.path.clone(),
+                IpBlacklist::from_file(conf.path.clone()).await,
+            );
+        }
+        let ip_lists = IpLists::new("", &IpBlacklist::default(), &named, &loaded);
+
+        // listed by both blacklists: the most severe wins
+        let hit = ip_lists.lookup(&IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)));
+        assert_eq!(
+            hit.map(|m| m.to_string()),
+            Some("ip_blacklist_valid_with_cidr (Scanner, High)".to_string())
+        );
+        // listed by a single blacklist
+        let hit = ip_lists.lookup(&"::123".parse::<IpAddr>().unwrap());
+        assert_eq!(
+            hit.map(|m| m.to_string()),
+            Some("C2 servers (Malware, Medium)".to_string())
+        );
+        // the allowlist overrides blacklist hits
+        assert_eq!(
+            ip_lists.lookup(&IpAddr::V4(Ipv4Addr::new(1, 2, 3, 255))),
+            None
+        );
+        assert_eq!(
+            ip_lists.lookup(&IpAddr::V4(Ipv4Addr::new(8, 8, 8, 9))),
+            None
+        );
+
+        // the default blacklist is named after its file
+        let default = IpBlacklist::from_file(named[0].path.clone()).await;
+        let ip_lists = IpLists::new(&named[0].path, &default, &[], &HashMap::new());
+        let hit = ip_lists.lookup(&IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)));
+        assert_eq!(
+            hit.map(|m| m.to_string()),
+            Some("ip_blacklist_valid (Other, High)".to_string())
+        );
+    }
 }
@@ -0,0 +1,133 @@
+//! Module defining the `LanDevice` struct, which represents a device of the local network.
+
+use crate::networking::types::data_representation::DataRepr;
+use crate::utils::types::timestamp::Timestamp;
+use std::net::IpAddr;
+
+include!(concat!(env!("OUT_DIR"), "/oui.rs"));
+
+/// Device of the local network, identified by its MAC address
+#[derive(Clone, Debug, Default)]
+pub struct LanDevice {
+    /// IP addresses bound to the MAC address of the device
+    pub ip_addresses: Vec<IpAddr>,
+    /// Name announced by the device (via DHCP, mDNS, or `NetBIOS`)
+    pub hostname: Option<String>,
+    /// Manufacturer of the network interface, derived from the MAC address
+    pub vendor: Option<&'static str>,
+    /// First occurrence of the device
+    pub first_seen: Timestamp,
+    /// Last occurrence of the device
+    pub last_seen: Timestamp,
+    /// Amount of packets sent or received by the device
+    pub transmitted_packets: u128,
+    /// Amount of bytes sent or received by the device
+    pub transmitted_bytes: u128,
+}
+
+impl LanDevice {
+    pub fn new(mac_address: &str, timestamp: Timestamp) -> Self {
+        Self {
+            vendor: get_vendor(mac_address),
+            first_seen: timestamp,
+            last_seen: timestamp,
+            ..Self::default()
+        }
+    }
+
+    pub fn add_packet(&mut self, bytes: u128, timestamp: Timestamp) {
+        self.transmitted_packets += 1;
+        self.transmitted_bytes += bytes;
+        self.last_seen = self.last_seen.max(timestamp);
+    }
+
+    pub fn add_ip_address(&mut self, ip_address: IpAddr) {
+        if !self.ip_addresses.contains(&ip_address) {
+            self.ip_addresses.push(ip_address);
+        }
+    }
+
+    pub fn refresh(&mut self, other: &Self) {
+        for ip_address in &other.ip_addresses {
+            self.add_ip_address(*ip_address);
+        }
+        if other.hostname.is_some() {
+            self.hostname.clone_from(&other.hostname);
+        }
+        self.first_seen = self.first_seen.min(other.first_seen);
+        self.last_seen = self.last_seen.max(other.last_seen);
+        self.transmitted_packets += other.transmitted_packets;
+        self.transmitted_bytes += other.transmitted_bytes;
+    }
+
+    pub fn transmitted_data(&self, data_repr: DataRepr) -> u128 {
+        match data_repr {
+            DataRepr::Packets => self.transmitted_packets,
+            DataRepr::Bytes => self.transmitted_bytes,
+            DataRepr::Bits => self.transmitted_bytes * 8,
+        }
+    }
+}
+
+/// Whether a MAC address identifies a single device
+/// (i.e., it isn't a broadcast or multicast address, and it isn't all zeros)
+pub fn is_unicast_mac(mac_address: &str) -> bool {
+    mac_address != "00:00:00:00:00:00"
+        && mac_address
+            .get(..2)
+            .and_then(|octet| u8::from_str_radix(octet, 16).ok())
+            .is_some_and(|octet| octet & 0x01 == 0)
+}
+
+/// Returns the manufacturer associated with the OUI of a MAC address (if known)
+fn get_vendor(mac_address: &str) -> Option<&'static str> {
+    let mut octets = mac_address
+        .split(':')
+        .map(|octet| u8::from_str_radix(octet, 16).ok());
+    let oui = [0, octets.next()??, octets.next()??, octets.next()??];
+    OUI_VENDORS.get(&u32::from_be_bytes(oui)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_vendor() {
+        assert_eq!(get_vendor("b8:27:eb:12:34:56"), Some("Raspberry Pi"));
+        assert_eq!(get_vendor("00:50:56:ab:cd:ef"), Some("VMware"));
+        assert_eq!(get_vendor("02:42:ac:11:00:02"), None);
+        assert_eq!(get_vendor("invalid"), None);
+    }
+
+    #[test]
+    fn test_is_unicast_mac() {
+        assert!(is_unicast_mac("b8:27:eb:12:34:56"));
+        assert!(!is_unicast_mac("ff:ff:ff:ff:ff:ff"));
+        assert!(!is_unicast_mac("01:00:5e:00:00:fb"));
+        assert!(!is_unicast_mac("33:33:00:00:00:01"));
+        assert!(!is_unicast_mac("00:00:00:00:00:00"));
+    }
+
+    #[test]
+    fn test_refresh_lan_device() {
+        let mut device = LanDevice::new("b8:27:eb:12:34:56", Timestamp::new(10, 0));
+        device.add_packet(100, Timestamp::new(12, 0));
+        device.add_ip_address("192.168.1.10".parse().unwrap());
+
+        let mut other = LanDevice::new("b8:27:eb:12:34:56", Timestamp::new(15, 0));
+        other.add_packet(50, Timestamp::new(16, 0));
+        other.add_ip_address("192.168.1.10".parse().unwrap());
+        other.add_ip_address("fe80::1".parse().unwrap());
+        other.hostname = Some("raspberrypi".to_string());
+
+        device.refresh(&other);
+        assert_eq!(device.vendor, Some("Raspberry Pi"));
+        assert_eq!(device.hostname.as_deref(), Some("raspberrypi"));
+        assert_eq!(device.ip_addresses.len(), 2);
+        assert_eq!(device.first_seen, Timestamp::new(10, 0));
+        assert_eq!(device.last_seen, Timestamp::new(16, 0));
+        assert_eq!(device.transmitted_data(DataRepr::Packets), 2);
+        assert_eq!(device.transmitted_data(DataRepr::Bits), 1200);
+    }
+}
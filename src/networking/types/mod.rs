@@ -1,25 +1,38 @@
 pub mod address_port_pair;
+pub mod arp_anomaly;
 pub mod arp_type;
 pub mod asn;
 pub mod bogon;
 pub mod capture_context;
+pub mod capture_writer;
 pub mod combobox_data_states;
 pub mod config_device;
 pub mod data_info;
 pub mod data_info_host;
 pub mod data_representation;
+pub mod flow;
+pub mod flow_export_conf;
 pub mod host;
 pub mod icmp_type;
+pub mod igmp_type;
 pub mod info_address_port_pair;
 pub mod info_traffic;
 pub mod ip_blacklist;
+pub mod lan_device;
 pub mod latency;
+pub mod mpls_labels;
 pub mod my_device;
 pub mod my_link_type;
+pub mod pcap_rotation;
 pub mod program;
 pub mod program_lookup;
 pub mod protocol;
+pub mod scan;
 pub mod service;
 pub mod service_query;
+pub mod tcp_metrics;
+pub mod tcp_state;
 pub mod traffic_direction;
 pub mod traffic_type;
+pub mod tunnel;
+pub mod vlan_ids;
@@ -0,0 +1,20 @@
+//! Module defining the `MplsLabels` struct, containing the MPLS label stack of a packet.
+
+use std::fmt;
+
+/// MPLS label stack, from the outermost label to the bottom one (empty if not MPLS)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MplsLabels(pub Vec<u32>);
+
+impl MplsLabels {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for MplsLabels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", labels.join(" / "))
+    }
+}
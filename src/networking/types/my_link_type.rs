@@ -14,6 +14,11 @@ pub enum MyLinkType {
     IPv6(Linktype),
     LinuxSll(Linktype),
     LinuxSll2(Linktype),
+    Ppp(Linktype),
+    PppoE(Linktype),
+    Nflog(Linktype),
+    Ieee80211(Linktype),
+    IpOverIb(Linktype),
     Unsupported(Linktype),
     #[default]
     NotYetAssigned,
@@ -34,6 +39,11 @@ pub fn from_pcap_link_type(link_type: Linktype) -> Self {
             Linktype::IPV6 => Self::IPv6(link_type),
             Linktype::LINUX_SLL => Self::LinuxSll(link_type),
             Linktype::LINUX_SLL2 => Self::LinuxSll2(link_type),
+            Linktype::PPP | Linktype::PPP_HDLC | Linktype::PPP_WITH_DIR => Self::Ppp(link_type),
+            Linktype::PPP_ETHER => Self::PppoE(link_type),
+            Linktype::NFLOG => Self::Nflog(link_type),
+            Linktype::IEEE802_11 | Linktype::IEEE802_11_RADIOTAP => Self::Ieee80211(link_type),
+            Linktype::IPOIB => Self::IpOverIb(link_type),
             _ => Self::Unsupported(link_type),
         }
     }
@@ -48,6 +58,11 @@ pub fn full_print_on_one_line(self, language: Language) -> String {
             | Self::IPv6(l)
             | Self::LinuxSll(l)
             | Self::LinuxSll2(l)
+            | Self::Ppp(l)
+            | Self::PppoE(l)
+            | Self::Nflog(l)
+            | Self::Ieee80211(l)
+            | Self::IpOverIb(l)
             | Self::Unsupported(l) => {
                 format!(
                     "{}: {}{}",
@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Time to wait before trying again to start a new file, after failing to create one
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Options to rotate the files where the captured packets are exported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PcapRotation {
+    /// Start a new file when the current one exceeds this size (in bytes)
+    pub file_size: Option<u64>,
+    /// Start a new file when the current one has been open for this long
+    pub file_duration: Option<Duration>,
+    /// Only keep this number of most recent files (ring buffer)
+    pub files_to_keep: Option<usize>,
+}
+
+impl PcapRotation {
+    pub fn is_active(&self) -> bool {
+        self.file_size.is_some() || self.file_duration.is_some()
+    }
+}
+
+/// State of the rotation of the files exported by a capture
+pub struct FileRotation {
+    options: PcapRotation,
+    base_path: String,
+    /// Progressive number of the current file
+    file_index: u32,
+    file_start: Instant,
+    /// Files written so far (only the ones still on disk)
+    files: VecDeque<String>,
+    /// When the last attempt to start a new file failed, the time to try again
+    retry_at: Option<Instant>,
+}
+
+impl FileRotation {
+    /// Returns None if the given options don't require rotating files
+    pub fn new(base_path: &str, options: PcapRotation) -> Option<Self> {
+        options.is_active().then(|| Self {
+            options,
+            base_path: base_path.to_string(),
+            file_index: 0,
+            file_start: Instant::now(),
+            files: VecDeque::new(),
+            retry_at: None,
+        })
+    }
+
+    /// Returns true if it's time to start a new file, given the size of the current one
+    pub fn is_due(&self, file_bytes: u64) -> bool {
+        if self
+            .retry_at
+            .is_some_and(|retry_at| Instant::now() < retry_at)
+        {
+            return false;
+        }
+        self.options.file_size.is_some_and(|max| file_bytes >= max)
+            || self
+                .options
+                .file_duration
+                .is_some_and(|max| self.file_start.elapsed() >= max)
+    }
+
+    /// Starts the next file, created at the given timestamped path by `create`.
+    ///
+    /// Only files actually created are tracked, and the oldest ones exceeding the ring buffer size are deleted;
+    /// if the creation fails, the current file should be kept and a new one isn't due for a while.
+    pub fn rotate<T, E>(&mut self, create: impl FnOnce(&str) -> Result<T, E>) -> Result<T, E> {
+        let timestamp = jiff::Zoned::now().strftime("%Y%m%d%H%M%S").to_string();
+        let path = rotated_path(&self.base_path, self.file_index + 1, &timestamp);
+        let file = match create(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                self.retry_at = Some(Instant::now() + RETRY_INTERVAL);
+                return Err(e);
+            }
+        };
+        self.file_index += 1;
+        self.file_start = Instant::now();
+        self.retry_at = None;
+        self.files.push_back(path);
+        if let Some(files_to_keep) = self.options.files_to_keep {
+            while self.files.len() > files_to_keep.max(1) {
+                if let Some(oldest) = self.files.pop_front() {
+                    // the file may have been already removed by the user
+                    let _ = std::fs::remove_file(oldest);
+                }
+            }
+        }
+        Ok(file)
+    }
+}
+
+/// Builds the path of a rotated file, adding a progressive number and a timestamp to the base name
+fn rotated_path(base_path: &str, file_index: u32, timestamp: &str) -> String {
+    let path = Path::new(base_path);
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let file_name = match path.extension() {
+        Some(ext) => format!(
+            "{stem}_{file_index:05}_{timestamp}.{}",
+            ext.to_string_lossy()
+        ),
+        None => format!("{stem}_{file_index:05}_{timestamp}"),
+    };
+    path.with_file_name(file_name).to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_path() {
+        assert_eq!(
+            rotated_path("/tmp/sniffnet.pcap", 1, "20240101120000"),
+            "/tmp/sniffnet_00001_20240101120000.pcap"
+        );
+        assert_eq!(
+            rotated_path("capture.pcapng", 42, "20240101120000"),
+            "capture_00042_20240101120000.pcapng"
+        );
+        assert_eq!(
+            rotated_path("/tmp/sniffnet", 7, "20240101120000"),
+            "/tmp/sniffnet_00007_20240101120000"
+        );
+    }
+
+    #[test]
+    fn test_file_rotation_inactive() {
+        assert!(FileRotation::new("sniffnet.pcap", PcapRotation::default()).is_none());
+        let ring_buffer_only = PcapRotation {
+            files_to_keep: Some(3),
+            ..PcapRotation::default()
+        };
+        assert!(FileRotation::new("sniffnet.pcap", ring_buffer_only).is_none());
+    }
+
+    #[test]
+    fn test_file_rotation_is_due() {
+        let rotation = FileRotation::new(
+            "sniffnet.pcap",
+            PcapRotation {
+                file_size: Some(1000),
+                ..PcapRotation::default()
+            },
+        )
+        .unwrap();
+        assert!(!rotation.is_due(999));
+        assert!(rotation.is_due(1000));
+
+        let rotation = FileRotation::new(
+            "sniffnet.pcap",
+            PcapRotation {
+                file_duration: Some(Duration::ZERO),
+                ..PcapRotation::default()
+            },
+        )
+        .unwrap();
+        assert!(rotation.is_due(0));
+    }
+
+    #[test]
+    fn test_file_rotation_ring_buffer() {
+        let dir = std::env::temp_dir().join("sniffnet_test_file_rotation_ring_buffer");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let base_path = dir.join("sniffnet.pcap").to_string_lossy().to_string();
+        let mut rotation = FileRotation::new(
+            &base_path,
+            PcapRotation {
+                file_size: Some(1),
+                files_to_keep: Some(2),
+                ..PcapRotation::default()
+            },
+        )
+        .unwrap();
+
+        let mut paths = Vec::new();
+        for _ in 0..4 {
+            let path = rotation
+                .rotate(|path| std::fs::write(path, "test").map(|()| path.to_string()))
+                .unwrap();
+            paths.push(path);
+        }
+        let exists: Vec<bool> = paths.iter().map(|p| Path::new(p).exists()).collect();
+        assert_eq!(exists, vec![false, false, true, true]);
+        assert!(paths[3].contains("sniffnet_00004_"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_rotation_creation_failed() {
+        let dir = std::env::temp_dir().join("sniffnet_test_file_rotation_creation_failed");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let base_path = dir.join("sniffnet.pcap").to_string_lossy().to_string();
+        let mut rotation = FileRotation::new(
+            &base_path,
+            PcapRotation {
+                file_size: Some(1),
+                files_to_keep: Some(1),
+                ..PcapRotation::default()
+            },
+        )
+        .unwrap();
+        let first = rotation
+            .rotate(|path| std::fs::write(path, "test").map(|()| path.to_string()))
+            .unwrap();
+        assert!(rotation.is_due(1));
+
+        // a file that couldn't be created isn't tracked, and doesn't evict the existing ones
+        let res: Result<(), &str> = rotation.rotate(|_| Err("disk full"));
+        assert_eq!(res, Err("disk full"));
+        assert!(Path::new(&first).exists());
+        assert_eq!(rotation.files, VecDeque::from([first.clone()]));
+
+        // retrying is postponed
+        assert!(!rotation.is_due(u64::MAX));
+        rotation.retry_at = Some(Instant::now());
+        assert!(rotation.is_due(1));
+
+        let second = rotation
+            .rotate(|path| std::fs::write(path, "test").map(|()| path.to_string()))
+            .unwrap();
+        assert!(second.contains("sniffnet_00002_"));
+        assert!(!Path::new(&first).exists());
+        assert_eq!(rotation.files, VecDeque::from([second]));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
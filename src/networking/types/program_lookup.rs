@@ -4,10 +4,9 @@
 use crate::gui::styles::types::style_type::StyleType;
 use crate::gui::types::message::Message;
 use crate::networking::manage_packets::get_local_port;
-use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_representation::DataRepr;
-use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::info_traffic::InfoTraffic;
 use crate::networking::types::program::Program;
 use iced::Element;
 use iced::widget::tooltip::Position;
@@ -124,7 +123,7 @@ fn lookup(&mut self, key: (u16, Protocol), is_new_connection: bool) -> Option<Pr
     pub fn update(
         &mut self,
         lookup_res: (u16, Protocol, Option<Process>),
-        connections: &mut HashMap<AddressPortPair, InfoAddressPortPair>,
+        info_traffic: &mut InfoTraffic,
     ) {
         let key = (lookup_res.0, lookup_res.1);
         let proc = lookup_res.2;
@@ -142,6 +141,7 @@ pub fn update(
         // associate unassigned recent connections on port with the program
         if proc.is_some() {
             let mut reassigned_data = DataInfo::default();
+            let connections = &mut info_traffic.map;
             connections
                 .iter_mut()
                 .filter(|(k, v)| {
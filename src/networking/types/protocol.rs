@@ -12,10 +12,56 @@ pub enum Protocol {
     ICMP,
     /// Address Resolution Protocol
     ARP,
+    /// Internet Group Management Protocol
+    IGMP,
+    /// Any other protocol carried over IP, identified by its number
+    Other(u8),
+}
+
+impl Protocol {
+    /// Number identifying the protocol in the IP header (ARP isn't carried over IP)
+    #[must_use]
+    pub fn ip_number(self, is_ipv6: bool) -> Option<u8> {
+        match self {
+            Protocol::TCP => Some(6),
+            Protocol::UDP => Some(17),
+            Protocol::ICMP if is_ipv6 => Some(58),
+            Protocol::ICMP => Some(1),
+            Protocol::IGMP => Some(2),
+            Protocol::Other(number) => Some(number),
+            Protocol::ARP => None,
+        }
+    }
+
+    /// Name of the protocols carried over IP that are commonly observed
+    fn ip_protocol_name(number: u8) -> Option<&'static str> {
+        match number {
+            4 => Some("IPIP"),
+            41 => Some("IPv6"),
+            47 => Some("GRE"),
+            50 => Some("ESP"),
+            51 => Some("AH"),
+            88 => Some("EIGRP"),
+            89 => Some("OSPF"),
+            103 => Some("PIM"),
+            112 => Some("VRRP"),
+            115 => Some("L2TP"),
+            132 => Some("SCTP"),
+            136 => Some("UDPLite"),
+            137 => Some("MPLS-in-IP"),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Protocol {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Protocol::Other(number) => match Protocol::ip_protocol_name(*number) {
+                Some(name) => write!(f, "{name}"),
+                None => write!(f, "IP-{number}"),
+            },
+            _ => write!(f, "{self:?}"),
+        }
     }
 }
@@ -0,0 +1,78 @@
+//! Module defining the `Scan` struct, representing reconnaissance activity of a remote host.
+
+use crate::translations::translations_6::{host_sweep_translation, port_scan_translation};
+use crate::translations::types::language::Language;
+use std::net::IpAddr;
+
+/// Maximum number of ports or hosts listed when displaying a scan
+const MAX_DISPLAYED: usize = 8;
+
+/// Kind of reconnaissance activity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanKind {
+    /// Many distinct local ports probed
+    PortScan,
+    /// Many distinct local hosts probed
+    HostSweep,
+}
+
+/// Reconnaissance activity of a remote host against the local network
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scan {
+    /// Address of the host probing the local network
+    pub source: IpAddr,
+    pub kind: ScanKind,
+    /// Local ports probed, sorted
+    pub ports: Vec<u16>,
+    /// Local hosts probed, sorted
+    pub hosts: Vec<IpAddr>,
+    /// Number of TCP probes for which the handshake was never completed
+    pub syn_only: usize,
+    /// Number of TCP probes answered with a reset
+    pub resets: usize,
+}
+
+impl Scan {
+    pub fn title(&self, language: Language) -> &'static str {
+        match self.kind {
+            ScanKind::PortScan => port_scan_translation(language),
+            ScanKind::HostSweep => host_sweep_translation(language),
+        }
+    }
+
+    /// Comma separated list of the probed ports or hosts (depending on the kind of scan)
+    pub fn targets_string(&self) -> String {
+        let targets: Vec<String> = match self.kind {
+            ScanKind::PortScan => self.ports.iter().map(ToString::to_string).collect(),
+            ScanKind::HostSweep => self.hosts.iter().map(ToString::to_string).collect(),
+        };
+        let mut displayed: Vec<String> = targets.iter().take(MAX_DISPLAYED).cloned().collect();
+        if targets.len() > MAX_DISPLAYED {
+            displayed.push(format!("… ({})", targets.len()));
+        }
+        displayed.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_targets_string() {
+        let mut scan = Scan {
+            source: IpAddr::from([203, 0, 113, 7]),
+            kind: ScanKind::PortScan,
+            ports: (20..30).collect(),
+            hosts: vec![IpAddr::from([192, 168, 1, 2])],
+            syn_only: 10,
+            resets: 9,
+        };
+        assert_eq!(
+            scan.targets_string(),
+            "20, 21, 22, 23, 24, 25, 26, 27, … (10)"
+        );
+        scan.kind = ScanKind::HostSweep;
+        assert_eq!(scan.targets_string(), "192.168.1.2");
+    }
+}
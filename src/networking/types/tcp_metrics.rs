@@ -0,0 +1,94 @@
+//! Module defining the `TcpMetrics` struct, containing the performance indicators
+//! passively measured from the TCP segments of a connection.
+
+/// Round trip time, retransmissions, and window events of TCP traffic
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TcpMetrics {
+    /// Number of round trip time samples
+    pub rtt_samples: u64,
+    /// Sum of the round trip time samples, in microseconds
+    pub rtt_sum_usecs: u64,
+    /// Smallest round trip time sample, in microseconds
+    pub rtt_min_usecs: Option<u64>,
+    /// Segments carrying data
+    pub data_segments: u64,
+    /// Segments carrying data that had already been sent
+    pub retransmissions: u64,
+    /// Acknowledgements repeated while data was outstanding
+    pub duplicate_acks: u64,
+    /// Segments advertising a zero receive window
+    pub zero_windows: u64,
+}
+
+impl TcpMetrics {
+    pub fn add(&mut self, other: Self) {
+        self.rtt_samples += other.rtt_samples;
+        self.rtt_sum_usecs += other.rtt_sum_usecs;
+        self.rtt_min_usecs = match (self.rtt_min_usecs, other.rtt_min_usecs) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.data_segments += other.data_segments;
+        self.retransmissions += other.retransmissions;
+        self.duplicate_acks += other.duplicate_acks;
+        self.zero_windows += other.zero_windows;
+    }
+
+    pub fn add_rtt_sample(&mut self, rtt_usecs: u64) {
+        self.add(Self {
+            rtt_samples: 1,
+            rtt_sum_usecs: rtt_usecs,
+            rtt_min_usecs: Some(rtt_usecs),
+            ..Self::default()
+        });
+    }
+
+    /// Average round trip time, in microseconds
+    pub fn avg_rtt_usecs(&self) -> Option<u64> {
+        self.rtt_sum_usecs.checked_div(self.rtt_samples)
+    }
+
+    /// Percentage of data segments that were retransmitted
+    #[allow(clippy::cast_precision_loss)]
+    pub fn retransmission_rate(&self) -> Option<f64> {
+        (self.data_segments > 0)
+            .then(|| 100.0 * self.retransmissions as f64 / self.data_segments as f64)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Formats a round trip time given in microseconds, e.g. `12.3 ms`
+#[allow(clippy::cast_precision_loss)]
+pub fn format_rtt(rtt_usecs: u64) -> String {
+    format!("{:.1} ms", rtt_usecs as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_metrics_aggregation() {
+        let mut metrics = TcpMetrics::default();
+        assert_eq!(metrics.avg_rtt_usecs(), None);
+        assert_eq!(metrics.retransmission_rate(), None);
+        assert!(metrics.is_empty());
+
+        metrics.add_rtt_sample(30_000);
+        metrics.add(TcpMetrics {
+            data_segments: 8,
+            retransmissions: 2,
+            ..TcpMetrics::default()
+        });
+        metrics.add_rtt_sample(10_000);
+
+        assert_eq!(metrics.rtt_samples, 2);
+        assert_eq!(metrics.avg_rtt_usecs(), Some(20_000));
+        assert_eq!(metrics.rtt_min_usecs, Some(10_000));
+        assert_eq!(metrics.retransmission_rate(), Some(25.0));
+        assert_eq!(format_rtt(metrics.avg_rtt_usecs().unwrap()), "20.0 ms");
+    }
+}
@@ -0,0 +1,183 @@
+//! Module defining the `TcpFlags` struct and the `TcpState` enum,
+//! used to keep track of the state of TCP connections.
+
+use etherparse::TcpHeader;
+use std::fmt;
+
+/// Number of TCP segments seen with each relevant combination of flags
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TcpFlags {
+    /// Segments opening a connection (SYN without ACK)
+    pub syn: usize,
+    /// Segments accepting a connection (SYN with ACK)
+    pub syn_ack: usize,
+    /// Segments acknowledging data, after the handshake (ACK without SYN)
+    pub ack: usize,
+    /// Segments closing a connection (FIN)
+    pub fin: usize,
+    /// Segments aborting a connection (RST)
+    pub rst: usize,
+}
+
+impl TcpFlags {
+    /// Flags of a single segment
+    pub fn from_header(tcp_header: &TcpHeader) -> Self {
+        Self {
+            syn: usize::from(tcp_header.syn && !tcp_header.ack),
+            syn_ack: usize::from(tcp_header.syn && tcp_header.ack),
+            ack: usize::from(!tcp_header.syn && tcp_header.ack),
+            fin: usize::from(tcp_header.fin),
+            rst: usize::from(tcp_header.rst),
+        }
+    }
+
+    pub fn add(&mut self, other: Self) {
+        self.syn += other.syn;
+        self.syn_ack += other.syn_ack;
+        self.ack += other.ack;
+        self.fin += other.fin;
+        self.rst += other.rst;
+    }
+
+    /// Union of the flags seen, encoded as in the TCP header
+    pub fn bits(&self) -> u8 {
+        let mut bits = 0;
+        if self.fin > 0 {
+            bits |= 0x01;
+        }
+        if self.syn > 0 || self.syn_ack > 0 {
+            bits |= 0x02;
+        }
+        if self.rst > 0 {
+            bits |= 0x04;
+        }
+        if self.syn_ack > 0 || self.ack > 0 {
+            bits |= 0x10;
+        }
+        bits
+    }
+
+    /// State of a connection, given the flags seen in both its directions
+    pub fn state(&self) -> Option<TcpState> {
+        if self.rst > 0 {
+            Some(TcpState::Reset)
+        } else if self.fin > 0 {
+            Some(TcpState::Closed)
+        } else if self.ack > 0 {
+            // the handshake was completed (or took place before the capture started)
+            Some(TcpState::Established)
+        } else if self.syn_ack > 0 && self.syn > 0 {
+            Some(TcpState::HalfOpen)
+        } else if self.syn_ack > 0 {
+            Some(TcpState::SynAck)
+        } else if self.syn > 0 {
+            Some(TcpState::Syn)
+        } else {
+            None
+        }
+    }
+
+    pub fn pretty_print(&self) -> String {
+        format!(
+            "   SYN ({})\n   SYN-ACK ({})\n   ACK ({})\n   FIN ({})\n   RST ({})",
+            self.syn, self.syn_ack, self.ack, self.fin, self.rst
+        )
+    }
+}
+
+/// State of a TCP connection, as observed from the flags of its segments
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TcpState {
+    /// Connection requested, but not answered (yet)
+    Syn,
+    /// Connection accepted, but its request hasn't been seen
+    SynAck,
+    /// Connection accepted, but the handshake was never completed by the client
+    HalfOpen,
+    /// Handshake completed, data can be exchanged
+    Established,
+    /// Connection closed by at least one of the peers
+    Closed,
+    /// Connection aborted
+    Reset,
+}
+
+impl fmt::Display for TcpState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            TcpState::Syn => "SYN sent",
+            TcpState::SynAck => "SYN-ACK received",
+            TcpState::HalfOpen => "Half-open",
+            TcpState::Established => "Established",
+            TcpState::Closed => "Closed (FIN)",
+            TcpState::Reset => "Reset (RST)",
+        };
+        write!(f, "{state}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(syn: bool, ack: bool, fin: bool, rst: bool) -> TcpFlags {
+        let mut tcp_header = TcpHeader::new(50000, 443, 1, 1024);
+        tcp_header.syn = syn;
+        tcp_header.ack = ack;
+        tcp_header.fin = fin;
+        tcp_header.rst = rst;
+        TcpFlags::from_header(&tcp_header)
+    }
+
+    #[test]
+    fn test_tcp_state_transitions() {
+        let mut flags = TcpFlags::default();
+        assert_eq!(flags.state(), None);
+
+        flags.add(segment(true, false, false, false));
+        assert_eq!(flags.state(), Some(TcpState::Syn));
+        flags.add(segment(true, true, false, false));
+        assert_eq!(flags.state(), Some(TcpState::HalfOpen));
+        flags.add(segment(false, true, false, false));
+        assert_eq!(flags.state(), Some(TcpState::Established));
+        flags.add(segment(false, true, true, false));
+        assert_eq!(flags.state(), Some(TcpState::Closed));
+        flags.add(segment(false, false, false, true));
+        assert_eq!(flags.state(), Some(TcpState::Reset));
+
+        assert_eq!(
+            flags,
+            TcpFlags {
+                syn: 1,
+                syn_ack: 1,
+                ack: 2,
+                fin: 1,
+                rst: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_tcp_state_only_syn_ack_seen() {
+        assert_eq!(
+            segment(true, true, false, false).state(),
+            Some(TcpState::SynAck)
+        );
+        assert_eq!(
+            segment(false, true, false, false).state(),
+            Some(TcpState::Established)
+        );
+    }
+
+    #[test]
+    fn test_tcp_flags_bits() {
+        assert_eq!(TcpFlags::default().bits(), 0);
+        assert_eq!(segment(true, false, false, false).bits(), 0x02);
+        assert_eq!(segment(true, true, false, false).bits(), 0x12);
+        let mut flags = segment(false, true, false, false);
+        flags.add(segment(false, true, true, false));
+        assert_eq!(flags.bits(), 0x11);
+        flags.add(segment(false, false, false, true));
+        assert_eq!(flags.bits(), 0x15);
+    }
+}
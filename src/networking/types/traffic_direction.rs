@@ -7,3 +7,13 @@ pub enum TrafficDirection {
     /// Outgoing traffic (from local interface to remote address)
     Outgoing,
 }
+
+impl TrafficDirection {
+    /// Direction of the traffic flowing the opposite way
+    pub fn reversed(self) -> Self {
+        match self {
+            TrafficDirection::Incoming => TrafficDirection::Outgoing,
+            TrafficDirection::Outgoing => TrafficDirection::Incoming,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Module defining the `Tunnel` struct, which describes the encapsulation
+//! of traffic that has been decapsulated before being analyzed.
+
+use std::fmt;
+use std::net::IpAddr;
+
+/// Encapsulation protocols that can be decapsulated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TunnelKind {
+    /// Virtual eXtensible Local Area Network (over UDP)
+    Vxlan,
+    /// Generic Network Virtualization Encapsulation (over UDP)
+    Geneve,
+    /// Generic Routing Encapsulation
+    Gre,
+    /// IPv4 or IPv6 directly encapsulated in IP
+    IpInIp,
+}
+
+impl fmt::Display for TunnelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            TunnelKind::Vxlan => "VXLAN",
+            TunnelKind::Geneve => "Geneve",
+            TunnelKind::Gre => "GRE",
+            TunnelKind::IpInIp => "IP-in-IP",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// Tunnel carrying the traffic of a connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tunnel {
+    pub kind: TunnelKind,
+    /// Source address of the outer packet
+    pub source: IpAddr,
+    /// Destination address of the outer packet
+    pub dest: IpAddr,
+    /// Virtual network identifier (VNI for VXLAN and Geneve, key for GRE)
+    pub id: Option<u32>,
+}
+
+impl fmt::Display for Tunnel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} → {}", self.kind, self.source, self.dest)?;
+        match (self.kind, self.id) {
+            (TunnelKind::Gre, Some(id)) => write!(f, " (key {id})"),
+            (_, Some(id)) => write!(f, " (VNI {id})"),
+            (_, None) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Module defining the `VlanIds` struct, which identifies the VLAN traffic belongs to.
+
+use etherparse::LinkExtHeader;
+use std::fmt;
+
+/// IDs of the 802.1Q VLAN tags of a packet (two of them with 802.1ad, also known as Q-in-Q)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VlanIds {
+    /// ID of the outermost tag (the service tag with Q-in-Q)
+    pub outer: Option<u16>,
+    /// ID of the inner tag (the customer tag with Q-in-Q)
+    pub inner: Option<u16>,
+}
+
+impl VlanIds {
+    pub fn from_link_exts(link_exts: &[LinkExtHeader]) -> Self {
+        let mut ids = link_exts.iter().filter_map(|ext| match ext {
+            LinkExtHeader::Vlan(vlan) => Some(vlan.vlan_id.value()),
+            LinkExtHeader::Macsec(_) => None,
+        });
+        Self {
+            outer: ids.next(),
+            inner: ids.next(),
+        }
+    }
+
+    pub fn is_tagged(self) -> bool {
+        self.outer.is_some()
+    }
+}
+
+impl fmt::Display for VlanIds {
+    /// Formats the IDs as in the names of VLAN sub-interfaces (e.g., `100` or `100.200`),
+    /// or as `-` for untagged traffic
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.outer, self.inner) {
+            (Some(outer), Some(inner)) => write!(f, "{outer}.{inner}"),
+            (Some(outer), None) => write!(f, "{outer}"),
+            _ => write!(f, "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{LaxPacketHeaders, PacketBuilder, VlanId};
+
+    fn vlan_ids(packet: &[u8]) -> VlanIds {
+        let headers = LaxPacketHeaders::from_ethernet(packet).unwrap();
+        VlanIds::from_link_exts(&headers.link_exts)
+    }
+
+    #[test]
+    fn test_vlan_ids_from_packets() {
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [2; 6])
+            .double_vlan(VlanId::try_new(100).unwrap(), VlanId::try_new(200).unwrap())
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[])
+            .unwrap();
+        let qinq = vlan_ids(&packet);
+        assert_eq!((qinq.outer, qinq.inner), (Some(100), Some(200)));
+        assert_eq!(qinq.to_string(), "100.200");
+
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [2; 6])
+            .single_vlan(VlanId::try_new(42).unwrap())
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[])
+            .unwrap();
+        assert_eq!(vlan_ids(&packet).to_string(), "42");
+
+        let mut packet = Vec::new();
+        PacketBuilder::ethernet2([1; 6], [2; 6])
+            .ipv4([10, 0, 0, 1], [10, 0, 0, 2], 64)
+            .udp(5000, 6000)
+            .write(&mut packet, &[])
+            .unwrap();
+        let untagged = vlan_ids(&packet);
+        assert!(!untagged.is_tagged());
+        assert_eq!(untagged.to_string(), "-");
+    }
+}
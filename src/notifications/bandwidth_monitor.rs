@@ -0,0 +1,171 @@
+//! Module to evaluate rate-based and quota-based bandwidth notifications.
+//!
+//! Both notifications use hysteresis to avoid being emitted over and over
+//! while the traffic keeps hovering around the configured threshold.
+
+use crate::networking::types::data_info::DataInfo;
+use crate::notifications::types::notifications::{QuotaNotification, RateNotification};
+use crate::utils::types::timestamp::Timestamp;
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+
+/// Keeps track of the traffic needed to emit bandwidth notifications
+#[derive(Default)]
+pub struct BandwidthMonitor {
+    /// Number of consecutive intervals in which the rate threshold was exceeded
+    rate_streak: u16,
+    /// Whether the rate notification was emitted and the traffic didn't drop enough since then
+    rate_notified: bool,
+    /// Local day to which the quota traffic refers
+    quota_day: Option<Date>,
+    /// Traffic exchanged during the current day
+    daily_data: DataInfo,
+    /// Number of quota notifications emitted during the current day
+    quota_notified: u128,
+}
+
+impl BandwidthMonitor {
+    /// Checks the traffic of the last interval against the rate notification,
+    /// returning the amount exchanged if a notification has to be emitted.
+    ///
+    /// A notification is emitted when the threshold is exceeded for `duration` intervals in a row;
+    /// another one is emitted only after the traffic dropped below the threshold by `hysteresis` percent.
+    pub fn check_rate(&mut self, data_info: DataInfo, rate: &RateNotification) -> Option<u128> {
+        let amount = rate.direction.data(&data_info, rate.data_repr);
+        let threshold = u128::from(rate.threshold);
+
+        if amount > threshold {
+            self.rate_streak = self.rate_streak.saturating_add(1);
+        } else {
+            self.rate_streak = 0;
+        }
+        if self.rate_notified
+            && amount * 100 <= threshold * u128::from(100 - rate.hysteresis.min(100))
+        {
+            self.rate_notified = false;
+        }
+
+        if !rate.is_active || self.rate_notified || self.rate_streak < rate.duration.max(1) {
+            return None;
+        }
+        self.rate_notified = true;
+        Some(amount)
+    }
+
+    /// Adds the traffic of the last interval to the daily total and checks it against the quota
+    /// notification, returning the daily amount if a notification has to be emitted.
+    ///
+    /// A notification is emitted when the quota is reached;
+    /// further ones are emitted each time the traffic grows by `hysteresis` percent of the quota.
+    pub fn check_quota(
+        &mut self,
+        data_info: DataInfo,
+        timestamp: Timestamp,
+        quota: &QuotaNotification,
+    ) -> Option<u128> {
+        let day = timestamp
+            .to_usecs()
+            .and_then(|usecs| jiff::Timestamp::from_microsecond(usecs).ok())
+            .map(|ts| TimeZone::system().to_datetime(ts).date());
+        if day != self.quota_day {
+            self.quota_day = day;
+            self.daily_data = DataInfo::default();
+            self.quota_notified = 0;
+        }
+        self.daily_data.refresh(data_info);
+
+        let amount = quota.direction.data(&self.daily_data, quota.data_repr);
+        let threshold = u128::from(quota.threshold);
+        if !quota.is_active || threshold == 0 || amount < threshold {
+            return None;
+        }
+        let step = threshold * u128::from(quota.hysteresis) / 100;
+        // without hysteresis the quota is only notified once
+        let reached = (amount - threshold).checked_div(step).unwrap_or_default() + 1;
+        if reached <= self.quota_notified {
+            return None;
+        }
+        self.quota_notified = reached;
+        Some(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::types::data_representation::DataRepr;
+    use crate::notifications::types::notifications::DataDirection;
+
+    fn incoming(bytes: u128) -> DataInfo {
+        DataInfo::new_for_tests(1, 1, bytes, 10)
+    }
+
+    #[test]
+    fn test_rate_duration_and_hysteresis() {
+        let mut monitor = BandwidthMonitor::default();
+        let rate = RateNotification {
+            is_active: true,
+            direction: DataDirection::Incoming,
+            data_repr: DataRepr::Bytes,
+            threshold: 1000,
+            duration: 3,
+            hysteresis: 20,
+            ..RateNotification::default()
+        };
+
+        // the threshold must be exceeded for 3 intervals in a row
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(500), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(3000), &rate), Some(3000));
+        assert_eq!(monitor.check_rate(incoming(3000), &rate), None);
+
+        // dropping just below the threshold isn't enough to emit it again
+        assert_eq!(monitor.check_rate(incoming(900), &rate), None);
+        for _ in 0..5 {
+            assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        }
+
+        // dropping below 80% of the threshold is
+        assert_eq!(monitor.check_rate(incoming(800), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), None);
+        assert_eq!(monitor.check_rate(incoming(2000), &rate), Some(2000));
+    }
+
+    #[test]
+    fn test_quota_steps_and_new_day() {
+        let mut monitor = BandwidthMonitor::default();
+        let mut quota = QuotaNotification {
+            is_active: true,
+            direction: DataDirection::Both,
+            data_repr: DataRepr::Bytes,
+            threshold: 1000,
+            hysteresis: 50,
+            ..QuotaNotification::default()
+        };
+        let day_1 = Timestamp::new(0, 0);
+        let day_2 = Timestamp::new(2 * 86_400, 0);
+
+        assert_eq!(monitor.check_quota(incoming(890), day_1, &quota), None);
+        assert_eq!(monitor.check_quota(incoming(90), day_1, &quota), Some(1000));
+        assert_eq!(monitor.check_quota(incoming(480), day_1, &quota), None);
+        // a single notification even if more steps are exceeded at once
+        assert_eq!(
+            monitor.check_quota(incoming(990), day_1, &quota),
+            Some(2490)
+        );
+        assert_eq!(monitor.check_quota(incoming(0), day_1, &quota), Some(2500));
+        assert_eq!(monitor.check_quota(incoming(0), day_1, &quota), None);
+
+        // the total is reset every day, and without hysteresis it's notified once
+        quota.hysteresis = 0;
+        assert_eq!(
+            monitor.check_quota(incoming(1990), day_2, &quota),
+            Some(2000)
+        );
+        assert_eq!(monitor.check_quota(incoming(5000), day_2, &quota), None);
+    }
+}
@@ -1,2 +1,3 @@
+pub mod bandwidth_monitor;
 pub mod notify_and_log;
 pub mod types;
@@ -1,17 +1,22 @@
-use crate::gui::types::favorite::{FavoriteItem, Favorites};
+use crate::gui::types::favorite::{FavoriteItem, FavoriteKey, Favorites};
 use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::scan_detection::ScanDetector;
 use crate::networking::types::capture_context::CaptureSource;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::data_representation::DataRepr;
 use crate::networking::types::host::Host;
+use crate::networking::types::ip_blacklist::BlacklistMatch;
 use crate::networking::types::service::Service;
+use crate::notifications::bandwidth_monitor::BandwidthMonitor;
 use crate::notifications::types::logged_notification::{
-    BlacklistedTransmitted, DataThresholdExceeded, FavoriteTransmitted, LoggedNotification,
-    LoggedNotifications,
+    ArpAnomalyDetected, BandwidthAlert, BandwidthAlertKind, BlacklistedTransmitted,
+    DataThresholdExceeded, FavoriteTransmitted, LoggedNotification, LoggedNotifications,
+    RuleThresholdExceeded, ScanDetected,
 };
 use crate::notifications::types::notifications::{Notifications, RemoteNotifications};
 use crate::notifications::types::sound::{Sound, play};
+use crate::notifications::types::threshold_rule::RuleTarget;
 use crate::report::types::sort_type::SortType;
 use crate::utils::error_logger::{ErrorLogger, Location};
 use crate::utils::formatted_strings::APP_VERSION;
@@ -33,6 +38,7 @@
 /// Checks if one or more notifications have to be emitted and logs them.
 ///
 /// It returns the number of new notifications emitted
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub fn notify_and_log(
     logged_notifications: &mut LoggedNotifications,
     notifications: &Notifications,
@@ -40,6 +46,8 @@ pub fn notify_and_log(
     favorites: &Favorites,
     cs: &CaptureSource,
     addresses_resolved: &HashMap<IpAddr, (String, Host)>,
+    scan_detector: &mut ScanDetector,
+    bandwidth_monitor: &mut BandwidthMonitor,
 ) -> usize {
     let mut sound_to_play = Sound::None;
     let emitted_notifications_prev = logged_notifications.tot();
@@ -74,6 +82,43 @@ pub fn notify_and_log(
         }
     }
 
+    // custom thresholds of favorites and searches
+    if notifications.threshold_rule_notification.is_active {
+        let mut exceeded = false;
+        for rule in &notifications.threshold_rules {
+            let rule_data_info = rule_last_interval(
+                &rule.target,
+                info_traffic_msg,
+                favorites,
+                addresses_resolved,
+            );
+            if rule_data_info.tot_data(rule.data_repr) > u128::from(rule.threshold) {
+                let notification =
+                    LoggedNotification::RuleThresholdExceeded(RuleThresholdExceeded {
+                        id: logged_notifications.tot(),
+                        entity: rule.target.name(),
+                        data_repr: rule.data_repr,
+                        threshold: rule.threshold,
+                        data_info: rule_data_info,
+                        timestamp: get_formatted_timestamp(timestamp),
+                    });
+
+                //log this notification
+                logged_notifications.push(&notification);
+
+                // send remote notification
+                send_remote_notification(notification, notifications.remote_notifications.clone());
+
+                exceeded = true;
+            }
+        }
+
+        // register sound to play
+        if exceeded && sound_to_play.eq(&Sound::None) {
+            sound_to_play = notifications.threshold_rule_notification.sound;
+        }
+    }
+
     // from favorites
     if notifications.favorite_notification.is_active {
         let favorites_last_interval = favorites_last_interval(info_traffic_msg, favorites);
@@ -102,11 +147,12 @@ pub fn notify_and_log(
 
     // IP blacklist
     if notifications.ip_blacklist_notification.is_active {
-        let mut blacklisted_last_interval: HashMap<IpAddr, (Host, DataInfoHost)> = HashMap::new();
-        for (k, v) in info_traffic_msg
+        let mut blacklisted_last_interval: HashMap<IpAddr, (Host, DataInfoHost, BlacklistMatch)> =
+            HashMap::new();
+        for (k, v, list) in info_traffic_msg
             .map
             .iter()
-            .filter(|(_, v)| v.is_blacklisted)
+            .filter_map(|(k, v)| v.blacklist_match.as_ref().map(|list| (k, v, list)))
         {
             let address_to_lookup = &get_address_to_lookup(k, v.traffic_direction);
             let host = addresses_resolved
@@ -121,21 +167,22 @@ pub fn notify_and_log(
             data_info_host.data_info = v.data_info();
             blacklisted_last_interval
                 .entry(*address_to_lookup)
-                .and_modify(|(_, existing_data_info_host)| {
+                .and_modify(|(_, existing_data_info_host, _)| {
                     existing_data_info_host
                         .data_info
                         .refresh(data_info_host.data_info);
                 })
-                .or_insert((host, data_info_host));
+                .or_insert((host, data_info_host, list.clone()));
         }
         if !blacklisted_last_interval.is_empty() {
-            for (ip, (host, data_info_host)) in blacklisted_last_interval {
+            for (ip, (host, data_info_host, list)) in blacklisted_last_interval {
                 let notification =
                     LoggedNotification::BlacklistedTransmitted(BlacklistedTransmitted {
                         id: logged_notifications.tot(),
                         ip,
                         host,
                         data_info_host,
+                        list,
                         timestamp: get_formatted_timestamp(timestamp),
                     });
 
@@ -153,8 +200,113 @@ pub fn notify_and_log(
         }
     }
 
+    // ARP anomalies
+    if notifications.arp_anomaly_notification.is_active
+        && !info_traffic_msg.arp_anomalies.is_empty()
+    {
+        for anomaly in &info_traffic_msg.arp_anomalies {
+            let notification = LoggedNotification::ArpAnomalyDetected(ArpAnomalyDetected {
+                id: logged_notifications.tot(),
+                anomaly: anomaly.clone(),
+                timestamp: get_formatted_timestamp(timestamp),
+            });
+
+            //log this notification
+            logged_notifications.push(&notification);
+
+            // send remote notification
+            send_remote_notification(notification, notifications.remote_notifications.clone());
+        }
+
+        // register sound to play
+        if sound_to_play.eq(&Sound::None) {
+            sound_to_play = notifications.arp_anomaly_notification.sound;
+        }
+    }
+
+    // port scans and host sweeps
+    if notifications.scan_notification.is_active {
+        let scans = scan_detector.update(
+            info_traffic_msg,
+            notifications.scan_notification.ports_threshold,
+            notifications.scan_notification.hosts_threshold,
+        );
+        if !scans.is_empty() {
+            for scan in scans {
+                let notification = LoggedNotification::ScanDetected(ScanDetected {
+                    id: logged_notifications.tot(),
+                    scan,
+                    timestamp: get_formatted_timestamp(timestamp),
+                });
+
+                //log this notification
+                logged_notifications.push(&notification);
+
+                // send remote notification
+                send_remote_notification(notification, notifications.remote_notifications.clone());
+            }
+
+            // register sound to play
+            if sound_to_play.eq(&Sound::None) {
+                sound_to_play = notifications.scan_notification.sound;
+            }
+        }
+    }
+
+    // sustained rate
+    let rate_notification = &notifications.rate_notification;
+    if let Some(amount) = bandwidth_monitor.check_rate(data_info, rate_notification) {
+        let notification = LoggedNotification::BandwidthAlert(BandwidthAlert {
+            id: logged_notifications.tot(),
+            kind: BandwidthAlertKind::Rate {
+                duration: rate_notification.duration,
+            },
+            direction: rate_notification.direction,
+            data_repr: rate_notification.data_repr,
+            threshold: rate_notification.threshold,
+            amount,
+            timestamp: get_formatted_timestamp(timestamp),
+        });
+
+        //log this notification
+        logged_notifications.push(&notification);
+
+        // send remote notification
+        send_remote_notification(notification, notifications.remote_notifications.clone());
+
+        // register sound to play
+        if sound_to_play.eq(&Sound::None) {
+            sound_to_play = rate_notification.sound;
+        }
+    }
+
+    // daily quota
+    let quota_notification = &notifications.quota_notification;
+    if let Some(amount) = bandwidth_monitor.check_quota(data_info, timestamp, quota_notification) {
+        let notification = LoggedNotification::BandwidthAlert(BandwidthAlert {
+            id: logged_notifications.tot(),
+            kind: BandwidthAlertKind::Quota,
+            direction: quota_notification.direction,
+            data_repr: quota_notification.data_repr,
+            threshold: quota_notification.threshold,
+            amount,
+            timestamp: get_formatted_timestamp(timestamp),
+        });
+
+        //log this notification
+        logged_notifications.push(&notification);
+
+        // send remote notification
+        send_remote_notification(notification, notifications.remote_notifications.clone());
+
+        // register sound to play
+        if sound_to_play.eq(&Sound::None) {
+            sound_to_play = quota_notification.sound;
+        }
+    }
+
     // don't play sound when importing data from pcap file
-    if matches!(cs, CaptureSource::Device(_)) {
+    if !matches!(cs, CaptureSource::File(_)) {
         play(sound_to_play, notifications.volume);
     }
 
@@ -228,6 +380,50 @@ fn favorites_last_interval(
     hosts.chain(services).chain(programs).collect()
 }
 
+/// Data exchanged during the last interval by the traffic a rule refers to
+fn rule_last_interval(
+    target: &RuleTarget,
+    info_traffic_msg: &InfoTraffic,
+    favorites: &Favorites,
+    addresses_resolved: &HashMap<IpAddr, (String, Host)>,
+) -> DataInfo {
+    match target {
+        RuleTarget::Favorite(FavoriteKey::Host(host)) => info_traffic_msg
+            .hosts
+            .get(host)
+            .map(|data_info_host| data_info_host.data_info)
+            .unwrap_or_default(),
+        RuleTarget::Favorite(FavoriteKey::Service(service)) => info_traffic_msg
+            .services
+            .get(service)
+            .copied()
+            .unwrap_or_default(),
+        RuleTarget::Favorite(FavoriteKey::Program(program)) => {
+            let mut data_info = DataInfo::default();
+            info_traffic_msg
+                .map
+                .values()
+                .filter(|v| v.program.eq(program))
+                .for_each(|v| data_info.refresh(v.data_info()));
+            data_info
+        }
+        RuleTarget::Search(search) => {
+            let mut data_info = DataInfo::default();
+            for (key, value) in &info_traffic_msg.map {
+                let address_to_lookup = &get_address_to_lookup(key, value.traffic_direction);
+                let r_dns_host = addresses_resolved.get(address_to_lookup);
+                let is_favorite = r_dns_host.is_some_and(|(_, host)| favorites.contains_host(host))
+                    || favorites.contains_service(&value.service)
+                    || favorites.contains_program(&value.program);
+                if search.match_entry(key, value, r_dns_host, is_favorite) {
+                    data_info.refresh(value.data_info());
+                }
+            }
+            data_info
+        }
+    }
+}
+
 fn send_remote_notification(
     notification: LoggedNotification,
     remote_notifications: RemoteNotifications,
@@ -1,11 +1,19 @@
 use crate::gui::types::favorite::FavoriteItem;
+use crate::networking::types::arp_anomaly::ArpAnomaly;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::data_representation::DataRepr;
 use crate::networking::types::host::Host;
+use crate::networking::types::ip_blacklist::BlacklistMatch;
+use crate::networking::types::scan::Scan;
 use crate::networking::types::service::Service;
+use crate::notifications::types::notifications::DataDirection;
 use crate::translations::translations::favorite_transmitted_translation;
 use crate::translations::translations_5::blacklisted_transmitted_translation;
+use crate::translations::translations_6::{
+    arp_anomaly_translation, custom_threshold_exceeded_translation, daily_quota_translation,
+    scan_translation, sustained_rate_translation,
+};
 use crate::translations::types::language::Language;
 use serde_json::json;
 use std::collections::VecDeque;
@@ -67,6 +75,14 @@ pub enum LoggedNotification {
     FavoriteTransmitted(FavoriteTransmitted),
     /// Blacklisted connection exchanged data
     BlacklistedTransmitted(BlacklistedTransmitted),
+    /// Suspicious ARP activity detected
+    ArpAnomalyDetected(ArpAnomalyDetected),
+    /// Port scan or host sweep detected
+    ScanDetected(ScanDetected),
+    /// Sustained rate or daily quota exceeded
+    BandwidthAlert(BandwidthAlert),
+    /// Threshold of a favorite or search exceeded
+    RuleThresholdExceeded(RuleThresholdExceeded),
 }
 
 impl LoggedNotification {
@@ -75,6 +91,10 @@ pub fn id(&self) -> usize {
             LoggedNotification::DataThresholdExceeded(d) => d.id,
             LoggedNotification::FavoriteTransmitted(f) => f.id,
             LoggedNotification::BlacklistedTransmitted(b) => b.id,
+            LoggedNotification::ArpAnomalyDetected(a) => a.id,
+            LoggedNotification::ScanDetected(s) => s.id,
+            LoggedNotification::BandwidthAlert(b) => b.id,
+            LoggedNotification::RuleThresholdExceeded(r) => r.id,
         }
     }
 
@@ -83,6 +103,10 @@ pub fn data_info(&self) -> DataInfo {
             LoggedNotification::DataThresholdExceeded(d) => d.data_info,
             LoggedNotification::FavoriteTransmitted(f) => f.favorite.data_info(),
             LoggedNotification::BlacklistedTransmitted(b) => b.data_info_host.data_info,
+            LoggedNotification::RuleThresholdExceeded(r) => r.data_info,
+            LoggedNotification::ArpAnomalyDetected(_)
+            | LoggedNotification::ScanDetected(_)
+            | LoggedNotification::BandwidthAlert(_) => DataInfo::default(),
         }
     }
 
@@ -90,7 +114,11 @@ pub fn expand(&mut self, expand: bool) {
         match self {
             LoggedNotification::DataThresholdExceeded(d) => d.is_expanded = expand,
             LoggedNotification::FavoriteTransmitted(_)
-            | LoggedNotification::BlacklistedTransmitted(_) => {}
+            | LoggedNotification::BlacklistedTransmitted(_)
+            | LoggedNotification::ArpAnomalyDetected(_)
+            | LoggedNotification::ScanDetected(_)
+            | LoggedNotification::BandwidthAlert(_)
+            | LoggedNotification::RuleThresholdExceeded(_) => {}
         }
     }
 
@@ -99,6 +127,10 @@ pub fn to_json(&self) -> String {
             LoggedNotification::DataThresholdExceeded(d) => d.to_json(),
             LoggedNotification::FavoriteTransmitted(f) => f.to_json(),
             LoggedNotification::BlacklistedTransmitted(b) => b.to_json(),
+            LoggedNotification::ArpAnomalyDetected(a) => a.to_json(),
+            LoggedNotification::ScanDetected(s) => s.to_json(),
+            LoggedNotification::BandwidthAlert(b) => b.to_json(),
+            LoggedNotification::RuleThresholdExceeded(r) => r.to_json(),
         }
     }
 }
@@ -164,6 +196,8 @@ pub struct BlacklistedTransmitted {
     pub(crate) ip: IpAddr,
     pub(crate) host: Host,
     pub(crate) data_info_host: DataInfoHost,
+    /// Blacklist containing the address
+    pub(crate) list: BlacklistMatch,
     pub(crate) timestamp: String,
 }
 
@@ -178,20 +212,140 @@ fn to_json(&self) -> String {
                 "domain": self.host.domain,
                 "asn": self.host.asn.name,
             },
+            "list": {
+                "name": self.list.name.to_string(),
+                "category": self.list.category.to_string(),
+                "severity": self.list.severity.to_string(),
+            },
             "data": DataRepr::Bytes.formatted_string(self.data_info_host.data_info.tot_data(DataRepr::Bytes)),
         })
         .to_string()
     }
 }
 
+#[derive(Clone)]
+pub struct ArpAnomalyDetected {
+    pub(crate) id: usize,
+    pub(crate) anomaly: ArpAnomaly,
+    pub(crate) timestamp: String,
+}
+
+impl ArpAnomalyDetected {
+    fn to_json(&self) -> String {
+        json!({
+            "info": arp_anomaly_translation(Language::EN),
+            "timestamp": self.timestamp,
+            "anomaly": self.anomaly.title(Language::EN),
+            "ip": self.anomaly.ip().map(|ip| ip.to_string()),
+            "mac_addresses": self.anomaly.mac_addresses(),
+        })
+        .to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct ScanDetected {
+    pub(crate) id: usize,
+    pub(crate) scan: Scan,
+    pub(crate) timestamp: String,
+}
+
+impl ScanDetected {
+    fn to_json(&self) -> String {
+        json!({
+            "info": scan_translation(Language::EN),
+            "timestamp": self.timestamp,
+            "scan": self.scan.title(Language::EN),
+            "source": self.scan.source.to_string(),
+            "ports": self.scan.ports,
+            "hosts": self.scan.hosts.iter().map(ToString::to_string).collect::<Vec<String>>(),
+            "syn_only": self.scan.syn_only,
+            "resets": self.scan.resets,
+        })
+        .to_string()
+    }
+}
+
+/// Kind of bandwidth notification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BandwidthAlertKind {
+    /// Threshold exceeded for `duration` seconds in a row
+    Rate { duration: u16 },
+    /// Daily quota exceeded
+    Quota,
+}
+
+#[derive(Clone)]
+pub struct BandwidthAlert {
+    pub(crate) id: usize,
+    pub(crate) kind: BandwidthAlertKind,
+    pub(crate) direction: DataDirection,
+    pub(crate) data_repr: DataRepr,
+    pub(crate) threshold: u64,
+    /// Traffic of the last second (rate) or of the current day (quota)
+    pub(crate) amount: u128,
+    pub(crate) timestamp: String,
+}
+
+impl BandwidthAlert {
+    pub fn title(&self, language: Language) -> &'static str {
+        match self.kind {
+            BandwidthAlertKind::Rate { .. } => sustained_rate_translation(language),
+            BandwidthAlertKind::Quota => daily_quota_translation(language),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        json!({
+            "info": self.title(Language::EN),
+            "timestamp": self.timestamp,
+            "direction": self.direction.get_label(Language::EN),
+            "threshold": self.data_repr.formatted_string(self.threshold.into()),
+            "data": self.data_repr.formatted_string(self.amount),
+            "duration_secs": match self.kind {
+                BandwidthAlertKind::Rate { duration } => Some(duration),
+                BandwidthAlertKind::Quota => None,
+            },
+        })
+        .to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct RuleThresholdExceeded {
+    pub(crate) id: usize,
+    /// Name of the favorite or search the rule refers to
+    pub(crate) entity: String,
+    pub(crate) data_repr: DataRepr,
+    pub(crate) threshold: u64,
+    pub(crate) data_info: DataInfo,
+    pub(crate) timestamp: String,
+}
+
+impl RuleThresholdExceeded {
+    fn to_json(&self) -> String {
+        json!({
+            "info": custom_threshold_exceeded_translation(Language::EN),
+            "timestamp": self.timestamp,
+            "entity": self.entity,
+            "threshold": self.data_repr.formatted_string(self.threshold.into()),
+            "data": self.data_repr.formatted_string(self.data_info.tot_data(self.data_repr)),
+        })
+        .to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::countries::types::country::Country;
     use crate::networking::types::asn::Asn;
+    use crate::networking::types::ip_blacklist::{ListCategory, ListSeverity};
     use crate::networking::types::program::Program;
+    use crate::networking::types::scan::ScanKind;
     use crate::networking::types::traffic_direction::TrafficDirection;
     use crate::networking::types::traffic_type::TrafficType;
+    use std::sync::Arc;
     use std::time::Instant;
 
     #[test]
@@ -219,6 +373,7 @@ fn test_favorite_host_transmitted_to_json() {
         let host = Host {
             country: Country::AE,
             domain: "example.com".to_string(),
+            dns_name: String::new(),
             asn: Asn {
                 code: "12345".to_string(),
                 name: "AS12345".to_string(),
@@ -285,6 +440,7 @@ fn test_blacklisted_transmitted_to_json() {
         let host = Host {
             country: Country::US,
             domain: "malicious.com".to_string(),
+            dns_name: String::new(),
             asn: Asn {
                 code: "54321".to_string(),
                 name: "AS54321".to_string(),
@@ -302,11 +458,96 @@ fn test_blacklisted_transmitted_to_json() {
             ip: IpAddr::from([8, 8, 8, 8]),
             host,
             data_info_host,
+            list: BlacklistMatch {
+                name: Arc::from("C2 servers"),
+                category: ListCategory::Malware,
+                severity: ListSeverity::High,
+            },
             timestamp: "2024-06-01T12:20:00Z".to_string(),
         };
         assert_eq!(
             notification.to_json(),
-            r#"{"info":"New data exchanged from a blacklisted IP","timestamp":"2024-06-01T12:20:00Z","ip":"8.8.8.8","host":{"country":"US","domain":"malicious.com","asn":"AS54321"},"data":"10 KB"}"#
+            r#"{"info":"New data exchanged from a blacklisted IP","timestamp":"2024-06-01T12:20:00Z","ip":"8.8.8.8","host":{"country":"US","domain":"malicious.com","asn":"AS54321"},"list":{"name":"C2 servers","category":"Malware","severity":"High"},"data":"10 KB"}"#
+        );
+    }
+
+    #[test]
+    fn test_arp_anomaly_detected_to_json() {
+        let notification = ArpAnomalyDetected {
+            id: 6,
+            anomaly: ArpAnomaly::MacChanged {
+                ip: [192, 168, 1, 1].into(),
+                previous_mac: "aa:00:00:00:00:01".to_string(),
+                new_mac: "bb:00:00:00:00:02".to_string(),
+            },
+            timestamp: "2024-06-01T12:25:00Z".to_string(),
+        };
+        assert_eq!(
+            notification.to_json(),
+            r#"{"info":"Suspicious ARP activity","timestamp":"2024-06-01T12:25:00Z","anomaly":"IP address claimed by a new MAC address","ip":"192.168.1.1","mac_addresses":["aa:00:00:00:00:01","bb:00:00:00:00:02"]}"#
+        );
+    }
+
+    #[test]
+    fn test_scan_detected_to_json() {
+        let notification = ScanDetected {
+            id: 7,
+            scan: Scan {
+                source: IpAddr::from([203, 0, 113, 7]),
+                kind: ScanKind::HostSweep,
+                ports: vec![22],
+                hosts: vec![
+                    IpAddr::from([192, 168, 1, 2]),
+                    IpAddr::from([192, 168, 1, 3]),
+                ],
+                syn_only: 2,
+                resets: 1,
+            },
+            timestamp: "2024-06-01T12:30:00Z".to_string(),
+        };
+        assert_eq!(
+            notification.to_json(),
+            r#"{"info":"Port scans and host sweeps","timestamp":"2024-06-01T12:30:00Z","scan":"Host sweep","source":"203.0.113.7","ports":[22],"hosts":["192.168.1.2","192.168.1.3"],"syn_only":2,"resets":1}"#
+        );
+    }
+
+    #[test]
+    fn test_bandwidth_alert_to_json() {
+        let mut notification = BandwidthAlert {
+            id: 8,
+            kind: BandwidthAlertKind::Rate { duration: 30 },
+            direction: DataDirection::Incoming,
+            data_repr: DataRepr::Bits,
+            threshold: 50_000_000,
+            amount: 80_000_000,
+            timestamp: "2024-06-01T12:35:00Z".to_string(),
+        };
+        assert_eq!(
+            notification.to_json(),
+            r#"{"info":"Sustained traffic rate exceeded","timestamp":"2024-06-01T12:35:00Z","direction":"Incoming","threshold":"50 Mb","data":"80 Mb","duration_secs":30}"#
+        );
+        notification.kind = BandwidthAlertKind::Quota;
+        notification.direction = DataDirection::Both;
+        notification.data_repr = DataRepr::Bytes;
+        assert_eq!(
+            notification.to_json(),
+            r#"{"info":"Daily traffic quota exceeded","timestamp":"2024-06-01T12:35:00Z","direction":"Both","threshold":"50 MB","data":"80 MB","duration_secs":null}"#
+        );
+    }
+
+    #[test]
+    fn test_rule_threshold_exceeded_to_json() {
+        let notification = RuleThresholdExceeded {
+            id: 9,
+            entity: "program:backup".to_string(),
+            data_repr: DataRepr::Bytes,
+            threshold: 1_000_000,
+            data_info: DataInfo::new_for_tests(10, 900, 1_000, 1_500_000),
+            timestamp: "2024-06-01T12:40:00Z".to_string(),
+        };
+        assert_eq!(
+            notification.to_json(),
+            r#"{"info":"Custom threshold exceeded","timestamp":"2024-06-01T12:40:00Z","entity":"program:backup","threshold":"1.0 MB","data":"1.5 MB"}"#
         );
     }
 }
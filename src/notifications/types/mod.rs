@@ -1,3 +1,4 @@
 pub mod logged_notification;
 pub mod notifications;
 pub mod sound;
+pub mod threshold_rule;
@@ -2,8 +2,13 @@
 
 use crate::ByteMultiple;
 use crate::gui::types::conf::deserialize_or_default;
+use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_representation::DataRepr;
 use crate::notifications::types::sound::Sound;
+use crate::notifications::types::threshold_rule::ThresholdRule;
+use crate::translations::translations::{incoming_translation, outgoing_translation};
+use crate::translations::translations_6::both_directions_translation;
+use crate::translations::types::language::Language;
 
 /// Used to contain the notifications configuration set by the user
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
@@ -18,6 +23,19 @@ pub struct Notifications {
     pub favorite_notification: SimpleNotification,
     #[serde(deserialize_with = "deserialize_or_default")]
     pub ip_blacklist_notification: SimpleNotification,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub arp_anomaly_notification: SimpleNotification,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub scan_notification: ScanNotification,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub rate_notification: RateNotification,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub quota_notification: QuotaNotification,
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub threshold_rule_notification: SimpleNotification,
+    /// Data thresholds for favorites and searches
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub threshold_rules: Vec<ThresholdRule>,
     #[allow(clippy::struct_field_names)]
     #[serde(deserialize_with = "deserialize_or_default")]
     pub remote_notifications: RemoteNotifications,
@@ -36,6 +54,18 @@ fn default() -> Self {
                 is_active: false,
                 sound: Sound::Swhoosh,
             },
+            arp_anomaly_notification: SimpleNotification {
+                is_active: false,
+                sound: Sound::Swhoosh,
+            },
+            scan_notification: ScanNotification::default(),
+            rate_notification: RateNotification::default(),
+            quota_notification: QuotaNotification::default(),
+            threshold_rule_notification: SimpleNotification {
+                is_active: false,
+                sound: Sound::Gulp,
+            },
+            threshold_rules: Vec::new(),
             remote_notifications: RemoteNotifications::default(),
         }
     }
@@ -50,6 +80,16 @@ pub enum Notification {
     Favorite(SimpleNotification),
     /// IP Blacklist notification
     IpBlacklist(SimpleNotification),
+    /// ARP anomaly notification
+    ArpAnomaly(SimpleNotification),
+    /// Port scan and host sweep notification
+    Scan(ScanNotification),
+    /// Sustained rate notification
+    Rate(RateNotification),
+    /// Daily quota notification
+    Quota(QuotaNotification),
+    /// Favorites and searches threshold notification
+    ThresholdRule(SimpleNotification),
 }
 
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
@@ -88,38 +128,8 @@ impl DataNotification {
     /// Arbitrary string constructor. Will fallback values to existing notification if set, or default otherwise
     pub fn from(value: &str, existing: Option<Self>) -> Self {
         let default = existing.unwrap_or_default();
-
-        let mut byte_multiple_inserted = ByteMultiple::B;
-        let chars: Vec<char> = value.trim().chars().collect();
-        let new_threshold = if chars.is_empty() {
-            0
-        } else if !chars.iter().map(|c| char::is_numeric(*c)).any(|x| !x) {
-            // no multiple
-            value.parse::<u64>().unwrap_or(default.previous_threshold)
-        } else {
-            // multiple
-            let last_char = chars.last().unwrap_or(&' ');
-            byte_multiple_inserted = ByteMultiple::from_char(*last_char);
-            let without_multiple: String = chars[0..chars.len() - 1].iter().collect();
-            if without_multiple.parse::<u64>().is_ok()
-                && TryInto::<u64>::try_into(
-                    without_multiple
-                        .parse::<u128>()
-                        .unwrap_or_default()
-                        .saturating_mul(u128::from(byte_multiple_inserted.multiplier())),
-                )
-                .is_ok()
-            {
-                without_multiple.parse::<u64>().unwrap_or_default()
-                    * byte_multiple_inserted.multiplier()
-            } else if without_multiple.is_empty() {
-                byte_multiple_inserted = ByteMultiple::B;
-                0
-            } else {
-                byte_multiple_inserted = default.byte_multiple;
-                default.previous_threshold
-            }
-        };
+        let (new_threshold, byte_multiple_inserted) =
+            parse_threshold(value, default.previous_threshold, default.byte_multiple);
         Self {
             threshold: Some(new_threshold),
             previous_threshold: new_threshold,
@@ -129,6 +139,192 @@ pub fn from(value: &str, existing: Option<Self>) -> Self {
     }
 }
 
+/// Parses a threshold with an optional multiple (e.g., "500k"),
+/// falling back to the previous value if the input is invalid
+pub(super) fn parse_threshold(
+    value: &str,
+    previous_threshold: u64,
+    previous_byte_multiple: ByteMultiple,
+) -> (u64, ByteMultiple) {
+    let mut byte_multiple_inserted = ByteMultiple::B;
+    let chars: Vec<char> = value.trim().chars().collect();
+    let new_threshold = if chars.is_empty() {
+        0
+    } else if !chars.iter().map(|c| char::is_numeric(*c)).any(|x| !x) {
+        // no multiple
+        value.parse::<u64>().unwrap_or(previous_threshold)
+    } else {
+        // multiple
+        let last_char = chars.last().unwrap_or(&' ');
+        byte_multiple_inserted = ByteMultiple::from_char(*last_char);
+        let without_multiple: String = chars[0..chars.len() - 1].iter().collect();
+        if without_multiple.parse::<u64>().is_ok()
+            && TryInto::<u64>::try_into(
+                without_multiple
+                    .parse::<u128>()
+                    .unwrap_or_default()
+                    .saturating_mul(u128::from(byte_multiple_inserted.multiplier())),
+            )
+            .is_ok()
+        {
+            without_multiple.parse::<u64>().unwrap_or_default()
+                * byte_multiple_inserted.multiplier()
+        } else if without_multiple.is_empty() {
+            byte_multiple_inserted = ByteMultiple::B;
+            0
+        } else {
+            byte_multiple_inserted = previous_byte_multiple;
+            previous_threshold
+        }
+    };
+    (new_threshold, byte_multiple_inserted)
+}
+
+/// Direction of the traffic considered by a notification
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug, Default)]
+pub enum DataDirection {
+    Incoming,
+    Outgoing,
+    #[default]
+    Both,
+}
+
+impl DataDirection {
+    pub const ALL: [DataDirection; 3] = [
+        DataDirection::Incoming,
+        DataDirection::Outgoing,
+        DataDirection::Both,
+    ];
+
+    pub fn get_label(self, language: Language) -> &'static str {
+        match self {
+            DataDirection::Incoming => incoming_translation(language),
+            DataDirection::Outgoing => outgoing_translation(language),
+            DataDirection::Both => both_directions_translation(language),
+        }
+    }
+
+    /// Amount of traffic exchanged in this direction
+    pub fn data(self, data_info: &DataInfo, data_repr: DataRepr) -> u128 {
+        match self {
+            DataDirection::Incoming => data_info.incoming_data(data_repr),
+            DataDirection::Outgoing => data_info.outgoing_data(data_repr),
+            DataDirection::Both => data_info.tot_data(data_repr),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
+#[serde(default)]
+pub struct RateNotification {
+    /// Flag to determine if this notification is enabled
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_active: bool,
+    /// The sound to emit
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub sound: Sound,
+    /// Direction of the traffic considered
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub direction: DataDirection,
+    /// Data representation
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub data_repr: DataRepr,
+    /// Traffic per second; if exceeded for `duration` seconds in a row a notification is emitted
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub threshold: u64,
+    /// B, KB, MB or GB
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub byte_multiple: ByteMultiple,
+    /// Seconds the threshold must be exceeded in a row
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub duration: u16,
+    /// Percentage below the threshold the traffic must drop to before another notification is emitted
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub hysteresis: u8,
+}
+
+impl Default for RateNotification {
+    fn default() -> Self {
+        RateNotification {
+            is_active: false,
+            sound: Sound::Gulp,
+            direction: DataDirection::Incoming,
+            data_repr: DataRepr::Bits,
+            threshold: 50_000_000,
+            byte_multiple: ByteMultiple::MB,
+            duration: 30,
+            hysteresis: 20,
+        }
+    }
+}
+
+impl RateNotification {
+    /// Returns the notification with the threshold parsed from a string
+    #[must_use]
+    pub fn with_threshold(self, value: &str) -> Self {
+        let (threshold, byte_multiple) = parse_threshold(value, self.threshold, self.byte_multiple);
+        Self {
+            threshold,
+            byte_multiple,
+            ..self
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
+#[serde(default)]
+pub struct QuotaNotification {
+    /// Flag to determine if this notification is enabled
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_active: bool,
+    /// The sound to emit
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub sound: Sound,
+    /// Direction of the traffic considered
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub direction: DataDirection,
+    /// Data representation
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub data_repr: DataRepr,
+    /// Traffic per day; if exceeded a notification is emitted
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub threshold: u64,
+    /// B, KB, MB or GB
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub byte_multiple: ByteMultiple,
+    /// Percentage of the quota the traffic must grow by before another notification is emitted
+    /// (0 means a single notification per day)
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub hysteresis: u8,
+}
+
+impl Default for QuotaNotification {
+    fn default() -> Self {
+        QuotaNotification {
+            is_active: false,
+            sound: Sound::Gulp,
+            direction: DataDirection::Both,
+            data_repr: DataRepr::Bytes,
+            threshold: 10_000_000_000,
+            byte_multiple: ByteMultiple::GB,
+            hysteresis: 10,
+        }
+    }
+}
+
+impl QuotaNotification {
+    /// Returns the notification with the threshold parsed from a string
+    #[must_use]
+    pub fn with_threshold(self, value: &str) -> Self {
+        let (threshold, byte_multiple) = parse_threshold(value, self.threshold, self.byte_multiple);
+        Self {
+            threshold,
+            byte_multiple,
+            ..self
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy, Default)]
 #[serde(default)]
 pub struct SimpleNotification {
@@ -158,6 +354,34 @@ pub fn off(sound: Sound) -> Self {
     }
 }
 
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
+#[serde(default)]
+pub struct ScanNotification {
+    /// Flag to determine if this notification is enabled
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_active: bool,
+    /// The sound to emit
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub sound: Sound,
+    /// Distinct local ports probed by a remote host within a minute to consider it a port scan
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub ports_threshold: u16,
+    /// Distinct local hosts probed by a remote host within a minute to consider it a host sweep
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub hosts_threshold: u16,
+}
+
+impl Default for ScanNotification {
+    fn default() -> Self {
+        ScanNotification {
+            is_active: false,
+            sound: Sound::Swhoosh,
+            ports_threshold: 20,
+            hosts_threshold: 10,
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Default)]
 #[serde(default)]
 pub struct RemoteNotifications {
@@ -0,0 +1,117 @@
+//! Module defining the `ThresholdRule` struct, representing a data threshold
+//! set for a specific favorite or for the connections matching a search.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ByteMultiple;
+use crate::gui::types::conf::deserialize_or_default;
+use crate::gui::types::favorite::FavoriteKey;
+use crate::networking::types::data_representation::DataRepr;
+use crate::notifications::types::notifications::parse_threshold;
+use crate::report::types::search_parameters::SearchParameters;
+
+/// Traffic to which a threshold rule applies
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleTarget {
+    /// A favorite host, service, or program
+    Favorite(FavoriteKey),
+    /// The connections matching a search
+    Search(Box<SearchParameters>),
+}
+
+impl RuleTarget {
+    /// Name of the entity the rule refers to
+    pub fn name(&self) -> String {
+        match self {
+            RuleTarget::Favorite(favorite) => favorite.to_entry_string(),
+            RuleTarget::Search(search) => search.to_string(),
+        }
+    }
+}
+
+/// Data threshold for a specific part of the traffic
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdRule {
+    pub target: RuleTarget,
+    /// Data representation
+    #[serde(default, deserialize_with = "deserialize_or_default")]
+    pub data_repr: DataRepr,
+    /// Traffic per second; if exceeded a notification is emitted
+    #[serde(default, deserialize_with = "deserialize_or_default")]
+    pub threshold: u64,
+    /// B, KB, MB or GB
+    #[serde(default, deserialize_with = "deserialize_or_default")]
+    pub byte_multiple: ByteMultiple,
+}
+
+impl ThresholdRule {
+    pub fn new(target: RuleTarget) -> Self {
+        Self {
+            target,
+            data_repr: DataRepr::Bytes,
+            threshold: 1_000_000,
+            byte_multiple: ByteMultiple::MB,
+        }
+    }
+
+    /// Returns the rule with the threshold parsed from a string
+    #[must_use]
+    pub fn with_threshold(&self, value: &str) -> Self {
+        let (threshold, byte_multiple) = parse_threshold(value, self.threshold, self.byte_multiple);
+        Self {
+            threshold,
+            byte_multiple,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::service::Service;
+
+    #[test]
+    fn test_rule_target_name() {
+        let host = Host {
+            domain: "storage.example.com".to_string(),
+            asn: Asn {
+                code: "64500".to_string(),
+                name: "EXAMPLE-CLOUD".to_string(),
+            },
+            ..Host::default()
+        };
+        assert_eq!(
+            RuleTarget::Favorite(FavoriteKey::Host(host)).name(),
+            "storage.example.com - EXAMPLE-CLOUD"
+        );
+        assert_eq!(
+            RuleTarget::Favorite(FavoriteKey::Service(Service::Name("https"))).name(),
+            "https"
+        );
+        let search = SearchParameters {
+            program: "backup".to_string(),
+            port_dst: "=443".to_string(),
+            only_favorites: true,
+            ..SearchParameters::default()
+        };
+        assert_eq!(
+            RuleTarget::Search(Box::new(search)).name(),
+            "dport:=443 program:backup favorites"
+        );
+        assert_eq!(RuleTarget::Search(Box::default()).name(), "*");
+    }
+
+    #[test]
+    fn test_rule_with_threshold() {
+        let rule = ThresholdRule::new(RuleTarget::Search(Box::default()));
+        let rule = rule.with_threshold("300k");
+        assert_eq!(rule.threshold, 300_000);
+        assert_eq!(rule.byte_multiple, ByteMultiple::KB);
+        // invalid values are ignored
+        let rule = rule.with_threshold("abc");
+        assert_eq!(rule.threshold, 300_000);
+    }
+}
@@ -0,0 +1,720 @@
+//! Module to export the analyzed traffic to JSON or CSV files.
+
+use crate::countries::types::country::Country;
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::flow::InfoFlow;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::info_traffic::InfoTraffic;
+use crate::networking::types::program::Program;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::report::types::report_format::ReportFormat;
+use crate::report::types::sort_type::SortType;
+use crate::utils::types::timestamp::Timestamp;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// Snapshot of the analyzed traffic, ready to be exported
+#[derive(Serialize, Default, Debug)]
+pub struct TrafficReport {
+    pub connections: Vec<ConnectionRecord>,
+    pub flows: Vec<FlowRecord>,
+    pub hosts: Vec<HostRecord>,
+    pub services: Vec<DataRecord>,
+    pub programs: Vec<DataRecord>,
+}
+
+impl TrafficReport {
+    /// Builds a report from the traffic analyzed so far
+    ///
+    /// Programs are only included when they've been looked up (i.e., in live captures)
+    pub fn new(
+        info_traffic: &InfoTraffic,
+        addresses_resolved: &HashMap<IpAddr, (String, Host)>,
+        programs: Option<&HashMap<Program, DataInfo>>,
+    ) -> Self {
+        let mut connections: Vec<(&AddressPortPair, &InfoAddressPortPair)> =
+            info_traffic.map.iter().collect();
+        connections.sort_by(|&(_, a), &(_, b)| a.compare(b, SortType::Descending, DataRepr::Bytes));
+
+        let mut flows: Vec<(AddressPortPair, InfoFlow)> =
+            InfoFlow::all_from_map(&info_traffic.map).collect();
+        flows.sort_by(|(_, a), (_, b)| {
+            a.info
+                .compare(&b.info, SortType::Descending, DataRepr::Bytes)
+        });
+
+        let mut hosts: Vec<_> = info_traffic.hosts.iter().collect();
+        hosts.sort_by(|&(_, a), &(_, b)| {
+            a.data_info
+                .compare(&b.data_info, SortType::Descending, DataRepr::Bytes)
+        });
+
+        let mut services: Vec<_> = info_traffic.services.iter().collect();
+        services.sort_by(|&(_, a), &(_, b)| a.compare(b, SortType::Descending, DataRepr::Bytes));
+
+        let mut programs: Vec<_> = programs.map(|p| p.iter().collect()).unwrap_or_default();
+        programs.sort_by(|&(_, a): &(&Program, &DataInfo), &(_, b)| {
+            a.compare(b, SortType::Descending, DataRepr::Bytes)
+        });
+
+        Self {
+            connections: connections
+                .into_iter()
+                .map(|(key, val)| {
+                    let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
+                    ConnectionRecord::new(key, val, addresses_resolved.get(&address_to_lookup))
+                })
+                .collect(),
+            flows: flows
+                .into_iter()
+                .map(|(key, flow)| {
+                    let address_to_lookup =
+                        get_address_to_lookup(&key, flow.info.traffic_direction);
+                    FlowRecord::new(&key, &flow, addresses_resolved.get(&address_to_lookup))
+                })
+                .collect(),
+            hosts: hosts
+                .into_iter()
+                .map(|(host, data_info_host)| HostRecord {
+                    domain: host.domain.clone(),
+                    dns_name: host.dns_name.clone(),
+                    country: country_string(host.country),
+                    asn_code: host.asn.code.clone(),
+                    asn_name: host.asn.name.clone(),
+                    is_local: data_info_host.is_local,
+                    is_loopback: data_info_host.is_loopback,
+                    bogon: data_info_host.is_bogon.unwrap_or_default().to_string(),
+                    data: DataCounters::from(data_info_host.data_info),
+                })
+                .collect(),
+            services: services
+                .into_iter()
+                .map(|(service, data_info)| DataRecord {
+                    name: service.to_string(),
+                    path: String::new(),
+                    data: DataCounters::from(*data_info),
+                })
+                .collect(),
+            programs: programs
+                .into_iter()
+                .map(|(program, data_info)| DataRecord {
+                    name: program.to_string(),
+                    path: program.path(),
+                    data: DataCounters::from(*data_info),
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the report to the given path
+    ///
+    /// JSON reports are written to a single file, while CSV reports are split into one file per table:
+    /// connections are written to the given path, while other tables are written next to it
+    /// (e.g., `report_flows.csv`, `report_hosts.csv`, `report_services.csv`, `report_programs.csv`)
+    pub fn write_to_file(&self, path: &Path, format: ReportFormat) -> std::io::Result<()> {
+        match format {
+            ReportFormat::Json => {
+                let mut writer = BufWriter::new(File::create(path)?);
+                self.write_json(&mut writer)?;
+                writer.flush()
+            }
+            ReportFormat::Csv => {
+                write_csv_file(path, &self.connections)?;
+                write_csv_file(&table_path(path, "flows"), &self.flows)?;
+                write_csv_file(&table_path(path, "hosts"), &self.hosts)?;
+                write_csv_file(&table_path(path, "services"), &self.services)?;
+                write_csv_file(&table_path(path, "programs"), &self.programs)
+            }
+        }
+    }
+
+    pub fn write_json(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, self)?;
+        writeln!(writer)
+    }
+}
+
+/// A connection (i.e., a row of the Inspect page table)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ConnectionRecord {
+    pub source: IpAddr,
+    pub source_port: Option<u16>,
+    pub destination: IpAddr,
+    pub destination_port: Option<u16>,
+    pub protocol: String,
+    pub source_mac: Option<String>,
+    pub destination_mac: Option<String>,
+    pub service: String,
+    pub server_name: Option<String>,
+    pub program: String,
+    pub direction: String,
+    pub domain: String,
+    /// Domain name queried via DNS to obtain the remote address
+    pub dns_name: String,
+    pub country: String,
+    pub asn_code: String,
+    pub asn_name: String,
+    pub adapters: Vec<String>,
+    /// Tunnel carrying the connection, if its traffic has been decapsulated
+    pub tunnel: Option<String>,
+    /// IDs of the VLAN the connection belongs to (e.g., `100`, or `100.200` with Q-in-Q)
+    pub vlan: Option<String>,
+    /// MPLS label stack of the latest packet, from the outermost label
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mpls_labels: Vec<u32>,
+    pub is_blacklisted: bool,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub packets: u128,
+    pub bytes: u128,
+    /// Number of IP fragments among the packets
+    pub fragments: usize,
+    /// Types of the ICMP messages exchanged, with the relative count
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub icmp_types: BTreeMap<String, usize>,
+    /// Types of the ARP operations, with the relative count
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub arp_types: BTreeMap<String, usize>,
+    /// Types of the IGMP messages exchanged, with the relative count
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub igmp_types: BTreeMap<String, usize>,
+}
+
+impl ConnectionRecord {
+    pub fn new(
+        key: &AddressPortPair,
+        val: &InfoAddressPortPair,
+        r_dns_host: Option<&(String, Host)>,
+    ) -> Self {
+        let (domain, host) = r_dns_host.cloned().unwrap_or_default();
+        Self {
+            source: key.source,
+            source_port: key.sport,
+            destination: key.dest,
+            destination_port: key.dport,
+            protocol: key.protocol.to_string(),
+            source_mac: val.mac_address1.clone(),
+            destination_mac: val.mac_address2.clone(),
+            service: val.service.to_string(),
+            server_name: val.server_name.clone(),
+            program: val.program.to_string(),
+            direction: match val.traffic_direction {
+                TrafficDirection::Incoming => "incoming",
+                TrafficDirection::Outgoing => "outgoing",
+            }
+            .to_string(),
+            domain,
+            dns_name: host.dns_name,
+            country: country_string(host.country),
+            asn_code: host.asn.code,
+            asn_name: host.asn.name,
+            adapters: val.adapters.clone(),
+            tunnel: val.tunnel.map(|t| t.to_string()),
+            vlan: key.vlan.is_tagged().then(|| key.vlan.to_string()),
+            mpls_labels: val.mpls_labels.0.clone(),
+            is_blacklisted: val.is_blacklisted(),
+            first_seen: timestamp_string(val.initial_timestamp),
+            last_seen: timestamp_string(val.final_timestamp),
+            packets: val.transmitted_packets,
+            bytes: val.transmitted_bytes,
+            fragments: val.fragments,
+            icmp_types: val
+                .icmp_types
+                .iter()
+                .map(|(t, n)| (t.to_string(), *n))
+                .collect(),
+            arp_types: val
+                .arp_types
+                .iter()
+                .map(|(t, n)| (t.to_string(), *n))
+                .collect(),
+            igmp_types: val
+                .igmp_types
+                .iter()
+                .map(|(t, n)| (t.to_string(), *n))
+                .collect(),
+        }
+    }
+}
+
+/// A bidirectional flow, oriented from the local host whenever possible
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FlowRecord {
+    /// Info about the flow as a whole (packets and bytes are the ones exchanged in both directions)
+    #[serde(flatten)]
+    pub connection: ConnectionRecord,
+    pub upstream_packets: u128,
+    pub upstream_bytes: u128,
+    pub downstream_packets: u128,
+    pub downstream_bytes: u128,
+    /// Time elapsed between the first and the last packet
+    pub duration_secs: f64,
+}
+
+impl FlowRecord {
+    pub fn new(
+        key: &AddressPortPair,
+        flow: &InfoFlow,
+        r_dns_host: Option<&(String, Host)>,
+    ) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let duration_secs = flow.duration_usecs() as f64 / 1_000_000.0;
+        Self {
+            connection: ConnectionRecord::new(key, &flow.info, r_dns_host),
+            upstream_packets: flow.upstream.packets,
+            upstream_bytes: flow.upstream.bytes,
+            downstream_packets: flow.downstream.packets,
+            downstream_bytes: flow.downstream.bytes,
+            duration_secs,
+        }
+    }
+}
+
+/// A host with the data exchanged with it
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HostRecord {
+    pub domain: String,
+    pub dns_name: String,
+    pub country: String,
+    pub asn_code: String,
+    pub asn_name: String,
+    pub is_local: bool,
+    pub is_loopback: bool,
+    pub bogon: String,
+    #[serde(flatten)]
+    pub data: DataCounters,
+}
+
+/// A service or a program with the data it exchanged
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DataRecord {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    #[serde(flatten)]
+    pub data: DataCounters,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct DataCounters {
+    pub incoming_packets: u128,
+    pub outgoing_packets: u128,
+    pub incoming_bytes: u128,
+    pub outgoing_bytes: u128,
+}
+
+impl From<DataInfo> for DataCounters {
+    fn from(data_info: DataInfo) -> Self {
+        Self {
+            incoming_packets: data_info.incoming_data(DataRepr::Packets),
+            outgoing_packets: data_info.outgoing_data(DataRepr::Packets),
+            incoming_bytes: data_info.incoming_data(DataRepr::Bytes),
+            outgoing_bytes: data_info.outgoing_data(DataRepr::Bytes),
+        }
+    }
+}
+
+/// Columns describing a connection, shared by the tables of connections and flows
+const CONNECTION_COLUMNS: [&str; 29] = [
+    "source",
+    "source_port",
+    "destination",
+    "destination_port",
+    "protocol",
+    "source_mac",
+    "destination_mac",
+    "service",
+    "server_name",
+    "program",
+    "direction",
+    "domain",
+    "dns_name",
+    "country",
+    "asn_code",
+    "asn_name",
+    "adapters",
+    "tunnel",
+    "vlan",
+    "mpls_labels",
+    "is_blacklisted",
+    "first_seen",
+    "last_seen",
+    "packets",
+    "bytes",
+    "fragments",
+    "icmp_types",
+    "arp_types",
+    "igmp_types",
+];
+
+/// Concatenates the columns of two tables
+const fn concat_columns<const A: usize, const B: usize, const N: usize>(
+    a: [&'static str; A],
+    b: [&'static str; B],
+) -> [&'static str; N] {
+    assert!(A + B == N);
+    let mut columns = [""; N];
+    let mut i = 0;
+    while i < A {
+        columns[i] = a[i];
+        i += 1;
+    }
+    while i < N {
+        columns[i] = b[i - A];
+        i += 1;
+    }
+    columns
+}
+
+/// A record that can be written as a CSV row
+pub trait CsvRecord {
+    const HEADER: &'static [&'static str];
+
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+impl CsvRecord for ConnectionRecord {
+    const HEADER: &'static [&'static str] = &CONNECTION_COLUMNS;
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.source.to_string(),
+            self.source_port.map(|p| p.to_string()).unwrap_or_default(),
+            self.destination.to_string(),
+            self.destination_port
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            self.protocol.clone(),
+            self.source_mac.clone().unwrap_or_default(),
+            self.destination_mac.clone().unwrap_or_default(),
+            self.service.clone(),
+            self.server_name.clone().unwrap_or_default(),
+            self.program.clone(),
+            self.direction.clone(),
+            self.domain.clone(),
+            self.dns_name.clone(),
+            self.country.clone(),
+            self.asn_code.clone(),
+            self.asn_name.clone(),
+            self.adapters.join(" "),
+            self.tunnel.clone().unwrap_or_default(),
+            self.vlan.clone().unwrap_or_default(),
+            self.mpls_labels
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+            self.is_blacklisted.to_string(),
+            self.first_seen.clone(),
+            self.last_seen.clone(),
+            self.packets.to_string(),
+            self.bytes.to_string(),
+            self.fragments.to_string(),
+            types_csv_field(&self.icmp_types),
+            types_csv_field(&self.arp_types),
+            types_csv_field(&self.igmp_types),
+        ]
+    }
+}
+
+impl CsvRecord for FlowRecord {
+    const HEADER: &'static [&'static str] = &concat_columns::<29, 5, 34>(
+        CONNECTION_COLUMNS,
+        [
+            "upstream_packets",
+            "upstream_bytes",
+            "downstream_packets",
+            "downstream_bytes",
+            "duration_secs",
+        ],
+    );
+
+    fn csv_fields(&self) -> Vec<String> {
+        let mut fields = self.connection.csv_fields();
+        fields.extend([
+            self.upstream_packets.to_string(),
+            self.upstream_bytes.to_string(),
+            self.downstream_packets.to_string(),
+            self.downstream_bytes.to_string(),
+            self.duration_secs.to_string(),
+        ]);
+        fields
+    }
+}
+
+impl CsvRecord for HostRecord {
+    const HEADER: &'static [&'static str] = &[
+        "domain",
+        "dns_name",
+        "country",
+        "asn_code",
+        "asn_name",
+        "is_local",
+        "is_loopback",
+        "bogon",
+        "incoming_packets",
+        "outgoing_packets",
+        "incoming_bytes",
+        "outgoing_bytes",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let mut fields = vec![
+            self.domain.clone(),
+            self.dns_name.clone(),
+            self.country.clone(),
+            self.asn_code.clone(),
+            self.asn_name.clone(),
+            self.is_local.to_string(),
+            self.is_loopback.to_string(),
+            self.bogon.clone(),
+        ];
+        fields.extend(self.data.csv_fields());
+        fields
+    }
+}
+
+impl CsvRecord for DataRecord {
+    const HEADER: &'static [&'static str] = &[
+        "name",
+        "path",
+        "incoming_packets",
+        "outgoing_packets",
+        "incoming_bytes",
+        "outgoing_bytes",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let mut fields = vec![self.name.clone(), self.path.clone()];
+        fields.extend(self.data.csv_fields());
+        fields
+    }
+}
+
+impl DataCounters {
+    fn csv_fields(self) -> [String; 4] {
+        [
+            self.incoming_packets.to_string(),
+            self.outgoing_packets.to_string(),
+            self.incoming_bytes.to_string(),
+            self.outgoing_bytes.to_string(),
+        ]
+    }
+}
+
+/// Writes the given connections to file (e.g., the results of a search in the Inspect page)
+pub fn write_connections_to_file(
+    path: &Path,
+    connections: &[ConnectionRecord],
+    format: ReportFormat,
+) -> std::io::Result<()> {
+    match format {
+        ReportFormat::Json => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut writer, connections)?;
+            writeln!(writer)?;
+            writer.flush()
+        }
+        ReportFormat::Csv => write_csv_file(path, connections),
+    }
+}
+
+pub fn write_csv<T: CsvRecord>(writer: &mut impl Write, records: &[T]) -> std::io::Result<()> {
+    writeln!(writer, "{}", T::HEADER.join(","))?;
+    for record in records {
+        let row: Vec<String> = record
+            .csv_fields()
+            .iter()
+            .map(|f| escape_csv_field(f))
+            .collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+fn write_csv_file<T: CsvRecord>(path: &Path, records: &[T]) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_csv(&mut writer, records)?;
+    writer.flush()
+}
+
+/// Message types with their count, e.g. `echo request (3); echo reply (3)`
+fn types_csv_field(types: &BTreeMap<String, usize>) -> String {
+    types
+        .iter()
+        .map(|(t, n)| format!("{t} ({n})"))
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Path of the CSV file containing the given table, placed next to the main one
+fn table_path(path: &Path, table: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{stem}_{table}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{table}"),
+    };
+    path.with_file_name(file_name)
+}
+
+fn country_string(country: Country) -> String {
+    if country == Country::ZZ {
+        String::new()
+    } else {
+        country.to_string()
+    }
+}
+
+/// RFC 3339 representation of the timestamp (in UTC)
+fn timestamp_string(t: Timestamp) -> String {
+    t.to_usecs()
+        .and_then(|usecs| jiff::Timestamp::from_microsecond(usecs).ok())
+        .map(|ts| ts.to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::types::protocol::Protocol;
+    use crate::networking::types::service::Service;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field(""), "");
+    }
+
+    #[test]
+    fn test_table_path() {
+        assert_eq!(
+            table_path(Path::new("/tmp/report.csv"), "hosts"),
+            PathBuf::from("/tmp/report_hosts.csv")
+        );
+        assert_eq!(
+            table_path(Path::new("report"), "services"),
+            PathBuf::from("report_services")
+        );
+    }
+
+    #[test]
+    fn test_traffic_report() {
+        let mut info_traffic = InfoTraffic::default();
+        let key = AddressPortPair::new(
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            Some(50000),
+            IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            Some(443),
+            Protocol::TCP,
+        );
+        info_traffic.map.insert(
+            key,
+            InfoAddressPortPair {
+                transmitted_bytes: 1500,
+                transmitted_packets: 3,
+                initial_timestamp: Timestamp::new(1_700_000_000, 0),
+                final_timestamp: Timestamp::new(1_700_000_001, 500_000),
+                service: Service::Name("https"),
+                traffic_direction: TrafficDirection::Outgoing,
+                adapters: vec!["eth0".to_string(), "tun0".to_string()],
+                mac_address1: Some("aa:bb:cc:dd:ee:ff".to_string()),
+                ..Default::default()
+            },
+        );
+        info_traffic.map.insert(
+            key.reversed(),
+            InfoAddressPortPair {
+                transmitted_bytes: 6000,
+                transmitted_packets: 4,
+                initial_timestamp: Timestamp::new(1_700_000_000, 250_000),
+                final_timestamp: Timestamp::new(1_700_000_002, 0),
+                traffic_direction: TrafficDirection::Incoming,
+                ..Default::default()
+            },
+        );
+        let host = Host {
+            domain: "dns.google".to_string(),
+            country: Country::US,
+            ..Default::default()
+        };
+        let addresses_resolved = HashMap::from([(
+            IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            ("dns.google".to_string(), host),
+        )]);
+
+        let report = TrafficReport::new(&info_traffic, &addresses_resolved, None);
+        assert_eq!(report.connections.len(), 2);
+        assert!(report.programs.is_empty());
+        let connection = &report.connections[1];
+        assert_eq!(connection.domain, "dns.google");
+        assert_eq!(connection.country, "US");
+        assert_eq!(connection.direction, "outgoing");
+        assert_eq!(connection.first_seen, "2023-11-14T22:13:20Z");
+        assert_eq!(connection.last_seen, "2023-11-14T22:13:21.5Z");
+
+        assert_eq!(report.flows.len(), 1);
+        let flow = &report.flows[0];
+        assert_eq!(flow.connection.source_port, Some(50000));
+        assert_eq!(flow.connection.domain, "dns.google");
+        assert_eq!(flow.connection.bytes, 7500);
+        assert_eq!((flow.upstream_packets, flow.upstream_bytes), (3, 1500));
+        assert_eq!((flow.downstream_packets, flow.downstream_bytes), (4, 6000));
+        assert!((flow.duration_secs - 2.0).abs() < f64::EPSILON);
+        assert_eq!(flow.connection.last_seen, "2023-11-14T22:13:22Z");
+        assert!(FlowRecord::HEADER.starts_with(ConnectionRecord::HEADER));
+        assert_eq!(FlowRecord::HEADER.len(), flow.csv_fields().len());
+
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &report.connections[1..]).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "source,source_port,destination,destination_port,protocol,source_mac,destination_mac,\
+            service,server_name,program,direction,domain,dns_name,country,asn_code,asn_name,adapters,tunnel,vlan,mpls_labels,is_blacklisted,\
+            first_seen,last_seen,packets,bytes,fragments,icmp_types,arp_types,igmp_types\n\
+            192.168.1.2,50000,8.8.8.8,443,TCP,aa:bb:cc:dd:ee:ff,,https,,-,outgoing,dns.google,,US,,,\
+            eth0 tun0,,,,false,2023-11-14T22:13:20Z,2023-11-14T22:13:21.5Z,3,1500,0,,,\n"
+        );
+
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(value["connections"][1]["destination_port"], 443);
+        assert_eq!(value["connections"][1]["adapters"][1], "tun0");
+        assert_eq!(value["flows"][0]["destination_port"], 443);
+        assert_eq!(value["flows"][0]["downstream_bytes"], 6000);
+        assert_eq!(
+            value["connections"][1]["destination_mac"],
+            serde_json::Value::Null
+        );
+        assert!(value["connections"][0].get("icmp_types").is_none());
+    }
+
+    #[test]
+    fn test_types_csv_field() {
+        let types = BTreeMap::from([
+            ("Echo reply".to_string(), 2),
+            ("Echo request".to_string(), 3),
+        ]);
+        assert_eq!(types_csv_field(&types), "Echo reply (2); Echo request (3)");
+        assert_eq!(types_csv_field(&BTreeMap::new()), "");
+    }
+}
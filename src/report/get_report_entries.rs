@@ -2,7 +2,9 @@
 use crate::networking::manage_packets::get_address_to_lookup;
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::flow::InfoFlow;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use std::borrow::Cow;
 use std::cmp::min;
 
 /// Return the elements that satisfy the search constraints and belong to the given page,
@@ -11,17 +13,53 @@
 pub fn get_searched_entries(
     sniffer: &Sniffer,
 ) -> (
-    Vec<(&AddressPortPair, &InfoAddressPortPair)>,
+    Vec<(AddressPortPair, Cow<'_, InfoAddressPortPair>)>,
     usize,
     DataInfo,
+) {
+    let (mut all_results, agglomerate) = get_all_searched_entries(sniffer);
+    let results_number = all_results.len();
+
+    let upper_bound = min(sniffer.page_number * 30, results_number);
+    let lower_bound = min((sniffer.page_number.saturating_sub(1)) * 30, upper_bound);
+    all_results.truncate(upper_bound);
+    all_results.drain(..lower_bound);
+
+    (all_results, results_number, agglomerate)
+}
+
+/// Return all the sorted elements that satisfy the search constraints,
+/// with their packets, in-bytes, and out-bytes count
+///
+/// Elements are bidirectional flows if they're enabled (built from the connections on demand),
+/// or single-direction connections otherwise
+pub fn get_all_searched_entries(
+    sniffer: &Sniffer,
+) -> (
+    Vec<(AddressPortPair, Cow<'_, InfoAddressPortPair>)>,
+    DataInfo,
 ) {
     let mut agglomerate = DataInfo::default();
     let info_traffic = &sniffer.info_traffic;
     let favorites = &sniffer.conf.favorites;
-    let mut all_results: Vec<(&AddressPortPair, &InfoAddressPortPair)> = info_traffic
-        .map
-        .iter()
-        .filter(|(key, value)| {
+    let entries: Box<dyn Iterator<Item = (AddressPortPair, Cow<InfoAddressPortPair>, DataInfo)>> =
+        if sniffer.conf.report_flows {
+            Box::new(
+                InfoFlow::all_from_map(&info_traffic.map).map(|(key, flow)| {
+                    let data_info = flow.data_info();
+                    (key, Cow::Owned(flow.info), data_info)
+                }),
+            )
+        } else {
+            Box::new(
+                info_traffic
+                    .map
+                    .iter()
+                    .map(|(key, val)| (*key, Cow::Borrowed(val), val.data_info())),
+            )
+        };
+    let mut all_results: Vec<(AddressPortPair, Cow<InfoAddressPortPair>)> = entries
+        .filter(|(key, value, _)| {
             let address_to_lookup = &get_address_to_lookup(key, value.traffic_direction);
             let r_dns_host = sniffer.addresses_resolved.get(address_to_lookup);
             // is this a favorite host?
@@ -43,24 +81,15 @@ pub fn get_searched_entries(
                 .search
                 .match_entry(key, value, r_dns_host, is_favorite)
         })
-        .map(|(key, val)| {
-            agglomerate.refresh(val.data_info());
+        .map(|(key, val, data_info)| {
+            agglomerate.refresh(data_info);
             (key, val)
         })
         .collect();
 
-    all_results.sort_by(|&(_, a), &(_, b)| {
+    all_results.sort_by(|(_, a), (_, b)| {
         a.compare(b, sniffer.conf.report_sort_type, sniffer.conf.data_repr)
     });
 
-    let upper_bound = min(sniffer.page_number * 30, all_results.len());
-
-    (
-        all_results
-            .get((sniffer.page_number.saturating_sub(1)) * 30..upper_bound)
-            .unwrap_or_default()
-            .to_vec(),
-        all_results.len(),
-        agglomerate,
-    )
+    (all_results, agglomerate)
 }
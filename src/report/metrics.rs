@@ -0,0 +1,601 @@
+//! Module to expose the analyzed traffic as Prometheus metrics, via an embedded HTTP endpoint.
+//!
+//! The metrics are rendered after each refresh of the traffic statistics, and the latest
+//! snapshot is served to every scrape at `/metrics` in the Prometheus text exposition format.
+
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_representation::DataRepr;
+use crate::networking::types::info_traffic::InfoTraffic;
+use crate::networking::types::program::Program;
+use crate::report::types::metrics_conf::MetricsConf;
+use crate::report::types::sort_type::SortType;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Label value under which the series exceeding the cardinality limits are aggregated
+const OTHER_LABEL_VALUE: &str = "other";
+
+/// Time to wait for new connections before checking if the server should be stopped
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum time to wait for a client to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Labels identifying a series, with their values
+type Labels = Vec<(&'static str, String)>;
+
+/// Embedded HTTP server exposing the latest metrics; it's stopped when dropped
+#[derive(Debug)]
+pub struct MetricsServer {
+    /// Address the server is listening on
+    address: SocketAddr,
+    /// Latest rendered metrics
+    metrics: Arc<RwLock<String>>,
+    /// Set to stop the server
+    stop: Arc<AtomicBool>,
+    /// Thread serving the requests, joined to release the address when the server is dropped
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Binds the given address and starts serving the metrics in a separate thread
+    pub fn start(address: SocketAddr) -> Result<Self, String> {
+        let listener = TcpListener::bind(address).map_err(|e| format!("{address}: {e}"))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let address = listener.local_addr().map_err(|e| e.to_string())?;
+
+        let metrics = Arc::new(RwLock::new(String::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let metrics_2 = metrics.clone();
+        let stop_2 = stop.clone();
+        let handle = thread::Builder::new()
+            .name("thread_metrics_server".to_string())
+            .spawn(move || serve(&listener, &metrics_2, &stop_2))
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            address,
+            metrics,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Replaces the metrics served to the next scrapes
+    pub fn set_metrics(&self, metrics: String) {
+        if let Ok(mut current) = self.metrics.write() {
+            *current = metrics;
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve(listener: &TcpListener, metrics: &RwLock<String>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            // scrapes are infrequent: requests are handled one at a time
+            Ok((stream, _)) => {
+                let _ = handle_request(stream, metrics);
+            }
+            Err(_) => thread::sleep(ACCEPT_INTERVAL),
+        }
+    }
+}
+
+fn handle_request(mut stream: TcpStream, metrics: &RwLock<String>) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // skip the headers
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path.split('?').next() == Some("/metrics") => (
+            "200 OK",
+            metrics.read().map(|m| m.clone()).unwrap_or_default(),
+        ),
+        (Some("GET"), _) => (
+            "404 Not Found",
+            "Metrics are served at /metrics\n".to_string(),
+        ),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// Renders the traffic analyzed so far in the Prometheus text exposition format
+///
+/// Programs are only included when they've been looked up (i.e., in live captures)
+pub fn render_metrics(
+    info_traffic: &InfoTraffic,
+    programs: Option<&HashMap<Program, DataInfo>>,
+    conf: &MetricsConf,
+) -> String {
+    let mut out = String::new();
+
+    write_data_metrics(
+        &mut out,
+        "",
+        "exchanged",
+        &[(Vec::new(), info_traffic.tot_data_info)],
+    );
+
+    write_header(
+        &mut out,
+        "sniffnet_dropped_packets_total",
+        "Packets dropped by the capture",
+        "counter",
+    );
+    let _ = writeln!(
+        out,
+        "sniffnet_dropped_packets_total {}",
+        info_traffic.dropped_packets
+    );
+
+    let services = limit_cardinality(
+        info_traffic
+            .services
+            .iter()
+            .map(|(service, data_info)| (service.to_string(), *data_info)),
+        conf.max_label_values,
+    );
+    write_data_metrics(
+        &mut out,
+        "service_",
+        "per upper layer service",
+        &labeled("service", services),
+    );
+
+    let mut countries: HashMap<String, DataInfo> = HashMap::new();
+    for (host, data_info_host) in &info_traffic.hosts {
+        countries
+            .entry(host.country.to_string())
+            .or_default()
+            .refresh(data_info_host.data_info);
+    }
+    let countries = limit_cardinality(countries.into_iter(), conf.max_label_values);
+    write_data_metrics(
+        &mut out,
+        "country_",
+        "per country of the remote hosts",
+        &labeled("country", countries),
+    );
+
+    let mut hosts: Vec<_> = info_traffic.hosts.iter().collect();
+    hosts.sort_by(|&(_, a), &(_, b)| {
+        a.data_info
+            .compare(&b.data_info, SortType::Descending, DataRepr::Bytes)
+    });
+    let hosts: Vec<(Labels, DataInfo)> = hosts
+        .into_iter()
+        .take(conf.top_hosts)
+        .map(|(host, data_info_host)| {
+            let labels = vec![
+                ("host", host.domain.clone()),
+                ("dns_name", host.dns_name.clone()),
+                ("asn", host.asn.name.clone()),
+                ("country", host.country.to_string()),
+            ];
+            (labels, data_info_host.data_info)
+        })
+        .collect();
+    write_data_metrics(&mut out, "host_", "with the top remote hosts", &hosts);
+
+    if let Some(programs) = programs {
+        let programs = limit_cardinality(
+            programs
+                .iter()
+                .map(|(program, data_info)| (program.to_string(), *data_info)),
+            conf.max_label_values,
+        );
+        write_data_metrics(
+            &mut out,
+            "program_",
+            "per local program",
+            &labeled("program", programs),
+        );
+    }
+
+    write_blacklist_metrics(&mut out, info_traffic);
+
+    out
+}
+
+/// Writes the number of connections with blacklisted hosts and their traffic, per list
+fn write_blacklist_metrics(out: &mut String, info_traffic: &InfoTraffic) {
+    let mut lists: HashMap<Labels, (usize, DataInfo)> = HashMap::new();
+    for info in info_traffic.map.values() {
+        let Some(blacklist_match) = &info.blacklist_match else {
+            continue;
+        };
+        let labels = vec![
+            ("list", blacklist_match.name.to_string()),
+            ("category", blacklist_match.category.to_string()),
+            ("severity", blacklist_match.severity.to_string()),
+        ];
+        let (hits, data_info) = lists.entry(labels).or_default();
+        *hits += 1;
+        data_info.refresh(info.data_info());
+    }
+    let mut lists: Vec<_> = lists.into_iter().collect();
+    lists.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    write_header(
+        out,
+        "sniffnet_blacklist_hits_total",
+        "Connections with hosts included in a blacklist",
+        "counter",
+    );
+    for (labels, (hits, _)) in &lists {
+        let _ = writeln!(
+            out,
+            "sniffnet_blacklist_hits_total{} {hits}",
+            format_labels(labels)
+        );
+    }
+    let lists: Vec<(Labels, DataInfo)> = lists
+        .into_iter()
+        .map(|(labels, (_, data_info))| (labels, data_info))
+        .collect();
+    write_data_metrics(out, "blacklist_", "with blacklisted hosts", &lists);
+}
+
+/// Writes the bytes and packets counters of the given series, split by direction
+fn write_data_metrics(
+    out: &mut String,
+    prefix: &str,
+    subject: &str,
+    series: &[(Labels, DataInfo)],
+) {
+    for (unit, data_repr) in [("bytes", DataRepr::Bytes), ("packets", DataRepr::Packets)] {
+        let name = format!("sniffnet_{prefix}{unit}_total");
+        write_header(
+            out,
+            &name,
+            &format!("{} {subject}", capitalize(unit)),
+            "counter",
+        );
+        for (labels, data_info) in series {
+            for (direction, value) in [
+                ("incoming", data_info.incoming_data(data_repr)),
+                ("outgoing", data_info.outgoing_data(data_repr)),
+            ] {
+                let mut labels = labels.clone();
+                labels.push(("direction", direction.to_string()));
+                let _ = writeln!(out, "{name}{} {value}", format_labels(&labels));
+            }
+        }
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {metric_type}");
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+fn labeled(label: &'static str, series: Vec<(String, DataInfo)>) -> Vec<(Labels, DataInfo)> {
+    series
+        .into_iter()
+        .map(|(value, data_info)| (vec![(label, value)], data_info))
+        .collect()
+}
+
+/// Sorts the series by exchanged bytes, keeping at most `max` of them:
+/// the remaining ones are aggregated under the `other` label value
+fn limit_cardinality(
+    series: impl Iterator<Item = (String, DataInfo)>,
+    max: usize,
+) -> Vec<(String, DataInfo)> {
+    let mut series: Vec<_> = series.collect();
+    series.sort_by(|(a_label, a), (b_label, b)| {
+        a.compare(b, SortType::Descending, DataRepr::Bytes)
+            .then_with(|| a_label.cmp(b_label))
+    });
+    if series.len() > max {
+        let other = series
+            .drain(max..)
+            .fold(DataInfo::default(), |mut acc, (_, data_info)| {
+                acc.refresh(data_info);
+                acc
+            });
+        series.push((OTHER_LABEL_VALUE.to_string(), other));
+    }
+    series
+}
+
+fn format_labels(labels: &[(&'static str, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+        .collect();
+    format!("{{{}}}", labels.join(","))
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Protocol;
+    use crate::Service;
+    use crate::countries::types::country::Country;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::ip_blacklist::{BlacklistMatch, ListCategory, ListSeverity};
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use std::io::Read;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Instant;
+
+    fn data(incoming: (u128, u128), outgoing: (u128, u128)) -> DataInfo {
+        let mut data_info = DataInfo::default();
+        let now = Instant::now();
+        data_info.add_packets(incoming.0, incoming.1, TrafficDirection::Incoming, now);
+        data_info.add_packets(outgoing.0, outgoing.1, TrafficDirection::Outgoing, now);
+        data_info
+    }
+
+    fn host(domain: &str, asn: &str, country: Country) -> Host {
+        Host {
+            domain: domain.to_string(),
+            dns_name: String::new(),
+            asn: Asn {
+                code: String::new(),
+                name: asn.to_string(),
+            },
+            country,
+        }
+    }
+
+    fn connection(
+        dest: [u8; 4],
+        bytes: u128,
+        list: Option<&str>,
+    ) -> (AddressPortPair, InfoAddressPortPair) {
+        let key = AddressPortPair::new(
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            Some(50000),
+            IpAddr::V4(Ipv4Addr::from(dest)),
+            Some(443),
+            Protocol::TCP,
+        );
+        let info = InfoAddressPortPair {
+            traffic_direction: TrafficDirection::Outgoing,
+            transmitted_packets: 1,
+            transmitted_bytes: bytes,
+            blacklist_match: list.map(|name| BlacklistMatch {
+                name: name.into(),
+                category: ListCategory::Malware,
+                severity: ListSeverity::High,
+            }),
+            ..InfoAddressPortPair::default()
+        };
+        (key, info)
+    }
+
+    fn info_traffic() -> InfoTraffic {
+        let host_info = |data_info| DataInfoHost {
+            data_info,
+            ..DataInfoHost::default()
+        };
+        InfoTraffic {
+            tot_data_info: data((30, 25000), (20, 3000)),
+            dropped_packets: 4,
+            services: HashMap::from([
+                (Service::Name("https"), data((20, 20000), (10, 2000))),
+                (Service::Name("dns"), data((5, 500), (5, 500))),
+                (Service::Unknown, data((5, 4500), (5, 500))),
+            ]),
+            hosts: HashMap::from([
+                (
+                    host("a.example.com", "Example \"Inc\"", Country::US),
+                    host_info(data((10, 15000), (5, 1000))),
+                ),
+                (
+                    host("b.example.it", "Esempio", Country::IT),
+                    host_info(data((10, 6000), (5, 1000))),
+                ),
+                (
+                    host("c.example.com", "Example \"Inc\"", Country::US),
+                    host_info(data((10, 4000), (10, 1000))),
+                ),
+            ]),
+            map: HashMap::from([
+                connection([203, 0, 113, 1], 700, Some("DROP")),
+                connection([203, 0, 113, 2], 300, Some("DROP")),
+                connection([8, 8, 8, 8], 100, None),
+            ]),
+            ..InfoTraffic::default()
+        }
+    }
+
+    fn lines(metrics: &str, name: &str) -> Vec<String> {
+        metrics
+            .lines()
+            .filter(|l| l.starts_with(&format!("{name}{{")) || l.starts_with(&format!("{name} ")))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_render_metrics_totals_and_blacklists() {
+        let metrics = render_metrics(&info_traffic(), None, &MetricsConf::default());
+
+        assert!(metrics.contains("# TYPE sniffnet_bytes_total counter\n"));
+        assert_eq!(
+            lines(&metrics, "sniffnet_bytes_total"),
+            vec![
+                "sniffnet_bytes_total{direction=\"incoming\"} 25000",
+                "sniffnet_bytes_total{direction=\"outgoing\"} 3000",
+            ]
+        );
+        assert_eq!(
+            lines(&metrics, "sniffnet_packets_total"),
+            vec![
+                "sniffnet_packets_total{direction=\"incoming\"} 30",
+                "sniffnet_packets_total{direction=\"outgoing\"} 20",
+            ]
+        );
+        assert_eq!(
+            lines(&metrics, "sniffnet_dropped_packets_total"),
+            vec!["sniffnet_dropped_packets_total 4"]
+        );
+        assert_eq!(
+            lines(&metrics, "sniffnet_blacklist_hits_total"),
+            vec![
+                "sniffnet_blacklist_hits_total{list=\"DROP\",category=\"Malware\",severity=\"High\"} 2"
+            ]
+        );
+        assert_eq!(
+            lines(&metrics, "sniffnet_blacklist_bytes_total"),
+            vec![
+                "sniffnet_blacklist_bytes_total{list=\"DROP\",category=\"Malware\",severity=\"High\",direction=\"incoming\"} 0",
+                "sniffnet_blacklist_bytes_total{list=\"DROP\",category=\"Malware\",severity=\"High\",direction=\"outgoing\"} 1000",
+            ]
+        );
+        // programs haven't been looked up
+        assert!(!metrics.contains("sniffnet_program_"));
+    }
+
+    #[test]
+    fn test_render_metrics_with_cardinality_limits() {
+        let conf = MetricsConf {
+            top_hosts: 1,
+            max_label_values: 2,
+            ..MetricsConf::default()
+        };
+        let programs = HashMap::from([
+            (
+                Program::NamePath(("firefox".to_string(), "/usr/bin/firefox".to_string())),
+                data((1, 100), (1, 100)),
+            ),
+            (Program::Unknown, data((1, 10), (0, 0))),
+        ]);
+        let metrics = render_metrics(&info_traffic(), Some(&programs), &conf);
+
+        // the least used services are aggregated
+        assert_eq!(
+            lines(&metrics, "sniffnet_service_bytes_total"),
+            vec![
+                "sniffnet_service_bytes_total{service=\"https\",direction=\"incoming\"} 20000",
+                "sniffnet_service_bytes_total{service=\"https\",direction=\"outgoing\"} 2000",
+                "sniffnet_service_bytes_total{service=\"?\",direction=\"incoming\"} 4500",
+                "sniffnet_service_bytes_total{service=\"?\",direction=\"outgoing\"} 500",
+                "sniffnet_service_bytes_total{service=\"other\",direction=\"incoming\"} 500",
+                "sniffnet_service_bytes_total{service=\"other\",direction=\"outgoing\"} 500",
+            ]
+        );
+        // hosts are aggregated per country
+        assert_eq!(
+            lines(&metrics, "sniffnet_country_packets_total"),
+            vec![
+                "sniffnet_country_packets_total{country=\"US\",direction=\"incoming\"} 20",
+                "sniffnet_country_packets_total{country=\"US\",direction=\"outgoing\"} 15",
+                "sniffnet_country_packets_total{country=\"IT\",direction=\"incoming\"} 10",
+                "sniffnet_country_packets_total{country=\"IT\",direction=\"outgoing\"} 5",
+            ]
+        );
+        // only the top hosts are exported, with escaped label values
+        assert_eq!(
+            lines(&metrics, "sniffnet_host_bytes_total"),
+            vec![
+                "sniffnet_host_bytes_total{host=\"a.example.com\",dns_name=\"\",asn=\"Example \\\"Inc\\\"\",country=\"US\",direction=\"incoming\"} 15000",
+                "sniffnet_host_bytes_total{host=\"a.example.com\",dns_name=\"\",asn=\"Example \\\"Inc\\\"\",country=\"US\",direction=\"outgoing\"} 1000",
+            ]
+        );
+        assert_eq!(
+            lines(&metrics, "sniffnet_program_packets_total"),
+            vec![
+                "sniffnet_program_packets_total{program=\"firefox\",direction=\"incoming\"} 1",
+                "sniffnet_program_packets_total{program=\"firefox\",direction=\"outgoing\"} 1",
+                "sniffnet_program_packets_total{program=\"?\",direction=\"incoming\"} 1",
+                "sniffnet_program_packets_total{program=\"?\",direction=\"outgoing\"} 0",
+            ]
+        );
+    }
+
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_metrics_server() {
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap()).unwrap();
+        let address = server.address();
+        assert_ne!(address.port(), 0);
+
+        server.set_metrics("sniffnet_dropped_packets_total 7\n".to_string());
+        let response = get(address, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(&format!("Content-Type: {CONTENT_TYPE}\r\n")));
+        assert!(response.ends_with("\r\n\r\nsniffnet_dropped_packets_total 7\n"));
+
+        let response = get(address, "/");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        // the address can't be bound twice
+        assert!(MetricsServer::start(address).is_err());
+
+        // the address is released once the server is dropped
+        drop(server);
+        assert!(MetricsServer::start(address).is_ok());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod export_report;
 pub mod get_report_entries;
+pub mod metrics;
 pub mod types;
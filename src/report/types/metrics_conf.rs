@@ -0,0 +1,74 @@
+use crate::gui::types::conf::deserialize_or_default;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+
+/// Settings of the embedded HTTP endpoint exposing the traffic statistics as Prometheus metrics
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(default)]
+pub struct MetricsConf {
+    /// Whether the endpoint is enabled
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub is_active: bool,
+    /// IP address the endpoint is bound to
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub address: String,
+    /// TCP port the endpoint is bound to
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub port: u16,
+    /// Number of hosts exported, starting from the ones that exchanged more data
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub top_hosts: usize,
+    /// Maximum number of services, countries, and programs exported;
+    /// the remaining ones are aggregated under the `other` label value
+    #[serde(deserialize_with = "deserialize_or_default")]
+    pub max_label_values: usize,
+}
+
+impl Default for MetricsConf {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            address: "127.0.0.1".to_string(),
+            port: 9797,
+            top_hosts: 10,
+            max_label_values: 20,
+        }
+    }
+}
+
+impl MetricsConf {
+    /// Socket address the endpoint is bound to, if the configured address is valid
+    pub fn socket_address(&self) -> Option<SocketAddr> {
+        let ip = self.address.trim().parse::<IpAddr>().ok()?;
+        Some(SocketAddr::new(ip, self.port))
+    }
+
+    /// Sets the socket address the endpoint is bound to
+    pub fn set_socket_address(&mut self, socket_address: SocketAddr) {
+        self.address = socket_address.ip().to_string();
+        self.port = socket_address.port();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_metrics_conf_socket_address() {
+        let mut conf = MetricsConf::default();
+        assert_eq!(
+            conf.socket_address(),
+            Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9797))
+        );
+
+        conf.set_socket_address(SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 9100));
+        assert_eq!(conf.address, "::");
+        assert_eq!(conf.port, 9100);
+        assert_eq!(conf.socket_address(), "[::]:9100".parse().ok());
+
+        conf.address = "localhost".to_string();
+        assert_eq!(conf.socket_address(), None);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod metrics_conf;
 pub mod report_col;
+pub mod report_format;
 pub mod search_parameters;
 pub mod sort_type;
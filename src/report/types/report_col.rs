@@ -5,6 +5,7 @@
 use crate::translations::translations::{address_translation, protocol_translation};
 use crate::translations::translations_2::{destination_translation, source_translation};
 use crate::translations::translations_3::{port_translation, service_translation};
+use crate::translations::translations_6::adapter_translation;
 use crate::translations::types::language::Language;
 
 // total width: 1012.0
@@ -23,21 +24,38 @@ pub enum ReportCol {
     DstPort,
     Proto,
     Service,
+    Adapter,
     Data,
 }
 
 impl ReportCol {
-    pub(crate) const ALL: [ReportCol; 7] = [
+    pub(crate) const ALL: [ReportCol; 8] = [
         ReportCol::SrcIp,
         ReportCol::SrcPort,
         ReportCol::DstIp,
         ReportCol::DstPort,
         ReportCol::Proto,
         ReportCol::Service,
+        ReportCol::Adapter,
         ReportCol::Data,
     ];
 
-    pub(crate) const FILTER_COLUMNS_WIDTH: f32 = 4.0 * SMALL_COL_WIDTH + 2.0 * LARGE_COL_WIDTH;
+    const FILTER_COLUMNS_WIDTH: f32 = 4.0 * SMALL_COL_WIDTH + 2.0 * LARGE_COL_WIDTH;
+
+    /// Columns to display (the adapter one is only shown when capturing from multiple adapters)
+    pub(crate) fn displayed(show_adapter: bool) -> impl Iterator<Item = ReportCol> {
+        Self::ALL
+            .into_iter()
+            .filter(move |col| show_adapter || *col != ReportCol::Adapter)
+    }
+
+    pub(crate) fn filter_columns_width(show_adapter: bool) -> f32 {
+        if show_adapter {
+            Self::FILTER_COLUMNS_WIDTH + SMALL_COL_WIDTH
+        } else {
+            Self::FILTER_COLUMNS_WIDTH
+        }
+    }
 
     pub(crate) fn get_title(&self, language: Language, data_repr: DataRepr) -> String {
         match self {
@@ -45,6 +63,7 @@ pub(crate) fn get_title(&self, language: Language, data_repr: DataRepr) -> Strin
             ReportCol::SrcPort | ReportCol::DstPort => port_translation(language).to_string(),
             ReportCol::Proto => protocol_translation(language).to_string(),
             ReportCol::Service => service_translation(language).to_string(),
+            ReportCol::Adapter => adapter_translation(language).to_string(),
             ReportCol::Data => {
                 let mut str = data_repr.get_label(language).to_string();
                 if str.is_empty() {
@@ -93,6 +112,7 @@ pub(crate) fn get_value(
             }
             ReportCol::Proto => key.protocol.to_string(),
             ReportCol::Service => val.service.to_string(),
+            ReportCol::Adapter => val.adapters.join(", "),
             ReportCol::Data => data_repr.formatted_string(val.transmitted_data(data_repr)),
         }
     }
@@ -126,6 +146,7 @@ pub(crate) fn get_filter_input_type(&self) -> FilterInputType {
             ReportCol::DstPort => FilterInputType::PortDst,
             ReportCol::Proto => FilterInputType::Proto,
             ReportCol::Service => FilterInputType::Service,
+            ReportCol::Adapter => FilterInputType::Adapter,
             ReportCol::Data => FilterInputType::Country, // just to not panic...
         }
     }
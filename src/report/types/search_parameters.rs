@@ -4,9 +4,13 @@
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::program::Program;
 use crate::networking::types::service::Service;
+use crate::networking::types::vlan_ids::VlanIds;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Used to express the search filters applied to GUI inspect page
-#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchParameters {
     /// IP address (source)
     pub address_src: String,
@@ -28,6 +32,14 @@ pub struct SearchParameters {
     pub as_name: String,
     /// Program name
     pub program: String,
+    /// Network adapter
+    pub adapter: String,
+    /// Server name indicated in the TLS or QUIC handshake
+    pub server_name: String,
+    /// State of the TCP connection
+    pub tcp_state: String,
+    /// VLAN IDs
+    pub vlan: String,
     /// Whether to display only favorites
     pub only_favorites: bool,
     /// Whether to display only blacklisted
@@ -59,7 +71,7 @@ pub fn match_entry(
         }
 
         // check blacklisted filter
-        if self.only_blacklisted && !value.is_blacklisted {
+        if self.only_blacklisted && !value.is_blacklisted() {
             return false;
         }
 
@@ -73,7 +85,11 @@ fn is_some_host_filter_active(&self) -> bool {
 
     pub fn new_host_search(host: &Host) -> Self {
         Self {
-            domain: host.domain.clone(),
+            domain: if host.dns_name.is_empty() {
+                host.domain.clone()
+            } else {
+                host.dns_name.clone()
+            },
             as_name: host.asn.name.clone(),
             country: if host.country == Country::ZZ {
                 String::new()
@@ -97,6 +113,37 @@ pub fn new_program_search(program: &Program) -> Self {
             ..SearchParameters::default()
         }
     }
+
+    pub fn new_vlan_search(vlan: VlanIds) -> Self {
+        Self {
+            vlan: format!("={vlan}"),
+            ..SearchParameters::default()
+        }
+    }
+}
+
+impl fmt::Display for SearchParameters {
+    /// Compact representation of the active filters (e.g., `program:firefox dport:=443`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut filters: Vec<String> = FilterInputType::ALL
+            .iter()
+            .filter_map(|filter_input_type| {
+                let value = filter_input_type.current_value(self);
+                (!value.is_empty()).then(|| format!("{}:{value}", filter_input_type.key()))
+            })
+            .collect();
+        if self.only_favorites {
+            filters.push("favorites".to_string());
+        }
+        if self.only_blacklisted {
+            filters.push("blacklisted".to_string());
+        }
+        if filters.is_empty() {
+            write!(f, "*")
+        } else {
+            write!(f, "{}", filters.join(" "))
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -111,10 +158,14 @@ pub enum FilterInputType {
     Domain,
     AsName,
     Program,
+    Adapter,
+    ServerName,
+    TcpState,
+    Vlan,
 }
 
 impl FilterInputType {
-    pub const ALL: [FilterInputType; 10] = [
+    pub const ALL: [FilterInputType; 14] = [
         Self::AddressSrc,
         Self::PortSrc,
         Self::AddressDst,
@@ -125,6 +176,10 @@ impl FilterInputType {
         Self::Domain,
         Self::AsName,
         Self::Program,
+        Self::Adapter,
+        Self::ServerName,
+        Self::TcpState,
+        Self::Vlan,
     ];
 
     pub fn matches_entry(
@@ -141,20 +196,52 @@ pub fn matches_entry(
         }
 
         let entry_value = self.entry_value(key, value, r_dns_host).to_lowercase();
+        // the domain filter also matches the name queried via DNS to reach the host
+        let dns_name = match self {
+            FilterInputType::Domain => r_dns_host
+                .map(|(_, host)| host.dns_name.to_lowercase())
+                .filter(|dns_name| !dns_name.is_empty()),
+            _ => None,
+        };
 
-        if let Some(equal_filter) = filter_value.strip_prefix('=') {
-            return entry_value.eq(equal_filter);
-        }
+        let (is_negated, is_equal, filter) = if let Some(f) = filter_value.strip_prefix('=') {
+            (false, true, f)
+        } else if let Some(f) = filter_value.strip_prefix("!=") {
+            (true, true, f)
+        } else if let Some(f) = filter_value.strip_prefix('!') {
+            (true, false, f)
+        } else {
+            (false, false, filter_value.as_str())
+        };
+        let is_match = |entry_value: &str| {
+            if is_equal {
+                entry_value.eq(filter)
+            } else {
+                entry_value.contains(filter)
+            }
+        };
 
-        if let Some(not_equal_filter) = filter_value.strip_prefix("!=") {
-            return !entry_value.eq(not_equal_filter);
-        }
+        (is_match(&entry_value) || dns_name.as_deref().is_some_and(is_match)) != is_negated
+    }
 
-        if let Some(not_contains_filter) = filter_value.strip_prefix('!') {
-            return !entry_value.contains(not_contains_filter);
+    /// Short name identifying the filter in textual representations
+    pub fn key(self) -> &'static str {
+        match self {
+            FilterInputType::AddressSrc => "src",
+            FilterInputType::PortSrc => "sport",
+            FilterInputType::AddressDst => "dst",
+            FilterInputType::PortDst => "dport",
+            FilterInputType::Proto => "proto",
+            FilterInputType::Service => "service",
+            FilterInputType::Country => "country",
+            FilterInputType::Domain => "domain",
+            FilterInputType::AsName => "as",
+            FilterInputType::Program => "program",
+            FilterInputType::Adapter => "adapter",
+            FilterInputType::ServerName => "sni",
+            FilterInputType::TcpState => "tcp",
+            FilterInputType::Vlan => "vlan",
         }
-
-        entry_value.contains(&filter_value)
     }
 
     pub fn current_value(self, search_params: &SearchParameters) -> &str {
@@ -169,6 +256,10 @@ pub fn current_value(self, search_params: &SearchParameters) -> &str {
             FilterInputType::Domain => &search_params.domain,
             FilterInputType::AsName => &search_params.as_name,
             FilterInputType::Program => &search_params.program,
+            FilterInputType::Adapter => &search_params.adapter,
+            FilterInputType::ServerName => &search_params.server_name,
+            FilterInputType::TcpState => &search_params.tcp_state,
+            FilterInputType::Vlan => &search_params.vlan,
         }
     }
 
@@ -213,6 +304,13 @@ pub fn entry_value(
                 .name
                 .clone(),
             FilterInputType::Program => value.program.to_string(),
+            FilterInputType::Adapter => value.adapters.join(", "),
+            FilterInputType::ServerName => value.server_name.clone().unwrap_or_default(),
+            FilterInputType::TcpState => value
+                .tcp_state
+                .map(|tcp_state| tcp_state.to_string())
+                .unwrap_or_default(),
+            FilterInputType::Vlan => key.vlan.to_string(),
         }
     }
 
@@ -229,6 +327,10 @@ pub fn clear_search(self, search_params: &SearchParameters) -> SearchParameters
             FilterInputType::Country => result.country = String::new(),
             FilterInputType::AsName => result.as_name = String::new(),
             FilterInputType::Program => result.program = String::new(),
+            FilterInputType::Adapter => result.adapter = String::new(),
+            FilterInputType::ServerName => result.server_name = String::new(),
+            FilterInputType::TcpState => result.tcp_state = String::new(),
+            FilterInputType::Vlan => result.vlan = String::new(),
         }
         result
     }
@@ -247,6 +349,10 @@ pub fn new_search(self, search_params: &SearchParameters, new_value: &str) -> Se
             FilterInputType::Country => result.country = trimmed,
             FilterInputType::AsName => result.as_name = trimmed,
             FilterInputType::Program => result.program = trimmed,
+            FilterInputType::Adapter => result.adapter = trimmed,
+            FilterInputType::ServerName => result.server_name = trimmed,
+            FilterInputType::TcpState => result.tcp_state = trimmed,
+            FilterInputType::Vlan => result.vlan = trimmed,
         }
         result
     }
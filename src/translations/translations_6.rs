@@ -9,3 +9,597 @@ pub fn latency_translation(language: Language) -> &'static str {
         _ => "Latency",
     }
 }
+
+pub fn network_adapters_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Network adapters",
+        Language::IT => "Adattatori di rete",
+        _ => "Network adapters",
+    }
+}
+
+pub fn adapter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Adapter",
+        Language::IT => "Adattatore",
+        _ => "Adapter",
+    }
+}
+
+pub fn export_results_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Export results",
+        Language::IT => "Esporta risultati",
+        _ => "Export results",
+    }
+}
+
+pub fn new_file_every_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "New file every",
+        Language::IT => "Nuovo file ogni",
+        _ => "New file every",
+    }
+}
+
+pub fn keep_last_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Keep only the last",
+        Language::IT => "Mantieni solo gli ultimi",
+        _ => "Keep only the last",
+    }
+}
+
+pub fn files_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "files",
+        Language::IT => "file",
+        _ => "files",
+    }
+}
+
+pub fn queried_domain_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Queried domain name",
+        Language::IT => "Nome di dominio richiesto",
+        _ => "Queried domain name",
+    }
+}
+
+pub fn server_name_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Server name",
+        Language::IT => "Nome del server",
+        _ => "Server name",
+    }
+}
+
+pub fn bidirectional_flows_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Merge both directions of connections into flows",
+        Language::IT => "Unisci entrambe le direzioni delle connessioni in flussi",
+        _ => "Merge both directions of connections into flows",
+    }
+}
+
+pub fn flow_duration_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Flow duration",
+        Language::IT => "Durata del flusso",
+        _ => "Flow duration",
+    }
+}
+
+pub fn tcp_state_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "TCP state",
+        Language::IT => "Stato TCP",
+        _ => "TCP state",
+    }
+}
+
+pub fn tcp_flags_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "TCP flags",
+        Language::IT => "Flag TCP",
+        _ => "TCP flags",
+    }
+}
+
+pub fn rtt_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Round-trip time",
+        Language::IT => "Tempo di andata e ritorno",
+        _ => "Round-trip time",
+    }
+}
+
+pub fn host_rtt_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Round-trip time with this host",
+        Language::IT => "Tempo di andata e ritorno con questo host",
+        _ => "Round-trip time with this host",
+    }
+}
+
+pub fn retransmissions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Retransmissions",
+        Language::IT => "Ritrasmissioni",
+        _ => "Retransmissions",
+    }
+}
+
+pub fn duplicate_acks_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Duplicate ACKs",
+        Language::IT => "ACK duplicati",
+        _ => "Duplicate ACKs",
+    }
+}
+
+pub fn zero_window_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Zero window events",
+        Language::IT => "Eventi di finestra nulla",
+        _ => "Zero window events",
+    }
+}
+
+pub fn tunnel_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Tunnel",
+        Language::IT => "Tunnel",
+        _ => "Tunnel",
+    }
+}
+
+pub fn tunnels_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Tunnels",
+        Language::IT => "Tunnel",
+        _ => "Tunnels",
+    }
+}
+
+pub fn decapsulate_tunnels_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Analyze the traffic carried by VXLAN, Geneve, GRE, and IP-in-IP tunnels",
+        Language::IT => "Analizza il traffico trasportato da tunnel VXLAN, Geneve, GRE e IP-in-IP",
+        _ => "Analyze the traffic carried by VXLAN, Geneve, GRE, and IP-in-IP tunnels",
+    }
+}
+
+pub fn server_names_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Server names",
+        Language::IT => "Nomi dei server",
+        _ => "Server names",
+    }
+}
+
+pub fn extract_server_names_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => {
+            "Extract the server names from TLS and QUIC handshakes (capturing larger packet slices)"
+        }
+        Language::IT => {
+            "Estrai i nomi dei server dagli handshake TLS e QUIC (catturando porzioni di pacchetto più grandi)"
+        }
+        _ => {
+            "Extract the server names from TLS and QUIC handshakes (capturing larger packet slices)"
+        }
+    }
+}
+
+pub fn mpls_labels_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "MPLS labels",
+        Language::IT => "Etichette MPLS",
+        _ => "MPLS labels",
+    }
+}
+
+pub fn untagged_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Untagged",
+        Language::IT => "Senza tag",
+        _ => "Untagged",
+    }
+}
+
+pub fn fragments_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP fragments",
+        Language::IT => "Frammenti IP",
+        _ => "IP fragments",
+    }
+}
+
+pub fn devices_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Devices",
+        Language::IT => "Dispositivi",
+        _ => "Devices",
+    }
+}
+
+pub fn vendor_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Vendor",
+        Language::IT => "Produttore",
+        _ => "Vendor",
+    }
+}
+
+pub fn known_vendors_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Only the most common manufacturers are recognized",
+        Language::IT => "Solo i produttori più comuni vengono riconosciuti",
+        _ => "Only the most common manufacturers are recognized",
+    }
+}
+
+pub fn ip_addresses_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP addresses",
+        Language::IT => "Indirizzi IP",
+        _ => "IP addresses",
+    }
+}
+
+pub fn hostname_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Hostname",
+        Language::IT => "Nome host",
+        _ => "Hostname",
+    }
+}
+
+pub fn first_seen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "First seen",
+        Language::IT => "Prima rilevazione",
+        _ => "First seen",
+    }
+}
+
+pub fn last_seen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Last seen",
+        Language::IT => "Ultima rilevazione",
+        _ => "Last seen",
+    }
+}
+
+pub fn no_devices_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "No device of the local network has been observed yet...",
+        Language::IT => "Nessun dispositivo della rete locale è stato ancora rilevato...",
+        _ => "No device of the local network has been observed yet...",
+    }
+}
+
+pub fn arp_anomaly_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Suspicious ARP activity",
+        Language::IT => "Attività ARP sospetta",
+        _ => "Suspicious ARP activity",
+    }
+}
+
+pub fn arp_mac_changed_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP address claimed by a new MAC address",
+        Language::IT => "Indirizzo IP rivendicato da un nuovo indirizzo MAC",
+        _ => "IP address claimed by a new MAC address",
+    }
+}
+
+pub fn arp_duplicate_ip_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP address claimed by two MAC addresses",
+        Language::IT => "Indirizzo IP rivendicato da due indirizzi MAC",
+        _ => "IP address claimed by two MAC addresses",
+    }
+}
+
+pub fn gratuitous_arp_flood_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Flood of gratuitous ARP packets",
+        Language::IT => "Raffica di pacchetti ARP gratuiti",
+        _ => "Flood of gratuitous ARP packets",
+    }
+}
+
+pub fn scan_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Port scans and host sweeps",
+        Language::IT => "Scansioni di porte e di host",
+        _ => "Port scans and host sweeps",
+    }
+}
+
+pub fn port_scan_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Port scan",
+        Language::IT => "Scansione di porte",
+        _ => "Port scan",
+    }
+}
+
+pub fn host_sweep_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Host sweep",
+        Language::IT => "Scansione di host",
+        _ => "Host sweep",
+    }
+}
+
+pub fn ports_threshold_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Ports probed within a minute",
+        Language::IT => "Porte sondate in un minuto",
+        _ => "Ports probed within a minute",
+    }
+}
+
+pub fn hosts_threshold_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Hosts probed within a minute",
+        Language::IT => "Host sondati in un minuto",
+        _ => "Hosts probed within a minute",
+    }
+}
+
+pub fn both_directions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Both",
+        Language::IT => "Entrambe",
+        _ => "Both",
+    }
+}
+
+pub fn sustained_rate_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Sustained traffic rate exceeded",
+        Language::IT => "Velocità di traffico prolungata superata",
+        _ => "Sustained traffic rate exceeded",
+    }
+}
+
+pub fn daily_quota_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Daily traffic quota exceeded",
+        Language::IT => "Quota di traffico giornaliera superata",
+        _ => "Daily traffic quota exceeded",
+    }
+}
+
+pub fn per_day_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "(per day)",
+        Language::IT => "(al giorno)",
+        _ => "(per day)",
+    }
+}
+
+pub fn duration_seconds_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Duration (seconds)",
+        Language::IT => "Durata (secondi)",
+        _ => "Duration (seconds)",
+    }
+}
+
+pub fn hysteresis_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Hysteresis (%)",
+        Language::IT => "Isteresi (%)",
+        _ => "Hysteresis (%)",
+    }
+}
+
+pub fn direction_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Direction",
+        Language::IT => "Direzione",
+        _ => "Direction",
+    }
+}
+
+pub fn custom_thresholds_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom thresholds for favorites and filters",
+        Language::IT => "Soglie personalizzate per preferiti e filtri",
+        _ => "Custom thresholds for favorites and filters",
+    }
+}
+
+pub fn custom_threshold_exceeded_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom threshold exceeded",
+        Language::IT => "Soglia personalizzata superata",
+        _ => "Custom threshold exceeded",
+    }
+}
+
+pub fn add_threshold_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add a threshold for",
+        Language::IT => "Aggiungi una soglia per",
+        _ => "Add a threshold for",
+    }
+}
+
+pub fn current_filters_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Current Inspect filters",
+        Language::IT => "Filtri attuali di Ispeziona",
+        _ => "Current Inspect filters",
+    }
+}
+
+pub fn custom_thresholds_hint_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => {
+            "Add favorites or apply filters in the Inspect page to set custom thresholds"
+        }
+        Language::IT => {
+            "Aggiungi preferiti o applica filtri nella pagina Ispeziona per impostare soglie personalizzate"
+        }
+        _ => "Add favorites or apply filters in the Inspect page to set custom thresholds",
+    }
+}
+
+pub fn ip_lists_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Additional IP lists",
+        Language::IT => "Liste IP aggiuntive",
+        _ => "Additional IP lists",
+    }
+}
+
+pub fn allowlist_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Allowlist",
+        Language::IT => "Lista consentiti",
+        _ => "Allowlist",
+    }
+}
+
+pub fn add_ip_list_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add a list (allowlisted addresses are never reported)",
+        Language::IT => "Aggiungi una lista (gli indirizzi consentiti non sono mai segnalati)",
+        _ => "Add a list (allowlisted addresses are never reported)",
+    }
+}
+
+pub fn feed_format_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Format",
+        Language::IT => "Formato",
+        _ => "Format",
+    }
+}
+
+pub fn csv_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Column",
+        Language::IT => "Colonna",
+        _ => "Column",
+    }
+}
+
+pub fn refresh_minutes_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Refresh every (minutes)",
+        Language::IT => "Aggiorna ogni (minuti)",
+        _ => "Refresh every (minutes)",
+    }
+}
+
+pub fn press_enter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "press Enter to add it",
+        Language::IT => "premi Invio per aggiungerla",
+        _ => "press Enter to add it",
+    }
+}
+
+pub fn metrics_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Prometheus metrics",
+        Language::IT => "Metriche Prometheus",
+        _ => "Prometheus metrics",
+    }
+}
+
+pub fn expose_metrics_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Expose the traffic statistics over HTTP",
+        Language::IT => "Esponi le statistiche del traffico via HTTP",
+        _ => "Expose the traffic statistics over HTTP",
+    }
+}
+
+pub fn top_hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Top hosts",
+        Language::IT => "Host principali",
+        _ => "Top hosts",
+    }
+}
+
+pub fn max_label_values_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Max values per label",
+        Language::IT => "Valori massimi per etichetta",
+        _ => "Max values per label",
+    }
+}
+
+pub fn flow_export_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Flow export",
+        Language::IT => "Esportazione dei flussi",
+        _ => "Flow export",
+    }
+}
+
+pub fn export_flows_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Export the connections to a flow collector",
+        Language::IT => "Esporta le connessioni verso un collettore di flussi",
+        _ => "Export the connections to a flow collector",
+    }
+}
+
+pub fn active_timeout_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Active timeout (s)",
+        Language::IT => "Timeout attivo (s)",
+        _ => "Active timeout (s)",
+    }
+}
+
+pub fn inactive_timeout_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Inactive timeout (s)",
+        Language::IT => "Timeout inattivo (s)",
+        _ => "Inactive timeout (s)",
+    }
+}
+
+pub fn invalid_address_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Invalid address",
+        Language::IT => "Indirizzo non valido",
+        _ => "Invalid address",
+    }
+}
+
+pub fn not_a_network_adapter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Not a network adapter",
+        Language::IT => "Non è un adattatore di rete",
+        _ => "Not a network adapter",
+    }
+}
+
+pub fn separate_capture_per_adapter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Use a separate capture for each network adapter",
+        Language::IT => "Usa una cattura separata per ogni adattatore di rete",
+        _ => "Use a separate capture for each network adapter",
+    }
+}
+
+pub fn skipped_adapters_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Some network adapters couldn't be captured",
+        Language::IT => "Non è stato possibile catturare alcuni adattatori di rete",
+        _ => "Some network adapters couldn't be captured",
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Map holding a bounded number of entries, used to keep state about the sniffed traffic.
+///
+/// When the map is full, the least recently inserted entries make room for the new ones,
+/// so that floods of packets (e.g., SYN or fragment floods) can't grow it without limits.
+/// Entries not seen for longer than the timeout are also forgotten, checking at most once per timeout.
+pub struct BoundedMap<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// Keys in insertion order, with the number of their insertion
+    /// (items referring to entries removed or inserted again since then are outdated)
+    order: VecDeque<(K, u64)>,
+    capacity: usize,
+    timeout_usecs: i64,
+    /// Number of insertions so far
+    insertions: u64,
+    /// Time of the next check for idle entries
+    next_check_usecs: i64,
+}
+
+struct Entry<V> {
+    value: V,
+    insertion: u64,
+    last_seen_usecs: i64,
+}
+
+impl<K: Copy + Eq + Hash, V> BoundedMap<K, V> {
+    pub fn new(capacity: usize, timeout_usecs: i64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+            timeout_usecs,
+            insertions: 0,
+            next_check_usecs: i64::MIN,
+        }
+    }
+
+    /// Returns the value of the given key, marking it as seen at the given time
+    pub fn get_mut(&mut self, key: &K, now_usecs: i64) -> Option<&mut V> {
+        let entry = self.entries.get_mut(key)?;
+        entry.last_seen_usecs = now_usecs;
+        Some(&mut entry.value)
+    }
+
+    /// Returns the value of the given key, marking it as seen at the given time
+    /// (if missing, the default value is inserted, evicting the oldest entry if the map is full)
+    pub fn get_or_insert_with(
+        &mut self,
+        key: K,
+        now_usecs: i64,
+        default: impl FnOnce() -> V,
+    ) -> &mut V {
+        self.make_room(&key, now_usecs);
+        let insertion = self.insertions;
+        let entry = self.entries.entry(key).or_insert_with(|| Entry {
+            value: default(),
+            insertion,
+            last_seen_usecs: now_usecs,
+        });
+        entry.last_seen_usecs = now_usecs;
+        &mut entry.value
+    }
+
+    /// Inserts a value at the given time, evicting the oldest entry if the map is full
+    pub fn insert(&mut self, key: K, value: V, now_usecs: i64) {
+        self.make_room(&key, now_usecs);
+        // updated values keep their position in the order of insertion
+        let insertion = self
+            .entries
+            .get(&key)
+            .map_or(self.insertions, |entry| entry.insertion);
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                insertion,
+                last_seen_usecs: now_usecs,
+            },
+        );
+    }
+
+    /// Returns the value of the given key, without marking it as seen
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Keeps only the entries for which the given predicate returns true
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        let len = self.entries.len();
+        self.entries.retain(|key, entry| f(key, &mut entry.value));
+        if self.entries.len() < len {
+            self.forget_outdated_order();
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.entries.remove(key).map(|entry| entry.value)
+    }
+
+    /// Forgets the idle entries and, if the given key is new, makes room for it
+    fn make_room(&mut self, key: &K, now_usecs: i64) {
+        self.forget_idle(now_usecs);
+        if self.entries.contains_key(key) {
+            return;
+        }
+        while self.entries.len() >= self.capacity {
+            self.forget_oldest();
+        }
+        if self.order.len() >= 2 * self.capacity {
+            self.forget_outdated_order();
+        }
+        self.insertions += 1;
+        self.order.push_back((*key, self.insertions));
+    }
+
+    fn forget_oldest(&mut self) {
+        while let Some((key, insertion)) = self.order.pop_front() {
+            if self
+                .entries
+                .get(&key)
+                .is_some_and(|entry| entry.insertion == insertion)
+            {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    fn forget_idle(&mut self, now_usecs: i64) {
+        if now_usecs < self.next_check_usecs {
+            return;
+        }
+        self.next_check_usecs = now_usecs.saturating_add(self.timeout_usecs);
+        let timeout_usecs = self.timeout_usecs;
+        let len = self.entries.len();
+        self.entries
+            .retain(|_, entry| now_usecs - entry.last_seen_usecs < timeout_usecs);
+        if self.entries.len() < len {
+            self.forget_outdated_order();
+        }
+    }
+
+    fn forget_outdated_order(&mut self) {
+        let entries = &self.entries;
+        self.order.retain(|(key, insertion)| {
+            entries
+                .get(key)
+                .is_some_and(|entry| entry.insertion == *insertion)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_map_evicts_oldest() {
+        let mut map = BoundedMap::new(3, 1_000);
+        for key in 0..3 {
+            map.insert(key, key * 10, 0);
+        }
+        // updating a value doesn't change the order of insertion
+        map.insert(0, 1, 0);
+        map.insert(3, 30, 0);
+        assert_eq!(map.entries.len(), 3);
+        assert!(!map.entries.contains_key(&0));
+
+        // removed and inserted again: it's the newest
+        assert_eq!(map.remove(&1), Some(10));
+        map.insert(1, 11, 0);
+        map.insert(4, 40, 0);
+        assert!(!map.entries.contains_key(&2));
+        assert_eq!(map.get_mut(&1, 0), Some(&mut 11));
+        assert!(map.entries.contains_key(&3));
+        assert!(map.entries.contains_key(&4));
+
+        // outdated items don't accumulate
+        for _ in 0..100 {
+            map.remove(&4);
+            map.insert(4, 40, 0);
+        }
+        assert!(map.order.len() <= 6);
+        assert_eq!(map.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_bounded_map_forgets_idle() {
+        let mut map = BoundedMap::new(100, 1_000);
+        map.insert(1, "a", 0);
+        map.insert(2, "b", 500);
+        // not checked yet
+        map.insert(3, "c", 999);
+        assert_eq!(map.entries.len(), 3);
+
+        map.get_mut(&2, 1_000);
+        map.insert(4, "d", 1_200);
+        assert!(!map.entries.contains_key(&1));
+        assert!(map.entries.contains_key(&2));
+        assert!(map.entries.contains_key(&3));
+        assert_eq!(map.order.len(), 3);
+
+        // checked at most once per timeout
+        map.insert(5, "e", 2_100);
+        assert_eq!(map.entries.len(), 4);
+        map.insert(6, "f", 2_200);
+        assert_eq!(map.entries.len(), 2);
+        assert!(map.entries.contains_key(&5));
+        assert!(map.entries.contains_key(&6));
+    }
+
+    #[test]
+    fn test_bounded_map_retain() {
+        let mut map = BoundedMap::new(3, 1_000);
+        for key in 0..3 {
+            map.insert(key, key * 10, 0);
+        }
+        map.retain(|key, value| {
+            *value += 1;
+            key % 2 == 0
+        });
+        assert_eq!(map.get(&0), Some(&1));
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.get(&2), Some(&21));
+        assert_eq!(map.order.len(), 2);
+
+        // the remaining entries keep their order of insertion
+        map.insert(3, 30, 0);
+        map.insert(4, 40, 0);
+        assert!(!map.contains_key(&0));
+        assert!(map.contains_key(&2));
+    }
+}
@@ -26,7 +26,7 @@ pub enum Icon {
     FunnelX,
     FunnelStar,
     GitHub,
-    // Globe,
+    Globe,
     HalfSun,
     Hourglass1,
     Hourglass2,
@@ -89,7 +89,7 @@ pub fn codepoint(&self) -> char {
             Icon::FunnelX => ';',
             Icon::FunnelStar => '6',
             Icon::GitHub => 'H',
-            // Icon::Globe => 'c',
+            Icon::Globe => 'c',
             Icon::HalfSun => 'K',
             Icon::Hourglass1 => '1',
             Icon::Hourglass2 => '2',
@@ -1,3 +1,4 @@
+pub mod bounded_map;
 pub mod case_insensitive_string;
 pub mod file_info;
 pub mod icon;
@@ -1,3 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 #[derive(Clone, Default, Debug, Copy, Eq, PartialEq)]
 pub struct Timestamp {
     secs: i64,
@@ -9,6 +11,17 @@ pub fn new(secs: i64, usecs: i64) -> Self {
         Self { secs, usecs }
     }
 
+    /// Current time, represented as the timestamps of the captured packets
+    pub fn now() -> Self {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            secs: i64::try_from(since_epoch.as_secs()).unwrap_or_default(),
+            usecs: i64::from(since_epoch.subsec_micros()),
+        }
+    }
+
     pub fn secs(&self) -> i64 {
         self.secs
     }